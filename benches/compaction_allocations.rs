@@ -0,0 +1,105 @@
+//! Counts the allocations made when compacting, flattening and producing the
+//! RDF quads of a shared expanded document.
+//!
+//! Run with `cargo bench --bench compaction_allocations`.
+use json_ld::syntax::{context::Context, Parse, TryFromJson};
+use json_ld::{Compact, Flatten, JsonLdProcessor, Process, RdfQuads, RemoteDocument};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Allocator counting the allocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.realloc(ptr, layout, new_size)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Returns the number of allocations made by `f`.
+fn count<T>(f: impl FnOnce() -> T) -> usize {
+	let start = ALLOCATIONS.load(Ordering::Relaxed);
+	let result = f();
+	let end = ALLOCATIONS.load(Ordering::Relaxed);
+	drop(result);
+	end - start
+}
+
+const NODES: usize = 1000;
+
+fn input() -> json_ld::syntax::Value {
+	let mut nodes = Vec::with_capacity(NODES);
+	for i in 0..NODES {
+		nodes.push(format!(
+			r#"{{
+				"@id": "http://example.org/node/{i}",
+				"@type": "http://schema.org/Person",
+				"http://schema.org/name": "Node {i}",
+				"http://schema.org/knows": {{ "@id": "http://example.org/node/{}" }}
+			}}"#,
+			(i + 1) % NODES
+		))
+	}
+
+	let (json, _) = json_ld::syntax::Value::parse_str(&format!("[{}]", nodes.join(","))).unwrap();
+	json
+}
+
+fn main() {
+	async_std::task::block_on(async {
+		let document = RemoteDocument::new(None, None, input());
+		let expanded = Arc::new(document.expand(&json_ld::NoLoader).await.unwrap());
+
+		let (context, _) = json_ld::syntax::Value::parse_str(
+			r#"{
+				"@vocab": "http://schema.org/",
+				"@base": "http://example.org/node/"
+			}"#,
+		)
+		.unwrap();
+		let context = Context::try_from_json(context).unwrap();
+		let processed = context
+			.process(&mut (), &json_ld::NoLoader, None)
+			.await
+			.unwrap();
+
+		let start = ALLOCATIONS.load(Ordering::Relaxed);
+		let compacted = expanded
+			.compact(processed.as_ref(), &json_ld::NoLoader)
+			.await
+			.unwrap();
+		let compaction = ALLOCATIONS.load(Ordering::Relaxed) - start;
+		drop(compacted);
+
+		let flattening = count(|| {
+			let mut generator = json_ld::rdf_types::generator::Blank::new();
+			expanded.clone().flatten(&mut generator, true).unwrap()
+		});
+
+		let quads = count(|| {
+			let mut generator = json_ld::rdf_types::generator::Blank::new();
+			expanded.rdf_quads(&mut generator, None).count()
+		});
+
+		println!("{NODES} nodes:");
+		println!("  compaction: {compaction} allocations");
+		println!("  flattening: {flattening} allocations");
+		println!("  RDF quads:  {quads} allocations");
+	})
+}
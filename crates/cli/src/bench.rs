@@ -0,0 +1,178 @@
+//! `bench` subcommand.
+use std::time::{Duration, Instant};
+
+use iref::IriBuf;
+use json_ld::{
+	loader::{RecordingLoader, ReplayLoader},
+	syntax::Parse,
+	Compact, Context, Loader, Print, Process, ReqwestLoader,
+};
+
+use crate::IriOrPath;
+
+/// Benchmarked phases, in order.
+const PHASES: [&str; 5] = ["load", "parse", "context", "expand", "compact"];
+
+/// Runs `iterations` times the processing pipeline on the given document
+/// and prints the timings of each phase.
+///
+/// The `load` phase reads the file or fetches the document (in which case
+/// it also includes parsing). Documents read from the standard input are
+/// only loaded once. Remote contexts are recorded by a first, untimed,
+/// iteration and replayed from memory by the timed ones.
+pub async fn run(
+	url_or_path: Option<IriOrPath>,
+	base_url: Option<IriBuf>,
+	iterations: usize,
+) -> Result<(), String> {
+	let loader = ReqwestLoader::new();
+
+	let stdin = match &url_or_path {
+		None => Some(std::io::read_to_string(std::io::stdin()).map_err(|e| e.to_string())?),
+		Some(_) => None,
+	};
+
+	let input = Input {
+		url_or_path: url_or_path.as_ref(),
+		base_url: base_url.as_ref(),
+		stdin: stdin.as_deref(),
+	};
+
+	let recorder = RecordingLoader::new(ReqwestLoader::new());
+	iteration(&input, &loader, &recorder).await?;
+	let context_loader = ReplayLoader::new(recorder.into_snapshot());
+
+	let mut timings: Vec<[Duration; 5]> = Vec::with_capacity(iterations);
+	for _ in 0..iterations {
+		timings.push(iteration(&input, &loader, &context_loader).await?)
+	}
+
+	println!("{:<10}{:>14}{:>14}{:>14}", "phase", "min", "mean", "max");
+	for (i, phase) in PHASES.iter().enumerate() {
+		let min = timings.iter().map(|t| t[i]).min().unwrap_or_default();
+		let max = timings.iter().map(|t| t[i]).max().unwrap_or_default();
+		let total: Duration = timings.iter().map(|t| t[i]).sum();
+		let mean = total / iterations.max(1) as u32;
+		println!(
+			"{phase:<10}{:>14}{:>14}{:>14}",
+			format!("{min:.2?}"),
+			format!("{mean:.2?}"),
+			format!("{max:.2?}")
+		);
+	}
+
+	match peak_memory() {
+		Some(kib) => println!("peak memory: {:.1} MiB", kib as f64 / 1024.0),
+		None => println!("peak memory: unavailable"),
+	}
+
+	Ok(())
+}
+
+/// Benchmarked document.
+struct Input<'a> {
+	url_or_path: Option<&'a IriOrPath>,
+	base_url: Option<&'a IriBuf>,
+	stdin: Option<&'a str>,
+}
+
+/// Runs the processing pipeline once and returns the duration of each phase.
+///
+/// The document is loaded with `loader`, and its remote contexts with
+/// `context_loader`. The context of the document is only processed once:
+/// the `expand` phase starts from the context processed in the `context`
+/// phase.
+async fn iteration(
+	input: &Input<'_>,
+	loader: &ReqwestLoader,
+	context_loader: &impl Loader,
+) -> Result<[Duration; 5], String> {
+	let mut t = [Duration::ZERO; 5];
+
+	let start = Instant::now();
+	let (content, url) = match input.url_or_path {
+		Some(IriOrPath::Iri(iri)) => {
+			let document = loader.load(iri).await.map_err(|e| e.to_string())?;
+			(
+				document.document().compact_print().to_string(),
+				Some(iri.clone()),
+			)
+		}
+		Some(IriOrPath::Path(path)) => (
+			std::fs::read_to_string(path).map_err(|e| e.to_string())?,
+			input.base_url.cloned(),
+		),
+		None => (input.stdin.unwrap().to_owned(), input.base_url.cloned()),
+	};
+	t[0] = start.elapsed();
+
+	let start = Instant::now();
+	let (mut json, _) = json_ld::syntax::Value::parse_str(&content).map_err(|e| e.to_string())?;
+	t[1] = start.elapsed();
+
+	let start = Instant::now();
+	let local_context = json
+		.as_object()
+		.and_then(|o| o.get_unique("@context").ok().flatten())
+		.map(json_ld::syntax::context::Context::try_from_json_ref)
+		.transpose()
+		.map_err(|e| e.to_string())?;
+	let context = match &local_context {
+		Some(local_context) => Some(
+			local_context
+				.process(&mut (), context_loader, url.clone())
+				.await
+				.map_err(|e| e.to_string())?,
+		),
+		None => None,
+	};
+	t[2] = start.elapsed();
+
+	let active_context = match &context {
+		Some(context) => {
+			if let Some(object) = json.as_object_mut() {
+				object.remove("@context");
+			}
+
+			context.as_ref().processed().clone()
+		}
+		None => Context::new(url.clone()),
+	};
+
+	let start = Instant::now();
+	let expanded = json_ld::expansion::Expand::expand_full(
+		&json,
+		&mut (),
+		active_context,
+		url.as_ref(),
+		context_loader,
+		Default::default(),
+		(),
+	)
+	.await
+	.map_err(|e| e.to_string())?;
+	t[3] = start.elapsed();
+
+	if let Some(context) = &context {
+		let start = Instant::now();
+		expanded
+			.compact(context.as_ref(), context_loader)
+			.await
+			.map_err(|e| e.to_string())?;
+		t[4] = start.elapsed();
+	}
+
+	Ok(t)
+}
+
+/// Returns the peak resident set size of the process, in KiB.
+///
+/// Only available on Linux.
+fn peak_memory() -> Option<u64> {
+	let status = std::fs::read_to_string("/proc/self/status").ok()?;
+	status
+		.lines()
+		.find_map(|line| line.strip_prefix("VmHWM:"))
+		.and_then(|value| value.trim().strip_suffix("kB"))
+		.and_then(|value| value.trim().parse().ok())
+}
@@ -7,6 +7,11 @@ use iref::IriBuf;
 use json_ld::{syntax::Parse, JsonLdProcessor, Print, RemoteDocument, RemoteDocumentReference};
 use rdf_types::vocabulary::{IriIndex, IriVocabulary, IriVocabularyMut};
 
+mod bench;
+mod options;
+
+use options::ProcessingArgs;
+
 #[derive(Parser)]
 #[clap(name="json-ld", author, version, about, long_about = None)]
 struct Args {
@@ -50,6 +55,9 @@ pub enum Command {
 
 		#[clap(long = "no-undef")]
 		no_undef: bool,
+
+		#[clap(flatten)]
+		processing: ProcessingArgs,
 	},
 
 	Flatten {
@@ -61,6 +69,51 @@ pub enum Command {
 		/// Base URL to use when reading from the standard input or file system.
 		#[clap(short, long)]
 		base_url: Option<IriBuf>,
+
+		#[clap(flatten)]
+		processing: ProcessingArgs,
+	},
+
+	/// Show the structure of the expanded document.
+	///
+	/// Prints a tree of the expanded nodes, values and lists, to help
+	/// understand what expansion produced.
+	Inspect {
+		/// URL or file path of the document to inspect.
+		///
+		/// If none, the standard input is used.
+		url_or_path: Option<IriOrPath>,
+
+		/// Base URL to use when reading from the standard input or file system.
+		#[clap(short, long)]
+		base_url: Option<IriBuf>,
+
+		/// Output a graphviz (DOT) graph instead of a tree.
+		#[clap(long)]
+		dot: bool,
+
+		#[clap(flatten)]
+		processing: ProcessingArgs,
+	},
+
+	/// Measure the time spent in each processing phase.
+	///
+	/// Prints the minimum, mean and maximum duration of the load, parse,
+	/// context processing, expansion and compaction phases, and the peak
+	/// memory usage when available.
+	Bench {
+		/// URL or file path of the document to process.
+		///
+		/// If none, the standard input is used.
+		url_or_path: Option<IriOrPath>,
+
+		/// Base URL to use when reading from the standard input or file system.
+		#[clap(short, long)]
+		base_url: Option<IriBuf>,
+
+		/// Number of iterations.
+		#[clap(short = 'n', long, default_value = "10")]
+		iterations: usize,
 	},
 }
 
@@ -120,21 +173,26 @@ async fn main() {
 			canonicalize,
 			no_vocab,
 			no_undef,
+			processing,
 		} => {
 			let remote_document = get_remote_document(&mut vocabulary, url_or_path, base_url);
 
-			let options = json_ld::Options {
-				expansion_policy: json_ld::expansion::Policy {
+			let mut options = get_options(
+				&processing,
+				json_ld::expansion::Policy {
 					invalid: json_ld::expansion::Action::Reject,
-					vocab: if no_vocab {
-						json_ld::expansion::Action::Reject
-					} else {
-						json_ld::expansion::Action::Keep
-					},
-					allow_undefined: !no_undef,
+					vocab: json_ld::expansion::Action::Keep,
+					allow_undefined: true,
 				},
-				..Default::default()
-			};
+			);
+
+			if no_vocab {
+				options.expansion_policy.vocab = json_ld::expansion::Action::Reject
+			}
+
+			if no_undef {
+				options.expansion_policy.allow_undefined = false
+			}
 
 			match remote_document
 				.expand_with_using(&mut vocabulary, &loader, options)
@@ -165,13 +223,15 @@ async fn main() {
 		Command::Flatten {
 			url_or_path,
 			base_url,
+			processing,
 		} => {
 			let remote_document = get_remote_document(&mut vocabulary, url_or_path, base_url);
+			let options = get_options(&processing, Default::default());
 
 			let mut generator = rdf_types::generator::Blank::new_with_prefix("b".to_string());
 
 			match remote_document
-				.flatten_with(&mut vocabulary, &mut generator, &loader)
+				.flatten_with_using(&mut vocabulary, &mut generator, &loader, options)
 				.await
 			{
 				Ok(flattened) => {
@@ -183,6 +243,55 @@ async fn main() {
 				}
 			}
 		}
+		Command::Inspect {
+			url_or_path,
+			base_url,
+			dot,
+			processing,
+		} => {
+			let remote_document = get_remote_document(&mut vocabulary, url_or_path, base_url);
+			let options = get_options(&processing, Default::default());
+
+			match remote_document
+				.expand_with_using(&mut vocabulary, &loader, options)
+				.await
+			{
+				Ok(expanded) => {
+					if dot {
+						print!("{}", expanded.to_dot_with(&vocabulary))
+					} else {
+						print!("{}", expanded.outline_with(&vocabulary))
+					}
+				}
+				Err(e) => {
+					eprintln!("error: {e}");
+					std::process::exit(1);
+				}
+			}
+		}
+		Command::Bench {
+			url_or_path,
+			base_url,
+			iterations,
+		} => {
+			if let Err(e) = bench::run(url_or_path, base_url, iterations).await {
+				eprintln!("error: {e}");
+				std::process::exit(1);
+			}
+		}
+	}
+}
+
+fn get_options(
+	processing: &ProcessingArgs,
+	policy: json_ld::expansion::Policy,
+) -> json_ld::Options<IriIndex> {
+	match processing.options(policy) {
+		Ok(options) => options,
+		Err(e) => {
+			eprintln!("error: {e}");
+			std::process::exit(1);
+		}
 	}
 }
 
@@ -0,0 +1,109 @@
+//! Processing options, set with flags or an options file.
+use std::path::PathBuf;
+
+use json_ld::{expansion::Policy, rdf::RdfDirection, ProcessingMode};
+
+/// Processing options flags, shared by the processing commands.
+#[derive(clap::Args)]
+pub struct ProcessingArgs {
+	/// JSON file holding the processing options.
+	///
+	/// Keys are the names of the JSON-LD API options (`processingMode`,
+	/// `ordered`, `rdfDirection`, `produceGeneralizedRdf`, `compactArrays`,
+	/// `compactToRelative`), plus `expansionPolicy` and `maxLiteralLength`.
+	/// Flags take precedence over the file.
+	#[clap(long = "options")]
+	options_file: Option<PathBuf>,
+
+	/// Processing mode (`json-ld-1.0` or `json-ld-1.1`).
+	#[clap(long)]
+	processing_mode: Option<ProcessingMode>,
+
+	/// Term expansion policy (`strict-vc`, `lossless-ingest` or
+	/// `permissive-web`).
+	#[clap(long)]
+	policy: Option<Policy>,
+
+	/// Direction representation in RDF (`i18n-datatype` or
+	/// `compound-literal`).
+	#[clap(long)]
+	rdf_direction: Option<RdfDirection>,
+
+	/// Process the entries in lexicographical order.
+	#[clap(long)]
+	ordered: bool,
+}
+
+/// Content of an options file.
+///
+/// Every entry is optional.
+#[derive(Default, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct OptionsFile {
+	processing_mode: Option<ProcessingMode>,
+	ordered: Option<bool>,
+	rdf_direction: Option<RdfDirection>,
+	produce_generalized_rdf: Option<bool>,
+	compact_arrays: Option<bool>,
+	compact_to_relative: Option<bool>,
+	expansion_policy: Option<Policy>,
+	max_literal_length: Option<usize>,
+}
+
+/// Options file reading error.
+#[derive(Debug)]
+pub enum Error {
+	IO(PathBuf, std::io::Error),
+	Parse(PathBuf, serde_json::Error),
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::IO(path, e) => write!(f, "unable to read `{}`: {e}", path.display()),
+			Self::Parse(path, e) => write!(f, "invalid options file `{}`: {e}", path.display()),
+		}
+	}
+}
+
+impl ProcessingArgs {
+	/// Builds the processing options.
+	///
+	/// `policy` is the expansion policy used if neither the options file nor
+	/// the flags define one.
+	pub fn options<I>(&self, policy: Policy) -> Result<json_ld::Options<I>, Error> {
+		let file = match &self.options_file {
+			Some(path) => {
+				let content =
+					std::fs::read_to_string(path).map_err(|e| Error::IO(path.clone(), e))?;
+				serde_json::from_str(&content).map_err(|e| Error::Parse(path.clone(), e))?
+			}
+			None => OptionsFile::default(),
+		};
+
+		let mut options = json_ld::Options::<I>::default();
+
+		if let Some(processing_mode) = self.processing_mode.or(file.processing_mode) {
+			options.processing_mode = processing_mode
+		}
+
+		options.ordered = self.ordered || file.ordered.unwrap_or(options.ordered);
+		options.rdf_direction = self.rdf_direction.or(file.rdf_direction);
+		options.expansion_policy = self.policy.or(file.expansion_policy).unwrap_or(policy);
+		options.max_literal_length = file.max_literal_length;
+
+		if let Some(b) = file.produce_generalized_rdf {
+			options.produce_generalized_rdf = b
+		}
+
+		if let Some(b) = file.compact_arrays {
+			options.compact_arrays = b
+		}
+
+		if let Some(b) = file.compact_to_relative {
+			options.compact_to_relative = b
+		}
+
+		Ok(options)
+	}
+}
@@ -2,6 +2,7 @@ use json_ld_core::{ExpandedDocument, FlattenedDocument, Loader, Term};
 use json_ld_syntax::{IntoJson, Keyword};
 use rdf_types::{vocabulary, Vocabulary};
 use std::hash::Hash;
+use std::sync::Arc;
 
 use crate::{
 	iri::{compact_iri, IriConfusedWithPrefix},
@@ -98,7 +99,17 @@ impl<I, B> Compact<I, B> for ExpandedDocument<I, B> {
 		B: Clone + Hash + Eq,
 		L: Loader,
 	{
-		let mut compacted_output = self
+		let inlined;
+		let document = if options.included == crate::IncludedShape::Inline {
+			let mut document = self.clone();
+			document.inline_included();
+			inlined = document;
+			&inlined
+		} else {
+			self
+		};
+
+		let mut compacted_output = document
 			.objects()
 			.compact_fragment_full(
 				vocabulary,
@@ -147,6 +158,25 @@ impl<I, B> Compact<I, B> for FlattenedDocument<I, B> {
 	}
 }
 
+/// Compacts a shared document without cloning it.
+impl<I, B, T: Compact<I, B>> Compact<I, B> for Arc<T> {
+	async fn compact_full<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: json_ld_context_processing::ProcessedRef<'a, 'a, I, B>,
+		loader: &'a L,
+		options: crate::Options,
+	) -> CompactDocumentResult
+	where
+		N: rdf_types::VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Hash + Eq,
+		B: Clone + Hash + Eq,
+		L: Loader,
+	{
+		T::compact_full(self, vocabulary, context, loader, options).await
+	}
+}
+
 impl EmbedContext for json_syntax::Value {
 	fn embed_context<N>(
 		&mut self,
@@ -21,12 +21,14 @@ mod document;
 mod iri;
 mod node;
 mod property;
+mod stack;
 mod value;
 
 pub use document::*;
 pub(crate) use iri::*;
 use node::*;
 use property::*;
+use stack::*;
 use value::*;
 
 #[derive(Debug, thiserror::Error)]
@@ -39,6 +41,15 @@ pub enum Error {
 
 	#[error("Context processing failed: {0}")]
 	ContextProcessing(json_ld_context_processing::Error),
+
+	/// An entry of the compacted output that must hold a map (an index,
+	/// language, identifier or type map, or nested properties) holds another
+	/// value.
+	///
+	/// This can only happen when another property is compacted to the same
+	/// key.
+	#[error("Entry `{0}` of the compacted output is not a map")]
+	ConflictingMapEntry(String),
 }
 
 impl Error {
@@ -47,6 +58,7 @@ impl Error {
 			Self::IriConfusedWithPrefix => ErrorCode::IriConfusedWithPrefix,
 			Self::InvalidNestValue => ErrorCode::InvalidNestValue,
 			Self::ContextProcessing(e) => e.code(),
+			Self::ConflictingMapEntry(_) => ErrorCode::InvalidContainerMapping,
 		}
 	}
 }
@@ -66,7 +78,16 @@ impl From<IriConfusedWithPrefix> for Error {
 pub type CompactFragmentResult = Result<json_syntax::Value, Error>;
 
 /// Compaction options.
+///
+/// With the `serde` feature, options are (de)serialized as an object with
+/// camel case keys (such as `compactArrays`). Missing keys take their
+/// default value.
 #[derive(Clone, Copy)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(default, rename_all = "camelCase")
+)]
 pub struct Options {
 	/// JSON-LD processing mode.
 	pub processing_mode: ProcessingMode,
@@ -81,6 +102,87 @@ pub struct Options {
 	/// If set to `true`, properties are processed by lexical order.
 	/// If `false`, order is not considered in processing.
 	pub ordered: bool,
+
+	/// Kinds of value objects that must keep their object form instead of
+	/// being simplified to native JSON values, whatever the term definitions
+	/// of the context.
+	pub keep_value_objects_for: KeepValueObjects,
+
+	/// Shape given to `@included` nodes.
+	pub included: IncludedShape,
+
+	/// If set to `true`, node references that are values of a term with
+	/// `@type: @vocab` are compacted relative to the vocabulary, into a term
+	/// or a `@vocab` relative IRI.
+	/// If set to `false`, they are compacted to a compact IRI or an absolute
+	/// IRI.
+	pub vocab_relative_values: bool,
+
+	/// If set to `true`, properties equal to the default value of their term
+	/// are omitted from the compacted node objects.
+	///
+	/// Default values are declared with the `@default` entry of expanded
+	/// term definitions, an extension to JSON-LD. They are compared with the
+	/// value of the property once expanded, so that a default value matches
+	/// the property value whatever their compacted forms.
+	pub omit_defaults: bool,
+
+	/// If set to `true`, the content of an anonymous graph compacted under
+	/// the `@none` key of a `["@graph", "@id"]` container is wrapped in
+	/// `@included` when it has more than one node, so that expanding the map
+	/// back gives a single graph, as for `@graph` containers.
+	/// If set to `false`, the compaction algorithm is followed to the letter
+	/// and each node becomes a separate anonymous graph when expanded back.
+	pub wrap_anonymous_graphs: bool,
+}
+
+/// Shape of the `@included` nodes in the compacted output.
+///
+/// Search indexers usually want each node to be self-contained, while
+/// renderers may prefer to keep shared nodes in `@included`.
+///
+/// The string form of a shape is `preserve`, `inline` or `drop`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "lowercase")
+)]
+pub enum IncludedShape {
+	/// Keep `@included` nodes as they are.
+	#[default]
+	Preserve,
+
+	/// Inline `@included` nodes in the properties referencing them.
+	///
+	/// See [`Node::inline_included`](json_ld_core::Node::inline_included).
+	Inline,
+
+	/// Drop `@included` nodes.
+	Drop,
+}
+
+/// Selects value objects that are never simplified during compaction.
+///
+/// By default, a value object is compacted to a native JSON value whenever
+/// the context allows it (for instance when its datatype matches the type
+/// mapping of the property). Consumers validating documents against a strict
+/// schema may prefer a consistent shape.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(default, rename_all = "camelCase")
+)]
+pub struct KeepValueObjects {
+	/// Keep value objects with a `@type`.
+	pub typed: bool,
+
+	/// Keep value objects with a `@language`.
+	pub language_tagged: bool,
+
+	/// Keep value objects with an `@index`.
+	pub indexed: bool,
 }
 
 impl Options {
@@ -96,6 +198,7 @@ impl From<Options> for json_ld_context_processing::Options {
 	fn from(options: Options) -> json_ld_context_processing::Options {
 		json_ld_context_processing::Options {
 			processing_mode: options.processing_mode,
+			default_values: options.omit_defaults,
 			..Default::default()
 		}
 	}
@@ -118,6 +221,11 @@ impl Default for Options {
 			compact_to_relative: true,
 			compact_arrays: true,
 			ordered: false,
+			keep_value_objects_for: KeepValueObjects::default(),
+			included: IncludedShape::default(),
+			vocab_relative_values: true,
+			omit_defaults: false,
+			wrap_anonymous_graphs: false,
 		}
 	}
 }
@@ -396,17 +504,59 @@ impl<I, B, T: Any<I, B>> CompactIndexedFragment<I, B> for T {
 	}
 }
 
+/// Removes every `key` entry of `map`, returning their values, the values of
+/// arrays being flattened.
+fn remove_values(map: &mut json_syntax::Object, key: &str) -> Vec<json_syntax::Value> {
+	let mut values = Vec::new();
+	for Entry { value, .. } in map.remove(key) {
+		match value {
+			json_syntax::Value::Array(items) => values.extend(items),
+			value => values.push(value),
+		}
+	}
+
+	values
+}
+
+/// Merges the `key` entries of `map` into a single array entry, if there is
+/// more than one.
+///
+/// The output of compaction never has duplicate keys, but the ones of the
+/// input may be left in the compacted values. Their values are merged, as if
+/// they were given in a single array.
+fn merge_duplicates(map: &mut json_syntax::Object, key: &str) {
+	if map.get_unique(key).is_err() {
+		let values = remove_values(map, key);
+		map.insert(key.into(), json_syntax::Value::Array(values));
+	}
+}
+
+/// Returns the object value of the `key` entry in `map`, inserting an empty
+/// object first if there is no such entry.
+fn get_or_insert_object<'a>(
+	map: &'a mut json_syntax::Object,
+	key: &str,
+) -> Result<&'a mut json_syntax::Object, Error> {
+	merge_duplicates(map, key);
+
+	if map.get(key).next().is_none() {
+		map.insert(key.into(), json_syntax::Object::default().into());
+	}
+
+	map.get_mut(key)
+		.next()
+		.and_then(json_syntax::Value::as_object_mut)
+		.ok_or_else(|| Error::ConflictingMapEntry(key.to_owned()))
+}
+
 /// Default value of `as_array` is false.
 fn add_value(map: &mut json_syntax::Object, key: &str, value: json_syntax::Value, as_array: bool) {
-	match map
-		.get_unique(key)
-		.ok()
-		.unwrap()
-		.map(|entry| entry.is_array())
-	{
+	merge_duplicates(map, key);
+
+	match map.get(key).next().map(json_syntax::Value::is_array) {
 		Some(false) => {
-			let Entry { key, value } = map.remove_unique(key).ok().unwrap().unwrap();
-			map.insert(key, json_syntax::Value::Array(vec![value]));
+			let value = remove_values(map, key);
+			map.insert(key.into(), json_syntax::Value::Array(value));
 		}
 		None if as_array => {
 			map.insert(key.into(), json_syntax::Value::Array(Vec::new()));
@@ -421,7 +571,7 @@ fn add_value(map: &mut json_syntax::Object, key: &str, value: json_syntax::Value
 			}
 		}
 		value => {
-			if let Some(array) = map.get_unique_mut(key).ok().unwrap() {
+			if let Some(array) = map.get_mut(key).next() {
 				array.as_array_mut().unwrap().push(value);
 				return;
 			}
@@ -466,7 +616,7 @@ where
 	let mut result = Vec::new();
 
 	for item in items {
-		let compacted_item = Box::pin(item.compact_fragment_full(
+		let compacted_item = grow_stack(item.compact_fragment_full(
 			vocabulary,
 			active_context,
 			type_scoped_context,
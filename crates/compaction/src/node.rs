@@ -1,7 +1,9 @@
-use crate::{add_value, compact_iri, compact_property, Error, Options};
+use crate::{add_value, compact_iri, compact_property, Error, IncludedShape, Options};
 use contextual::WithContext;
 use json_ld_context_processing::{Options as ProcessingOptions, Process, ProcessingMode};
+use json_ld_core::context::{BindingRef, ExpandedDefaults};
 use json_ld_core::{Container, ContainerKind, Context, Id, Loader, Node, Term, Type};
+use json_ld_expansion::Expand;
 use json_ld_syntax::Keyword;
 use mown::Mown;
 use rdf_types::VocabularyMut;
@@ -162,14 +164,30 @@ where
 			// Otherwise, if the type mapping of active property is set to @vocab,
 			// set result to the result of IRI compacting the value associated with the @id entry.
 			if type_mapping == Some(&Type::Vocab) {
-				let compacted_value = compact_iri(
-					vocabulary,
-					active_context.as_ref(),
-					&id,
-					true,
-					false,
-					options,
-				)?;
+				let compacted_value = if options.vocab_relative_values {
+					compact_iri(
+						vocabulary,
+						active_context.as_ref(),
+						&id,
+						true,
+						false,
+						options,
+					)?
+				} else {
+					// Relative IRIs would be expanded against the vocabulary
+					// mapping, hence the `compact_to_relative` override.
+					compact_iri(
+						vocabulary,
+						active_context.as_ref(),
+						&id,
+						false,
+						false,
+						Options {
+							compact_to_relative: false,
+							..options
+						},
+					)?
+				};
 				return Ok(optional_string(compacted_value));
 			}
 		}
@@ -352,7 +370,10 @@ where
 		.await?
 	}
 
-	if let Some(included_entry) = node.included_entry() {
+	if let Some(included_entry) = node
+		.included_entry()
+		.filter(|_| options.included != IncludedShape::Drop)
+	{
 		compact_property(
 			vocabulary,
 			&mut result,
@@ -366,9 +387,153 @@ where
 		.await?
 	}
 
+	if options.omit_defaults {
+		omit_defaults(
+			vocabulary,
+			node,
+			&mut result,
+			active_context.as_ref(),
+			loader,
+			options,
+		)
+		.await
+	}
+
 	Ok(result.into())
 }
 
+/// Removes the entries of `result` whose term has a default value equal, once
+/// expanded, to the value of its property in `node`.
+///
+/// Comparing expanded values makes the comparison independent of the
+/// compacted form of the values (single values or arrays, value objects or
+/// coerced values, compact or absolute IRIs). A default value that cannot be
+/// expanded is never equal to the property value.
+///
+/// The default values are expanded once per active context, see
+/// [`expand_defaults`].
+async fn omit_defaults<N, L>(
+	vocabulary: &mut N,
+	node: &Node<N::Iri, N::BlankId>,
+	result: &mut json_syntax::Object,
+	active_context: &Context<N::Iri, N::BlankId>,
+	loader: &L,
+	options: Options,
+) where
+	N: VocabularyMut,
+	N::Iri: Clone + Hash + Eq,
+	N::BlankId: Clone + Hash + Eq,
+	L: Loader,
+{
+	let defaults = match active_context.expanded_defaults() {
+		Some(defaults) => defaults,
+		None => {
+			let defaults = expand_defaults(vocabulary, active_context, loader, options).await;
+			active_context.init_expanded_defaults(defaults)
+		}
+	};
+
+	let mut omitted = Vec::new();
+	for entry in result.iter() {
+		let key = entry.key.as_str();
+		let Some(Some(default)) = defaults.get(key) else {
+			continue;
+		};
+
+		let Some(Term::Id(prop)) = active_context.get(key).and_then(|d| d.value()) else {
+			continue;
+		};
+
+		if same_objects(
+			node.properties().get(prop).collect(),
+			default.iter().collect(),
+		) {
+			omitted.push(key.to_owned())
+		}
+	}
+
+	if !omitted.is_empty() {
+		let mut retained = json_syntax::Object::new();
+		for entry in std::mem::take(result) {
+			if !omitted.iter().any(|key| *key == entry.key.as_str()) {
+				retained.insert(entry.key, entry.value);
+			}
+		}
+
+		*result = retained
+	}
+}
+
+/// Expands the default values of the terms of the given active context.
+///
+/// Each default value is expanded as the value of its term in a node object.
+/// Reverse properties have no default values.
+async fn expand_defaults<N, L>(
+	vocabulary: &mut N,
+	active_context: &Context<N::Iri, N::BlankId>,
+	loader: &L,
+	options: Options,
+) -> ExpandedDefaults<N::Iri, N::BlankId>
+where
+	N: VocabularyMut,
+	N::Iri: Clone + Hash + Eq,
+	N::BlankId: Clone + Hash + Eq,
+	L: Loader,
+{
+	let mut defaults = ExpandedDefaults::new();
+	for binding in active_context.definitions() {
+		let BindingRef::Normal(key, definition) = binding else {
+			continue;
+		};
+
+		let (Some(default), Some(Term::Id(prop))) = (definition.default_value(), &definition.value)
+		else {
+			continue;
+		};
+
+		if definition.reverse_property {
+			continue;
+		}
+
+		let mut default_node = json_syntax::Object::new();
+		default_node.insert(key.as_str().into(), default.clone());
+		let expanded = json_syntax::Value::Object(default_node)
+			.expand_full(
+				vocabulary,
+				active_context.clone(),
+				active_context.base_iri(),
+				loader,
+				json_ld_expansion::Options {
+					processing_mode: options.processing_mode,
+					..Default::default()
+				},
+				(),
+			)
+			.await;
+
+		let objects = expanded
+			.ok()
+			.and_then(|d| d.into_main_node())
+			.map(|node| node.properties().get(prop).cloned().collect());
+
+		defaults.insert(key.clone(), objects);
+	}
+
+	defaults
+}
+
+/// Checks that the given lists hold the same objects, in any order.
+fn same_objects<T: PartialEq>(a: Vec<&T>, mut b: Vec<&T>) -> bool {
+	a.len() == b.len()
+		&& a.into_iter().all(|x| match b.iter().position(|y| *y == x) {
+			Some(i) => {
+				b.swap_remove(i);
+				true
+			}
+			None => false,
+		})
+}
+
 /// Compact the given list of types into the given `result` compacted object.
 fn compact_types<N>(
 	vocabulary: &mut N,
@@ -1,6 +1,7 @@
 use crate::{
-	add_value, compact_collection_with, compact_iri, compact_iri_with, compact_key, value_value,
-	CompactFragment, CompactIndexedFragment, Error, Options,
+	add_value, compact_collection_with, compact_iri, compact_iri_with, compact_key,
+	get_or_insert_object, grow_stack, remove_values, value_value, CompactFragment,
+	CompactIndexedFragment, Error, Options,
 };
 
 use json_ld_core::{
@@ -9,7 +10,7 @@ use json_ld_core::{
 	Container, ContainerKind, Context, Id, Indexed, Loader, Node, Object, Term,
 };
 use json_ld_syntax::Keyword;
-use rdf_types::VocabularyMut;
+use rdf_types::{Vocabulary, VocabularyMut};
 use std::hash::Hash;
 
 #[allow(clippy::too_many_arguments)]
@@ -32,7 +33,7 @@ where
 	L: Loader,
 {
 	// If expanded item is a list object:
-	let mut compacted_item = Box::pin(compact_collection_with(
+	let mut compacted_item = grow_stack(compact_collection_with(
 		vocabulary,
 		list.iter(),
 		active_context,
@@ -117,7 +118,7 @@ where
 	L: Loader,
 {
 	// If expanded item is a graph object
-	let mut compacted_item = Box::pin(node.graph().unwrap().compact_fragment_full(
+	let mut compacted_item = grow_stack(node.graph().unwrap().compact_fragment_full(
 		vocabulary,
 		active_context,
 		active_context,
@@ -132,24 +133,7 @@ where
 		// Initialize `map_object` to the value of `item_active_property`
 		// in `nest_result`, initializing it to a new empty map,
 		// if necessary.
-		if nest_result
-			.get_unique(item_active_property)
-			.ok()
-			.unwrap()
-			.is_none()
-		{
-			nest_result.insert(
-				item_active_property.into(),
-				json_ld_syntax::Object::default().into(),
-			);
-		}
-
-		let map_object = nest_result
-			.get_unique_mut(item_active_property)
-			.ok()
-			.unwrap()
-			.unwrap();
-		let map_object = map_object.as_object_mut().unwrap();
+		let map_object = get_or_insert_object(nest_result, item_active_property)?;
 
 		// Initialize `map_key` by IRI compacting the value of @id in
 		// `expanded_item` or @none if no such value exists
@@ -163,6 +147,12 @@ where
 		let map_key =
 			compact_iri(vocabulary, active_context, &id_value, vocab, false, options)?.unwrap();
 
+		// If the graph is anonymous, multiple objects would be interpreted
+		// as different (anonymous) named graphs when expanding the map back.
+		if options.wrap_anonymous_graphs && node.id.is_none() {
+			compacted_item = wrap_in_included(vocabulary, active_context, compacted_item, options)?;
+		}
+
 		// Use `add_value` to add `compacted_item` to
 		// the `map_key` entry in `map_object` using `as_array`.
 		add_value(map_object, &map_key, compacted_item, as_array)
@@ -173,24 +163,7 @@ where
 		// Initialize `map_object` to the value of `item_active_property`
 		// in `nest_result`, initializing it to a new empty map,
 		// if necessary.
-		if nest_result
-			.get_unique(item_active_property)
-			.ok()
-			.unwrap()
-			.is_none()
-		{
-			nest_result.insert(
-				item_active_property.into(),
-				json_ld_syntax::Object::default().into(),
-			);
-		}
-
-		let map_object = nest_result
-			.get_unique_mut(item_active_property)
-			.ok()
-			.unwrap()
-			.unwrap();
-		let map_object = map_object.as_object_mut().unwrap();
+		let map_object = get_or_insert_object(nest_result, item_active_property)?;
 
 		// Initialize `map_key` the value of @index in `expanded_item`
 		// or @none, if no such value exists.
@@ -210,23 +183,7 @@ where
 		// Set `compacted_item` to a new map,
 		// containing the key from IRI compacting @included and
 		// the original `compacted_item` as the value.
-		compacted_item = match compacted_item {
-			json_syntax::Value::Array(items) if items.len() > 1 => {
-				let key = compact_iri(
-					vocabulary,
-					active_context,
-					&Term::Keyword(Keyword::Included),
-					true,
-					false,
-					options,
-				)?
-				.unwrap();
-				let mut map = json_syntax::Object::default();
-				map.insert(key.into(), json_syntax::Value::Array(items));
-				json_syntax::Value::Object(map)
-			}
-			item => item,
-		};
+		compacted_item = wrap_in_included(vocabulary, active_context, compacted_item, options)?;
 
 		// Use `add_value` to add `compacted_item` to the
 		// `item_active_property` entry in `nest_result` using `as_array`.
@@ -300,6 +257,41 @@ where
 	Ok(())
 }
 
+/// Wraps the given compacted graph in a map containing the key from IRI
+/// compacting `@included`, if it is an array with more than one value.
+///
+/// Otherwise each value would be interpreted as a different anonymous named
+/// graph when expanded.
+fn wrap_in_included<N>(
+	vocabulary: &N,
+	active_context: &Context<N::Iri, N::BlankId>,
+	compacted_item: json_syntax::Value,
+	options: Options,
+) -> Result<json_syntax::Value, Error>
+where
+	N: Vocabulary,
+	N::Iri: Clone + Hash + Eq,
+	N::BlankId: Clone + Hash + Eq,
+{
+	match compacted_item {
+		json_syntax::Value::Array(items) if items.len() > 1 => {
+			let key = compact_iri(
+				vocabulary,
+				active_context,
+				&Term::Keyword(Keyword::Included),
+				true,
+				false,
+				options,
+			)?
+			.unwrap();
+			let mut map = json_syntax::Object::default();
+			map.insert(key.into(), json_syntax::Value::Array(items));
+			Ok(json_syntax::Value::Object(map))
+		}
+		item => Ok(item),
+	}
+}
+
 fn select_nest_result<'a, I, B>(
 	result: &'a mut json_syntax::Object,
 	active_context: &Context<I, B>,
@@ -328,26 +320,8 @@ where
 
 					// If result does not have a nest_term entry,
 					// initialize it to an empty map.
-					if result
-						.get_unique(nest_term.as_str())
-						.ok()
-						.unwrap()
-						.is_none()
-					{
-						result.insert(
-							nest_term.as_str().into(),
-							json_syntax::Object::default().into(),
-						);
-					}
-
 					// Initialize `nest_result` to the value of `nest_term` in result.
-					let value = result
-						.get_unique_mut(nest_term.as_str())
-						.ok()
-						.unwrap()
-						.unwrap();
-					let sub_object = value.as_object_mut().unwrap();
-					sub_object
+					get_or_insert_object(result, nest_term.as_str())?
 					// SubObject::Sub(result.get_mut(nest_term).unwrap().as_object_mut().unwrap())
 				}
 				None => {
@@ -467,7 +441,7 @@ where
 					.await?
 				}
 				_ => {
-					let mut compacted_item = Box::pin(expanded_item.compact_fragment_full(
+					let mut compacted_item = grow_stack(expanded_item.compact_fragment_full(
 						vocabulary,
 						active_context,
 						active_context,
@@ -488,24 +462,8 @@ where
 						// Initialize `map_object` to the value of
 						// `item_active_property` in `nest_result`,
 						// initializing it to a new empty map, if necessary.
-						if nest_result
-							.get_unique(item_active_property.as_str())
-							.ok()
-							.unwrap()
-							.is_none()
-						{
-							nest_result.insert(
-								item_active_property.clone().into(),
-								json_syntax::Object::default().into(),
-							);
-						}
-
-						let map_object = nest_result
-							.get_unique_mut(item_active_property.as_str())
-							.ok()
-							.unwrap()
-							.unwrap();
-						let map_object = map_object.as_object_mut().unwrap();
+						let map_object =
+							get_or_insert_object(nest_result, item_active_property.as_str())?;
 
 						// Initialize container key by IRI compacting either
 						// @language, @index, @id, or @type based on the contents of container.
@@ -548,7 +506,16 @@ where
 								compacted_item = value_value(value)
 							}
 
-							expanded_item.language().map(|lang| lang.to_string())
+							// Language tags are case-insensitive: reuse the
+							// key of a previous item with the same language,
+							// preserving its case.
+							expanded_item.language().map(|lang| {
+								map_object
+									.iter()
+									.find(|entry| lang.eq_ignore_case(&entry.key))
+									.map(|entry| entry.key.to_string())
+									.unwrap_or_else(|| lang.to_string())
+							})
 						} else if container_type == ContainerKind::Index {
 							match index_key {
 								Some(index_key) => {
@@ -570,33 +537,7 @@ where
 									// `container_key` in `compacted_item`, if any.
 									let (map_key, remaining_values) = match &mut compacted_item {
 										json_syntax::Value::Object(map) => {
-											match map
-												.remove_unique(
-													container_key.as_ref().unwrap().as_str(),
-												)
-												.ok()
-												.unwrap()
-											{
-												Some(entry) => match entry.value {
-													json_syntax::Value::String(s) => {
-														(Some(s.to_string()), Vec::new())
-													}
-													json_syntax::Value::Array(values) => {
-														let mut values = values.into_iter();
-														match values.next() {
-															Some(first_value) => (
-																first_value
-																	.as_str()
-																	.map(|v| v.to_string()),
-																values.collect(),
-															),
-															None => (None, values.collect()),
-														}
-													}
-													other => (None, vec![other]),
-												},
-												None => (None, Vec::new()),
-											}
+											take_map_key(map, container_key.as_deref().unwrap())
 										}
 										_ => (None, Vec::new()),
 									};
@@ -633,46 +574,16 @@ where
 							// set `map_key` to the value of `container_key` in
 							// `compacted_item` and remove `container_key` from
 							// `compacted_item`.
-							compacted_item
-								.as_object_mut()
-								.and_then(|map| {
-									map.remove_unique(container_key.unwrap().as_str())
-										.ok()
-										.unwrap()
-										.map(|entry| entry.value.as_str().map(ToOwned::to_owned))
-								})
-								.flatten()
+							compacted_item.as_object_mut().and_then(|map| {
+								take_map_key(map, container_key.as_deref().unwrap()).0
+							})
 						} else {
 							// Otherwise, if container includes @type:
 
 							// Set `map_key` to the first value of `container_key` in
 							// `compacted_item`, if any.
 							let (map_key, remaining_values) = match compacted_item.as_object_mut() {
-								Some(map) => {
-									match map
-										.remove_unique(container_key.as_ref().unwrap().as_str())
-										.ok()
-										.unwrap()
-									{
-										Some(entry) => match entry.value {
-											json_syntax::Value::String(s) => {
-												(Some((*s).to_string()), Vec::new())
-											}
-											json_syntax::Value::Array(values) => {
-												let mut values = values.into_iter();
-												match values.next() {
-													Some(first_value) => (
-														first_value.as_str().map(ToOwned::to_owned),
-														values.collect(),
-													),
-													None => (None, values.collect()),
-												}
-											}
-											other => (None, vec![other]),
-										},
-										None => (None, Vec::new()),
-									}
-								}
+								Some(map) => take_map_key(map, container_key.as_deref().unwrap()),
 								_ => (None, Vec::new()),
 							};
 
@@ -701,11 +612,13 @@ where
 							// `active_property`, and a map composed of the single
 							// entry for @id from `expanded_item` for `element`.
 							if let Some(map) = compacted_item.as_object() {
-								if map.len() == 1 && map.get_unique("@id").ok().unwrap().is_some() {
+								if map.len() == 1
+									&& map.get_unique("@id").is_ok_and(|v| v.is_some())
+								{
 									let obj = Object::node(Node::with_id(
 										expanded_item.id().unwrap().clone(),
 									));
-									compacted_item = Box::pin(obj.compact_indexed_fragment(
+									compacted_item = grow_stack(obj.compact_indexed_fragment(
 										vocabulary,
 										None,
 										active_context,
@@ -784,3 +697,17 @@ where
 
 	Ok(())
 }
+
+/// Removes the `key` entries of `map`, returning the first of their values if
+/// it is a string, and the other values.
+fn take_map_key(
+	map: &mut json_syntax::Object,
+	key: &str,
+) -> (Option<String>, Vec<json_syntax::Value>) {
+	let mut values = remove_values(map, key).into_iter();
+	match values.next() {
+		Some(json_syntax::Value::String(s)) => (Some(s.to_string()), values.collect()),
+		Some(first_value) => (None, std::iter::once(first_value).chain(values).collect()),
+		None => (None, Vec::new()),
+	}
+}
@@ -0,0 +1,36 @@
+//! Stack growth for the recursive compaction of deep trees.
+use std::{
+	future::Future,
+	pin::Pin,
+	task::{Context, Poll},
+};
+
+/// Minimum amount of stack left before polling a nested fragment.
+const RED_ZONE: usize = 128 * 1024;
+
+/// Size of the stack segments allocated when the red zone is reached.
+const STACK_SIZE: usize = 2 * 1024 * 1024;
+
+/// Boxed future polled on a freshly allocated stack segment when the current
+/// one is running out.
+///
+/// Compacting a fragment compacts its nested fragments with boxed futures.
+/// Polling such a future polls the futures of every nested level on the call
+/// stack, so the stack usage grows with the depth of the document, no matter
+/// how large the futures themselves are.
+pub(crate) struct GrowStack<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for GrowStack<F> {
+	type Output = F::Output;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+		stacker::maybe_grow(RED_ZONE, STACK_SIZE, || self.0.as_mut().poll(cx))
+	}
+}
+
+/// Boxes the given future so that it is polled with enough stack space.
+///
+/// Used in place of `Box::pin` for recursive calls.
+pub(crate) fn grow_stack<F: Future>(future: F) -> GrowStack<F> {
+	GrowStack(Box::pin(future))
+}
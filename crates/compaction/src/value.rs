@@ -104,10 +104,25 @@ where
 	let remove_index =
 		(index.is_some() && container_mapping.contains(ContainerKind::Index)) || index.is_none();
 
+	// The value must not be simplified if the options say so.
+	let keep = options.keep_value_objects_for;
+	let keep_object = (keep.indexed && index.is_some())
+		|| match value {
+			Value::Literal(_, ty) => keep.typed && ty.is_some(),
+			Value::LangString(ls) => keep.language_tagged && ls.language().is_some(),
+			Value::Json(_) => false,
+		};
+
 	match value {
 		Value::Literal(lit, ty) => {
 			use object::value::Literal;
-			if ty.clone().map(Type::Iri) == type_mapping && remove_index {
+			let type_matches = match (ty, &type_mapping) {
+				(None, None) => true,
+				(Some(ty), Some(Type::Iri(mapping))) => ty == mapping,
+				_ => false,
+			};
+
+			if !keep_object && type_matches && remove_index {
 				match lit {
 					Literal::Null => return Ok(json_syntax::Value::Null),
 					Literal::Boolean(b) => return Ok(json_syntax::Value::Boolean(*b)),
@@ -189,7 +204,8 @@ where
 			let ls_language = ls.language(); //.map(|l| Nullable::Some(l));
 			let ls_direction = ls.direction(); //.map(|d| Nullable::Some(d));
 
-			if remove_index
+			if !keep_object
+			&& remove_index
 			&& (ls_language.is_none() || language == ls_language) // || (ls.language().is_none() && language.is_none()))
 			&& (ls_direction.is_none() || direction == ls_direction)
 			{
@@ -241,7 +257,7 @@ where
 			}
 		}
 		Value::Json(value) => {
-			if type_mapping == Some(Type::Json) && remove_index {
+			if !keep_object && type_mapping == Some(Type::Json) && remove_index {
 				return Ok(value.clone());
 			} else {
 				let compact_key = compact_key(
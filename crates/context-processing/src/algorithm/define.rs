@@ -3,6 +3,7 @@ use crate::{Error, Options, ProcessingStack, Warning, WarningHandler};
 use iref::{Iri, IriRef};
 use json_ld_core::{
 	context::{NormalTermDefinition, TypeTermDefinition},
+	warning::Location,
 	Container, Context, Id, Loader, ProcessingMode, Term, Type, ValidId,
 };
 use json_ld_syntax::{
@@ -179,8 +180,11 @@ where
 
 					// Create a new term definition, `definition`, initializing `prefix` flag to
 					// `false`, `protected` to `protected`, and `reverse_property` to `false`.
+					// The remote context document being processed, if any, is recorded as
+					// the source of the definition.
 					let mut definition = NormalTermDefinition::<N::Iri, N::BlankId> {
 						protected,
+						source: remote_contexts.top().cloned(),
 						..Default::default()
 					};
 
@@ -248,9 +252,10 @@ where
 						// If the value associated with the @reverse entry is a string having
 						// the form of a keyword, return; processors SHOULD generate a warning.
 						if reverse_value.is_keyword_like() {
-							env.warnings.handle(
+							env.warnings.handle_at(
 								env.vocabulary,
 								Warning::KeywordLikeValue(reverse_value.to_string()),
+								&Location::root().key(term.as_str()).key("@reverse"),
 							);
 							return Ok(());
 						}
@@ -331,9 +336,10 @@ where
 									// processors SHOULD generate a warning.
 									if id_value.is_keyword_like() && !id_value.is_keyword() {
 										debug_assert!(Keyword::try_from(id_value.as_str()).is_err());
-										env.warnings.handle(
+										env.warnings.handle_at(
 											env.vocabulary,
 											Warning::KeywordLikeValue(id_value.to_string()),
+											&Location::root().key(term.as_str()).key("@id"),
 										);
 										return Ok(());
 									}
@@ -742,6 +748,17 @@ where
 						}
 					}
 
+					// Extension: if value contains the entry @default, set the default value of
+					// definition to it. Unless enabled by the processing options, this is an
+					// invalid term definition error, as for any other unknown entry.
+					if let Some(default_value) = value.default {
+						if !options.default_values {
+							return Err(Error::InvalidTermDefinition);
+						}
+
+						definition.default = Some(default_value.clone());
+					}
+
 					// If value contains any entry other than @id, @reverse, @container, @context,
 					// @direction, @index, @language, @nest, @prefix, @protected, or @type, an
 					// invalid term definition error has been detected and processing is aborted.
@@ -207,7 +207,15 @@ where
 	Term::Id(Id::Invalid(value))
 }
 
+/// Action taken on a term.
+///
+/// The string form of an action is `keep`, `drop` or `reject`.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "lowercase")
+)]
 pub enum Action {
 	#[default]
 	Keep,
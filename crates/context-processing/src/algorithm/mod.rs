@@ -3,10 +3,16 @@ use std::hash::Hash;
 use crate::{
 	Error, Options, Process, Processed, ProcessingResult, ProcessingStack, WarningHandler,
 };
-use iref::IriRef;
-use json_ld_core::{Context, Environment, ExtractContext, Loader, ProcessingMode, Term};
+use futures::{stream, StreamExt};
+use iref::{IriBuf, IriRef};
+use json_ld_core::{
+	Context, Environment, ExtractContext, Loader, LoadingResult, ProcessingMode, Term,
+	CONTEXT_PROFILE,
+};
 use json_ld_syntax::{self as syntax, Nullable};
-use rdf_types::{vocabulary::IriVocabularyMut, VocabularyMut};
+use rdf_types::{vocabulary::IriVocabularyMut, Id, VocabularyMut};
+use std::cell::RefCell;
+use std::collections::HashMap;
 
 mod define;
 mod iri;
@@ -65,6 +71,115 @@ fn resolve_iri<I>(
 	}
 }
 
+/// Maximum number of remote contexts loaded concurrently.
+const MAX_CONCURRENT_LOADS: usize = 8;
+
+/// Loads concurrently the remote contexts listed by `local_context`.
+///
+/// Loading remote contexts does not depend on the order in which they are
+/// processed, so the documents are loaded ahead of time when `local_context`
+/// is an array with more than one remote context. Contexts already in the
+/// processing stack, or provided already processed by the loader, are not
+/// loaded.
+async fn prefetch_remote_contexts<N, L>(
+	vocabulary: &mut N,
+	loader: &L,
+	local_context: &syntax::context::Context,
+	remote_contexts: &ProcessingStack<N::Iri>,
+	base_url: Option<&N::Iri>,
+) -> HashMap<N::Iri, LoadingResult<IriBuf>>
+where
+	N: VocabularyMut,
+	N::Iri: Clone + Eq + Hash,
+	L: Loader,
+{
+	let mut iris = Vec::new();
+	for context in local_context {
+		if let syntax::ContextEntry::IriRef(iri_ref) = context {
+			if let Some(iri) = resolve_iri(vocabulary, iri_ref.as_iri_ref(), base_url) {
+				if !remote_contexts.cycle(&iri) && !iris.iter().any(|(i, _)| *i == iri) {
+					let lexical_iri = vocabulary.iri(&iri).unwrap().to_owned();
+					if loader.processed_context(&lexical_iri).is_none() {
+						iris.push((iri, lexical_iri))
+					}
+				}
+			}
+		}
+	}
+
+	if iris.len() < 2 {
+		return HashMap::new();
+	}
+
+	stream::iter(iris)
+		.map(|(iri, lexical_iri)| async move {
+			let document = loader
+				.load_with_profile(&lexical_iri, CONTEXT_PROFILE)
+				.await;
+			(iri, document)
+		})
+		.buffered(MAX_CONCURRENT_LOADS)
+		.collect()
+		.await
+}
+
+/// Returns the result of processing, on top of the newly-initialized context
+/// `result`, the remote context behind the given processed context,
+/// interpreting its identifiers with the vocabulary.
+///
+/// The processed context was defined on top of another newly-initialized
+/// context, standing for `result`. Its definitions are hence imported into
+/// `result`, as well as its previous contexts (set when the remote context
+/// does not propagate), so that `result` keeps its base IRI and term
+/// preferences.
+fn import_processed_context<N>(
+	vocabulary: &mut N,
+	result: &Context<N::Iri, N::BlankId>,
+	processed: &Context,
+) -> Context<N::Iri, N::BlankId>
+where
+	N: VocabularyMut,
+	N::Iri: Clone,
+	N::BlankId: Clone,
+{
+	let previous_context = processed
+		.previous_context()
+		.map(|previous| import_processed_context(vocabulary, result, previous));
+
+	let mut result = result.clone();
+	if let Some(previous_context) = previous_context {
+		result.set_previous_context(previous_context)
+	}
+
+	let vocabulary = RefCell::new(vocabulary);
+	let map_iri = |iri: IriBuf| vocabulary.borrow_mut().insert_owned(iri);
+	let map_id = |id: Id| match id {
+		Id::Iri(iri) => Id::Iri(map_iri(iri)),
+		Id::Blank(b) => Id::Blank(vocabulary.borrow_mut().insert_blank_id(&b)),
+	};
+
+	result.set_vocabulary(processed.vocabulary().cloned().map(|v| v.map_id(map_id)));
+	result.set_default_language(processed.default_language().map(ToOwned::to_owned));
+	result.set_default_base_direction(processed.default_base_direction());
+
+	if let Some(version) = processed.declared_version() {
+		result.set_declared_version(Some(version))
+	}
+
+	let (normal, type_) = processed
+		.definitions()
+		.clone()
+		.map_ids(map_iri, map_id)
+		.into_parts();
+
+	for (key, definition) in normal {
+		result.set_normal(key, Some(definition));
+	}
+
+	result.set_type(type_);
+	result
+}
+
 // This function tries to follow the recommended context processing algorithm.
 // See `https://www.w3.org/TR/json-ld11-api/#context-processing-algorithm`.
 //
@@ -106,6 +221,16 @@ where
 		result.set_previous_context(active_context.clone());
 	}
 
+	// Load the remote contexts of the array ahead of time.
+	let mut prefetched = prefetch_remote_contexts(
+		env.vocabulary,
+		env.loader,
+		local_context,
+		&remote_contexts,
+		base_url.as_ref(),
+	)
+	.await;
+
 	// 4) If local context is not an array, set it to an array containing only local context.
 	// 5) For each item context in local context:
 	for context in local_context {
@@ -127,6 +252,7 @@ where
 					// `base_iri` and `original_base_url` to the value of `original_base_url` in
 					// active context, ...
 					result = Context::new(active_context.original_base_url().cloned());
+					result.set_term_preferences(active_context.term_preferences().cloned());
 
 					// ... and, if `propagate` is `false`, `previous_context` in `result` to the
 					// previous value of `result`.
@@ -166,10 +292,34 @@ where
 				// context has been detected and processing is aborted.
 				// Set loaded context to the value of that entry.
 				if remote_contexts.push(context_iri.clone()) {
-					let loaded_context = env
-						.loader
-						.load_with(env.vocabulary, context_iri.clone())
-						.await?
+					// Use the processed context provided by the loader, if any. It is
+					// the result of processing the remote context on top of a
+					// newly-initialized context, so it can only be used on such context.
+					// It was processed in `json-ld-1.1` mode, whose features may be
+					// forbidden in `json-ld-1.0` mode, in which the remote context is
+					// processed again.
+					if result.is_initial() && options.processing_mode == ProcessingMode::JsonLd1_1 {
+						let lexical_iri = env.vocabulary.iri(&context_iri).unwrap();
+						if let Some(processed) = env.loader.processed_context(lexical_iri) {
+							result = import_processed_context(env.vocabulary, &result, &processed);
+							continue;
+						}
+					}
+
+					let loaded_document = match prefetched.remove(&context_iri) {
+						Some(document) => {
+							document?.map_iris(|iri| env.vocabulary.insert_owned(iri))
+						}
+						None => {
+							let lexical_iri = env.vocabulary.iri(&context_iri).unwrap();
+							env.loader
+								.load_with_profile(lexical_iri, CONTEXT_PROFILE)
+								.await?
+								.map_iris(|iri| env.vocabulary.insert_owned(iri))
+						}
+					};
+
+					let loaded_context = loaded_document
 						.into_document()
 						.into_ld_context()
 						.map_err(Error::ContextExtractionFailed)?;
@@ -204,13 +354,24 @@ where
 
 			// 5.4) Context definition.
 			syntax::ContextEntry::Definition(context) => {
+				// A context that declared `@version` was processed in `json-ld-1.1`
+				// mode and cannot be extended in `json-ld-1.0` mode, unless it has
+				// been reset with `null`.
+				if options.processing_mode == ProcessingMode::JsonLd1_0
+					&& result.declared_version().is_some()
+				{
+					return Err(Error::ProcessingModeConflict);
+				}
+
 				// 5.5) If context has a @version entry:
-				if context.version.is_some() {
+				if let Some(version) = context.version {
 					// 5.5.2) If processing mode is set to json-ld-1.0, a processing mode conflict
 					// error has been detected.
 					if options.processing_mode == ProcessingMode::JsonLd1_0 {
 						return Err(Error::ProcessingModeConflict);
 					}
+
+					result.set_declared_version(Some(version));
 				}
 
 				// 5.6) If context has an @import entry:
@@ -232,9 +393,10 @@ where
 						.ok_or(Error::InvalidImportValue)?;
 
 						// 5.6.4) Dereference import.
+						let lexical_import = env.vocabulary.iri(&import).unwrap();
 						let import_context = env
 							.loader
-							.load_with(env.vocabulary, import.clone())
+							.load_with_profile(lexical_import, CONTEXT_PROFILE)
 							.await?
 							.into_document()
 							.into_ld_context()
@@ -1,7 +1,7 @@
 //! JSON-LD context processing types and algorithms.
 use algorithm::{Action, RejectVocab};
 pub use json_ld_core::{warning, Context, ProcessingMode};
-use json_ld_core::{ExtractContextError, LoadError, Loader};
+use json_ld_core::{Environment, ExtractContextError, LoadError, Loader};
 use json_ld_syntax::ErrorCode;
 use rdf_types::VocabularyMut;
 use std::{fmt, hash::Hash};
@@ -130,7 +130,7 @@ impl Error {
 			Self::CyclicIriMapping => ErrorCode::CyclicIriMapping,
 			Self::InvalidTermDefinition => ErrorCode::InvalidTermDefinition,
 			Self::KeywordRedefinition => ErrorCode::KeywordRedefinition,
-			Self::InvalidProtectedValue => ErrorCode::InvalidPropagateValue,
+			Self::InvalidProtectedValue => ErrorCode::InvalidProtectedValue,
 			Self::InvalidTypeMapping => ErrorCode::InvalidTypeMapping,
 			Self::InvalidReverseProperty => ErrorCode::InvalidReverseProperty,
 			Self::InvalidIriMapping => ErrorCode::InvalidIriMapping,
@@ -167,6 +167,36 @@ pub trait Process {
 		L: Loader,
 		W: WarningHandler<N>;
 
+	/// Process the local context in the given environment.
+	///
+	/// This is equivalent to [`Process::process_full`] with the vocabulary,
+	/// loader and warning handler of `env`.
+	#[allow(async_fn_in_trait)]
+	async fn process_in<N, L, W>(
+		&self,
+		env: Environment<'_, N, L, W>,
+		active_context: &Context<N::Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		options: Options,
+	) -> Result<Processed<N::Iri, N::BlankId>, Error>
+	where
+		N: VocabularyMut,
+		N::Iri: Clone + Eq + Hash,
+		N::BlankId: Clone + PartialEq,
+		L: Loader,
+		W: WarningHandler<N>,
+	{
+		self.process_full(
+			env.vocabulary,
+			active_context,
+			env.loader,
+			base_url,
+			options,
+			env.warnings,
+		)
+		.await
+	}
+
 	/// Process the local context with specific options.
 	#[allow(clippy::type_complexity)]
 	#[allow(async_fn_in_trait)]
@@ -224,7 +254,16 @@ pub trait Process {
 }
 
 /// Options of the Context Processing Algorithm.
+///
+/// With the `serde` feature, options are (de)serialized as an object with
+/// camel case keys (such as `processingMode`). Missing keys take their
+/// default value.
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(default, rename_all = "camelCase")
+)]
 pub struct Options {
 	/// The processing mode
 	pub processing_mode: ProcessingMode,
@@ -237,9 +276,24 @@ pub struct Options {
 
 	/// Forbid the use of `@vocab` to expand terms.
 	pub vocab: Action,
+
+	/// Accept the `@default` extension entry in expanded term definitions.
+	///
+	/// This entry is not part of the JSON-LD specification: if set to
+	/// `false`, it is an invalid term definition error, like any unknown
+	/// entry.
+	pub default_values: bool,
 }
 
 impl Options {
+	/// Returns the default options for the given processing mode.
+	pub fn with_defaults_for(processing_mode: ProcessingMode) -> Self {
+		Self {
+			processing_mode,
+			..Self::default()
+		}
+	}
+
 	/// Return the same set of options, but with `override_protected` set to `true`.
 	#[must_use]
 	pub fn with_override(&self) -> Options {
@@ -272,6 +326,7 @@ impl Default for Options {
 			override_protected: false,
 			propagate: true,
 			vocab: Action::Keep,
+			default_values: false,
 		}
 	}
 }
@@ -50,6 +50,11 @@ impl<I> ProcessingStack<I> {
 		self.head.is_none()
 	}
 
+	/// Returns the URL of the last loaded context, if any.
+	pub fn top(&self) -> Option<&I> {
+		self.head.as_ref().map(|head| &head.url)
+	}
+
 	/// Checks if the given URL is already in the stack.
 	///
 	/// This is used for loop detection.
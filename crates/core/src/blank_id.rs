@@ -0,0 +1,200 @@
+//! Non-conforming blank node identifiers.
+//!
+//! Some systems produce blank node labels that do not follow the
+//! [N-Quads grammar](https://www.w3.org/TR/n-quads/#grammar-production-BLANK_NODE_LABEL),
+//! such as `_:node 1` or `_:a:b`. The expansion algorithm keeps them as
+//! invalid identifiers, which are dropped from the RDF serialization. This
+//! module provides the means to relabel them into conforming identifiers
+//! instead, either with [`ExpandedDocument::relabel_non_conforming_blank_ids`]
+//! or during expansion with the `relabel_non_conforming_blank_ids` option.
+//!
+//! Note that the algorithms are generic over the blank node identifier type
+//! `B`: systems using their own identifier type can use it by implementing
+//! [`BlankIdVocabularyMut`](rdf_types::vocabulary::BlankIdVocabularyMut)
+//! on a vocabulary mapping their identifiers to conforming labels.
+use crate::{ExpandedDocument, Id, ValidId};
+use hashbrown::HashSet;
+use indexmap::IndexMap;
+use rdf_types::{vocabulary::VocabularyMut, BlankId, BlankIdBuf};
+use std::{fmt, hash::Hash, ops::Deref};
+
+/// Blank node identifier that may not be conforming.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LenientBlankIdBuf(String);
+
+impl LenientBlankIdBuf {
+	/// Creates a new lenient blank node identifier.
+	///
+	/// Any string starting with `_:` is accepted.
+	pub fn new(s: String) -> Result<Self, String> {
+		if s.starts_with("_:") {
+			Ok(Self(s))
+		} else {
+			Err(s)
+		}
+	}
+
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// Returns the label of the blank node identifier, without the `_:`
+	/// prefix.
+	pub fn suffix(&self) -> &str {
+		&self.0[2..]
+	}
+
+	pub fn is_conforming(&self) -> bool {
+		BlankId::new(self.as_str()).is_ok()
+	}
+
+	pub fn as_conforming(&self) -> Option<&BlankId> {
+		BlankId::new(self.as_str()).ok()
+	}
+
+	/// Returns a conforming blank node identifier close to this one.
+	///
+	/// Conforming identifiers are returned as is. Otherwise every character
+	/// of the label other than an ASCII letter, digit, `_` or `-` is replaced
+	/// with `_`, as is a leading `-`. An empty label becomes `_`.
+	pub fn to_conforming(&self) -> BlankIdBuf {
+		if let Some(b) = self.as_conforming() {
+			return b.to_owned();
+		}
+
+		let mut label = String::from("_:");
+		for (i, c) in self.suffix().chars().enumerate() {
+			if c.is_ascii_alphanumeric() || c == '_' || (c == '-' && i > 0) {
+				label.push(c)
+			} else {
+				label.push('_')
+			}
+		}
+
+		if label.len() == 2 {
+			label.push('_')
+		}
+
+		BlankIdBuf::new(label).unwrap()
+	}
+}
+
+impl From<BlankIdBuf> for LenientBlankIdBuf {
+	fn from(value: BlankIdBuf) -> Self {
+		Self(value.to_string())
+	}
+}
+
+impl fmt::Display for LenientBlankIdBuf {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+/// Report of the relabeling of non-conforming blank node identifiers.
+///
+/// See [`ExpandedDocument::relabel_non_conforming_blank_ids_with`].
+#[derive(Debug, Default, Clone)]
+pub struct BlankIdRelabeling(IndexMap<String, BlankIdBuf>);
+
+impl BlankIdRelabeling {
+	/// Returns the number of relabeled identifiers.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Checks if no identifier has been relabeled.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns the conforming identifier given to the non-conforming
+	/// `original` identifier, if any.
+	pub fn get(&self, original: &str) -> Option<&BlankId> {
+		self.0.get(original).map(Deref::deref)
+	}
+
+	/// Returns an iterator over the non-conforming identifiers and their
+	/// conforming replacement, in order of appearance.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &BlankId)> {
+		self.0
+			.iter()
+			.map(|(original, b)| (original.as_str(), b.deref()))
+	}
+}
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Replaces every non-conforming blank node identifier (an invalid
+	/// identifier starting with `_:`) with a conforming one, using the given
+	/// vocabulary.
+	///
+	/// The replacement is computed with [`LenientBlankIdBuf::to_conforming`],
+	/// suffixed with `-1`, `-2`, etc. if it is already used by another blank
+	/// node. Occurrences of the same non-conforming identifier are replaced
+	/// by the same identifier. Returns the replacements performed.
+	pub fn relabel_non_conforming_blank_ids_with<V>(
+		&mut self,
+		vocabulary: &mut V,
+	) -> BlankIdRelabeling
+	where
+		V: VocabularyMut<Iri = T, BlankId = B>,
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		let mut used: HashSet<String> = self
+			.blank_ids()
+			.into_iter()
+			.map(|b| vocabulary.blank_id(b).unwrap().to_string())
+			.collect();
+
+		let mut relabeling = BlankIdRelabeling::default();
+		let objects = self.take_objects();
+		*self = ExpandedDocument::from(objects).map_ids(
+			|i| i,
+			|id| match id {
+				Id::Invalid(s) => match LenientBlankIdBuf::new(s) {
+					Ok(original) => {
+						let b = relabeling
+							.0
+							.entry(original.0.clone())
+							.or_insert_with(|| fresh(&original, &mut used));
+						Id::Valid(ValidId::Blank(vocabulary.insert_blank_id(b)))
+					}
+					Err(s) => Id::Invalid(s),
+				},
+				id => id,
+			},
+		);
+
+		relabeling
+	}
+
+	/// Replaces every non-conforming blank node identifier with a conforming
+	/// one.
+	///
+	/// See [`Self::relabel_non_conforming_blank_ids_with`].
+	pub fn relabel_non_conforming_blank_ids(&mut self) -> BlankIdRelabeling
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+		(): VocabularyMut<Iri = T, BlankId = B>,
+	{
+		self.relabel_non_conforming_blank_ids_with(rdf_types::vocabulary::no_vocabulary_mut())
+	}
+}
+
+/// Returns a conforming identifier for `original` not in `used`, and marks
+/// it as used.
+fn fresh(original: &LenientBlankIdBuf, used: &mut HashSet<String>) -> BlankIdBuf {
+	let base = original.to_conforming();
+	let mut b = base.clone();
+	let mut n = 1;
+
+	while used.contains(b.as_str()) {
+		b = BlankIdBuf::new(format!("{base}-{n}")).unwrap();
+		n += 1
+	}
+
+	used.insert(b.to_string());
+	b
+}
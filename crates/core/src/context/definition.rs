@@ -365,6 +365,17 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the IRI of the remote context document that defined this
+	/// term, if any.
+	///
+	/// See [`NormalTermDefinition::source`].
+	pub fn source(&self) -> Option<&T> {
+		match self {
+			Self::Type(_) => None,
+			Self::Normal(d) => d.source.as_ref(),
+		}
+	}
+
 	pub fn context(&self) -> Option<&json_ld_syntax::context::Context> {
 		match self {
 			Self::Type(_) => None,
@@ -465,6 +476,17 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the IRI of the remote context document that defined this
+	/// term, if any.
+	///
+	/// See [`NormalTermDefinition::source`].
+	pub fn source(&self) -> Option<&'a T> {
+		match self {
+			Self::Type(_) => None,
+			Self::Normal(d) => d.source.as_ref(),
+		}
+	}
+
 	pub fn context(&self) -> Option<&'a json_ld_syntax::context::Context> {
 		match self {
 			Self::Type(_) => None,
@@ -513,6 +535,16 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 			Self::Normal(d) => d.typ.as_ref(),
 		}
 	}
+
+	/// Returns the default value of the term, if any.
+	///
+	/// See [`NormalTermDefinition::default_value`].
+	pub fn default_value(&self) -> Option<&'a json_syntax::Value> {
+		match self {
+			Self::Type(_) => None,
+			Self::Normal(d) => d.default.as_ref(),
+		}
+	}
 }
 
 impl<'a, T, B> Clone for TermDefinitionRef<'a, T, B> {
@@ -541,6 +573,9 @@ pub struct NormalTermDefinition<T = IriBuf, B = BlankIdBuf> {
 	// Optional base URL.
 	pub base_url: Option<T>,
 
+	// IRI of the remote context document defining the term, if any.
+	pub source: Option<T>,
+
 	// Optional context.
 	pub context: Option<Box<json_ld_syntax::context::Context>>,
 
@@ -561,6 +596,9 @@ pub struct NormalTermDefinition<T = IriBuf, B = BlankIdBuf> {
 
 	// Optional type mapping.
 	pub typ: Option<Type<T>>,
+
+	// Optional default value (`@default` extension).
+	pub default: Option<json_syntax::Value>,
 }
 
 impl<T, B> NormalTermDefinition<T, B> {
@@ -572,6 +610,24 @@ impl<T, B> NormalTermDefinition<T, B> {
 		self.base_url.as_ref()
 	}
 
+	/// Returns the IRI of the remote context document that defined (or last
+	/// redefined) this term.
+	///
+	/// Returns `None` if the term was defined by an inline context, including
+	/// type-scoped and property-scoped contexts applied during expansion.
+	pub fn source(&self) -> Option<&T> {
+		self.source.as_ref()
+	}
+
+	/// Returns the default value of the term, declared with the `@default`
+	/// extension entry of its definition.
+	///
+	/// This is the compacted form of the value, as it would appear in a
+	/// compacted document.
+	pub fn default_value(&self) -> Option<&json_syntax::Value> {
+		self.default.as_ref()
+	}
+
 	pub fn into_syntax_definition(
 		self,
 		vocabulary: &impl Vocabulary<Iri = T, BlankId = B>,
@@ -636,6 +692,7 @@ impl<T, B> NormalTermDefinition<T, B> {
 			prefix: if self.prefix { Some(true) } else { None },
 			propagate: None,
 			protected: if self.protected { Some(true) } else { None },
+			default: self.default,
 		}
 		.simplify()
 	}
@@ -651,6 +708,7 @@ impl<T, B> NormalTermDefinition<T, B> {
 			protected: self.protected,
 			reverse_property: self.reverse_property,
 			base_url: self.base_url.map(&mut map_iri),
+			source: self.source.map(&mut map_iri),
 			context: self.context,
 			container: self.container,
 			direction: self.direction,
@@ -658,6 +716,7 @@ impl<T, B> NormalTermDefinition<T, B> {
 			language: self.language,
 			nest: self.nest,
 			typ: self.typ.map(|t| t.map(map_iri)),
+			default: self.default,
 		}
 	}
 }
@@ -670,6 +729,7 @@ impl<T, B> Default for NormalTermDefinition<T, B> {
 			protected: false,
 			reverse_property: false,
 			base_url: None,
+			source: None,
 			typ: None,
 			language: None,
 			direction: None,
@@ -677,6 +737,7 @@ impl<T, B> Default for NormalTermDefinition<T, B> {
 			nest: None,
 			index: None,
 			container: Container::new(),
+			default: None,
 		}
 	}
 }
@@ -688,7 +749,7 @@ impl<'a, 'b, T: PartialEq, B: PartialEq> PartialEq<ModuloProtected<&'b NormalTer
 	for ModuloProtected<&'a NormalTermDefinition<T, B>>
 {
 	fn eq(&self, other: &ModuloProtected<&'b NormalTermDefinition<T, B>>) -> bool {
-		// NOTE we ignore the `protected` flag.
+		// NOTE we ignore the `protected` flag and the `source`.
 		self.0.prefix == other.0.prefix
 			&& self.0.reverse_property == other.0.reverse_property
 			&& self.0.language == other.0.language
@@ -700,6 +761,7 @@ impl<'a, 'b, T: PartialEq, B: PartialEq> PartialEq<ModuloProtected<&'b NormalTer
 			&& self.0.value == other.0.value
 			&& self.0.typ == other.0.typ
 			&& self.0.context == other.0.context
+			&& self.0.default == other.0.default
 	}
 }
 
@@ -732,6 +794,7 @@ impl<'a, 'b, T: PartialEq, B: PartialEq> PartialEq<ModuloProtected<TermDefinitio
 			&& self.0.value() == other.0.value()
 			&& self.0.typ() == other.0.typ()
 			&& self.0.context() == other.0.context()
+			&& self.0.default_value() == other.0.default_value()
 	}
 }
 
@@ -4,7 +4,6 @@ use super::BindingRef;
 use super::Context;
 use super::Key;
 use crate::{Container, Direction, LenientLangTag, LenientLangTagBuf, Nullable, Term, Type};
-use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
@@ -287,16 +286,22 @@ impl<'a, T: Clone + Hash + Eq, B: Clone + Hash + Eq> From<&'a Context<T, B>>
 	fn from(context: &'a Context<T, B>) -> Self {
 		let mut result = InverseContext::new();
 
+		let preferences = context.term_preferences().map(|p| &**p).unwrap_or_default();
+		let rank = |term: &str| {
+			preferences
+				.iter()
+				.position(|p| p == term)
+				.unwrap_or(preferences.len())
+		};
+
 		let mut definitions: Vec<_> = context.definitions().iter().collect();
 		definitions.sort_by(|a, b| {
 			let a = a.term().as_str();
 			let b = b.term().as_str();
-			let ord = a.len().cmp(&b.len());
-			if ord == Ordering::Equal {
-				a.cmp(b)
-			} else {
-				ord
-			}
+			rank(a)
+				.cmp(&rank(b))
+				.then_with(|| a.len().cmp(&b.len()))
+				.then_with(|| a.cmp(b))
 		});
 
 		for binding in definitions {
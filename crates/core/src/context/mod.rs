@@ -2,23 +2,33 @@
 mod definition;
 pub mod inverse;
 
-use crate::{Direction, LenientLangTag, LenientLangTagBuf, Term};
+use crate::{Direction, IndexedObject, LenientLangTag, LenientLangTagBuf, Term};
 use contextual::WithContext;
 use iref::IriBuf;
 use json_ld_syntax::{KeywordType, Nullable};
 use once_cell::sync::OnceCell;
 use rdf_types::{BlankIdBuf, Id, Vocabulary};
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::Arc;
 
 pub use json_ld_syntax::context::{
-	definition::{Key, KeyOrType, Type},
+	definition::{Key, KeyOrType, Type, Version},
 	term_definition::Nest,
 };
 
 pub use definition::*;
 pub use inverse::InverseContext;
 
+/// Expanded default values of the terms of a context.
+///
+/// Maps each term having a default value to the objects of its property once
+/// the default value is expanded, or to `None` if the default value cannot be
+/// expanded.
+pub type ExpandedDefaults<T = IriBuf, B = BlankIdBuf> =
+	HashMap<Key, Option<Vec<IndexedObject<T, B>>>>;
+
 /// Processed JSON-LD context.
 ///
 /// Represents the result of the [context processing algorithm][1] implemented
@@ -34,7 +44,10 @@ pub struct Context<T = IriBuf, B = BlankIdBuf> {
 	default_base_direction: Option<Direction>,
 	previous_context: Option<Box<Self>>,
 	definitions: Definitions<T, B>,
+	term_preferences: Option<Arc<[String]>>,
+	version: Option<Version>,
 	inverse: OnceCell<InverseContext<T, B>>,
+	expanded_defaults: OnceCell<ExpandedDefaults<T, B>>,
 }
 
 impl<T, B> Default for Context<T, B> {
@@ -47,7 +60,10 @@ impl<T, B> Default for Context<T, B> {
 			default_base_direction: None,
 			previous_context: None,
 			definitions: Definitions::default(),
+			term_preferences: None,
+			version: None,
 			inverse: OnceCell::default(),
+			expanded_defaults: OnceCell::default(),
 		}
 	}
 }
@@ -68,7 +84,10 @@ impl<T, B> Context<T, B> {
 			default_base_direction: None,
 			previous_context: None,
 			definitions: Definitions::default(),
+			term_preferences: None,
+			version: None,
 			inverse: OnceCell::default(),
+			expanded_defaults: OnceCell::default(),
 		}
 	}
 
@@ -154,6 +173,19 @@ impl<T, B> Context<T, B> {
 		self.definitions.is_empty()
 	}
 
+	/// Checks if this context is equivalent to a newly-initialized context.
+	///
+	/// Such context defines no term, vocabulary mapping, default language or
+	/// default base direction, and has no previous context. Its base IRI,
+	/// term preferences and declared version are not considered.
+	pub fn is_initial(&self) -> bool {
+		self.definitions.is_empty()
+			&& self.vocabulary.is_none()
+			&& self.default_language.is_none()
+			&& self.default_base_direction.is_none()
+			&& self.previous_context.is_none()
+	}
+
 	/// Returns a handle to the term definitions.
 	pub fn definitions(&self) -> &Definitions<T, B> {
 		&self.definitions
@@ -179,6 +211,60 @@ impl<T, B> Context<T, B> {
 		self.inverse.get_or_init(|| self.into())
 	}
 
+	/// Returns the expanded default values of the terms of this context, if
+	/// they were stored with [`Self::init_expanded_defaults`].
+	///
+	/// They are used by the compaction algorithm to omit the properties equal
+	/// to their default value, and are reset when the context is modified.
+	pub fn expanded_defaults(&self) -> Option<&ExpandedDefaults<T, B>> {
+		self.expanded_defaults.get()
+	}
+
+	/// Stores the expanded default values of the terms of this context,
+	/// unless they were already stored, and returns the stored values.
+	pub fn init_expanded_defaults(
+		&self,
+		defaults: ExpandedDefaults<T, B>,
+	) -> &ExpandedDefaults<T, B> {
+		self.expanded_defaults.get_or_init(|| defaults)
+	}
+
+	/// Returns the terms to prefer when multiple terms can be selected to
+	/// compact the same IRI, if any.
+	pub fn term_preferences(&self) -> Option<&Arc<[String]>> {
+		self.term_preferences.as_ref()
+	}
+
+	/// Sets the terms to prefer when multiple terms can be selected to compact
+	/// the same IRI.
+	///
+	/// During term selection, a term appearing in `preferences` is chosen
+	/// over any term that does not, and over any term appearing after it.
+	/// Remaining ties are broken by choosing the shortest term, then the
+	/// lexicographically least. Preferences are kept by contexts derived
+	/// from this one by context processing.
+	pub fn set_term_preferences(&mut self, preferences: Option<Arc<[String]>>) {
+		self.inverse.take();
+		self.term_preferences = preferences
+	}
+
+	/// Returns the JSON-LD version declared with `@version` by the processed
+	/// context definitions, if any.
+	///
+	/// A context declaring a version requires the `json-ld-1.1` processing
+	/// mode: processing another context definition on top of it in
+	/// `json-ld-1.0` mode fails with a processing mode conflict. A `null`
+	/// context resets the declared version along with the rest of the
+	/// context.
+	pub fn declared_version(&self) -> Option<Version> {
+		self.version
+	}
+
+	/// Sets the declared JSON-LD version.
+	pub fn set_declared_version(&mut self, version: Option<Version>) {
+		self.version = version
+	}
+
 	/// Sets the normal definition for the given term `key`.
 	pub fn set_normal(
 		&mut self,
@@ -186,6 +272,7 @@ impl<T, B> Context<T, B> {
 		definition: Option<NormalTermDefinition<T, B>>,
 	) -> Option<NormalTermDefinition<T, B>> {
 		self.inverse.take();
+		self.expanded_defaults.take();
 		self.definitions.set_normal(key, definition)
 	}
 
@@ -197,30 +284,35 @@ impl<T, B> Context<T, B> {
 	/// Sets the base IRI.
 	pub fn set_base_iri(&mut self, iri: Option<T>) {
 		self.inverse.take();
+		self.expanded_defaults.take();
 		self.base_iri = iri
 	}
 
 	/// Sets the `@vocab` value.
 	pub fn set_vocabulary(&mut self, vocab: Option<Term<T, B>>) {
 		self.inverse.take();
+		self.expanded_defaults.take();
 		self.vocabulary = vocab;
 	}
 
 	/// Sets the default `@language` value.
 	pub fn set_default_language(&mut self, lang: Option<LenientLangTagBuf>) {
 		self.inverse.take();
+		self.expanded_defaults.take();
 		self.default_language = lang;
 	}
 
 	/// Sets the default `@direction` value.
 	pub fn set_default_base_direction(&mut self, dir: Option<Direction>) {
 		self.inverse.take();
+		self.expanded_defaults.take();
 		self.default_base_direction = dir;
 	}
 
 	/// Sets the previous context.
 	pub fn set_previous_context(&mut self, previous: Self) {
 		self.inverse.take();
+		self.expanded_defaults.take();
 		self.previous_context = Some(Box::new(previous))
 	}
 
@@ -241,7 +333,7 @@ impl<T, B> Context<T, B> {
 			propagate: None,
 			protected: None,
 			type_: type_.map(TypeTermDefinition::into_syntax_definition),
-			version: None,
+			version: self.version,
 			vocab: self.vocabulary.map(|v| match v {
 				Term::Null => Nullable::Null,
 				Term::Id(r) => Nullable::Some(r.with(vocabulary).to_string().into()),
@@ -277,7 +369,10 @@ impl<T, B> Context<T, B> {
 				.previous_context
 				.map(|c| Box::new((*c).map_ids_with(map_iri, map_id))),
 			definitions: self.definitions.map_ids(map_iri, map_id),
+			term_preferences: self.term_preferences,
+			version: self.version,
 			inverse: OnceCell::new(),
+			expanded_defaults: OnceCell::new(),
 		}
 	}
 }
@@ -320,7 +415,10 @@ impl<T: Clone, B: Clone> Clone for Context<T, B> {
 			default_base_direction: self.default_base_direction,
 			previous_context: self.previous_context.clone(),
 			definitions: self.definitions.clone(),
+			term_preferences: self.term_preferences.clone(),
+			version: self.version,
 			inverse: OnceCell::default(),
+			expanded_defaults: OnceCell::default(),
 		}
 	}
 }
@@ -1,23 +1,46 @@
-use crate::object::{FragmentRef, InvalidExpandedJson, Traverse};
-use crate::{Id, Indexed, IndexedObject, Node, Object, Relabel, TryFromJson};
+use crate::object::{lenient, FragmentRef, InvalidExpandedJson, Ref, Traverse};
+use crate::{warning, Id, Indexed, IndexedObject, Node, Object, Relabel, TryFromJson};
 use hashbrown::HashMap;
 use indexmap::IndexSet;
 use iref::IriBuf;
+use once_cell::sync::OnceCell;
 use rdf_types::vocabulary::VocabularyMut;
-use rdf_types::{BlankIdBuf, Generator, Vocabulary};
+use rdf_types::{BlankIdBuf, Generator, InvalidBlankId, Subject, Vocabulary};
 use std::collections::HashSet;
 use std::hash::Hash;
+use std::num::NonZeroUsize;
 
 /// Result of the document expansion algorithm.
 ///
 /// It is just an alias for a set of (indexed) objects.
+///
+/// # Iteration order
+///
+/// Top level objects are iterated in insertion order. For a document
+/// returned by the expansion algorithm, this is the order in which they
+/// appear in the input document, duplicates being only kept once. The
+/// properties of each node are iterated in the order in which the expansion
+/// algorithm processed them, which is the order of the input document
+/// entries (or their lexicographic order with the `ordered` expansion
+/// option). The values of a property keep the order of the input document.
+///
+/// This order is stable: expanding the same document twice gives the same
+/// order. It does however depend on the way the input document is written,
+/// so that reordering its entries reorders the expanded document. Use
+/// [`Self::sort_by_id`] and [`Self::sort_properties`] to get an order that
+/// only depends on the identifiers of nodes and properties.
 #[derive(Debug, Clone)]
-pub struct ExpandedDocument<T = IriBuf, B = BlankIdBuf>(IndexSet<IndexedObject<T, B>>);
+pub struct ExpandedDocument<T = IriBuf, B = BlankIdBuf>(IndexSet<IndexedObject<T, B>>, IndexCache);
+
+/// Lazily built map from `@index` values to the position of the objects
+/// carrying them.
+#[derive(Debug, Clone, Default)]
+struct IndexCache(OnceCell<HashMap<String, Vec<usize>>>);
 
 impl<T, B> Default for ExpandedDocument<T, B> {
 	#[inline(always)]
 	fn default() -> Self {
-		Self(IndexSet::new())
+		Self(IndexSet::new(), IndexCache::default())
 	}
 }
 
@@ -62,6 +85,37 @@ impl<T, B> ExpandedDocument<T, B> {
 		self.traverse().filter(f).count()
 	}
 
+	/// Returns the top level objects with the given `@index` value, in
+	/// document order.
+	///
+	/// The first call builds a map from index values to objects, reused by
+	/// subsequent calls until the document is modified.
+	pub fn by_index<'a>(
+		&'a self,
+		index: &str,
+	) -> impl 'a + Iterator<Item = &'a IndexedObject<T, B>> {
+		let map = self.1 .0.get_or_init(|| {
+			let mut map: HashMap<String, Vec<usize>> = HashMap::new();
+			for (i, object) in self.0.iter().enumerate() {
+				if let Some(index) = object.index() {
+					map.entry(index.to_owned()).or_default().push(i)
+				}
+			}
+			map
+		});
+
+		map.get(index)
+			.into_iter()
+			.flatten()
+			.filter_map(|i| self.0.get_index(*i))
+	}
+
+	/// Takes the objects out of the document, invalidating the index map.
+	fn take_objects(&mut self) -> IndexSet<IndexedObject<T, B>> {
+		self.1 = IndexCache::default();
+		std::mem::take(&mut self.0)
+	}
+
 	/// Give an identifier (`@id`) to every nodes using the given generator to
 	/// generate fresh identifiers for anonymous nodes.
 	#[inline(always)]
@@ -73,7 +127,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Eq + Hash,
 		B: Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = self.take_objects();
 		for mut object in objects {
 			object.identify_all_with(vocabulary, generator);
 			self.0.insert(object);
@@ -104,7 +158,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Clone + Eq + Hash,
 		B: Clone + Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = self.take_objects();
 		let mut relabeling = HashMap::new();
 		let mut buffer = ryu_js::Buffer::new();
 		for mut object in objects {
@@ -137,7 +191,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Clone + Eq + Hash,
 		B: Clone + Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = self.take_objects();
 		let mut relabeling = HashMap::new();
 		for mut object in objects {
 			object.relabel_with(vocabulary, generator, &mut relabeling);
@@ -165,7 +219,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Eq + Hash,
 		B: Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = self.take_objects();
 		for mut object in objects {
 			object.canonicalize_with(buffer);
 			self.0.insert(object);
@@ -197,6 +251,7 @@ impl<T, B> ExpandedDocument<T, B> {
 				.into_iter()
 				.map(|i| i.map_inner(|o| o.map_ids(&mut map_iri, &mut map_id)))
 				.collect(),
+			IndexCache::default(),
 		)
 	}
 
@@ -210,6 +265,422 @@ impl<T, B> ExpandedDocument<T, B> {
 			.collect()
 	}
 
+	/// Renames every blank node identifier of the document to a fresh
+	/// identifier generated with the given `prefix`, using the given
+	/// `vocabulary`.
+	///
+	/// For instance with the prefix `doc1-`, the blank nodes `_:b0` and
+	/// `_:alice` are renamed `_:doc1-0` and `_:doc1-1`. Giving a different
+	/// prefix to each document before merging them ensures that their blank
+	/// nodes are kept apart. Nodes without identifier are left as they are.
+	///
+	/// Fails if `_:prefix` is not itself a valid blank node identifier, in
+	/// which case the document is left untouched.
+	pub fn isolate_blank_nodes_with<V>(
+		&mut self,
+		vocabulary: &mut V,
+		prefix: &str,
+	) -> Result<(), InvalidBlankId<String>>
+	where
+		V: VocabularyMut<Iri = T, BlankId = B>,
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		BlankIdBuf::new(format!("_:{prefix}"))?;
+		let mut generator = rdf_types::generator::Blank::new_with_prefix(prefix.to_owned());
+		self.rename_blank_ids(vocabulary, &mut generator);
+		Ok(())
+	}
+
+	/// Renames every blank node identifier of the document to a fresh
+	/// identifier generated with the given `prefix`.
+	///
+	/// See [`Self::isolate_blank_nodes_with`] for more details.
+	pub fn isolate_blank_nodes(&mut self, prefix: &str) -> Result<(), InvalidBlankId<String>>
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+		(): VocabularyMut<Iri = T, BlankId = B>,
+	{
+		self.isolate_blank_nodes_with(rdf_types::vocabulary::no_vocabulary_mut(), prefix)
+	}
+
+	/// Merges the `other` document into this one, using the given
+	/// `vocabulary`.
+	///
+	/// Before being merged, the blank nodes of `other` are isolated with
+	/// [`Self::isolate_blank_nodes_with`] using a prefix of the form `m<n>-`
+	/// that no blank node label of this document starts with. This way
+	/// blank nodes of the two documents never clash. Use
+	/// [`Self::merge_unisolated`] to merge the documents as they are.
+	pub fn merge_with<V>(&mut self, vocabulary: &mut V, mut other: Self)
+	where
+		V: VocabularyMut<Iri = T, BlankId = B>,
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		// Identifiers unknown to the vocabulary cannot clash with the ones it
+		// generates.
+		let labels: Vec<&str> = self
+			.blank_ids()
+			.into_iter()
+			.filter_map(|b| vocabulary.blank_id(b))
+			.map(|b| b.suffix())
+			.collect();
+
+		let mut n = 0usize;
+		let prefix = loop {
+			let prefix = format!("m{n}-");
+			if labels.iter().all(|l| !l.starts_with(prefix.as_str())) {
+				break prefix;
+			}
+
+			n += 1
+		};
+
+		let mut generator = rdf_types::generator::Blank::new_with_prefix(prefix);
+		other.rename_blank_ids(vocabulary, &mut generator);
+		self.merge_unisolated(other)
+	}
+
+	/// Merges the `other` document into this one.
+	///
+	/// See [`Self::merge_with`] for more details.
+	pub fn merge(&mut self, other: Self)
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+		(): VocabularyMut<Iri = T, BlankId = B>,
+	{
+		self.merge_with(rdf_types::vocabulary::no_vocabulary_mut(), other)
+	}
+
+	/// Renames every blank node identifier of the document to a fresh
+	/// identifier produced by `generator`.
+	fn rename_blank_ids<V, G>(&mut self, vocabulary: &mut V, generator: &mut G)
+	where
+		V: VocabularyMut<Iri = T, BlankId = B>,
+		G: Generator<V>,
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		let mut renaming: HashMap<B, Subject<T, B>> = HashMap::new();
+		let objects = self.take_objects();
+		*self = ExpandedDocument::from(objects).map_ids(
+			|i| i,
+			|id| match id {
+				Id::Valid(Subject::Blank(b)) => Id::Valid(
+					renaming
+						.entry(b)
+						.or_insert_with(|| generator.next(vocabulary))
+						.clone(),
+				),
+				id => id,
+			},
+		);
+	}
+
+	/// Merges the `other` document into this one without renaming any blank
+	/// node.
+	///
+	/// Blank nodes sharing the same label in both documents will be
+	/// considered to be the same node.
+	pub fn merge_unisolated(&mut self, other: Self)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		self.extend(other)
+	}
+
+	/// Removes the `@included` entry of every node of the document.
+	///
+	/// See [`Node::drop_included`].
+	pub fn drop_included(&mut self)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		let objects = self.take_objects();
+		for mut object in objects {
+			if let Some(node) = object.inner_mut().as_node_mut() {
+				node.drop_included()
+			}
+
+			self.0.insert(object);
+		}
+	}
+
+	/// Inlines the `@included` nodes of the document where they are
+	/// referenced.
+	///
+	/// See [`Node::inline_included`].
+	pub fn inline_included(&mut self)
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		let objects = self.take_objects();
+		for mut object in objects {
+			if let Some(node) = object.inner_mut().as_node_mut() {
+				node.inline_included()
+			}
+
+			self.0.insert(object);
+		}
+	}
+
+	/// Removes the duplicate property values of every node of the document.
+	///
+	/// See [`Node::dedup_values`].
+	pub fn dedup(&mut self)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		let objects = self.take_objects();
+		for mut object in objects {
+			object.inner_mut().dedup_values();
+			self.0.insert(object);
+		}
+	}
+
+	/// Checks if the document contains a list of lists.
+	pub fn has_nested_lists(&self) -> bool {
+		self.traverse().any(|fragment| match fragment {
+			FragmentRef::Object(Object::List(list)) => list.has_nested_list(),
+			FragmentRef::IndexedObject(object) => object
+				.inner()
+				.as_list()
+				.is_some_and(|list| list.has_nested_list()),
+			_ => false,
+		})
+	}
+
+	/// Splices the items of every list nested in another list into the
+	/// enclosing list.
+	///
+	/// See [`Object::flatten_nested_lists`].
+	pub fn flatten_nested_lists(&mut self)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		let objects = self.take_objects();
+		for mut object in objects {
+			object.inner_mut().flatten_nested_lists();
+			self.0.insert(object);
+		}
+	}
+
+	/// Sorts the top level objects by node identifier.
+	///
+	/// Node objects without identifier come first, followed by identified
+	/// nodes in increasing identifier order, and finally value and list
+	/// objects. The sort is stable: objects with equal keys keep their
+	/// relative order.
+	///
+	/// The properties of nodes are not sorted, see
+	/// [`Self::sort_properties`].
+	pub fn sort_by_id(&mut self)
+	where
+		T: Ord,
+		B: Ord,
+	{
+		fn key<T, B>(object: &IndexedObject<T, B>) -> (bool, Option<&Id<T, B>>) {
+			match object.inner().as_node() {
+				Some(node) => (false, node.id.as_ref()),
+				None => (true, None),
+			}
+		}
+
+		self.1 = IndexCache::default();
+		self.0.sort_by(|a, b| key(a).cmp(&key(b)))
+	}
+
+	/// Sorts the properties of every node of the document by identifier.
+	///
+	/// See [`Node::sort_properties`].
+	pub fn sort_properties(&mut self)
+	where
+		T: Ord + Eq + Hash,
+		B: Ord + Eq + Hash,
+	{
+		let objects = self.take_objects();
+		for mut object in objects {
+			object.inner_mut().sort_properties();
+			self.0.insert(object);
+		}
+	}
+
+	/// Splits the document into one document per graph.
+	///
+	/// The first returned document is the default graph: it contains every
+	/// top level object, stripped of its `@graph` entry. Nodes that were only
+	/// graph objects are left out of it. Each following document contains a
+	/// top level graph object, with the `@id` and `@index` of the node it was
+	/// taken from. Graph objects sharing the same identifier are put in the
+	/// same document.
+	///
+	/// Merging the returned documents with [`Self::merge_unisolated`] gives
+	/// back an equivalent document.
+	pub fn partition_by_graph(self) -> Vec<Self>
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		let mut default_graph = Self::new();
+		let mut named_graphs: Vec<Self> = Vec::new();
+		let mut graph_names: HashMap<Id<T, B>, usize> = HashMap::new();
+
+		for object in self {
+			let (object, index) = object.into_parts();
+			match object {
+				Object::Node(mut node) => {
+					if let Some(graph) = node.graph.take() {
+						let mut graph_node = Node::new();
+						graph_node.id = node.id.clone();
+						graph_node.graph = Some(graph);
+						let graph_object = Indexed::new(Object::node(graph_node), index.clone());
+
+						match &node.id {
+							Some(id) => match graph_names.get(id) {
+								Some(&i) => {
+									named_graphs[i].insert(graph_object);
+								}
+								None => {
+									graph_names.insert(id.clone(), named_graphs.len());
+									named_graphs.push(std::iter::once(graph_object).collect())
+								}
+							},
+							None => named_graphs.push(std::iter::once(graph_object).collect()),
+						}
+
+						if node.is_empty() {
+							continue;
+						}
+					}
+
+					default_graph.insert(Indexed::new(Object::Node(node), index));
+				}
+				object => {
+					default_graph.insert(Indexed::new(object, index));
+				}
+			}
+		}
+
+		let mut result = Vec::with_capacity(named_graphs.len() + 1);
+		result.push(default_graph);
+		result.extend(named_graphs);
+		result
+	}
+
+	/// Splits the document into documents describing at most `chunk_size`
+	/// subjects each.
+	///
+	/// Top level node objects sharing the same `@id` are put in the same
+	/// document. Anonymous nodes and top level values or lists each count as
+	/// a distinct subject. The order of the objects is preserved.
+	pub fn partition_by_subject(self, chunk_size: NonZeroUsize) -> Vec<Self>
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		let chunk_size = chunk_size.get();
+		let objects: Vec<_> = self.into_iter().collect();
+		let mut subjects = HashMap::new();
+		let mut groups = Vec::with_capacity(objects.len());
+		let mut count = 0;
+		for object in &objects {
+			let group = match object.inner().as_node().and_then(|node| node.id.as_ref()) {
+				Some(id) => *subjects.entry(id).or_insert(count),
+				None => count,
+			};
+
+			if group == count {
+				count += 1
+			}
+
+			groups.push(group)
+		}
+
+		let mut result: Vec<Self> = (0..count.div_ceil(chunk_size))
+			.map(|_| Self::new())
+			.collect();
+		for (object, group) in objects.into_iter().zip(groups) {
+			result[group / chunk_size].insert(object);
+		}
+
+		result
+	}
+
+	/// Returns a page of the document.
+	///
+	/// Top level objects are sorted by `sort_key` (objects with equal keys
+	/// keep their document order), and the page contains the objects at
+	/// positions `offset..offset + limit`. Top level node objects describing
+	/// nodes referenced by the page, directly or through at most `depth`
+	/// references, are added after them, so that clients do not need to
+	/// fetch other pages to resolve those references.
+	///
+	/// Requesting the same page of the same document always gives the same
+	/// result.
+	pub fn page<K: Ord>(
+		&self,
+		offset: usize,
+		limit: usize,
+		depth: usize,
+		mut sort_key: impl FnMut(&IndexedObject<T, B>) -> K,
+	) -> Self
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		let mut objects: Vec<_> = self.iter().collect();
+		objects.sort_by_cached_key(|object| sort_key(object));
+
+		let mut subjects: HashMap<&Id<T, B>, Vec<&IndexedObject<T, B>>> = HashMap::new();
+		for object in self {
+			if let Some(id) = object.inner().as_node().and_then(|node| node.id.as_ref()) {
+				subjects.entry(id).or_default().push(object)
+			}
+		}
+
+		let mut result = Self::new();
+		let mut frontier: Vec<_> = objects.into_iter().skip(offset).take(limit).collect();
+		for object in &frontier {
+			result.insert((*object).clone());
+		}
+
+		for _ in 0..depth {
+			let mut next = Vec::new();
+			for object in frontier {
+				for fragment in object.traverse() {
+					let Some(Ref::Node(node)) = fragment.into_ref() else {
+						continue;
+					};
+
+					if let Some(described) = node.id.as_ref().and_then(|id| subjects.get(id)) {
+						for &subject in described {
+							if !result.0.contains(subject) {
+								result.insert(subject.clone());
+								next.push(subject)
+							}
+						}
+					}
+				}
+			}
+
+			if next.is_empty() {
+				break;
+			}
+
+			frontier = next
+		}
+
+		result
+	}
+
 	/// Returns the main node object of the document, if any.
 	///
 	/// The main node is the unique top level (root) node object. If multiple
@@ -254,6 +725,7 @@ impl<T, B> ExpandedDocument<T, B> {
 impl<T: Hash + Eq, B: Hash + Eq> ExpandedDocument<T, B> {
 	#[inline(always)]
 	pub fn insert(&mut self, object: IndexedObject<T, B>) -> bool {
+		self.1 = IndexCache::default();
 		self.0.insert(object)
 	}
 }
@@ -268,6 +740,26 @@ impl<T: Eq + Hash, B: Eq + Hash> From<Indexed<Node<T, B>>> for ExpandedDocument<
 	}
 }
 
+impl<T: Eq + Hash, B: Eq + Hash> ExpandedDocument<T, B> {
+	/// Converts an "almost expanded" JSON document into an expanded document.
+	///
+	/// Contrarily to [`TryFromJson::try_from_json_in`], common deviations
+	/// from the expanded form (single values not wrapped in arrays, bare
+	/// strings not wrapped in value objects) are fixed before the
+	/// conversion. Each applied fix is reported to the `warnings` handler.
+	pub fn try_from_lenient_json_in<V>(
+		vocabulary: &mut V,
+		value: json_syntax::Value,
+		mut warnings: impl warning::Handler<V, lenient::Fix>,
+	) -> Result<Self, InvalidExpandedJson>
+	where
+		V: VocabularyMut<Iri = T, BlankId = B>,
+	{
+		let value = lenient::normalize_document(vocabulary, value, &mut warnings);
+		Self::try_from_json_in(vocabulary, value)
+	}
+}
+
 impl<T: Eq + Hash, B: Eq + Hash> TryFromJson<T, B> for ExpandedDocument<T, B> {
 	fn try_from_json_in(
 		vocabulary: &mut impl VocabularyMut<Iri = T, BlankId = B>,
@@ -277,8 +769,10 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJson<T, B> for ExpandedDocument<T, B> {
 			json_syntax::Value::Array(items) => {
 				let mut result = Self::new();
 
-				for item in items {
-					result.insert(Indexed::try_from_json_in(vocabulary, item)?);
+				for (i, item) in items.into_iter().enumerate() {
+					result.insert(
+						Indexed::try_from_json_in(vocabulary, item).map_err(|e| e.at_index(i))?,
+					);
 				}
 
 				Ok(result)
@@ -331,18 +825,19 @@ impl<T, B> Iterator for IntoIter<T, B> {
 
 impl<T: Hash + Eq, B: Hash + Eq> FromIterator<IndexedObject<T, B>> for ExpandedDocument<T, B> {
 	fn from_iter<I: IntoIterator<Item = IndexedObject<T, B>>>(iter: I) -> Self {
-		Self(iter.into_iter().collect())
+		Self(iter.into_iter().collect(), IndexCache::default())
 	}
 }
 
 impl<T: Hash + Eq, B: Hash + Eq> Extend<IndexedObject<T, B>> for ExpandedDocument<T, B> {
 	fn extend<I: IntoIterator<Item = IndexedObject<T, B>>>(&mut self, iter: I) {
+		self.1 = IndexCache::default();
 		self.0.extend(iter)
 	}
 }
 
 impl<T, B> From<IndexSet<IndexedObject<T, B>>> for ExpandedDocument<T, B> {
 	fn from(set: IndexSet<IndexedObject<T, B>>) -> Self {
-		Self(set)
+		Self(set, IndexCache::default())
 	}
 }
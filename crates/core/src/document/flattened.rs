@@ -1,6 +1,8 @@
 use rdf_types::{Generator, Vocabulary};
 
-use crate::{IdentifyAll, IndexedNode, Relabel};
+use crate::{
+	ExpandedDocument, IdentifyAll, Indexed, IndexedNode, IndexedObject, Node, Object, Relabel,
+};
 use std::{collections::HashSet, hash::Hash};
 
 /// Result of the document flattening algorithm.
@@ -41,3 +43,109 @@ impl<T, B> Relabel<T, B> for FlattenedDocument<T, B> {
 }
 
 pub type UnorderedFlattenedDocument<T, B> = HashSet<IndexedNode<T, B>>;
+
+/// Error returned when converting an [`ExpandedDocument`] that is not in
+/// flattened form into a [`FlattenedDocument`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum NotFlattened {
+	/// A top level or graph object is not a node object.
+	#[error("not a node object")]
+	NotANode,
+
+	/// A node object has no identifier.
+	#[error("anonymous node object")]
+	AnonymousNode,
+
+	/// A node object is nested in another, instead of being referenced.
+	#[error("embedded node object")]
+	EmbeddedNode,
+
+	/// A node object has an `@included` entry.
+	#[error("unexpected `@included` entry")]
+	Included,
+
+	/// A node object has an `@reverse` entry.
+	#[error("unexpected `@reverse` entry")]
+	Reverse,
+}
+
+impl<T: Eq + Hash, B: Eq + Hash> From<FlattenedDocument<T, B>> for ExpandedDocument<T, B> {
+	/// Turns the flattened nodes into top level node objects.
+	fn from(document: FlattenedDocument<T, B>) -> Self {
+		document
+			.into_iter()
+			.map(|node| node.map_inner(Object::node))
+			.collect()
+	}
+}
+
+impl<T, B> TryFrom<ExpandedDocument<T, B>> for FlattenedDocument<T, B> {
+	type Error = NotFlattened;
+
+	/// Checks that the document is in flattened form and returns its nodes.
+	///
+	/// Every top level object must be an identified node object, whose
+	/// properties only refer to other nodes through node references, and
+	/// without `@included` or `@reverse` entries. Graph objects must only
+	/// contain such nodes.
+	fn try_from(document: ExpandedDocument<T, B>) -> Result<Self, Self::Error> {
+		document
+			.into_iter()
+			.map(|object| {
+				let (object, index) = object.into_parts();
+				match object {
+					Object::Node(node) => {
+						check_flattened_node(&node)?;
+						Ok(Indexed::new(*node, index))
+					}
+					_ => Err(NotFlattened::NotANode),
+				}
+			})
+			.collect()
+	}
+}
+
+fn check_flattened_node<T, B>(node: &Node<T, B>) -> Result<(), NotFlattened> {
+	if node.id.is_none() {
+		return Err(NotFlattened::AnonymousNode);
+	}
+
+	if node.included().is_some() {
+		return Err(NotFlattened::Included);
+	}
+
+	if node.reverse_properties().is_some() {
+		return Err(NotFlattened::Reverse);
+	}
+
+	for (_, objects) in node.properties() {
+		for object in objects {
+			check_flattened_value(object)?
+		}
+	}
+
+	if let Some(graph) = node.graph() {
+		for object in graph {
+			match object.inner() {
+				Object::Node(node) => check_flattened_node(node)?,
+				_ => return Err(NotFlattened::NotANode),
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn check_flattened_value<T, B>(object: &IndexedObject<T, B>) -> Result<(), NotFlattened> {
+	match object.inner() {
+		Object::Value(_) => Ok(()),
+		Object::Node(node) => {
+			if node.id.is_some() && node.is_empty() {
+				Ok(())
+			} else {
+				Err(NotFlattened::EmbeddedNode)
+			}
+		}
+		Object::List(list) => list.iter().try_for_each(check_flattened_value),
+	}
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use iref::Iri;
+
+use crate::{Profile, RemoteDocument, StandardProfile};
+
+/// JSON-LD document form.
+///
+/// See: <https://www.w3.org/TR/json-ld11/#forms-of-json-ld>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DocumentForm {
+	/// Expanded document form.
+	Expanded,
+
+	/// Compacted document form.
+	Compacted,
+
+	/// Flattened document form.
+	Flattened,
+
+	/// Framed document form.
+	Framed,
+}
+
+impl DocumentForm {
+	/// Returns the form matching the given profile, if any.
+	pub fn from_profile(profile: StandardProfile) -> Option<Self> {
+		match profile {
+			StandardProfile::Expanded => Some(Self::Expanded),
+			StandardProfile::Compacted => Some(Self::Compacted),
+			StandardProfile::Flattened => Some(Self::Flattened),
+			StandardProfile::Framed => Some(Self::Framed),
+			StandardProfile::Context => None,
+		}
+	}
+
+	/// Returns the form identified by the given IRI
+	/// (`http://www.w3.org/ns/json-ld#expanded`, etc.), if any.
+	pub fn from_iri(iri: &Iri) -> Option<Self> {
+		StandardProfile::from_iri(iri).and_then(Self::from_profile)
+	}
+
+	/// Returns the form declared by the `profile` of the given remote
+	/// document, if any.
+	///
+	/// A flattened or framed document is also compacted when it has a
+	/// context, hence the most specific declared form is returned.
+	pub fn of<I, T>(remote: &RemoteDocument<I, T>) -> Option<Self> {
+		remote
+			.profile()
+			.iter()
+			.filter_map(|p| match p {
+				Profile::Standard(p) => Self::from_profile(*p),
+				Profile::Custom(_) => None,
+			})
+			.max_by_key(|form| match form {
+				Self::Compacted => 0,
+				Self::Expanded => 1,
+				Self::Flattened => 2,
+				Self::Framed => 3,
+			})
+	}
+
+	/// Detects the form of the given document from its structure.
+	///
+	/// Only the expanded form can be told apart this way: it is an array of
+	/// objects without context, whose entries are keywords or absolute IRIs
+	/// bound to arrays. Compacted, flattened and framed documents can share
+	/// the same structure, so `None` is returned for any other document.
+	pub fn detect(document: &json_syntax::Value) -> Option<Self> {
+		match document {
+			json_syntax::Value::Array(items) if items.iter().all(is_expanded_object) => {
+				Some(Self::Expanded)
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns the profile identifying this form.
+	pub fn profile(&self) -> StandardProfile {
+		match self {
+			Self::Expanded => StandardProfile::Expanded,
+			Self::Compacted => StandardProfile::Compacted,
+			Self::Flattened => StandardProfile::Flattened,
+			Self::Framed => StandardProfile::Framed,
+		}
+	}
+
+	/// Returns the IRI identifying this form.
+	pub fn iri(&self) -> &'static Iri {
+		self.profile().iri()
+	}
+
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Expanded => "expanded",
+			Self::Compacted => "compacted",
+			Self::Flattened => "flattened",
+			Self::Framed => "framed",
+		}
+	}
+}
+
+impl fmt::Display for DocumentForm {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
+	}
+}
+
+/// Checks that the given value is an object in expanded form.
+fn is_expanded_object(value: &json_syntax::Value) -> bool {
+	let Some(object) = value.as_object() else {
+		return false;
+	};
+
+	object.iter().all(|entry| match entry.key.as_str() {
+		"@context" => false,
+		// Literal values, not checked.
+		"@value" => true,
+		"@graph" | "@list" | "@set" | "@included" => entry
+			.value
+			.as_array()
+			.is_some_and(|items| items.iter().all(is_expanded_object)),
+		"@reverse" => is_expanded_object(&entry.value),
+		key if key.starts_with('@') => true,
+		key => {
+			key.contains(':')
+				&& entry
+					.value
+					.as_array()
+					.is_some_and(|items| items.iter().all(is_expanded_object))
+		}
+	})
+}
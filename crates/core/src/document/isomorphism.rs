@@ -0,0 +1,327 @@
+//! Blank node isomorphism between expanded documents.
+//!
+//! Blank node identifiers are local to a document: two documents that only
+//! differ by the labels of their blank nodes describe the same data. The
+//! isomorphism check first builds, for each document, a graph whose vertices
+//! are the blank node identifiers and the objects of the document. Vertices
+//! are then partitioned by color refinement, coloring each vertex with the
+//! colors of its neighbors. Blank nodes that cannot be distinguished this way
+//! are then individualized one at a time, backtracking on failure.
+use super::ExpandedDocument;
+use crate::object::{List, Node, Object};
+use crate::{utils, Id, IndexedObject, ValidId};
+use hashbrown::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Identifier, with the blank node identifiers left out.
+#[derive(Hash)]
+enum Term<'a, T> {
+	Iri(&'a T),
+	Invalid(&'a str),
+	Blank,
+}
+
+impl<'a, T, B> From<&'a Id<T, B>> for Term<'a, T> {
+	fn from(id: &'a Id<T, B>) -> Self {
+		match id {
+			Id::Valid(ValidId::Iri(iri)) => Self::Iri(iri),
+			Id::Valid(ValidId::Blank(_)) => Self::Blank,
+			Id::Invalid(id) => Self::Invalid(id),
+		}
+	}
+}
+
+/// Vertex, identifying the initial color of a vertex.
+#[derive(Hash)]
+enum Vertex<'a, T> {
+	Id(Term<'a, T>),
+	Node(Option<&'a str>),
+	List(Option<&'a str>),
+	Value(u64),
+}
+
+/// Edge label.
+#[derive(Hash)]
+enum Edge<'a, T> {
+	Id,
+	Type,
+	Graph,
+	Included,
+	Property(Term<'a, T>),
+	ReverseProperty(Term<'a, T>),
+	Item(usize),
+}
+
+/// Expanded document seen as a graph.
+///
+/// The first vertices are the blank node identifiers of the document, in
+/// the order of [`Self::blank_ids`].
+struct Graph<'a, B> {
+	/// Blank node identifiers.
+	blank_ids: Vec<&'a B>,
+
+	/// Index of each blank node identifier.
+	indices: HashMap<&'a B, usize>,
+
+	/// Initial color of each vertex.
+	colors: Vec<u64>,
+
+	/// Edges of each vertex, with their label.
+	edges: Vec<Vec<(u64, usize)>>,
+}
+
+impl<'a, B: Eq + Hash> Graph<'a, B> {
+	fn new<T: Eq + Hash>(document: &'a ExpandedDocument<T, B>) -> Self {
+		let blank_ids: Vec<_> = document.blank_ids().into_iter().collect();
+		let indices = blank_ids
+			.iter()
+			.enumerate()
+			.map(|(i, id)| (*id, i))
+			.collect();
+		let blank = hash(&Vertex::<T>::Id(Term::Blank));
+
+		let mut graph = Self {
+			colors: vec![blank; blank_ids.len()],
+			edges: vec![Vec::new(); blank_ids.len()],
+			blank_ids,
+			indices,
+		};
+
+		for object in document.iter() {
+			graph.object(object);
+		}
+
+		graph
+	}
+
+	fn vertex<T: Hash>(&mut self, vertex: Vertex<T>) -> usize {
+		self.colors.push(hash(&vertex));
+		self.edges.push(Vec::new());
+		self.colors.len() - 1
+	}
+
+	fn edge<T: Hash>(&mut self, a: usize, label: Edge<T>, b: usize) {
+		let label = hash(&label);
+		self.edges[a].push((hash(&(label, false)), b));
+		self.edges[b].push((hash(&(label, true)), a));
+	}
+
+	fn id<T: Hash>(&mut self, id: &'a Id<T, B>) -> usize {
+		match id {
+			Id::Valid(ValidId::Blank(id)) => self.indices[id],
+			id => self.vertex(Vertex::Id(id.into())),
+		}
+	}
+
+	fn object<T: Hash>(&mut self, object: &'a IndexedObject<T, B>) -> usize {
+		match object.inner() {
+			Object::Value(value) => self.vertex(Vertex::<T>::Value(hash(&(object.index(), value)))),
+			Object::Node(node) => self.node(object.index(), node),
+			Object::List(list) => self.list(object.index(), list),
+		}
+	}
+
+	fn list<T: Hash>(&mut self, index: Option<&'a str>, list: &'a List<T, B>) -> usize {
+		let v = self.vertex(Vertex::<T>::List(index));
+
+		for (i, item) in list.iter().enumerate() {
+			let w = self.object(item);
+			self.edge(v, Edge::<T>::Item(i), w)
+		}
+
+		v
+	}
+
+	fn node<T: Hash>(&mut self, index: Option<&'a str>, node: &'a Node<T, B>) -> usize {
+		let v = self.vertex(Vertex::<T>::Node(index));
+
+		if let Some(id) = &node.id {
+			let w = self.id(id);
+			self.edge(v, Edge::<T>::Id, w)
+		}
+
+		for ty in node.types() {
+			let w = self.id(ty);
+			self.edge(v, Edge::<T>::Type, w)
+		}
+
+		for object in node.graph().into_iter().flatten() {
+			let w = self.object(object);
+			self.edge(v, Edge::<T>::Graph, w)
+		}
+
+		for included in node.included().into_iter().flatten() {
+			let w = self.node(included.index(), included);
+			self.edge(v, Edge::<T>::Included, w)
+		}
+
+		for (property, objects) in node.properties() {
+			let key = self.id(property);
+			self.edge(v, Edge::Property(property.into()), key);
+			for object in objects {
+				let w = self.object(object);
+				self.edge(key, Edge::Property(property.into()), w)
+			}
+		}
+
+		for (property, nodes) in node.reverse_properties().into_iter().flatten() {
+			let key = self.id(property);
+			self.edge(v, Edge::ReverseProperty(property.into()), key);
+			for reverse in nodes {
+				let w = self.node(reverse.index(), reverse);
+				self.edge(key, Edge::ReverseProperty(property.into()), w)
+			}
+		}
+
+		v
+	}
+
+	/// Computes the next color of each vertex.
+	fn recolor(&self, colors: &[u64]) -> Vec<u64> {
+		self.edges
+			.iter()
+			.zip(colors)
+			.map(|(edges, color)| {
+				let mut hasher = DefaultHasher::new();
+				color.hash(&mut hasher);
+				utils::hash_set(
+					edges.iter().map(|(label, w)| (*label, colors[*w])),
+					&mut hasher,
+				);
+				hasher.finish()
+			})
+			.collect()
+	}
+
+	/// Groups blank nodes by color.
+	fn classes(&self, colors: &[u64]) -> HashMap<u64, Vec<usize>> {
+		let mut classes: HashMap<u64, Vec<usize>> = HashMap::new();
+
+		for (i, color) in colors[..self.blank_ids.len()].iter().enumerate() {
+			classes.entry(*color).or_default().push(i)
+		}
+
+		classes
+	}
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<T, B> {
+	/// Checks if this document is equal to `other` up to a renaming of blank
+	/// node identifiers.
+	///
+	/// This is how the documents of the W3C test suite are compared.
+	pub fn is_isomorphic_to(&self, other: &Self) -> bool {
+		let a = Graph::new(self);
+		let b = Graph::new(other);
+
+		if a.blank_ids.len() != b.blank_ids.len() || a.colors.len() != b.colors.len() {
+			return false;
+		}
+
+		if a.blank_ids.is_empty() {
+			return self == other;
+		}
+
+		search(self, other, &a, &b, a.colors.clone(), b.colors.clone())
+	}
+}
+
+/// Searches a blank node bijection between the documents `a` and `b`, whose
+/// graphs are `a_graph` and `b_graph`, compatible with the given colorings.
+fn search<T: Clone + Eq + Hash, B: Clone + Eq + Hash>(
+	a: &ExpandedDocument<T, B>,
+	b: &ExpandedDocument<T, B>,
+	a_graph: &Graph<B>,
+	b_graph: &Graph<B>,
+	mut a_colors: Vec<u64>,
+	mut b_colors: Vec<u64>,
+) -> bool {
+	refine(a_graph, &mut a_colors, b_graph, &mut b_colors);
+
+	let a_classes = a_graph.classes(&a_colors);
+	let b_classes = b_graph.classes(&b_colors);
+
+	if a_classes.len() != b_classes.len()
+		|| a_classes
+			.iter()
+			.any(|(color, ids)| b_classes.get(color).map(Vec::len) != Some(ids.len()))
+	{
+		return false;
+	}
+
+	let ambiguous = a_classes
+		.iter()
+		.filter(|(_, ids)| ids.len() > 1)
+		.min_by_key(|(color, ids)| (ids.len(), **color));
+
+	match ambiguous {
+		Some((color, ids)) => {
+			let x = ids[0];
+			let fresh = hash(&(*color, a_colors.len()));
+
+			b_classes[color].iter().any(|&y| {
+				let mut a_colors = a_colors.clone();
+				let mut b_colors = b_colors.clone();
+				a_colors[x] = fresh;
+				b_colors[y] = fresh;
+				search(a, b, a_graph, b_graph, a_colors, b_colors)
+			})
+		}
+		None => {
+			let mapping: HashMap<&B, &B> = a_classes
+				.into_iter()
+				.map(|(color, ids)| {
+					(
+						a_graph.blank_ids[ids[0]],
+						b_graph.blank_ids[b_classes[&color][0]],
+					)
+				})
+				.collect();
+
+			let relabeled = a.clone().map_ids(
+				|iri| iri,
+				|id| match id {
+					Id::Valid(ValidId::Blank(id)) => {
+						let id = mapping.get(&id).map_or(id, |id| (*id).clone());
+						Id::Valid(ValidId::Blank(id))
+					}
+					id => id,
+				},
+			);
+
+			relabeled == *b
+		}
+	}
+}
+
+/// Refines the colorings of `a` and `b` until they are stable.
+fn refine<B: Eq + Hash>(
+	a: &Graph<B>,
+	a_colors: &mut Vec<u64>,
+	b: &Graph<B>,
+	b_colors: &mut Vec<u64>,
+) {
+	loop {
+		let a_count = count(a_colors);
+		let b_count = count(b_colors);
+
+		*a_colors = a.recolor(a_colors);
+		*b_colors = b.recolor(b_colors);
+
+		if count(a_colors) == a_count && count(b_colors) == b_count {
+			break;
+		}
+	}
+}
+
+/// Counts the distinct colors of a coloring.
+fn count(colors: &[u64]) -> usize {
+	colors.iter().collect::<HashSet<_>>().len()
+}
+
+fn hash(value: &impl Hash) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	value.hash(&mut hasher);
+	hasher.finish()
+}
@@ -7,22 +7,76 @@ use rdf_types::{vocabulary::IriVocabularyMut, BlankIdBuf, Interpretation, Vocabu
 
 pub mod expanded;
 pub mod flattened;
+mod form;
+mod isomorphism;
+pub mod outline;
+pub mod proof;
 
 pub use expanded::ExpandedDocument;
-pub use flattened::FlattenedDocument;
+pub use flattened::{FlattenedDocument, NotFlattened};
+pub use form::DocumentForm;
+pub use proof::SplitProof;
 
 use crate::RemoteDocument;
 
-/// JSON-LD document in both compact and expanded form.
+/// JSON-LD document in both its original and expanded form.
+///
+/// The original document is tagged with its [form](DocumentForm), if known.
 #[derive(Debug, Clone)]
 pub struct Document<I = IriBuf, B = BlankIdBuf> {
 	remote: RemoteDocument<I>,
 	expanded: ExpandedDocument<I, B>,
+	form: Option<DocumentForm>,
 }
 
 impl<I, B> Document<I, B> {
+	/// Creates a new document.
+	///
+	/// The form of the document is the one declared by the `profile` of the
+	/// remote document. If none is declared, the form is
+	/// [detected](DocumentForm::detect) from the document itself, and is left
+	/// unknown if it cannot be.
 	pub fn new(remote: RemoteDocument<I>, expanded: ExpandedDocument<I, B>) -> Self {
-		Self { remote, expanded }
+		let form = DocumentForm::of(&remote).or_else(|| DocumentForm::detect(remote.document()));
+		Self {
+			remote,
+			expanded,
+			form,
+		}
+	}
+
+	/// Creates a new document in the given form.
+	pub fn with_form(
+		remote: RemoteDocument<I>,
+		expanded: ExpandedDocument<I, B>,
+		form: DocumentForm,
+	) -> Self {
+		Self {
+			remote,
+			expanded,
+			form: Some(form),
+		}
+	}
+
+	/// Returns the form of the original document, if known.
+	pub fn form(&self) -> Option<DocumentForm> {
+		self.form
+	}
+
+	/// Sets the form of the original document.
+	pub fn set_form(&mut self, form: Option<DocumentForm>) {
+		self.form = form
+	}
+
+	/// Returns the original document if it is in the given form.
+	///
+	/// The expanded form is always available through [`Self::as_expanded`].
+	pub fn as_form(&self, form: DocumentForm) -> Option<&json_ld_syntax::Value> {
+		if self.form == Some(form) {
+			Some(self.remote.document())
+		} else {
+			None
+		}
 	}
 
 	pub fn into_remote(self) -> RemoteDocument<I> {
@@ -0,0 +1,330 @@
+//! Textual and graphviz views of an expanded document.
+//!
+//! Those are meant for debugging: when expansion produces an unexpected
+//! structure, the JSON output is often too verbose to see what nodes were
+//! actually created and how they are connected.
+use super::ExpandedDocument;
+use crate::object::Literal;
+use crate::{Id, IndexedObject, Node, Object, Value};
+use contextual::WithContext;
+use hashbrown::HashMap;
+use rdf_types::Vocabulary;
+use std::fmt::Write;
+
+/// Maximum number of characters of a literal value shown in an outline or
+/// DOT graph.
+const MAX_LITERAL_LEN: usize = 32;
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Returns a compact textual tree of the document.
+	///
+	/// Each line describes an object: nodes with their identifier, types and
+	/// number of properties, values with their (truncated) literal, and
+	/// lists with their length. Nested objects are indented under the
+	/// property (or `@graph`, `@included`, `@reverse`) introducing them.
+	pub fn outline_with<V: Vocabulary<Iri = T, BlankId = B>>(&self, vocabulary: &V) -> String {
+		let mut out = String::new();
+		for object in self {
+			outline_object(vocabulary, object, 0, &mut out)
+		}
+		out
+	}
+
+	/// Returns a compact textual tree of the document.
+	///
+	/// See [`Self::outline_with`].
+	pub fn outline(&self) -> String
+	where
+		(): Vocabulary<Iri = T, BlankId = B>,
+	{
+		self.outline_with(&())
+	}
+
+	/// Returns a graphviz representation of the document, in the DOT
+	/// language.
+	///
+	/// Nodes and values are graph vertices, properties are labeled edges.
+	/// Anonymous nodes each get their own vertex, while nodes sharing the
+	/// same identifier are merged.
+	pub fn to_dot_with<V: Vocabulary<Iri = T, BlankId = B>>(&self, vocabulary: &V) -> String {
+		let mut dot = Dot::new(vocabulary);
+		for object in self {
+			dot.object(object);
+		}
+		dot.end()
+	}
+
+	/// Returns a graphviz representation of the document, in the DOT
+	/// language.
+	///
+	/// See [`Self::to_dot_with`].
+	pub fn to_dot(&self) -> String
+	where
+		(): Vocabulary<Iri = T, BlankId = B>,
+	{
+		self.to_dot_with(&())
+	}
+}
+
+fn indent(depth: usize, out: &mut String) {
+	for _ in 0..depth {
+		out.push_str("  ")
+	}
+}
+
+fn id_label<V: Vocabulary>(vocabulary: &V, id: &Id<V::Iri, V::BlankId>) -> String {
+	id.with(vocabulary).to_string()
+}
+
+fn types_label<V: Vocabulary>(vocabulary: &V, node: &Node<V::Iri, V::BlankId>) -> String {
+	let types: Vec<_> = node
+		.types()
+		.iter()
+		.map(|ty| id_label(vocabulary, ty))
+		.collect();
+	types.join(", ")
+}
+
+fn node_label<V: Vocabulary>(vocabulary: &V, node: &Node<V::Iri, V::BlankId>) -> String {
+	match &node.id {
+		Some(id) => id_label(vocabulary, id),
+		None => "(anonymous)".to_string(),
+	}
+}
+
+fn value_label<V: Vocabulary>(vocabulary: &V, value: &Value<V::Iri>) -> String {
+	fn truncate(s: &str) -> String {
+		match s.char_indices().nth(MAX_LITERAL_LEN) {
+			Some((i, _)) => format!("{:?}…", &s[..i]),
+			None => format!("{s:?}"),
+		}
+	}
+
+	match value {
+		Value::Literal(lit, ty) => {
+			let mut label = match lit {
+				Literal::Null => "null".to_string(),
+				Literal::Boolean(b) => b.to_string(),
+				Literal::Number(n) => n.to_string(),
+				Literal::String(s) => truncate(s),
+			};
+
+			if let Some(iri) = ty.as_ref().and_then(|ty| vocabulary.iri(ty)) {
+				write!(label, "^^<{iri}>").unwrap()
+			}
+
+			label
+		}
+		Value::LangString(s) => {
+			let mut label = truncate(s.as_str());
+			if let Some(lang) = s.language() {
+				write!(label, "@{lang}").unwrap()
+			}
+			label
+		}
+		Value::Json(_) => "(JSON literal)".to_string(),
+	}
+}
+
+fn outline_object<V: Vocabulary>(
+	vocabulary: &V,
+	object: &IndexedObject<V::Iri, V::BlankId>,
+	depth: usize,
+	out: &mut String,
+) {
+	indent(depth, out);
+	match object.inner() {
+		Object::Value(value) => writeln!(out, "value {}", value_label(vocabulary, value)).unwrap(),
+		Object::List(list) => {
+			writeln!(out, "list ({} items)", list.len()).unwrap();
+			for item in list.iter() {
+				outline_object(vocabulary, item, depth + 1, out)
+			}
+		}
+		Object::Node(node) => outline_node(vocabulary, node, depth, out),
+	}
+}
+
+fn outline_node<V: Vocabulary>(
+	vocabulary: &V,
+	node: &Node<V::Iri, V::BlankId>,
+	depth: usize,
+	out: &mut String,
+) {
+	write!(out, "node {}", node_label(vocabulary, node)).unwrap();
+
+	if !node.types().is_empty() {
+		write!(out, " [{}]", types_label(vocabulary, node)).unwrap()
+	}
+
+	writeln!(out, " ({} properties)", node.properties().len()).unwrap();
+
+	for (prop, objects) in node.properties() {
+		indent(depth + 1, out);
+		writeln!(out, "{} ({})", id_label(vocabulary, prop), objects.len()).unwrap();
+		for object in objects.iter() {
+			outline_object(vocabulary, object, depth + 2, out)
+		}
+	}
+
+	if let Some(reverse) = node.reverse_properties() {
+		for (prop, nodes) in reverse {
+			indent(depth + 1, out);
+			writeln!(
+				out,
+				"@reverse {} ({})",
+				id_label(vocabulary, prop),
+				nodes.len()
+			)
+			.unwrap();
+			for node in nodes {
+				indent(depth + 2, out);
+				outline_node(vocabulary, node, depth + 2, out)
+			}
+		}
+	}
+
+	if let Some(graph) = node.graph() {
+		indent(depth + 1, out);
+		writeln!(out, "@graph ({})", graph.len()).unwrap();
+		for object in graph {
+			outline_object(vocabulary, object, depth + 2, out)
+		}
+	}
+
+	if let Some(included) = node.included() {
+		indent(depth + 1, out);
+		writeln!(out, "@included ({})", included.len()).unwrap();
+		for node in included {
+			indent(depth + 2, out);
+			outline_node(vocabulary, node, depth + 2, out)
+		}
+	}
+}
+
+/// DOT graph builder.
+struct Dot<'v, V> {
+	vocabulary: &'v V,
+	out: String,
+	count: usize,
+	named: HashMap<String, String>,
+}
+
+impl<'v, V: Vocabulary> Dot<'v, V> {
+	fn new(vocabulary: &'v V) -> Self {
+		Self {
+			vocabulary,
+			out: "digraph {\n".to_string(),
+			count: 0,
+			named: HashMap::new(),
+		}
+	}
+
+	fn end(mut self) -> String {
+		self.out.push_str("}\n");
+		self.out
+	}
+
+	fn vertex(&mut self, label: &str, shape: &str) -> String {
+		let name = format!("n{}", self.count);
+		self.count += 1;
+		writeln!(
+			self.out,
+			"  {name} [label=\"{}\", shape={shape}];",
+			escape(label)
+		)
+		.unwrap();
+		name
+	}
+
+	fn edge(&mut self, from: &str, to: &str, label: &str) {
+		writeln!(self.out, "  {from} -> {to} [label=\"{}\"];", escape(label)).unwrap()
+	}
+
+	fn object(&mut self, object: &IndexedObject<V::Iri, V::BlankId>) -> String {
+		match object.inner() {
+			Object::Value(value) => {
+				let label = value_label(self.vocabulary, value);
+				self.vertex(&label, "box")
+			}
+			Object::List(list) => {
+				let name = self.vertex("@list", "point");
+				for (i, item) in list.iter().enumerate() {
+					let item = self.object(item);
+					self.edge(&name, &item, &i.to_string())
+				}
+				name
+			}
+			Object::Node(node) => self.node(node),
+		}
+	}
+
+	fn node(&mut self, node: &Node<V::Iri, V::BlankId>) -> String {
+		let mut label = node_label(self.vocabulary, node);
+		if !node.types().is_empty() {
+			write!(label, "\n[{}]", types_label(self.vocabulary, node)).unwrap()
+		}
+
+		let name = match &node.id {
+			Some(id) => {
+				let key = id_label(self.vocabulary, id);
+				match self.named.get(&key) {
+					Some(name) => name.clone(),
+					None => {
+						let name = self.vertex(&label, "ellipse");
+						self.named.insert(key, name.clone());
+						name
+					}
+				}
+			}
+			None => self.vertex(&label, "ellipse"),
+		};
+
+		for (prop, objects) in node.properties() {
+			let prop = id_label(self.vocabulary, prop);
+			for object in objects.iter() {
+				let object = self.object(object);
+				self.edge(&name, &object, &prop)
+			}
+		}
+
+		if let Some(reverse) = node.reverse_properties() {
+			for (prop, nodes) in reverse {
+				let prop = id_label(self.vocabulary, prop);
+				for subject in nodes {
+					let subject = self.node(subject);
+					self.edge(&subject, &name, &prop)
+				}
+			}
+		}
+
+		if let Some(graph) = node.graph() {
+			for object in graph {
+				let object = self.object(object);
+				self.edge(&name, &object, "@graph")
+			}
+		}
+
+		if let Some(included) = node.included() {
+			for node in included {
+				let node = self.node(node);
+				self.edge(&name, &node, "@included")
+			}
+		}
+
+		name
+	}
+}
+
+fn escape(s: &str) -> String {
+	let mut result = String::with_capacity(s.len());
+	for c in s.chars() {
+		match c {
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			c => result.push(c),
+		}
+	}
+	result
+}
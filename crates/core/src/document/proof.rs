@@ -0,0 +1,174 @@
+//! Document signing pre-processing.
+//!
+//! Data integrity proofs are embedded in the document they sign, under the
+//! `https://w3id.org/security#proof` property. Before a signature can be
+//! computed or verified, the proofs must be taken out of the document and
+//! both the document and each proof must be turned into canonical N-Quads,
+//! using the [RDFC-1.0] canonicalization required by Data Integrity proofs.
+//!
+//! [RDFC-1.0]: https://www.w3.org/TR/rdf-canon/
+use super::ExpandedDocument;
+use crate::rdf::{canon, RdfQuads, Value};
+use crate::{Id, IndexedObject, ValidId};
+use iref::{Iri, IriBuf};
+use rdf_types::vocabulary::{IriVocabularyMut, VocabularyMut};
+use rdf_types::{generator, BlankIdBuf, RdfDisplayWithContext};
+use static_iref::iri;
+use std::hash::Hash;
+
+/// Proof property IRI.
+pub const SEC_PROOF: &Iri = iri!("https://w3id.org/security#proof");
+
+/// Document split from its proofs.
+///
+/// See [`ExpandedDocument::split_proof`].
+#[derive(Debug, Clone)]
+pub struct SplitProof<T = IriBuf, B = BlankIdBuf> {
+	/// Document without its proofs.
+	pub document: ExpandedDocument<T, B>,
+
+	/// Proofs, each in its own document.
+	pub proofs: Vec<ExpandedDocument<T, B>>,
+}
+
+impl<T, B> SplitProof<T, B> {
+	/// Returns the canonical N-Quads of the document and of each proof, in
+	/// the order of [`Self::proofs`].
+	///
+	/// See [`ExpandedDocument::canonical_nquads_with`].
+	pub fn canonical_nquads_with<V>(&self, vocabulary: &mut V) -> (String, Vec<String>)
+	where
+		V: VocabularyMut<Iri = T, BlankId = B>,
+		V::Literal: Clone,
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+		ValidId<T, B>: RdfDisplayWithContext<V>,
+		Value<T, B, V::Literal>: RdfDisplayWithContext<V>,
+	{
+		let document = self.document.canonical_nquads_with(vocabulary);
+		let proofs = self
+			.proofs
+			.iter()
+			.map(|proof| proof.canonical_nquads_with(vocabulary))
+			.collect();
+
+		(document, proofs)
+	}
+}
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Removes the `https://w3id.org/security#proof` entries of the top
+	/// level nodes, and returns the document along with the removed proofs.
+	///
+	/// A proof given as a graph object becomes a document made of the graph
+	/// content. Other proofs become single object documents.
+	pub fn split_proof_with<V: IriVocabularyMut<Iri = T>>(
+		self,
+		vocabulary: &mut V,
+	) -> SplitProof<T, B>
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		let proof_property = Id::iri(vocabulary.insert(SEC_PROOF));
+		let mut document = ExpandedDocument::new();
+		let mut proofs = Vec::new();
+
+		for mut object in self {
+			if let Some(node) = object.inner_mut().as_node_mut() {
+				if let Some(values) = node.properties_mut().remove(&proof_property) {
+					proofs.extend(values.into_iter().map(proof_document))
+				}
+			}
+
+			document.insert(object);
+		}
+
+		SplitProof { document, proofs }
+	}
+
+	/// Removes the `https://w3id.org/security#proof` entries of the top
+	/// level nodes, and returns the document along with the removed proofs.
+	///
+	/// See [`Self::split_proof_with`].
+	pub fn split_proof(self) -> SplitProof<T, B>
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+		(): IriVocabularyMut<Iri = T>,
+	{
+		self.split_proof_with(rdf_types::vocabulary::no_vocabulary_mut())
+	}
+
+	/// Returns the N-Quads serialization of the document in canonical form.
+	///
+	/// Literals are put in canonical form, then the RDF dataset of the
+	/// document is canonicalized with RDFC-1.0 (see [`canon`]): blank nodes
+	/// get their canonical labels, and the quads are sorted and
+	/// deduplicated. Two documents describing isomorphic datasets produce
+	/// the same output.
+	pub fn canonical_nquads_with<V>(&self, vocabulary: &mut V) -> String
+	where
+		V: VocabularyMut<Iri = T, BlankId = B>,
+		V::Literal: Clone,
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+		ValidId<T, B>: RdfDisplayWithContext<V>,
+		Value<T, B, V::Literal>: RdfDisplayWithContext<V>,
+	{
+		let mut document = self.clone();
+		let mut generator = generator::Blank::new();
+		document.relabel_and_canonicalize_with(vocabulary, &mut generator);
+
+		let quads: Vec<_> = document
+			.rdf_quads_full(vocabulary, &mut generator, None, false)
+			.cloned()
+			.collect();
+
+		canon::canonical_nquads(vocabulary, &quads)
+	}
+}
+
+fn proof_document<T: Eq + Hash, B: Eq + Hash>(
+	mut value: IndexedObject<T, B>,
+) -> ExpandedDocument<T, B> {
+	if value.inner().is_graph() {
+		if let Some(graph) = value.inner_mut().as_node_mut().and_then(|n| n.graph_mut()) {
+			return std::mem::take(graph).into();
+		}
+	}
+
+	std::iter::once(value).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use crate::TryFromJson;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	fn document(json: &str) -> ExpandedDocument {
+		ExpandedDocument::try_from_json_in(no_vocabulary_mut(), fixture::json(json)).unwrap()
+	}
+
+	#[test]
+	fn canonical_nquads() {
+		let a = document(
+			r#"[
+				{ "@id": "_:alice", "http://xmlns.com/foaf/0.1/knows": [{ "@id": "_:bob" }] },
+				{ "@id": "_:bob", "http://xmlns.com/foaf/0.1/name": [{ "@value": "Bob" }] }
+			]"#,
+		);
+		let b = document(
+			r#"[
+				{ "@id": "_:b0", "http://xmlns.com/foaf/0.1/name": [{ "@value": "Bob" }] },
+				{ "@id": "_:b1", "http://xmlns.com/foaf/0.1/knows": [{ "@id": "_:b0" }] }
+			]"#,
+		);
+
+		let nquads = a.canonical_nquads_with(no_vocabulary_mut());
+		assert_eq!(nquads, b.canonical_nquads_with(no_vocabulary_mut()));
+		assert!(nquads.contains("_:c14n0") && nquads.contains("_:c14n1"))
+	}
+}
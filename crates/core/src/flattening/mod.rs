@@ -5,6 +5,7 @@ use contextual::WithContext;
 use rdf_types::{Generator, Vocabulary};
 use std::collections::HashSet;
 use std::hash::Hash;
+use std::sync::Arc;
 
 mod environment;
 mod node_map;
@@ -57,6 +58,35 @@ pub trait Flatten<I, B> {
 }
 
 impl<I: Clone + Eq + Hash, B: Clone + Eq + Hash> Flatten<I, B> for ExpandedDocument<I, B> {
+	fn flatten_with<V, G: Generator<V>>(
+		self,
+		vocabulary: &mut V,
+		generator: G,
+		ordered: bool,
+	) -> FlattenResult<I, B>
+	where
+		V: Vocabulary<Iri = I, BlankId = B>,
+	{
+		(&self).flatten_with(vocabulary, generator, ordered)
+	}
+
+	fn flatten_unordered_with<V, G: Generator<V>>(
+		self,
+		vocabulary: &mut V,
+		generator: G,
+	) -> FlattenUnorderedResult<I, B>
+	where
+		V: Vocabulary<Iri = I, BlankId = B>,
+	{
+		(&self).flatten_unordered_with(vocabulary, generator)
+	}
+}
+
+/// Flattens a borrowed document.
+///
+/// The node map is built from the borrowed objects, so the document is not
+/// cloned.
+impl<'d, I: Clone + Eq + Hash, B: Clone + Eq + Hash> Flatten<I, B> for &'d ExpandedDocument<I, B> {
 	fn flatten_with<V, G: Generator<V>>(
 		self,
 		vocabulary: &mut V,
@@ -85,6 +115,32 @@ impl<I: Clone + Eq + Hash, B: Clone + Eq + Hash> Flatten<I, B> for ExpandedDocum
 	}
 }
 
+/// Flattens a shared document without cloning it.
+impl<I: Clone + Eq + Hash, B: Clone + Eq + Hash> Flatten<I, B> for Arc<ExpandedDocument<I, B>> {
+	fn flatten_with<V, G: Generator<V>>(
+		self,
+		vocabulary: &mut V,
+		generator: G,
+		ordered: bool,
+	) -> FlattenResult<I, B>
+	where
+		V: Vocabulary<Iri = I, BlankId = B>,
+	{
+		(&*self).flatten_with(vocabulary, generator, ordered)
+	}
+
+	fn flatten_unordered_with<V, G: Generator<V>>(
+		self,
+		vocabulary: &mut V,
+		generator: G,
+	) -> FlattenUnorderedResult<I, B>
+	where
+		V: Vocabulary<Iri = I, BlankId = B>,
+	{
+		(&*self).flatten_unordered_with(vocabulary, generator)
+	}
+}
+
 fn filter_graph<T, B>(node: IndexedNode<T, B>) -> Option<IndexedNode<T, B>> {
 	if node.index().is_none() && node.is_empty() {
 		None
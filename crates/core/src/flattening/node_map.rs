@@ -20,6 +20,13 @@ pub struct ConflictingIndexes<T, B> {
 	pub conflicting_index: String,
 }
 
+impl<T, B> ConflictingIndexes<T, B> {
+	/// Returns the code of this error.
+	pub fn code(&self) -> json_ld_syntax::ErrorCode {
+		json_ld_syntax::ErrorCode::ConflictingIndexes
+	}
+}
+
 pub type Parts<T, B> = (NodeMapGraph<T, B>, HashMap<Id<T, B>, NodeMapGraph<T, B>>);
 
 /// Node identifier to node definition map.
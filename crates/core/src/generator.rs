@@ -0,0 +1,85 @@
+//! Scoped blank node identifier generation.
+//!
+//! A single vocabulary (such as an `IndexVocabulary`) can be shared by
+//! several processors running concurrently. Each processor usually comes with
+//! its own blank node identifier generator, and since those generators are not
+//! aware of each other, they produce the same labels (`_:0`, `_:1`, ...)
+//! which the shared vocabulary maps to the same identifiers: blank nodes of
+//! unrelated documents get confused.
+//!
+//! Wrapping each generator into a [`ScopedGenerator`] with a distinct scope
+//! (for instance the task identifier) ensures that the generated labels never
+//! collide:
+//!
+//! ```
+//! use json_ld_core::generator::ScopedGenerator;
+//! use rdf_types::{generator, vocabulary::IndexVocabulary, Generator};
+//!
+//! let mut vocabulary = IndexVocabulary::new();
+//!
+//! let mut a = ScopedGenerator::new("task1-", generator::Blank::new()).unwrap();
+//! let mut b = ScopedGenerator::new("task2-", generator::Blank::new()).unwrap();
+//!
+//! // Both inner generators produce `_:0`, but the scoped labels differ.
+//! let x = a.next(&mut vocabulary);
+//! let y = b.next(&mut vocabulary);
+//! assert_ne!(x, y);
+//! ```
+//!
+//! Alternatively, each task can use its own vocabulary and only share the
+//! lexical form of the produced documents (using `map_ids`), at the cost of
+//! re-interning identifiers.
+use crate::ValidId;
+use rdf_types::{BlankIdBuf, Generator, InvalidBlankId, VocabularyMut};
+
+/// Generator prefixing the labels of the blank node identifiers produced by
+/// an inner generator with a scope.
+///
+/// IRIs produced by the inner generator are left untouched.
+pub struct ScopedGenerator<G> {
+	scope: String,
+	inner: G,
+}
+
+impl<G> ScopedGenerator<G> {
+	/// Creates a new scoped generator.
+	///
+	/// Fails if the scope cannot appear in a blank node identifier label.
+	pub fn new(scope: impl Into<String>, inner: G) -> Result<Self, InvalidBlankId<String>> {
+		let scope = scope.into();
+		BlankIdBuf::new(format!("_:{scope}"))?;
+		Ok(Self { scope, inner })
+	}
+
+	/// Returns the scope.
+	pub fn scope(&self) -> &str {
+		&self.scope
+	}
+
+	/// Returns a reference to the inner generator.
+	pub fn inner(&self) -> &G {
+		&self.inner
+	}
+
+	/// Returns the inner generator.
+	pub fn into_inner(self) -> G {
+		self.inner
+	}
+}
+
+impl<V: VocabularyMut, G: Generator<V>> Generator<V> for ScopedGenerator<G> {
+	fn next(&mut self, vocabulary: &mut V) -> ValidId<V::Iri, V::BlankId> {
+		match self.inner.next(vocabulary) {
+			ValidId::Blank(b) => {
+				let label = format!(
+					"_:{}{}",
+					self.scope,
+					vocabulary.blank_id(&b).unwrap().suffix()
+				);
+				let scoped = BlankIdBuf::new(label).unwrap();
+				ValidId::Blank(vocabulary.insert_blank_id(&scoped))
+			}
+			iri => iri,
+		}
+	}
+}
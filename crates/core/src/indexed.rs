@@ -116,7 +116,7 @@ impl<T, B, O: TryFromJsonObject<T, B>> TryFromJsonObject<T, B> for Indexed<O> {
 		{
 			Some(index_entry) => match index_entry.value {
 				json_syntax::Value::String(index) => Some(index.to_string()),
-				_ => return Err(InvalidExpandedJson::InvalidIndex),
+				_ => return Err(InvalidExpandedJson::InvalidIndex.at_key("@index")),
 			},
 			None => None,
 		};
@@ -146,10 +146,10 @@ impl LangString {
 		let data = match value {
 			json_syntax::Value::String(s) => s,
 			v => {
-				return Err(InvalidExpandedJson::Unexpected(
-					v.kind(),
-					json_syntax::Kind::String,
-				))
+				return Err(
+					InvalidExpandedJson::Unexpected(v.kind(), json_syntax::Kind::String)
+						.at_key("@value"),
+				)
 			}
 		};
 
@@ -159,10 +159,10 @@ impl LangString {
 				Some(tag)
 			}
 			Some(v) => {
-				return Err(InvalidExpandedJson::Unexpected(
-					v.kind(),
-					json_syntax::Kind::String,
-				))
+				return Err(
+					InvalidExpandedJson::Unexpected(v.kind(), json_syntax::Kind::String)
+						.at_key("@language"),
+				)
 			}
 			None => None,
 		};
@@ -170,13 +170,13 @@ impl LangString {
 		let direction = match direction {
 			Some(json_syntax::Value::String(value)) => match Direction::try_from(value.as_str()) {
 				Ok(direction) => Some(direction),
-				Err(_) => return Err(InvalidExpandedJson::InvalidDirection),
+				Err(_) => return Err(InvalidExpandedJson::InvalidDirection.at_key("@direction")),
 			},
 			Some(v) => {
-				return Err(InvalidExpandedJson::Unexpected(
-					v.kind(),
-					json_syntax::Kind::String,
-				))
+				return Err(
+					InvalidExpandedJson::Unexpected(v.kind(), json_syntax::Kind::String)
+						.at_key("@direction"),
+				)
 			}
 			None => None,
 		};
@@ -1,11 +1,13 @@
 //! JSON-LD core types.
 pub use json_ld_syntax::{Direction, LenientLangTag, LenientLangTagBuf, Nullable};
 
+pub mod blank_id;
 mod container;
 pub mod context;
 mod deserialization;
 mod document;
 pub mod flattening;
+pub mod generator;
 pub mod id;
 mod indexed;
 mod lang_string;
@@ -21,6 +23,7 @@ mod ty;
 pub mod utils;
 pub mod warning;
 
+pub use blank_id::{BlankIdRelabeling, LenientBlankIdBuf};
 pub use container::{Container, ContainerKind};
 pub use context::Context;
 pub use document::*;
@@ -37,8 +40,102 @@ pub use rdf::RdfQuads;
 pub use term::*;
 pub use ty::*;
 
+/// Processing environment.
+///
+/// Bundles the vocabulary used to interpret identifiers, the loader used to
+/// fetch remote documents and the warning handler, so that they can be
+/// threaded as a single value through the processing algorithms
+/// (see `Expand::expand_in` and `Process::process_in`).
+///
+/// ```
+/// use json_ld_core::{Environment, NoLoader};
+///
+/// let mut vocabulary = ();
+/// let loader = NoLoader;
+/// let mut warnings = json_ld_core::warning::Print;
+///
+/// let env = Environment::builder(&mut vocabulary, &loader).with_warnings(&mut warnings);
+///
+/// // Ignoring warnings.
+/// let mut vocabulary = ();
+/// let mut builder = Environment::builder(&mut vocabulary, &loader);
+/// let env = builder.build();
+/// ```
 pub struct Environment<'a, N, L, W> {
+	/// Vocabulary.
 	pub vocabulary: &'a mut N,
+
+	/// Document loader.
 	pub loader: &'a L,
+
+	/// Warning handler.
 	pub warnings: &'a mut W,
 }
+
+impl<'a, N, L, W> Environment<'a, N, L, W> {
+	/// Creates a new environment.
+	pub fn new(vocabulary: &'a mut N, loader: &'a L, warnings: &'a mut W) -> Self {
+		Self {
+			vocabulary,
+			loader,
+			warnings,
+		}
+	}
+
+	/// Reborrows the environment for a shorter lifetime, so that it can be
+	/// passed to a function while still being usable afterwards.
+	pub fn reborrow(&mut self) -> Environment<'_, N, L, W> {
+		Environment {
+			vocabulary: self.vocabulary,
+			loader: self.loader,
+			warnings: self.warnings,
+		}
+	}
+}
+
+impl<'a, N, L> Environment<'a, N, L, ()> {
+	/// Starts building an environment with the given vocabulary and loader.
+	///
+	/// Warnings are ignored unless a handler is given with
+	/// [`EnvironmentBuilder::with_warnings`].
+	pub fn builder(vocabulary: &'a mut N, loader: &'a L) -> EnvironmentBuilder<'a, N, L> {
+		EnvironmentBuilder {
+			vocabulary,
+			loader,
+			ignored_warnings: (),
+		}
+	}
+}
+
+/// [`Environment`] builder.
+pub struct EnvironmentBuilder<'a, N, L> {
+	vocabulary: &'a mut N,
+	loader: &'a L,
+
+	/// Warning handler ignoring warnings, lent by [`Self::build`].
+	ignored_warnings: (),
+}
+
+impl<'a, N, L> EnvironmentBuilder<'a, N, L> {
+	/// Sets the loader.
+	pub fn with_loader<M>(self, loader: &'a M) -> EnvironmentBuilder<'a, N, M> {
+		EnvironmentBuilder {
+			vocabulary: self.vocabulary,
+			loader,
+			ignored_warnings: (),
+		}
+	}
+
+	/// Sets the warning handler and builds the environment.
+	pub fn with_warnings<W>(self, warnings: &'a mut W) -> Environment<'a, N, L, W> {
+		Environment::new(self.vocabulary, self.loader, warnings)
+	}
+
+	/// Builds the environment, ignoring warnings.
+	///
+	/// The environment borrows the builder, which owns the handler ignoring
+	/// the warnings.
+	pub fn build(&mut self) -> Environment<'_, N, L, ()> {
+		Environment::new(self.vocabulary, self.loader, &mut self.ignored_warnings)
+	}
+}
@@ -0,0 +1,72 @@
+//! Blocking loaders.
+use super::{DynLoader, FsLoader, Loader, NoLoader, RemoteDocument, StaticLoader};
+use crate::LoadingResult;
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use std::collections::{BTreeMap, HashMap};
+
+/// Blocking document loader.
+///
+/// Loader that does not require an async runtime, for instance to process
+/// JSON-LD documents from a command line tool or a build script. A blocking
+/// loader can be used where a [`Loader`] is expected by wrapping it in a
+/// [`Blocking`] adapter.
+pub trait SyncLoader {
+	/// Loads the document behind the given IRI, blocking the current thread.
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf>;
+}
+
+impl<'l, L: SyncLoader> SyncLoader for &'l L {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		L::load_sync(self, url)
+	}
+}
+
+/// Adapter using a [`SyncLoader`] as a [`Loader`].
+///
+/// The futures returned by this loader are always ready, so they can be
+/// polled to completion by any executor, such as
+/// [`futures::executor::block_on`].
+pub struct Blocking<L>(pub L);
+
+impl<L: SyncLoader> Loader for Blocking<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.0.load_sync(url)
+	}
+}
+
+impl<L: SyncLoader + Send + Sync> DynLoader for Blocking<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		self.0.load_dyn(url)
+	}
+}
+
+impl SyncLoader for NoLoader {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		futures::executor::block_on(self.load(url))
+	}
+}
+
+impl SyncLoader for StaticLoader {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		futures::executor::block_on(self.load(url))
+	}
+}
+
+impl SyncLoader for FsLoader {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		futures::executor::block_on(self.load(url))
+	}
+}
+
+impl SyncLoader for HashMap<IriBuf, RemoteDocument> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		futures::executor::block_on(self.load(url))
+	}
+}
+
+impl SyncLoader for BTreeMap<IriBuf, RemoteDocument> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		futures::executor::block_on(self.load(url))
+	}
+}
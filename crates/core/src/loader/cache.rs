@@ -0,0 +1,420 @@
+//! Caching metadata of remote documents.
+//!
+//! Loaders may attach to each loaded [`RemoteDocument`] the HTTP caching
+//! metadata of the response, so that caches built on top of them can decide
+//! when a document must be revalidated. HTTP loaders can also be given an
+//! [`HttpCache`] that they use to serve fresh documents and revalidate stale
+//! ones with conditional requests.
+use super::RemoteDocument;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf};
+use json_syntax::Print;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// HTTP caching metadata of a remote document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheMetadata {
+	/// Time at which the document was received.
+	pub fetched_at: Option<SystemTime>,
+
+	/// Age of the document when it was received (`Age` header).
+	pub age: Option<Duration>,
+
+	/// Freshness lifetime of the document (`Cache-Control: max-age`).
+	pub max_age: Option<Duration>,
+
+	/// Freshness lifetime of the document in shared caches
+	/// (`Cache-Control: s-maxage`), overriding [`Self::max_age`] there.
+	pub s_maxage: Option<Duration>,
+
+	/// Freshness lifetime of the document given by the `Expires` header,
+	/// that is the time between the `Date` header (or the reception of the
+	/// document) and the expiration date.
+	///
+	/// Only used without `max-age` directive. An invalid expiration date is
+	/// given as a zero lifetime.
+	pub expires_after: Option<Duration>,
+
+	/// The document must be revalidated before each use
+	/// (`Cache-Control: no-cache`).
+	pub no_cache: bool,
+
+	/// The document must not be stored (`Cache-Control: no-store`).
+	pub no_store: bool,
+
+	/// Entity tag of the document (`ETag` header), to be used in an
+	/// `If-None-Match` header when revalidating.
+	pub etag: Option<String>,
+}
+
+impl CacheMetadata {
+	/// Returns the freshness lifetime of the document in a private cache,
+	/// given by the `max-age` directive or else by the `Expires` header.
+	pub fn freshness_lifetime(&self) -> Option<Duration> {
+		self.max_age.or(self.expires_after)
+	}
+
+	/// Returns the freshness lifetime of the document in a shared cache,
+	/// given by the `s-maxage` directive, the `max-age` directive or else
+	/// by the `Expires` header.
+	pub fn shared_freshness_lifetime(&self) -> Option<Duration> {
+		self.s_maxage.or(self.freshness_lifetime())
+	}
+
+	/// Checks if the document must be revalidated at the given time, in a
+	/// private cache (such as [`HttpCache`]).
+	///
+	/// A document is stale if it must always be revalidated, if its
+	/// reception time or [freshness lifetime](Self::freshness_lifetime) is
+	/// unknown, or if its current age (its age when received plus the time
+	/// elapsed since) reached its freshness lifetime.
+	pub fn is_stale(&self, now: SystemTime) -> bool {
+		self.is_stale_for(now, self.freshness_lifetime())
+	}
+
+	/// Checks if the document must be revalidated at the given time, in a
+	/// shared cache.
+	///
+	/// Same as [`Self::is_stale`], using the
+	/// [shared freshness lifetime](Self::shared_freshness_lifetime).
+	pub fn is_stale_shared(&self, now: SystemTime) -> bool {
+		self.is_stale_for(now, self.shared_freshness_lifetime())
+	}
+
+	fn is_stale_for(&self, now: SystemTime, lifetime: Option<Duration>) -> bool {
+		if self.no_cache || self.no_store {
+			return true;
+		}
+
+		match (self.fetched_at, lifetime) {
+			(Some(fetched_at), Some(lifetime)) => {
+				let elapsed = now.duration_since(fetched_at).unwrap_or_default();
+				self.age.unwrap_or_default() + elapsed >= lifetime
+			}
+			_ => true,
+		}
+	}
+
+	/// Checks if the document is worth storing in a private cache, that is
+	/// if it may be stored and has a freshness lifetime or can be
+	/// revalidated.
+	pub fn is_storable(&self) -> bool {
+		!self.no_store
+			&& (self.etag.is_some()
+				|| (!self.no_cache && self.freshness_lifetime().is_some_and(|d| !d.is_zero())))
+	}
+
+	/// Updates this metadata with the metadata of a `304 Not Modified`
+	/// response revalidating the document.
+	///
+	/// The entity tag is kept if the response does not provide one.
+	pub fn revalidate(&mut self, revalidated: CacheMetadata) {
+		let etag = revalidated.etag.or(self.etag.take());
+		*self = Self {
+			etag,
+			..revalidated
+		}
+	}
+}
+
+/// In-memory cache of remote documents, following the HTTP caching
+/// metadata of the responses.
+///
+/// HTTP loaders given a cache serve the cached documents that are still
+/// fresh (see [`CacheMetadata::is_stale`]) without network queries, and
+/// revalidate stale documents having an entity tag with a conditional
+/// `If-None-Match` request. Only [storable](CacheMetadata::is_storable)
+/// documents are kept.
+///
+/// Documents are cached by URL and `Accept` header, since a server may serve
+/// different representations of the same URL depending on the requested
+/// profiles (for instance when loading contexts).
+///
+/// The cache holds at most [`capacity`](Self::capacity) documents: once
+/// full, the least recently used document is evicted to store a new one.
+///
+/// The cache uses interior mutability so it can be shared, behind an
+/// [`Arc`](std::sync::Arc), by several loaders.
+pub struct HttpCache {
+	capacity: usize,
+	entries: Mutex<Entries>,
+}
+
+/// Cache key: the document URL and the `Accept` header of the request.
+type Key = (IriBuf, String);
+
+/// Cached documents, with the time of their last use.
+#[derive(Default)]
+struct Entries {
+	documents: HashMap<Key, (RemoteDocument, u64)>,
+	clock: u64,
+}
+
+impl Entries {
+	fn tick(&mut self) -> u64 {
+		self.clock += 1;
+		self.clock
+	}
+}
+
+impl Default for HttpCache {
+	fn default() -> Self {
+		Self::with_capacity(Self::DEFAULT_CAPACITY)
+	}
+}
+
+impl HttpCache {
+	/// Default capacity of a cache.
+	pub const DEFAULT_CAPACITY: usize = 1024;
+
+	/// Creates a new empty cache with the default capacity.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new empty cache holding at most `capacity` documents.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			capacity,
+			entries: Mutex::new(Entries::default()),
+		}
+	}
+
+	/// Returns the maximum number of cached documents.
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Returns the cached document for the given URL and `Accept` header,
+	/// fresh or not.
+	pub fn get(&self, url: &Iri, accept: &str) -> Option<RemoteDocument> {
+		let key = (url.to_owned(), accept.to_owned());
+		let mut entries = self.entries.lock().unwrap();
+		let now = entries.tick();
+		entries.documents.get_mut(&key).map(|(document, used_at)| {
+			*used_at = now;
+			document.clone()
+		})
+	}
+
+	/// Stores the document loaded from the given URL with the given `Accept`
+	/// header, if it is [storable](CacheMetadata::is_storable), evicting the
+	/// least recently used document if the cache is full.
+	///
+	/// Otherwise, any previously cached document for this URL and `Accept`
+	/// header is removed.
+	pub fn insert(&self, url: IriBuf, accept: &str, document: RemoteDocument) {
+		let key = (url, accept.to_owned());
+		let mut entries = self.entries.lock().unwrap();
+		if !document.cache_metadata().is_storable() || self.capacity == 0 {
+			entries.documents.remove(&key);
+			return;
+		}
+
+		if entries.documents.len() >= self.capacity && !entries.documents.contains_key(&key) {
+			let least_recently_used = entries
+				.documents
+				.iter()
+				.min_by_key(|(_, (_, used_at))| *used_at)
+				.map(|(key, _)| key.clone());
+
+			if let Some(key) = least_recently_used {
+				entries.documents.remove(&key);
+			}
+		}
+
+		let now = entries.tick();
+		entries.documents.insert(key, (document, now));
+	}
+
+	/// Removes the cached document for the given URL and `Accept` header.
+	pub fn remove(&self, url: &Iri, accept: &str) -> Option<RemoteDocument> {
+		self.entries
+			.lock()
+			.unwrap()
+			.documents
+			.remove(&(url.to_owned(), accept.to_owned()))
+			.map(|(document, _)| document)
+	}
+
+	/// Removes every cached document.
+	pub fn clear(&self) {
+		self.entries.lock().unwrap().documents.clear()
+	}
+
+	/// Returns the number of cached documents.
+	pub fn len(&self) -> usize {
+		self.entries.lock().unwrap().documents.len()
+	}
+
+	/// Checks if the cache is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl<I, T> RemoteDocument<I, T> {
+	/// Returns the caching metadata provided by the loader.
+	pub fn cache_metadata(&self) -> &CacheMetadata {
+		&self.cache
+	}
+
+	/// Sets the caching metadata of the document.
+	pub fn set_cache_metadata(&mut self, cache: CacheMetadata) {
+		self.cache = cache
+	}
+
+	/// Checks if the document must be revalidated at the given time.
+	///
+	/// See [`CacheMetadata::is_stale`].
+	pub fn is_stale(&self, now: SystemTime) -> bool {
+		self.cache.is_stale(now)
+	}
+
+	/// Checks if the document was served from a cache (such as the
+	/// [`HttpCache`] or the [`FileCacheLoader`](super::FileCacheLoader))
+	/// instead of being downloaded, revalidated or not.
+	pub fn is_from_cache(&self) -> bool {
+		self.from_cache
+	}
+
+	/// Sets whether the document was served from a cache.
+	///
+	/// Caching loaders set this flag on the documents they serve.
+	pub fn set_from_cache(&mut self, value: bool) {
+		self.from_cache = value
+	}
+}
+
+impl<I> RemoteDocument<I> {
+	/// Returns a hash of the content of the document.
+	///
+	/// The hash is computed from the canonical form of the document, so it
+	/// does not depend on whitespaces or on the order of object entries, and
+	/// is stable across executions and platforms. It is suitable as a cache
+	/// key, but not as a cryptographic digest.
+	pub fn content_hash(&self) -> u64 {
+		let mut document = self.document.clone();
+		document.canonicalize_with(&mut ryu_js::Buffer::new());
+		fnv1a(document.compact_print().to_string().as_bytes())
+	}
+}
+
+/// 64-bit FNV-1a hash.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+	let mut hash = 0xcbf29ce484222325u64;
+	for b in bytes {
+		hash ^= *b as u64;
+		hash = hash.wrapping_mul(0x100000001b3)
+	}
+
+	hash
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+
+	const ACCEPT: &str = "application/ld+json, application/json";
+
+	#[test]
+	fn staleness() {
+		let mut a = fixture::document(
+			iri!("http://example.org/a"),
+			r#"{ "a": 1, "b": [true, null] }"#,
+		);
+		let b = fixture::document(
+			iri!("http://example.org/b"),
+			r#"{"b":[true,null],   "a": 1 }"#,
+		);
+		assert_eq!(a.content_hash(), b.content_hash());
+
+		// No caching metadata.
+		let now = SystemTime::now();
+		assert!(a.is_stale(now));
+
+		a.set_cache_metadata(CacheMetadata {
+			fetched_at: Some(now),
+			age: Some(Duration::from_secs(30)),
+			max_age: Some(Duration::from_secs(60)),
+			..Default::default()
+		});
+		assert!(!a.is_stale(now + Duration::from_secs(29)));
+		assert!(a.is_stale(now + Duration::from_secs(30)));
+	}
+
+	#[test]
+	fn revalidation() {
+		let url = iri!("http://example.org/context.jsonld");
+		let mut document = fixture::document(url, "{}");
+
+		// Documents without freshness lifetime nor validator are not stored.
+		let cache = HttpCache::new();
+		cache.insert(url.to_owned(), ACCEPT, document.clone());
+		assert!(cache.is_empty());
+
+		document.set_cache_metadata(CacheMetadata {
+			fetched_at: Some(SystemTime::UNIX_EPOCH),
+			no_cache: true,
+			etag: Some("\"v1\"".to_owned()),
+			..Default::default()
+		});
+		cache.insert(url.to_owned(), ACCEPT, document);
+
+		let mut cached = cache.get(url, ACCEPT).unwrap();
+
+		// Documents loaded with another `Accept` header are cached apart.
+		assert!(cache.get(url, "application/ld+json").is_none());
+		assert!(cached.is_stale(SystemTime::now()));
+
+		let now = SystemTime::now();
+		cached.cache.revalidate(CacheMetadata {
+			fetched_at: Some(now),
+			max_age: Some(Duration::from_secs(60)),
+			..Default::default()
+		});
+		assert!(!cached.is_stale(now));
+		assert_eq!(cached.cache_metadata().etag.as_deref(), Some("\"v1\""));
+	}
+
+	#[test]
+	fn eviction() {
+		let fresh = CacheMetadata {
+			fetched_at: Some(SystemTime::now()),
+			max_age: Some(Duration::from_secs(60)),
+			..Default::default()
+		};
+
+		let cache = HttpCache::with_capacity(2);
+		for url in [
+			iri!("http://example.org/a"),
+			iri!("http://example.org/b"),
+			iri!("http://example.org/c"),
+		] {
+			let mut document = fixture::document(url, "{}");
+			document.set_cache_metadata(fresh.clone());
+			cache.insert(url.to_owned(), ACCEPT, document);
+
+			// Keep `a` in use.
+			cache.get(iri!("http://example.org/a"), ACCEPT);
+		}
+
+		assert_eq!(cache.len(), 2);
+		assert!(cache.get(iri!("http://example.org/a"), ACCEPT).is_some());
+		assert!(cache.get(iri!("http://example.org/b"), ACCEPT).is_none());
+
+		// `no-store` documents are never stored, even with a validator.
+		let url = iri!("http://example.org/a");
+		let mut document = fixture::document(url, "{}");
+		document.set_cache_metadata(CacheMetadata {
+			no_store: true,
+			etag: Some("\"v1\"".to_owned()),
+			..fresh
+		});
+		cache.insert(url.to_owned(), ACCEPT, document);
+		assert!(cache.get(url, ACCEPT).is_none());
+	}
+}
@@ -1,9 +1,11 @@
 use core::fmt;
 
-use crate::{LoadError, LoadErrorCause, LoadingResult};
+use crate::{Context, LoadError, LoadErrorCause, LoadTrace, LoadingResult, Profile};
+use futures::future::BoxFuture;
 use iref::{Iri, IriBuf};
+use std::sync::Arc;
 
-use super::Loader;
+use super::{DynLoader, Loader};
 
 /// * [`ChainLoader`]: loads document from the first loader, otherwise falls back to the second one.
 ///
@@ -11,8 +13,17 @@ use super::Loader;
 /// an [`FsLoader`](super::FsLoader) for loading some contexts from a local cache,
 /// and a [`ReqwestLoader`](super::ReqwestLoader) for loading any other context from the web.
 ///
-/// Note that it is also possible to nest several [`ChainLoader`]s,
-/// to combine more than two loaders.
+/// More loaders can be appended with [`ChainLoader::then`], each one being
+/// tried in order until one succeeds:
+///
+/// ```
+/// use json_ld_core::{loader::{ChainLoader, StaticLoader}, FsLoader, NoLoader};
+///
+/// let loader = ChainLoader::new(StaticLoader::new(), FsLoader::new()).then(NoLoader);
+/// ```
+///
+/// If every loader fails, the returned [`Error`] holds the error of each
+/// loader (see [`Error::causes`]).
 pub struct ChainLoader<L1, L2>(L1, L2);
 
 impl<L1, L2> ChainLoader<L1, L2> {
@@ -20,6 +31,11 @@ impl<L1, L2> ChainLoader<L1, L2> {
 	pub fn new(l1: L1, l2: L2) -> Self {
 		ChainLoader(l1, l2)
 	}
+
+	/// Appends a loader to the chain, tried if every previous loader fails.
+	pub fn then<L3>(self, l3: L3) -> ChainLoader<Self, L3> {
+		ChainLoader(self, l3)
+	}
 }
 
 impl<L1, L2> Loader for ChainLoader<L1, L2>
@@ -30,18 +46,147 @@ where
 	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
 		match self.0.load(url).await {
 			Ok(doc) => Ok(doc),
-			Err(LoadError { cause: e1, .. }) => match self.1.load(url).await {
+			Err(e1) => self.1.load(url).await.map_err(|e2| merge_errors(e1, e2)),
+		}
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		match self.0.load_with_profile(url, profile).await {
+			Ok(doc) => Ok(doc),
+			Err(e1) => self
+				.1
+				.load_with_profile(url, profile)
+				.await
+				.map_err(|e2| merge_errors(e1, e2)),
+		}
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.0
+			.processed_context(url)
+			.or_else(|| self.1.processed_context(url))
+	}
+}
+
+impl<L1, L2> DynLoader for ChainLoader<L1, L2>
+where
+	L1: DynLoader,
+	L2: DynLoader,
+{
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			match self.0.load_dyn(url).await {
 				Ok(doc) => Ok(doc),
-				Err(LoadError { target, cause: e2 }) => Err(LoadError::new(target, Error(e1, e2))),
-			},
+				Err(e1) => self
+					.1
+					.load_dyn(url)
+					.await
+					.map_err(|e2| merge_errors(e1, e2)),
+			}
+		})
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			match self.0.load_with_profile_dyn(url, profile).await {
+				Ok(doc) => Ok(doc),
+				Err(e1) => self
+					.1
+					.load_with_profile_dyn(url, profile)
+					.await
+					.map_err(|e2| merge_errors(e1, e2)),
+			}
+		})
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.0
+			.processed_context_dyn(url)
+			.or_else(|| self.1.processed_context_dyn(url))
+	}
+}
+
+/// Merges the errors of the two loaders of a chain.
+fn merge_errors(e1: LoadError, e2: LoadError) -> LoadError {
+	let LoadError {
+		cause: c1,
+		trace: t1,
+		..
+	} = e1;
+	let LoadError {
+		target,
+		cause: c2,
+		trace: t2,
+	} = e2;
+	let mut error = LoadError::new(target, Error(c1, c2));
+	error.trace = merge_traces(t1, t2);
+	error
+}
+
+/// Merges the traces of two failed loads, in order.
+///
+/// The rejecting loaders of both traces are kept. The HTTP part of the
+/// trace (redirections, status and content type) is taken from the last
+/// trace that has one.
+fn merge_traces(t1: Option<Box<LoadTrace>>, t2: Option<Box<LoadTrace>>) -> Option<Box<LoadTrace>> {
+	match (t1, t2) {
+		(Some(mut t1), Some(t2)) => {
+			let t2 = *t2;
+			if !t2.redirects.is_empty() || t2.status.is_some() || t2.content_type.is_some() {
+				t1.redirects = t2.redirects;
+				t1.status = t2.status;
+				t1.content_type = t2.content_type;
+			}
+
+			t1.rejected_by.extend(t2.rejected_by);
+			Some(t1)
 		}
+		(t1, t2) => t1.or(t2),
 	}
 }
 
 /// Either-or error.
+///
+/// The errors of chains built with [`ChainLoader::then`] are nested: the
+/// first error is itself a chain error. Use [`Self::causes`] or
+/// [`Self::into_causes`] to get the error of each loader of the chain.
 #[derive(Debug)]
 pub struct Error(pub LoadErrorCause, pub LoadErrorCause);
 
+impl Error {
+	/// Returns the error of each loader of the chain, in order, including
+	/// the loaders of nested chains.
+	pub fn causes(&self) -> Vec<&(dyn std::error::Error + Send + Sync + 'static)> {
+		let mut result = Vec::new();
+		for e in [&self.0, &self.1] {
+			match e.downcast_ref::<Self>() {
+				Some(e) => result.extend(e.causes()),
+				None => result.push(e.as_ref()),
+			}
+		}
+
+		result
+	}
+
+	/// Turns this error into the error of each loader of the chain, in order,
+	/// including the loaders of nested chains.
+	pub fn into_causes(self) -> Vec<LoadErrorCause> {
+		let mut result = Vec::new();
+		for e in [self.0, self.1] {
+			match e.downcast::<Self>() {
+				Ok(e) => result.extend(e.into_causes()),
+				Err(e) => result.push(e),
+			}
+		}
+
+		result
+	}
+}
+
 impl fmt::Display for Error {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		let Error(e1, e2) = self;
@@ -50,3 +195,37 @@ impl fmt::Display for Error {
 }
 
 impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, NoLoader, StaticLoader};
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn fallback() {
+		let fallback = fixture::static_loader(&[(iri!("http://example.org/doc"), "{}")]);
+		let loader = ChainLoader::new(StaticLoader::new(), NoLoader).then(fallback);
+		assert!(loader.load(iri!("http://example.org/doc")).await.is_ok());
+
+		let error = loader
+			.load(iri!("http://example.org/missing"))
+			.await
+			.unwrap_err();
+		let error = error.cause.downcast::<Error>().unwrap();
+		assert_eq!(error.causes().len(), 3);
+	}
+
+	#[async_std::test]
+	async fn merged_traces() {
+		let loader = ChainLoader::new(StaticLoader::new(), NoLoader);
+		let error = loader
+			.load(iri!("https://example.org/missing"))
+			.await
+			.unwrap_err();
+		let trace = error.trace().unwrap();
+		assert_eq!(trace.rejected_by, ["StaticLoader", "NoLoader"]);
+		assert_eq!(trace.status, None);
+		assert!(error.to_string().contains("FsLoader::mount"));
+	}
+}
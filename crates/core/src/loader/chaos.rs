@@ -0,0 +1,221 @@
+//! Fault injection loader, for resilience testing.
+use super::{DynLoader, Loader, RemoteDocument};
+use crate::{Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Faults injected by a [`ChaosLoader`].
+#[derive(Debug, Clone, Default)]
+pub struct ChaosOptions {
+	/// Seed of the random number generator.
+	///
+	/// Two loaders with the same seed inject the same faults for the same
+	/// sequence of loads.
+	pub seed: u64,
+
+	/// Probability (between `0.0` and `1.0`) that a load fails.
+	pub failure_rate: f64,
+
+	/// Probability (between `0.0` and `1.0`) that a loaded document is
+	/// corrupted.
+	pub corruption_rate: f64,
+
+	/// Minimum delay added to each load.
+	pub min_delay: Duration,
+
+	/// Maximum delay added to each load.
+	pub max_delay: Duration,
+}
+
+/// Error injected by a [`ChaosLoader`].
+#[derive(Debug, thiserror::Error)]
+#[error("injected failure")]
+pub struct InjectedFailure;
+
+/// Loader wrapping another loader to inject delays, failures and corrupted
+/// documents.
+///
+/// Faults are drawn from a seeded random number generator, so that tests of
+/// retry or fallback strategies are reproducible. A corrupted document is
+/// replaced by `null`, by a string, or loses one of its top level entries.
+///
+/// Delays are measured by the [`async_io`] timer (the one of `async-std`
+/// and `smol`), which also works with other runtimes.
+///
+/// ```
+/// use json_ld_core::loader::{ChaosLoader, ChaosOptions, NoLoader};
+///
+/// let loader = ChaosLoader::new(NoLoader, ChaosOptions {
+///   seed: 42,
+///   failure_rate: 0.5,
+///   ..Default::default()
+/// });
+/// ```
+pub struct ChaosLoader<L> {
+	inner: L,
+	options: ChaosOptions,
+	state: AtomicU64,
+}
+
+impl<L> ChaosLoader<L> {
+	/// Creates a new loader injecting faults in the loads of `inner`.
+	pub fn new(inner: L, options: ChaosOptions) -> Self {
+		Self {
+			state: AtomicU64::new(options.seed),
+			inner,
+			options,
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns the injected faults.
+	pub fn options(&self) -> &ChaosOptions {
+		&self.options
+	}
+
+	/// Returns the next pseudo-random number (SplitMix64).
+	fn next_u64(&self) -> u64 {
+		let mut z = self
+			.state
+			.fetch_add(0x9e3779b97f4a7c15, Ordering::Relaxed)
+			.wrapping_add(0x9e3779b97f4a7c15);
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+		z ^ (z >> 31)
+	}
+
+	/// Returns a pseudo-random number in `[0, 1)`.
+	fn next_f64(&self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+	}
+
+	fn corrupt(&self, document: &mut json_syntax::Value) {
+		match self.next_u64() % 3 {
+			0 => *document = json_syntax::Value::Null,
+			1 => *document = "corrupted".into(),
+			_ => {
+				if let json_syntax::Value::Object(object) = document {
+					if !object.is_empty() {
+						let i = (self.next_u64() % object.len() as u64) as usize;
+						let key = object.entries()[i].key.clone();
+						object.remove(&key);
+					}
+				}
+			}
+		}
+	}
+}
+
+impl<L> ChaosLoader<L> {
+	/// Runs the given load of `url`, injecting delays, failures and
+	/// corruptions.
+	async fn disturb(
+		&self,
+		url: &Iri,
+		load: impl Future<Output = LoadingResult<IriBuf>>,
+	) -> LoadingResult<IriBuf> {
+		let ChaosOptions {
+			min_delay,
+			max_delay,
+			..
+		} = self.options;
+		let delay = min_delay + max_delay.saturating_sub(min_delay).mul_f64(self.next_f64());
+		let fail = self.next_f64() < self.options.failure_rate;
+		let corrupt = self.next_f64() < self.options.corruption_rate;
+
+		if !delay.is_zero() {
+			async_io::Timer::after(delay).await;
+		}
+
+		if fail {
+			return Err(LoadError::new(url.to_owned(), InjectedFailure));
+		}
+
+		let mut document: RemoteDocument = load.await?;
+		if corrupt {
+			self.corrupt(&mut document.document)
+		}
+
+		Ok(document)
+	}
+}
+
+impl<L: Loader> Loader for ChaosLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.disturb(url, self.inner.load(url)).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.disturb(url, self.inner.load_with_profile(url, profile))
+			.await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for ChaosLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.disturb(url, self.inner.load_dyn(url)))
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.disturb(url, self.inner.load_with_profile_dyn(url, profile)))
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+
+	/// Returns the outcome of each of the given number of loads: `None` for
+	/// a failure, the loaded document otherwise.
+	async fn outcomes(seed: u64, loads: usize) -> Vec<Option<json_syntax::Value>> {
+		let url = iri!("http://example.org/document.jsonld");
+		let loader = ChaosLoader::new(
+			fixture::static_loader(&[(url, r#"{ "a": 1, "b": 2 }"#)]),
+			ChaosOptions {
+				seed,
+				failure_rate: 0.3,
+				corruption_rate: 0.3,
+				min_delay: Duration::ZERO,
+				max_delay: Duration::from_millis(2),
+			},
+		);
+
+		let mut result = Vec::new();
+		for _ in 0..loads {
+			result.push(loader.load(url).await.ok().map(|d| d.document))
+		}
+
+		result
+	}
+
+	#[async_std::test]
+	async fn deterministic() {
+		let a = outcomes(42, 32).await;
+		assert_eq!(a, outcomes(42, 32).await);
+		assert!(a.iter().any(Option::is_none));
+		assert!(a.iter().any(Option::is_some));
+		assert_ne!(a, outcomes(7, 32).await)
+	}
+}
@@ -0,0 +1,86 @@
+//! Loaders dedicated to remote contexts.
+use super::{DynLoader, Loader};
+use crate::{Context, LoadingResult, Profile};
+use iref::{Iri, IriBuf};
+use std::sync::Arc;
+
+/// Remote context loader.
+///
+/// A context loader can be used instead of the document loader to fetch the
+/// remote contexts of a document, for instance to resolve them against a
+/// pinned registry while the documents themselves are fetched from the web.
+///
+/// Every [`Loader`] is a context loader. The processing options carry their
+/// context loader as a [`DynLoader`], so that its type does not leak into
+/// the options.
+pub trait ContextLoader: Loader {}
+
+impl<L: Loader + ?Sized> ContextLoader for L {}
+
+/// Loader delegating to a [`ContextLoader`], if any.
+///
+/// Processing algorithms only use their loader to fetch remote contexts.
+/// This adapter is used to pass them the context loader when one is given,
+/// or the document loader otherwise.
+pub struct WithContextLoader<'a, L, C: ?Sized = dyn DynLoader> {
+	context_loader: Option<&'a C>,
+	loader: L,
+}
+
+impl<'a, L, C: ?Sized> WithContextLoader<'a, L, C> {
+	/// Creates a new loader delegating to `context_loader` if it is not
+	/// `None`, and to `loader` otherwise.
+	pub fn new(context_loader: Option<&'a C>, loader: L) -> Self {
+		Self {
+			context_loader,
+			loader,
+		}
+	}
+}
+
+impl<'a, L: Loader, C: ContextLoader + ?Sized> Loader for WithContextLoader<'a, L, C> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.context_loader {
+			Some(context_loader) => context_loader.load(url).await,
+			None => self.loader.load(url).await,
+		}
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		match self.context_loader {
+			Some(context_loader) => context_loader.load_with_profile(url, profile).await,
+			None => self.loader.load_with_profile(url, profile).await,
+		}
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		match self.context_loader {
+			Some(context_loader) => context_loader.processed_context(url),
+			None => self.loader.processed_context(url),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, NoLoader, WithProcessedContexts};
+	use static_iref::iri;
+	use std::collections::HashMap;
+
+	#[async_std::test]
+	async fn forwarding() {
+		let url = iri!("http://example.org/context.jsonld");
+		let contexts = HashMap::from([(url.to_owned(), Arc::new(Context::new(None)))]);
+		let registry = WithProcessedContexts::new(contexts, fixture::static_loader(&[(url, "{}")]));
+
+		let loader = WithContextLoader::new(Some(&registry), NoLoader);
+		assert!(loader.load(url).await.is_ok());
+		assert!(loader.load_with_profile(url, &[]).await.is_ok());
+		assert!(loader.processed_context(url).is_some());
+
+		let loader = WithContextLoader::<_, NoLoader>::new(None, NoLoader);
+		assert!(loader.load(url).await.is_err());
+		assert!(loader.processed_context(url).is_none())
+	}
+}
@@ -0,0 +1,114 @@
+//! Object safe loaders.
+use super::{Loader, SyncLoader};
+use crate::{Context, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use std::sync::Arc;
+
+/// Object safe document loader.
+///
+/// [`Loader`] is not object safe, because of its asynchronous and generic
+/// functions. This trait can be used instead where a loader must be chosen
+/// at run time, for instance to store loaders of different types in the
+/// same registry, or to inject a loader through a dependency injection
+/// container. `dyn DynLoader` implements [`Loader`], and so do
+/// `Box<dyn DynLoader>`, `Arc<dyn DynLoader>` and `&dyn DynLoader`.
+///
+/// This trait is implemented by every [`SyncLoader`] (such as
+/// [`StaticLoader`](super::StaticLoader), [`FsLoader`](super::FsLoader) or
+/// maps), by the [`ReqwestLoader`](super::ReqwestLoader), and by the
+/// wrapping loaders (such as [`ChainLoader`](super::ChainLoader) or
+/// [`RetryLoader`](super::RetryLoader)) whose inner loaders implement it.
+///
+/// The [`RouterLoader`](super::RouterLoader),
+/// [`ResolverLoader`](super::ResolverLoader) and `HyperLoader` do not
+/// implement it, since the futures of their routes, resolvers and services
+/// are not known to be [`Send`]. Such loaders, and custom asynchronous
+/// loaders, can implement it by boxing the futures returned by
+/// [`Loader::load`] and [`Loader::load_with_profile`], provided they are
+/// [`Send`].
+///
+/// ```
+/// use std::sync::Arc;
+/// use json_ld_core::loader::{DynLoader, FsLoader, NoLoader};
+///
+/// let loaders: Vec<Arc<dyn DynLoader>> = vec![
+///   Arc::new(NoLoader),
+///   Arc::new(FsLoader::new()),
+/// ];
+/// ```
+pub trait DynLoader: Send + Sync {
+	/// Loads the document behind the given IRI.
+	///
+	/// See [`Loader::load`].
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>>;
+
+	/// Loads the document behind the given IRI, requesting the given
+	/// profiles.
+	///
+	/// See [`Loader::load_with_profile`]. By default, the profiles are
+	/// ignored and the document is loaded with [`Self::load_dyn`].
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		let _ = profile;
+		self.load_dyn(url)
+	}
+
+	/// Returns the already processed context behind the given IRI, if any.
+	///
+	/// See [`Loader::processed_context`].
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		let _ = url;
+		None
+	}
+}
+
+impl<L: SyncLoader + Send + Sync> DynLoader for L {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(std::future::ready(self.load_sync(url)))
+	}
+}
+
+impl<'d> Loader for dyn DynLoader + 'd {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.load_dyn(url).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.load_with_profile_dyn(url, profile).await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.processed_context_dyn(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, ChainLoader, NoLoader, RetryLoader, RetryOptions};
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn registry() {
+		let url = iri!("http://example.org/context.jsonld");
+		let loader = fixture::static_loader(&[(url, "{}")]);
+		let registry: Vec<Arc<dyn DynLoader>> = vec![Arc::new(NoLoader), Arc::new(loader)];
+
+		assert!(registry[0].load(url).await.is_err());
+		assert!(registry[1].load(url).await.is_ok());
+	}
+
+	#[async_std::test]
+	async fn wrapped() {
+		let url = iri!("http://example.org/context.jsonld");
+		let loader = ChainLoader::new(NoLoader, fixture::static_loader(&[(url, "{}")]));
+		let loader: Arc<dyn DynLoader> =
+			Arc::new(RetryLoader::new(loader, RetryOptions::default()));
+
+		assert!(loader.load(url).await.is_ok());
+	}
+}
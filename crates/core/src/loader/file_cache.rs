@@ -0,0 +1,482 @@
+//! Disk-persistent document cache.
+use super::{cache::fnv1a, CacheMetadata, DynLoader, Loader, Profile, RemoteDocument};
+use crate::{Context, LoadingResult};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use json_syntax::{Parse, Print};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Number of temporary files created by this process, used to give each of
+/// them a unique name.
+static TMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Loader storing the documents loaded by another loader in a cache
+/// directory.
+///
+/// Each document is stored in its own file, named after a hash of its IRI
+/// and [requested profiles](Loader::load_with_profile), along with its URL,
+/// content type, context URL, profiles and [caching
+/// metadata](RemoteDocument::cache_metadata). Documents found in the cache
+/// directory are served without calling the inner loader, including across
+/// program executions. Unreadable cache entries are ignored and replaced.
+/// Failing to write an entry is logged, and does not fail the load.
+///
+/// Entries of documents served with a freshness lifetime (or requiring
+/// revalidation) expire according to their caching metadata. Documents that
+/// must not be stored are not. Other entries never expire:
+/// [`Self::set_refresh`] forces the documents to be loaded again (and the
+/// cache entries to be replaced).
+///
+/// The cache files are read and written on a thread pool, so that the
+/// loader does not block the async runtime.
+///
+/// ```
+/// use json_ld_core::loader::{FileCacheLoader, NoLoader};
+///
+/// let loader = FileCacheLoader::new(NoLoader, std::env::temp_dir().join("json-ld-cache"));
+/// ```
+pub struct FileCacheLoader<L> {
+	inner: L,
+	directory: PathBuf,
+	refresh: bool,
+}
+
+impl<L> FileCacheLoader<L> {
+	/// Creates a new loader caching the documents loaded by `inner` in the
+	/// given directory.
+	///
+	/// The directory is created when the first document is stored.
+	pub fn new(inner: L, directory: impl Into<PathBuf>) -> Self {
+		Self {
+			inner,
+			directory: directory.into(),
+			refresh: false,
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns the cache directory.
+	pub fn directory(&self) -> &Path {
+		&self.directory
+	}
+
+	/// Sets whether cache entries are ignored, so that every document is
+	/// loaded again with the inner loader and its entry replaced.
+	pub fn set_refresh(&mut self, refresh: bool) {
+		self.refresh = refresh
+	}
+
+	/// Returns the path of the cache entry for the given `url`.
+	pub fn filepath(&self, url: &Iri) -> PathBuf {
+		self.filepath_with_profile(url, &[])
+	}
+
+	/// Returns the path of the cache entry for the given `url` requested with
+	/// the given profiles.
+	pub fn filepath_with_profile(&self, url: &Iri, profile: &[Profile]) -> PathBuf {
+		let mut key = url.as_str().to_owned();
+		for p in profile {
+			key.push(' ');
+			key.push_str(p.iri().as_str())
+		}
+
+		self.directory
+			.join(format!("{:016x}.json", fnv1a(key.as_bytes())))
+	}
+
+	/// Reads the cache entry of the given `url` and profiles, if any and not
+	/// expired.
+	async fn read(&self, url: &Iri, profile: &[Profile]) -> Option<RemoteDocument> {
+		let path = self.filepath_with_profile(url, profile);
+		let content = blocking::unblock(move || std::fs::read_to_string(path))
+			.await
+			.ok()?;
+		let (entry, _) = json_syntax::Value::parse_str(&content).ok()?;
+		let (iri, requested, document) = decode_entry(entry.as_object()?)?;
+
+		// Different keys may share the same hash.
+		if iri.as_str() != url.as_str() || requested != profile {
+			return None;
+		}
+
+		if is_expired(document.cache_metadata(), SystemTime::now()) {
+			return None;
+		}
+
+		Some(document)
+	}
+
+	/// Writes the cache entry of the given `url` and profiles.
+	///
+	/// The entry is first written to a temporary file, unique to this write,
+	/// then moved in place, so that concurrent loads of the same document
+	/// never read a partially written entry.
+	async fn write(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+		document: &RemoteDocument,
+	) -> std::io::Result<()> {
+		let directory = self.directory.clone();
+		let path = self.filepath_with_profile(url, profile);
+		let tmp_path = path.with_extension(format!(
+			"json.{}.{}.tmp",
+			std::process::id(),
+			TMP_FILES.fetch_add(1, Ordering::Relaxed)
+		));
+		let content = json_syntax::Value::Object(encode_entry(url, profile, document))
+			.compact_print()
+			.to_string();
+
+		blocking::unblock(move || {
+			std::fs::create_dir_all(directory)?;
+			std::fs::write(&tmp_path, content)?;
+			std::fs::rename(&tmp_path, path).inspect_err(|_| {
+				let _ = std::fs::remove_file(&tmp_path);
+			})
+		})
+		.await
+	}
+
+	/// Loads the document behind `url` with the given profiles, from the
+	/// cache or with `load`.
+	async fn cached(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+		load: impl Future<Output = LoadingResult<IriBuf>>,
+	) -> LoadingResult<IriBuf> {
+		if !self.refresh {
+			if let Some(mut document) = self.read(url, profile).await {
+				document.set_from_cache(true);
+				return Ok(document);
+			}
+		}
+
+		let document = load.await?;
+		if !document.cache_metadata().no_store {
+			if let Err(e) = self.write(url, profile, &document).await {
+				log::warn!("unable to write cache entry for {url}: {e}")
+			}
+		}
+
+		Ok(document)
+	}
+}
+
+/// Checks if a cache entry with the given caching metadata expired at the
+/// given time.
+///
+/// Only the entries having a freshness lifetime, or requiring revalidation,
+/// expire.
+fn is_expired(cache: &CacheMetadata, now: SystemTime) -> bool {
+	(cache.freshness_lifetime().is_some() || cache.no_cache) && cache.is_stale(now)
+}
+
+/// Encodes the document loaded from `url` with the `requested` profiles,
+/// along with its URL, content type, context URL and profiles.
+///
+/// The document is stored as its raw content if available.
+pub(super) fn encode_entry(
+	url: &Iri,
+	requested: &[Profile],
+	document: &RemoteDocument,
+) -> json_syntax::Object {
+	let mut entry = json_syntax::Object::new();
+	entry.insert("iri".into(), url.as_str().into());
+
+	if !requested.is_empty() {
+		entry.insert("requestedProfile".into(), encode_profiles(requested));
+	}
+
+	if let Some(doc_url) = document.url() {
+		entry.insert("url".into(), doc_url.as_str().into());
+	}
+
+	if let Some(content_type) = document.content_type() {
+		entry.insert("contentType".into(), content_type.as_ref().into());
+	}
+
+	if let Some(context_url) = document.context_url() {
+		entry.insert("contextUrl".into(), context_url.as_str().into());
+	}
+
+	entry.insert("profile".into(), encode_profiles(&document.profile));
+
+	let cache = encode_cache(document.cache_metadata());
+	if !cache.is_empty() {
+		entry.insert("cache".into(), json_syntax::Value::Object(cache));
+	}
+
+	// The raw content is stored instead of the document when available, so
+	// that it can be checked by an `IntegrityLoader`.
+	match document
+		.raw_content()
+		.and_then(|content| std::str::from_utf8(content).ok())
+	{
+		Some(content) => entry.insert("content".into(), content.into()),
+		None => entry.insert("document".into(), document.document().clone()),
+	};
+
+	entry
+}
+
+fn encode_profiles<'a>(profile: impl IntoIterator<Item = &'a Profile>) -> json_syntax::Value {
+	json_syntax::Value::Array(
+		profile
+			.into_iter()
+			.map(|p| p.iri().as_str().into())
+			.collect(),
+	)
+}
+
+/// Encodes the caching metadata of a document, durations and times being
+/// given in seconds (since the Unix epoch for times).
+fn encode_cache(cache: &CacheMetadata) -> json_syntax::Object {
+	let mut entry = json_syntax::Object::new();
+	let secs = |d: Duration| json_syntax::Value::Number(d.as_secs().into());
+
+	if let Some(fetched_at) = cache.fetched_at {
+		let since_epoch = fetched_at.duration_since(UNIX_EPOCH).unwrap_or_default();
+		entry.insert("fetchedAt".into(), secs(since_epoch));
+	}
+
+	if let Some(age) = cache.age {
+		entry.insert("age".into(), secs(age));
+	}
+
+	if let Some(max_age) = cache.max_age {
+		entry.insert("maxAge".into(), secs(max_age));
+	}
+
+	if let Some(s_maxage) = cache.s_maxage {
+		entry.insert("sMaxAge".into(), secs(s_maxage));
+	}
+
+	if let Some(expires_after) = cache.expires_after {
+		entry.insert("expiresAfter".into(), secs(expires_after));
+	}
+
+	if cache.no_cache {
+		entry.insert("noCache".into(), json_syntax::Value::Boolean(true));
+	}
+
+	if let Some(etag) = &cache.etag {
+		entry.insert("etag".into(), etag.as_str().into());
+	}
+
+	entry
+}
+
+/// Decodes caching metadata encoded with [`encode_cache`].
+fn decode_cache(entry: &json_syntax::Object) -> CacheMetadata {
+	let get = |key: &str| entry.get_unique(key).ok().flatten();
+	let secs = |key: &str| match get(key)? {
+		json_syntax::Value::Number(n) => n.as_str().parse().ok().map(Duration::from_secs),
+		_ => None,
+	};
+
+	CacheMetadata {
+		fetched_at: secs("fetchedAt").map(|d| UNIX_EPOCH + d),
+		age: secs("age"),
+		max_age: secs("maxAge"),
+		s_maxage: secs("sMaxAge"),
+		expires_after: secs("expiresAfter"),
+		no_cache: matches!(get("noCache"), Some(json_syntax::Value::Boolean(true))),
+		no_store: false,
+		etag: get("etag")
+			.and_then(json_syntax::Value::as_str)
+			.map(ToOwned::to_owned),
+	}
+}
+
+/// Decodes an entry encoded with [`encode_entry`], returning the IRI the
+/// document was loaded from, the requested profiles and the document.
+pub(super) fn decode_entry(
+	entry: &json_syntax::Object,
+) -> Option<(IriBuf, Vec<Profile>, RemoteDocument)> {
+	let get_str = |key: &str| {
+		entry
+			.get_unique(key)
+			.ok()
+			.flatten()
+			.and_then(json_syntax::Value::as_str)
+	};
+	let get_iri = |key: &str| get_str(key).and_then(|iri| IriBuf::new(iri.to_owned()).ok());
+
+	let get_profiles = |key: &str| {
+		entry
+			.get_unique(key)
+			.ok()
+			.flatten()
+			.and_then(json_syntax::Value::as_array)
+			.into_iter()
+			.flatten()
+			.filter_map(|p| Iri::new(p.as_str()?).ok())
+			.map(Profile::new)
+	};
+
+	let iri = get_iri("iri")?;
+	let requested = get_profiles("requestedProfile").collect();
+
+	let content = get_str("content");
+	let document = match content {
+		Some(content) => json_syntax::Value::parse_str(content).ok()?.0,
+		None => entry.get_unique("document").ok().flatten()?.clone(),
+	};
+
+	let mut document = RemoteDocument::new_full(
+		get_iri("url"),
+		get_str("contentType").and_then(|t| t.parse().ok()),
+		get_iri("contextUrl"),
+		get_profiles("profile").collect(),
+		document,
+	);
+	document.set_raw_content(content.map(|content| content.as_bytes().into()));
+
+	if let Some(cache) = entry
+		.get_unique("cache")
+		.ok()
+		.flatten()
+		.and_then(json_syntax::Value::as_object)
+	{
+		document.set_cache_metadata(decode_cache(cache))
+	}
+
+	Some((iri, requested, document))
+}
+
+impl<L: Loader> Loader for FileCacheLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.cached(url, &[], self.inner.load(url)).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.cached(url, profile, self.inner.load_with_profile(url, profile))
+			.await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for FileCacheLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.cached(url, &[], self.inner.load_dyn(url)))
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.cached(url, profile, self.inner.load_with_profile_dyn(url, profile)))
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, NoLoader};
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn persist_and_refresh() {
+		let directory = fixture::TempDir::new("file-cache");
+		let url = iri!("http://example.org/context.jsonld");
+
+		let inner = fixture::static_loader(&[(
+			url,
+			r#"{ "@context": { "@vocab": "http://example.org/" } }"#,
+		)]);
+		let loaded = FileCacheLoader::new(inner, directory.path())
+			.load(url)
+			.await
+			.unwrap();
+
+		let mut cached_loader = FileCacheLoader::new(NoLoader, directory.path());
+		let cached = cached_loader.load(url).await.unwrap();
+		assert_eq!(cached.url(), loaded.url());
+		assert_eq!(cached.document(), loaded.document());
+
+		// Documents requested with a profile are cached separately.
+		let compacted = Profile::Standard(crate::loader::StandardProfile::Compacted);
+		assert!(cached_loader
+			.load_with_profile(url, &[compacted])
+			.await
+			.is_err());
+
+		cached_loader.set_refresh(true);
+		assert!(cached_loader.load(url).await.is_err());
+	}
+
+	/// Loader serving documents with the given caching metadata.
+	struct WithMetadata(CacheMetadata);
+
+	impl Loader for WithMetadata {
+		async fn load(&self, url: &Iri) -> LoadingResult {
+			let mut document = fixture::document(url, "{}");
+			document.set_cache_metadata(self.0.clone());
+			Ok(document)
+		}
+	}
+
+	#[async_std::test]
+	async fn expiry() {
+		let directory = fixture::TempDir::new("file-cache-expiry");
+		let url = iri!("http://example.org/context.jsonld");
+		let metadata = |fetched_at| CacheMetadata {
+			fetched_at: Some(fetched_at),
+			max_age: Some(Duration::from_secs(3600)),
+			..Default::default()
+		};
+
+		let fresh = metadata(SystemTime::now());
+		FileCacheLoader::new(WithMetadata(fresh), directory.path())
+			.load(url)
+			.await
+			.unwrap();
+		let cached = FileCacheLoader::new(NoLoader, directory.path())
+			.load(url)
+			.await
+			.unwrap();
+		assert_eq!(
+			cached.cache_metadata().max_age,
+			Some(Duration::from_secs(3600))
+		);
+
+		let stale = metadata(UNIX_EPOCH);
+		FileCacheLoader::new(WithMetadata(stale), directory.path())
+			.load(url)
+			.await
+			.unwrap();
+		assert!(FileCacheLoader::new(NoLoader, directory.path())
+			.load(url)
+			.await
+			.is_err());
+	}
+
+	#[async_std::test]
+	async fn write_failure() {
+		let directory = fixture::TempDir::new("file-cache-write-failure");
+		let url = iri!("http://example.org/context.jsonld");
+
+		// The cache directory cannot be created over a file.
+		let file = directory.write("file", "");
+		let loader = FileCacheLoader::new(fixture::static_loader(&[(url, "{}")]), file);
+		assert!(loader.load(url).await.is_ok())
+	}
+}
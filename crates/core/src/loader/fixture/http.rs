@@ -0,0 +1,197 @@
+//! Local HTTP server, for the HTTP loader tests.
+//!
+//! Also included by the `json-ld` crate tests.
+// Not every HTTP loader uses every helper.
+#![allow(dead_code)]
+
+use iref::IriBuf;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Request received by a [`Server`].
+#[derive(Debug, Clone)]
+pub struct Request {
+	/// Path (and query) of the request.
+	pub path: String,
+
+	/// Headers of the request.
+	pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+	/// Returns the value of the given header (case insensitive), if any.
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers
+			.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+}
+
+/// Response sent by a [`Server`].
+pub struct Response {
+	status: u16,
+	headers: Vec<(String, String)>,
+	body: Vec<u8>,
+	content_length: bool,
+}
+
+impl Response {
+	/// Creates an empty response with the given status.
+	pub fn new(status: u16) -> Self {
+		Self {
+			status,
+			headers: Vec::new(),
+			body: Vec::new(),
+			content_length: true,
+		}
+	}
+
+	/// Creates a `200 OK` response serving the given JSON-LD document.
+	pub fn json_ld(body: &str) -> Self {
+		Self::new(200)
+			.header("Content-Type", "application/ld+json")
+			.body(body)
+	}
+
+	/// Creates a redirection to the given location.
+	pub fn redirect(location: &str) -> Self {
+		Self::new(302).header("Location", location)
+	}
+
+	/// Adds a header.
+	pub fn header(mut self, name: &str, value: &str) -> Self {
+		self.headers.push((name.to_owned(), value.to_owned()));
+		self
+	}
+
+	/// Sets the body.
+	pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+		self.body = body.into();
+		self
+	}
+
+	/// Omits the `Content-Length` header, the end of the body being
+	/// signaled by closing the connection.
+	pub fn without_content_length(mut self) -> Self {
+		self.content_length = false;
+		self
+	}
+
+	fn write(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+		write!(
+			stream,
+			"HTTP/1.1 {} Status\r\nConnection: close\r\n",
+			self.status
+		)?;
+		if self.content_length {
+			write!(stream, "Content-Length: {}\r\n", self.body.len())?;
+		}
+
+		for (name, value) in &self.headers {
+			write!(stream, "{name}: {value}\r\n")?;
+		}
+
+		stream.write_all(b"\r\n")?;
+		stream.write_all(&self.body)
+	}
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// HTTP server answering each request with a handler, and recording the
+/// requests. Stopped when dropped.
+pub struct Server {
+	addr: SocketAddr,
+	requests: Arc<Mutex<Vec<Request>>>,
+	stopped: Arc<AtomicBool>,
+}
+
+impl Server {
+	/// Starts a server on a free local port.
+	pub fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = listener.local_addr().unwrap();
+		let requests = Arc::new(Mutex::new(Vec::new()));
+		let stopped = Arc::new(AtomicBool::new(false));
+		let handler: Arc<Handler> = Arc::new(handler);
+
+		let server_requests = requests.clone();
+		let server_stopped = stopped.clone();
+		std::thread::spawn(move || {
+			for stream in listener.incoming() {
+				if server_stopped.load(Ordering::SeqCst) {
+					break;
+				}
+
+				let Ok(stream) = stream else { continue };
+				let requests = server_requests.clone();
+				let handler = handler.clone();
+				std::thread::spawn(move || serve(stream, &requests, &*handler));
+			}
+		});
+
+		Self {
+			addr,
+			requests,
+			stopped,
+		}
+	}
+
+	/// Returns the URL of the given path (starting with `/`) on this
+	/// server.
+	pub fn url(&self, path: &str) -> IriBuf {
+		IriBuf::new(format!("http://{}{path}", self.addr)).unwrap()
+	}
+
+	/// Returns the requests received so far.
+	pub fn requests(&self) -> Vec<Request> {
+		self.requests.lock().unwrap().clone()
+	}
+}
+
+impl Drop for Server {
+	fn drop(&mut self) {
+		self.stopped.store(true, Ordering::SeqCst);
+		// Wake the listener up so that it notices it is stopped.
+		let _ = TcpStream::connect(self.addr);
+	}
+}
+
+/// Reads a request from the given stream and answers it.
+fn serve(mut stream: TcpStream, requests: &Mutex<Vec<Request>>, handler: &Handler) {
+	let mut reader = BufReader::new(&stream);
+	let mut line = String::new();
+	if reader.read_line(&mut line).is_err() {
+		return;
+	}
+
+	let Some(path) = line.split_whitespace().nth(1) else {
+		return;
+	};
+
+	let mut request = Request {
+		path: path.to_owned(),
+		headers: Vec::new(),
+	};
+
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line).is_err() {
+			return;
+		}
+
+		match line.trim_end().split_once(':') {
+			Some((name, value)) => request
+				.headers
+				.push((name.trim().to_owned(), value.trim().to_owned())),
+			None => break,
+		}
+	}
+
+	let response = handler(&request);
+	requests.lock().unwrap().push(request);
+	let _ = response.write(&mut stream);
+}
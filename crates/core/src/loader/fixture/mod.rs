@@ -0,0 +1,75 @@
+//! Fixtures shared by the loader tests.
+use super::{RemoteDocument, StaticLoader};
+use iref::Iri;
+use json_syntax::Parse;
+use std::path::{Path, PathBuf};
+
+/// Parses the given JSON document.
+pub fn json(content: &str) -> json_syntax::Value {
+	json_syntax::Value::parse_str(content).unwrap().0
+}
+
+/// Creates a remote document loaded from `url` with the given content.
+pub fn document(url: &Iri, content: &str) -> RemoteDocument {
+	let mut document = RemoteDocument::new(Some(url.to_owned()), None, json(content));
+	document.set_raw_content(Some(content.as_bytes().into()));
+	document
+}
+
+/// Creates a loader serving the given documents.
+pub fn static_loader(documents: &[(&Iri, &str)]) -> StaticLoader {
+	let mut loader = StaticLoader::new();
+	for (url, content) in documents {
+		loader.insert_str((*url).to_owned(), content).unwrap();
+	}
+
+	loader
+}
+
+/// Returns the string value of the `from` entry of the given document,
+/// used by the tests to know which loader or file served a document.
+pub fn from(document: &RemoteDocument) -> &str {
+	document
+		.document()
+		.as_object()
+		.unwrap()
+		.get_unique("from")
+		.unwrap()
+		.unwrap()
+		.as_str()
+		.unwrap()
+}
+
+/// Temporary directory, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+	/// Creates a new temporary directory whose name starts with `name`.
+	pub fn new(name: &str) -> Self {
+		let path = std::env::temp_dir().join(format!("json-ld-{name}-{}", std::process::id()));
+		std::fs::create_dir_all(&path).unwrap();
+		Self(path)
+	}
+
+	/// Returns the path of the directory.
+	pub fn path(&self) -> &Path {
+		&self.0
+	}
+
+	/// Writes a file in the directory, creating the parent directories.
+	pub fn write(&self, path: &str, content: impl AsRef<[u8]>) -> PathBuf {
+		let path = self.0.join(path);
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(&path, content).unwrap();
+		path
+	}
+}
+
+impl Drop for TempDir {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_dir_all(&self.0);
+	}
+}
+
+#[cfg(any(feature = "reqwest", feature = "ureq", feature = "hyper"))]
+pub mod http;
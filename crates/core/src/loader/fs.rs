@@ -1,7 +1,8 @@
-use super::{Loader, RemoteDocument};
+use super::{Loader, RemoteDocument, TooLarge};
 use crate::{LoadError, LoadingResult};
 use iref::{Iri, IriBuf};
 use json_syntax::Parse;
+use mime::Mime;
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::{Path, PathBuf};
@@ -22,16 +23,135 @@ pub enum Error {
 	Parse(json_syntax::parse::Error),
 }
 
+/// How the IRIs of a mount point are mapped to file paths.
+///
+/// By default, each segment of the IRI path (relative to the mount point
+/// IRI) is used as is as a path component.
+#[derive(Debug, Default, Clone)]
+pub struct PathMapping {
+	/// Extension appended to the file name when it has none (for instance
+	/// `jsonld`).
+	pub extension: Option<String>,
+
+	/// File name used when the IRI path is empty or ends with a `/` (for
+	/// instance `index.jsonld`). If unset, such IRIs are mapped to the
+	/// directory itself, which cannot be loaded.
+	pub index: Option<String>,
+
+	/// Whether path segments are percent-decoded.
+	///
+	/// Segments decoding to `.`, `..` or containing a path separator are
+	/// rejected.
+	pub percent_decode: bool,
+}
+
+impl PathMapping {
+	/// Returns the file path of the given IRI path suffix, relative to the
+	/// mount point directory.
+	fn map(&self, suffix: &str) -> Option<PathBuf> {
+		let mut segments: Vec<String> = Vec::new();
+		for segment in suffix.split('/').filter(|s| !s.is_empty()) {
+			let segment = if self.percent_decode {
+				percent_decode(segment)?
+			} else {
+				segment.to_owned()
+			};
+
+			if segment == "." || segment == ".." || segment.contains(['/', '\\']) {
+				return None;
+			}
+
+			segments.push(segment)
+		}
+
+		if suffix.is_empty() || suffix.ends_with('/') {
+			if let Some(index) = &self.index {
+				segments.push(index.clone())
+			}
+		} else if let Some(extension) = &self.extension {
+			if let Some(last) = segments.last_mut() {
+				if Path::new(last.as_str()).extension().is_none() {
+					last.push('.');
+					last.push_str(extension)
+				}
+			}
+		}
+
+		Some(segments.into_iter().collect())
+	}
+}
+
+/// Decodes the percent-encoded characters of the given string.
+fn percent_decode(s: &str) -> Option<String> {
+	let mut bytes = Vec::with_capacity(s.len());
+	let mut chars = s.bytes();
+	while let Some(b) = chars.next() {
+		if b == b'%' {
+			let hex = [chars.next()?, chars.next()?];
+			bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?)
+		} else {
+			bytes.push(b)
+		}
+	}
+
+	String::from_utf8(bytes).ok()
+}
+
+/// File-system mount point.
+struct MountPoint {
+	url: IriBuf,
+	path: PathBuf,
+	mapping: PathMapping,
+	priority: i32,
+}
+
 /// File-system loader.
 ///
 /// This is a special JSON-LD document loader that can load document from the file system by
 /// attaching a directory to specific URLs.
 ///
+/// The same IRI prefix can be mounted on several directories: mount points
+/// are searched by decreasing priority (then in mount order) and the first
+/// existing file is loaded.
+///
+/// ```
+/// use json_ld_core::{loader::fs::PathMapping, FsLoader};
+/// use static_iref::iri;
+///
+/// let mut loader = FsLoader::new();
+/// loader.mount(iri!("https://example.com/").to_owned(), "vendor");
+/// loader.mount_with(
+///   iri!("https://example.com/").to_owned(),
+///   "overrides",
+///   PathMapping {
+///     extension: Some("jsonld".to_owned()),
+///     index: Some("index.jsonld".to_owned()),
+///     percent_decode: true,
+///   },
+///   1,
+/// );
+/// ```
+///
 /// Loaded documents are not cached: a new file system read is made each time
 /// an URL is loaded even if it has already been queried before.
+///
+/// The size of the loaded files can be bounded with
+/// [`Self::set_max_file_size`].
+///
+/// With the `gzip` feature, files with the `.gz` extension are transparently
+/// decompressed, and a file is looked for with an additional `.gz`
+/// extension when it does not exist. For instance the IRI
+/// `https://schema.org/` can be served by a `schema.org.jsonld.gz` file.
+///
+/// Files are served with the `application/ld+json` content type, unless they
+/// have no extension (ignoring `.gz`): the content type is then inferred
+/// from the content. Documents using a keyword at the top level (such as
+/// `@context`) are served as `application/ld+json`, other documents as
+/// `application/json`.
 #[derive(Default)]
 pub struct FsLoader {
-	mount_points: Vec<(PathBuf, IriBuf)>,
+	mount_points: Vec<MountPoint>,
+	max_file_size: Option<u64>,
 }
 
 impl FsLoader {
@@ -46,46 +166,347 @@ impl FsLoader {
 	/// the referenced local directory.
 	#[inline(always)]
 	pub fn mount<P: AsRef<Path>>(&mut self, url: IriBuf, path: P) {
-		self.mount_points.push((path.as_ref().into(), url));
+		self.mount_with(url, path, PathMapping::default(), 0)
+	}
+
+	/// Bind the given IRI prefix to the given path, using the given path
+	/// mapping.
+	///
+	/// Mount points with a higher `priority` are searched first.
+	pub fn mount_with<P: AsRef<Path>>(
+		&mut self,
+		url: IriBuf,
+		path: P,
+		mapping: PathMapping,
+		priority: i32,
+	) {
+		let i = self
+			.mount_points
+			.iter()
+			.position(|m| m.priority < priority)
+			.unwrap_or(self.mount_points.len());
+
+		self.mount_points.insert(
+			i,
+			MountPoint {
+				url,
+				path: path.as_ref().into(),
+				mapping,
+				priority,
+			},
+		);
+	}
+
+	/// Returns the maximum size (in bytes) of the loaded files, if any.
+	pub fn max_file_size(&self) -> Option<u64> {
+		self.max_file_size
+	}
+
+	/// Sets the maximum size (in bytes) of the loaded files.
+	///
+	/// Loading a larger file fails with a [`TooLarge`] error without reading
+	/// it entirely. No limit is set by default.
+	pub fn set_max_file_size(&mut self, limit: Option<u64>) {
+		self.max_file_size = limit
 	}
 
 	/// Returns the local file path associated to the given `url` if any.
+	///
+	/// This is the path given by the first matching mount point, which may
+	/// not exist. See [`Self::filepaths`] to get every candidate.
 	pub fn filepath(&self, url: &Iri) -> Option<PathBuf> {
-		for (path, target_url) in &self.mount_points {
-			if let Some((suffix, _, _)) = url.as_iri_ref().suffix(target_url) {
-				let mut filepath = path.clone();
-				for seg in suffix.as_path().segments() {
-					filepath.push(seg.as_str())
-				}
+		self.filepaths(url).next()
+	}
+
+	/// Returns the local file paths associated to the given `url`, in search
+	/// order.
+	pub fn filepaths<'a>(&'a self, url: &'a Iri) -> impl Iterator<Item = PathBuf> + 'a {
+		self.mount_points.iter().filter_map(|m| {
+			let (suffix, _, _) = url.as_iri_ref().suffix(&m.url)?;
+			let relative_path = m.mapping.map(suffix.as_path().as_str())?;
+			Some(m.path.join(relative_path))
+		})
+	}
 
-				return Some(filepath);
+	/// Returns the path of the file to read for the given candidate path, if
+	/// any.
+	fn existing(filepath: PathBuf) -> Option<PathBuf> {
+		if filepath.is_file() {
+			return Some(filepath);
+		}
+
+		#[cfg(feature = "gzip")]
+		{
+			let mut compressed = filepath.into_os_string();
+			compressed.push(".gz");
+			let compressed = PathBuf::from(compressed);
+			if compressed.is_file() {
+				return Some(compressed);
 			}
 		}
 
 		None
 	}
+
+	/// Reads the document at the given path.
+	fn read(&self, url: &Iri, filepath: &Path) -> LoadingResult<IriBuf> {
+		let io_error = |e| LoadError::new(url.to_owned(), Error::IO(e));
+		let file = File::open(filepath).map_err(io_error)?;
+		let mut contents = String::new();
+
+		if let Some(limit) = self.max_file_size {
+			// The compressed size is smaller than the decompressed size.
+			if file.metadata().map_err(io_error)?.len() > limit {
+				return Err(LoadError::new(url.to_owned(), TooLarge(limit)));
+			}
+		}
+
+		let file = decompress(filepath, file);
+
+		match self.max_file_size {
+			Some(limit) => {
+				// The file may grow between the check and the read, and the
+				// decompressed content may be larger than the file.
+				let mut buf_reader = BufReader::new(file).take(limit.saturating_add(1));
+				buf_reader.read_to_string(&mut contents).map_err(io_error)?;
+
+				if contents.len() as u64 > limit {
+					return Err(LoadError::new(url.to_owned(), TooLarge(limit)));
+				}
+			}
+			None => {
+				let mut buf_reader = BufReader::new(file);
+				buf_reader.read_to_string(&mut contents).map_err(io_error)?;
+			}
+		}
+
+		let (doc, _) = json_syntax::Value::parse_str(&contents)
+			.map_err(|e| LoadError::new(url.to_owned(), Error::Parse(e)))?;
+		let content_type = content_type(filepath, &doc);
+		let mut document = RemoteDocument::new(Some(url.to_owned()), Some(content_type), doc);
+		document.set_raw_content(Some(contents.into_bytes().into()));
+		Ok(document)
+	}
+}
+
+/// Checks if the given file is gzip-compressed, from its extension.
+fn is_gzip(filepath: &Path) -> bool {
+	filepath.extension().is_some_and(|e| e == "gz")
+}
+
+/// Returns a reader over the decompressed content of the given file.
+#[cfg(feature = "gzip")]
+fn decompress(filepath: &Path, file: File) -> Box<dyn Read> {
+	if is_gzip(filepath) {
+		Box::new(flate2::read::GzDecoder::new(file))
+	} else {
+		Box::new(file)
+	}
+}
+
+/// Returns a reader over the decompressed content of the given file.
+///
+/// Without the `gzip` feature, files are read as is.
+#[cfg(not(feature = "gzip"))]
+fn decompress(_filepath: &Path, file: File) -> File {
+	file
+}
+
+/// Returns the content type of the given file.
+///
+/// Files without extension (ignoring `.gz`) are sniffed: they are JSON-LD if
+/// they use a keyword at the top level.
+fn content_type(filepath: &Path, document: &json_syntax::Value) -> Mime {
+	let filepath = if is_gzip(filepath) {
+		filepath.with_extension("")
+	} else {
+		filepath.to_owned()
+	};
+
+	let uses_keyword = |value: &json_syntax::Value| {
+		value
+			.as_object()
+			.is_some_and(|o| o.iter().any(|e| e.key.starts_with('@')))
+	};
+
+	let json_ld = filepath.extension().is_some()
+		|| match document {
+			json_syntax::Value::Array(items) => items.iter().any(uses_keyword),
+			value => uses_keyword(value),
+		};
+
+	if json_ld {
+		"application/ld+json".parse().unwrap()
+	} else {
+		mime::APPLICATION_JSON
+	}
 }
 
 impl Loader for FsLoader {
 	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
-		match self.filepath(url) {
-			Some(filepath) => {
-				let file = File::open(filepath)
-					.map_err(|e| LoadError::new(url.to_owned(), Error::IO(e)))?;
-				let mut buf_reader = BufReader::new(file);
-				let mut contents = String::new();
-				buf_reader
-					.read_to_string(&mut contents)
-					.map_err(|e| LoadError::new(url.to_owned(), Error::IO(e)))?;
-				let (doc, _) = json_syntax::Value::parse_str(&contents)
-					.map_err(|e| LoadError::new(url.to_owned(), Error::Parse(e)))?;
-				Ok(RemoteDocument::new(
-					Some(url.to_owned()),
-					Some("application/ld+json".parse().unwrap()),
-					doc,
-				))
+		let mut mounted = false;
+
+		for filepath in self.filepaths(url) {
+			if let Some(filepath) = Self::existing(filepath) {
+				return self.read(url, &filepath);
 			}
-			None => Err(LoadError::new(url.to_owned(), Error::NoMountPoint)),
+
+			mounted = true
+		}
+
+		let error = if mounted {
+			Error::IO(std::io::ErrorKind::NotFound.into())
+		} else {
+			Error::NoMountPoint
+		};
+
+		Err(LoadError::new(url.to_owned(), error).rejected_by("FsLoader"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn mounts() {
+		let root = fixture::TempDir::new("fs-mounts");
+		root.write("vendor/a", r#"{ "from": "vendor" }"#);
+		root.write("vendor/ns/index.jsonld", r#"{ "from": "index" }"#);
+		root.write("overrides/b c.jsonld", r#"{ "from": "overrides" }"#);
+
+		let mut loader = FsLoader::new();
+		loader.mount(
+			iri!("https://example.org/").to_owned(),
+			root.path().join("vendor"),
+		);
+		loader.mount_with(
+			iri!("https://example.org/").to_owned(),
+			root.path().join("overrides"),
+			PathMapping {
+				extension: Some("jsonld".to_owned()),
+				index: None,
+				percent_decode: true,
+			},
+			1,
+		);
+		loader.mount_with(
+			iri!("https://example.org/ns/").to_owned(),
+			root.path().join("vendor/ns"),
+			PathMapping {
+				index: Some("index.jsonld".to_owned()),
+				..Default::default()
+			},
+			0,
+		);
+
+		let a = loader.load(iri!("https://example.org/a")).await.unwrap();
+		assert_eq!(fixture::from(&a), "vendor");
+		let b = loader
+			.load(iri!("https://example.org/b%20c"))
+			.await
+			.unwrap();
+		assert_eq!(fixture::from(&b), "overrides");
+		let ns = loader.load(iri!("https://example.org/ns/")).await.unwrap();
+		assert_eq!(fixture::from(&ns), "index");
+		assert!(loader
+			.load(iri!("https://example.org/missing"))
+			.await
+			.is_err());
+	}
+
+	#[async_std::test]
+	async fn content_sniffing() {
+		let root = fixture::TempDir::new("fs-sniffing");
+		root.write("ld", r#"{ "@context": {}, "name": "a" }"#);
+		root.write("plain", r#"{ "name": "a" }"#);
+		root.write("plain.json", r#"{ "name": "a" }"#);
+
+		let mut loader = FsLoader::new();
+		loader.mount(iri!("https://example.org/").to_owned(), root.path());
+
+		let content_type = |doc: RemoteDocument| doc.content_type().unwrap().to_string();
+
+		let ld = loader.load(iri!("https://example.org/ld")).await.unwrap();
+		assert_eq!(content_type(ld), "application/ld+json");
+		let plain = loader
+			.load(iri!("https://example.org/plain"))
+			.await
+			.unwrap();
+		assert_eq!(content_type(plain), "application/json");
+		let with_extension = loader
+			.load(iri!("https://example.org/plain.json"))
+			.await
+			.unwrap();
+		assert_eq!(content_type(with_extension), "application/ld+json");
+	}
+
+	#[async_std::test]
+	async fn max_file_size() {
+		let root = fixture::TempDir::new("fs-max-size");
+		root.write("doc.jsonld", r#"{ "name": "a" }"#);
+
+		let mut loader = FsLoader::new();
+		loader.mount(iri!("https://example.org/").to_owned(), root.path());
+		loader.set_max_file_size(Some(2));
+		assert!(loader
+			.load(iri!("https://example.org/doc.jsonld"))
+			.await
+			.unwrap_err()
+			.is_too_large());
+		loader.set_max_file_size(None);
+		assert!(loader
+			.load(iri!("https://example.org/doc.jsonld"))
+			.await
+			.is_ok());
+	}
+
+	#[cfg(feature = "gzip")]
+	#[async_std::test]
+	async fn gzip() {
+		use flate2::{write::GzEncoder, Compression};
+		use std::io::Write;
+
+		let compress = |content: &str| {
+			let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+			encoder.write_all(content.as_bytes()).unwrap();
+			encoder.finish().unwrap()
+		};
+
+		let root = fixture::TempDir::new("fs-gzip");
+		root.write(
+			"schema.jsonld.gz",
+			compress(r#"{ "@context": {}, "from": "schema" }"#),
+		);
+		root.write("plain.gz", compress(r#"{ "from": "plain" }"#));
+
+		let mut loader = FsLoader::new();
+		loader.mount(iri!("https://example.org/").to_owned(), root.path());
+
+		// The `.gz` extension is optional in the URL.
+		for url in [
+			iri!("https://example.org/schema.jsonld"),
+			iri!("https://example.org/schema.jsonld.gz"),
+		] {
+			let schema = loader.load(url).await.unwrap();
+			assert_eq!(fixture::from(&schema), "schema");
+			assert_eq!(
+				schema.content_type().unwrap().to_string(),
+				"application/ld+json"
+			);
 		}
+
+		// The content type of the decompressed content is sniffed.
+		let plain = loader
+			.load(iri!("https://example.org/plain"))
+			.await
+			.unwrap();
+		assert_eq!(fixture::from(&plain), "plain");
+		assert_eq!(
+			plain.content_type().unwrap().to_string(),
+			"application/json"
+		);
 	}
 }
@@ -0,0 +1,371 @@
+//! Document and context loader based on [`hyper`](https://crates.io/crates/hyper)
+//! or any [`tower`](https://crates.io/crates/tower) HTTP service.
+use super::negotiation::{
+	self, accept_header, check_content_length, negotiate, to_uri, trace_response, Negotiated,
+};
+use super::{Loader, TooLarge};
+use crate::{LoadError, LoadTrace, LoadingResult, Profile};
+use bytes::{Buf, BufMut, Bytes};
+use http::{
+	header::{ACCEPT, LOCATION},
+	Request, Response, StatusCode,
+};
+use http_body_util::{BodyExt, Empty};
+use iref::{Iri, IriBuf};
+use tower_service::Service;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Loader options.
+pub struct Options {
+	/// One or more IRIs to use in the request as a profile parameter.
+	///
+	/// (See [IANA Considerations](https://www.w3.org/TR/json-ld11/#iana-considerations)).
+	pub request_profile: Vec<Profile>,
+
+	/// Maximum number of allowed redirections before the loader fails.
+	///
+	/// Defaults to 8.
+	///
+	/// Note: unlike [`ReqwestLoader`](super::ReqwestLoader), this counts
+	/// both regular HTTP redirections and `Link` header redirections, since
+	/// HTTP services do not follow redirections by themselves.
+	pub max_redirections: usize,
+
+	/// Maximum size (in bytes) of a response body.
+	///
+	/// The body is read incrementally and the loader fails with a
+	/// [`TooLarge`](super::TooLarge) error as soon as it exceeds the limit
+	/// (or right away if the `Content-Length` exceeds it).
+	///
+	/// Defaults to `None` (no limit).
+	pub max_response_bytes: Option<usize>,
+}
+
+impl Default for Options {
+	fn default() -> Self {
+		Self {
+			request_profile: Vec::new(),
+			max_redirections: 8,
+			max_response_bytes: None,
+		}
+	}
+}
+
+/// Loading error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("service error: {0}")]
+	Service(BoxError),
+
+	#[error("invalid request: {0}")]
+	Request(http::Error),
+
+	#[error("response body error: {0}")]
+	Body(BoxError),
+
+	#[error("query failed: status code {0}")]
+	QueryFailed(StatusCode),
+
+	#[error("invalid content type")]
+	InvalidContentType,
+
+	#[error("multiple context link headers")]
+	MultipleContextLinkHeaders,
+
+	#[error("too many redirections")]
+	TooManyRedirections,
+
+	#[error("JSON parse error: {0}")]
+	Parse(json_syntax::parse::Error<std::io::Error>),
+}
+
+impl From<negotiation::Error> for Error {
+	fn from(value: negotiation::Error) -> Self {
+		match value {
+			negotiation::Error::InvalidContentType => Self::InvalidContentType,
+			negotiation::Error::MultipleContextLinkHeaders => Self::MultipleContextLinkHeaders,
+		}
+	}
+}
+
+/// Loader sending requests through a [`tower`](https://crates.io/crates/tower)
+/// HTTP service, such as a `hyper` client.
+///
+/// This loader does not depend on any particular HTTP client or runtime: the
+/// `service` is in charge of the connection (and TLS), and can be wrapped in
+/// any `tower` middleware (timeouts, retries, tracing). For instance, with a
+/// `hyper-util` client (a TLS connector, such as the one of `hyper-rustls`,
+/// is required for HTTPS):
+///
+/// ```
+/// use hyper_util::{
+///   client::legacy::{connect::HttpConnector, Client},
+///   rt::TokioExecutor,
+/// };
+/// use json_ld_core::loader::HyperLoader;
+///
+/// let loader = HyperLoader::new(Client::builder(TokioExecutor::new()).build(HttpConnector::new()));
+/// ```
+///
+/// The loader performs the same content negotiation as the
+/// [`ReqwestLoader`](super::ReqwestLoader), following HTTP redirections and
+/// `Link` headers. IRIs are converted into URIs before being requested.
+///
+/// Loaded documents are not cached: a new network query is made each time
+/// an URL is loaded even if it has already been queried before.
+pub struct HyperLoader<S> {
+	service: S,
+	options: Options,
+	accept_header: String,
+}
+
+impl<S> HyperLoader<S> {
+	/// Creates a new loader sending requests through the given `service`,
+	/// with the default options.
+	pub fn new(service: S) -> Self {
+		Self::new_using(service, Options::default())
+	}
+
+	/// Creates a new loader sending requests through the given `service`,
+	/// with the given options.
+	pub fn new_using(service: S, options: Options) -> Self {
+		Self {
+			service,
+			accept_header: accept_header(&options.request_profile),
+			options,
+		}
+	}
+
+	/// Returns the underlying HTTP service.
+	pub fn service(&self) -> &S {
+		&self.service
+	}
+}
+
+impl<S, B> HyperLoader<S>
+where
+	S: Service<Request<Empty<Bytes>>, Response = Response<B>> + Clone,
+	S::Error: Into<BoxError>,
+	B: http_body::Body,
+	B::Error: Into<BoxError>,
+{
+	/// Loads the document behind the given IRI, requesting the given
+	/// profiles instead of [`Options::request_profile`].
+	///
+	/// See [`ReqwestLoader::load_with_profile`](super::ReqwestLoader::load_with_profile).
+	pub async fn load_with_profile(
+		&self,
+		url: &Iri,
+		request_profile: &[Profile],
+	) -> LoadingResult<IriBuf> {
+		self.fetch(url, &accept_header(request_profile)).await
+	}
+
+	/// Loads the document behind the given IRI using the given `Accept`
+	/// header value.
+	///
+	/// On failure, the error carries the trace of the load.
+	async fn fetch(&self, url: &Iri, accept_header: &str) -> LoadingResult<IriBuf> {
+		let mut trace = LoadTrace::default();
+		self.fetch_traced(url, accept_header, &mut trace)
+			.await
+			.map_err(|e| e.with_trace(trace).rejected_by("HyperLoader"))
+	}
+
+	/// Loads the document behind the given IRI using the given `Accept`
+	/// header value, recording the redirections and responses in `trace`.
+	async fn fetch_traced(
+		&self,
+		url: &Iri,
+		accept_header: &str,
+		trace: &mut LoadTrace,
+	) -> LoadingResult<IriBuf> {
+		let requested = url;
+		let mut redirection_number = 0;
+		let mut url = url.to_owned();
+		loop {
+			if redirection_number > self.options.max_redirections {
+				return Err(LoadError::new(url, Error::TooManyRedirections));
+			}
+
+			log::debug!("downloading: {}", url);
+			let request = Request::get(to_uri(&url))
+				.header(ACCEPT, accept_header)
+				.body(Empty::new())
+				.map_err(|e| LoadError::new(url.clone(), Error::Request(e)))?;
+
+			let mut service = self.service.clone();
+			futures::future::poll_fn(|cx| service.poll_ready(cx))
+				.await
+				.map_err(|e| LoadError::new(url.clone(), Error::Service(e.into())))?;
+			let response = service
+				.call(request)
+				.await
+				.map_err(|e| LoadError::new(url.clone(), Error::Service(e.into())))?;
+
+			trace_response(trace, response.status(), response.headers());
+			let (parts, body) = response.into_parts();
+			let limit = self.options.max_response_bytes;
+			match parts.status {
+				StatusCode::OK => match negotiate(&url, &parts.headers) {
+					Ok(Negotiated::Document(metadata)) => {
+						check_content_length(&parts.headers, limit)
+							.map_err(|e| LoadError::new(url.clone(), e))?;
+						let bytes = read_body(&url, body, limit).await?;
+
+						let mut document = metadata
+							.into_document(url.clone(), &bytes)
+							.map_err(|e| LoadError::new(url, Error::Parse(e)))?;
+						document.set_requested_url(requested.to_owned());
+						break Ok(document);
+					}
+					Ok(Negotiated::Alternate(alternate)) => {
+						drain_body(body, limit).await;
+						trace.redirects.push(alternate.clone());
+						url = alternate;
+						redirection_number += 1;
+					}
+					Err(e) => break Err(LoadError::new(url, Error::from(e))),
+				},
+				code if code.is_redirection() => {
+					drain_body(body, limit).await;
+					let location = parts
+						.headers
+						.get(LOCATION)
+						.and_then(|value| value.to_str().ok())
+						.and_then(|value| iref::IriRef::new(value).ok());
+
+					match location {
+						Some(location) => {
+							url = location.resolved(&url);
+							trace.redirects.push(url.clone());
+							redirection_number += 1;
+						}
+						None => break Err(LoadError::new(url, Error::QueryFailed(code))),
+					}
+				}
+				code => break Err(LoadError::new(url, Error::QueryFailed(code))),
+			}
+		}
+	}
+}
+
+impl<S, B> Loader for HyperLoader<S>
+where
+	S: Service<Request<Empty<Bytes>>, Response = Response<B>> + Clone,
+	S::Error: Into<BoxError>,
+	B: http_body::Body,
+	B::Error: Into<BoxError>,
+{
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.fetch(url, &self.accept_header).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.fetch(url, &accept_header(profile)).await
+	}
+}
+
+/// Reads and discards the body of a response that is not used, so that the
+/// connection can be reused by the service.
+///
+/// Bodies longer than `limit` bytes are not read entirely.
+async fn drain_body<B: http_body::Body>(body: B, limit: Option<usize>) {
+	let mut body = std::pin::pin!(body);
+	let mut len = 0;
+	while let Some(Ok(frame)) = body.frame().await {
+		if let Ok(data) = frame.into_data() {
+			len += data.remaining();
+			if limit.is_some_and(|limit| len > limit) {
+				break;
+			}
+		}
+	}
+}
+
+/// Reads the body of a response to a request on `url`, failing if it is
+/// longer than `limit` bytes.
+async fn read_body<B>(url: &IriBuf, body: B, limit: Option<usize>) -> Result<Vec<u8>, LoadError>
+where
+	B: http_body::Body,
+	B::Error: Into<BoxError>,
+{
+	let mut body = std::pin::pin!(body);
+	let mut bytes = Vec::new();
+	while let Some(frame) = body.frame().await {
+		let frame = frame.map_err(|e| LoadError::new(url.clone(), Error::Body(e.into())))?;
+		if let Ok(data) = frame.into_data() {
+			if let Some(limit) = limit {
+				if bytes.len() + data.remaining() > limit {
+					return Err(LoadError::new(url.clone(), TooLarge(limit as u64)));
+				}
+			}
+
+			bytes.put(data)
+		}
+	}
+
+	Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use http::header::CONTENT_TYPE;
+	use http_body_util::Full;
+	use static_iref::iri;
+	use std::sync::{Arc, Mutex};
+	use std::task::{Context, Poll};
+
+	/// Service answering requests without network, recording the requested
+	/// URIs.
+	#[derive(Clone, Default)]
+	struct Server(Arc<Mutex<Vec<http::Uri>>>);
+
+	impl Service<Request<Empty<Bytes>>> for Server {
+		type Response = Response<Full<Bytes>>;
+		type Error = std::convert::Infallible;
+		type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+		fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+			Poll::Ready(Ok(()))
+		}
+
+		fn call(&mut self, request: Request<Empty<Bytes>>) -> Self::Future {
+			self.0.lock().unwrap().push(request.uri().clone());
+			let response = match request.uri().path() {
+				"/old" => Response::builder()
+					.status(StatusCode::FOUND)
+					.header(LOCATION, "/new")
+					.body(Full::new(Bytes::from("moved"))),
+				_ => Response::builder()
+					.header(CONTENT_TYPE, "application/ld+json")
+					.body(Full::new(Bytes::from("{}"))),
+			};
+
+			std::future::ready(Ok(response.unwrap()))
+		}
+	}
+
+	#[async_std::test]
+	async fn redirect() {
+		let loader = HyperLoader::new(Server::default());
+		let document = loader.load(iri!("http://example.org/old")).await.unwrap();
+		assert!(document.is_redirected());
+		assert_eq!(
+			document.url(),
+			Some(&iri!("http://example.org/new").to_owned())
+		);
+	}
+
+	#[async_std::test]
+	async fn iri_to_uri() {
+		let server = Server::default();
+		let loader = HyperLoader::new(server.clone());
+		let url = iri!("http://example.org/café");
+		let document = loader.load(url).await.unwrap();
+		assert_eq!(document.url(), Some(&url.to_owned()));
+		assert_eq!(server.0.lock().unwrap()[0], "http://example.org/caf%C3%A9");
+	}
+}
@@ -0,0 +1,194 @@
+//! Loader calling user hooks around each load.
+use super::{DynLoader, Loader, LoaderStats, RemoteDocument};
+use crate::{Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use json_syntax::Print;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type StartHook = Box<dyn Fn(&Iri) + Send + Sync>;
+
+type EndHook = Box<dyn Fn(&LoadEvent) + Send + Sync>;
+
+/// Completed load, passed to the hooks registered with
+/// [`InstrumentedLoader::on_end`].
+pub struct LoadEvent<'a> {
+	/// Loaded URL.
+	pub url: &'a Iri,
+
+	/// Time spent by the inner loader.
+	pub duration: Duration,
+
+	/// Loaded document, or loading error.
+	pub result: Result<&'a RemoteDocument, &'a LoadError>,
+}
+
+impl<'a> LoadEvent<'a> {
+	/// Checks if the load succeeded.
+	pub fn is_ok(&self) -> bool {
+		self.result.is_ok()
+	}
+
+	/// Returns the size (in bytes) of the loaded document, if the load
+	/// succeeded.
+	///
+	/// This is the size of the compact JSON serialization of the document,
+	/// computed on each call, which may differ from the size of the
+	/// original response.
+	pub fn size(&self) -> Option<usize> {
+		self.result
+			.ok()
+			.map(|document| document.document().compact_print().to_string().len())
+	}
+}
+
+/// Loader calling user hooks before and after each load of another loader.
+///
+/// This can be used to log, trace or meter the loads of remote documents
+/// (such as contexts) without reimplementing the loader.
+///
+/// ```
+/// use json_ld_core::loader::{InstrumentedLoader, NoLoader};
+///
+/// let loader = InstrumentedLoader::new(NoLoader)
+///   .on_start(|url| eprintln!("loading {url}"))
+///   .on_end(|event| eprintln!("loaded {} in {:?}", event.url, event.duration));
+/// ```
+pub struct InstrumentedLoader<L> {
+	inner: L,
+	start_hooks: Vec<StartHook>,
+	end_hooks: Vec<EndHook>,
+}
+
+impl<L> InstrumentedLoader<L> {
+	/// Creates a new loader without hooks, delegating to `inner`.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			start_hooks: Vec::new(),
+			end_hooks: Vec::new(),
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Adds a hook called with the URL of each load, before the load starts.
+	pub fn on_start(mut self, f: impl Fn(&Iri) + Send + Sync + 'static) -> Self {
+		self.start_hooks.push(Box::new(f));
+		self
+	}
+
+	/// Adds a hook called after each load, with its outcome.
+	pub fn on_end(mut self, f: impl Fn(&LoadEvent) + Send + Sync + 'static) -> Self {
+		self.end_hooks.push(Box::new(f));
+		self
+	}
+
+	/// Adds a hook [recording](LoaderStats::record) each load in the given
+	/// statistics.
+	pub fn with_stats(self, stats: Arc<LoaderStats>) -> Self {
+		self.on_end(move |event| stats.record(event))
+	}
+}
+
+impl<L> InstrumentedLoader<L> {
+	/// Runs the given load of `url`, calling the hooks around it.
+	async fn instrument(
+		&self,
+		url: &Iri,
+		load: impl Future<Output = LoadingResult<IriBuf>>,
+	) -> LoadingResult<IriBuf> {
+		for hook in &self.start_hooks {
+			hook(url)
+		}
+
+		let start = Instant::now();
+		let result = load.await;
+
+		if !self.end_hooks.is_empty() {
+			let event = LoadEvent {
+				url,
+				duration: start.elapsed(),
+				result: result.as_ref(),
+			};
+
+			for hook in &self.end_hooks {
+				hook(&event)
+			}
+		}
+
+		result
+	}
+}
+
+impl<L: Loader> Loader for InstrumentedLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.instrument(url, self.inner.load(url)).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.instrument(url, self.inner.load_with_profile(url, profile))
+			.await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for InstrumentedLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.instrument(url, self.inner.load_dyn(url)))
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.instrument(url, self.inner.load_with_profile_dyn(url, profile)))
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+	use std::sync::{Arc, Mutex};
+
+	#[async_std::test]
+	async fn hooks() {
+		let inner = fixture::static_loader(&[(iri!("http://example.org/doc"), "{}")]);
+
+		let started = Arc::new(Mutex::new(Vec::new()));
+		let ended = Arc::new(Mutex::new(Vec::new()));
+		let loader = InstrumentedLoader::new(inner)
+			.on_start({
+				let started = started.clone();
+				move |url| started.lock().unwrap().push(url.to_owned())
+			})
+			.on_end({
+				let ended = ended.clone();
+				move |event| ended.lock().unwrap().push((event.is_ok(), event.size()))
+			});
+
+		assert!(loader.load(iri!("http://example.org/doc")).await.is_ok());
+		assert!(loader
+			.load(iri!("http://example.org/missing"))
+			.await
+			.is_err());
+
+		assert_eq!(started.lock().unwrap().len(), 2);
+		assert_eq!(*ended.lock().unwrap(), [(true, Some(2)), (false, None)]);
+	}
+}
@@ -0,0 +1,246 @@
+//! Integrity verification of remote documents.
+//!
+//! Verifiable credentials and other signed documents depend on the exact
+//! content of their contexts. The [`IntegrityLoader`] wraps another loader
+//! and checks each loaded document against an expected SHA-256 [`Digest`],
+//! failing instead of silently processing a modified context.
+use super::{DynLoader, Loader};
+use crate::{Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf};
+use sha2::{Digest as _, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// SHA-256 digest of a document.
+///
+/// The digest is computed over the raw content of the document, as fetched,
+/// so that it matches the digests published by the document authors (for
+/// instance with `sha256sum context.jsonld`). It is displayed and parsed as
+/// a lowercase hexadecimal string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Digest([u8; 32]);
+
+impl Digest {
+	/// Creates a digest from its bytes.
+	pub fn new(bytes: [u8; 32]) -> Self {
+		Self(bytes)
+	}
+
+	/// Computes the digest of the given raw content.
+	pub fn of(content: &[u8]) -> Self {
+		Self(Sha256::digest(content).into())
+	}
+
+	/// Returns the bytes of the digest.
+	pub fn as_bytes(&self) -> &[u8; 32] {
+		&self.0
+	}
+}
+
+impl fmt::Display for Digest {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for b in self.0 {
+			write!(f, "{b:02x}")?
+		}
+
+		Ok(())
+	}
+}
+
+/// Invalid hexadecimal digest.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid SHA-256 digest `{0}`")]
+pub struct InvalidDigest(pub String);
+
+impl FromStr for Digest {
+	type Err = InvalidDigest;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || InvalidDigest(s.to_owned());
+
+		if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+			return Err(invalid());
+		}
+
+		let mut bytes = [0; 32];
+		for (i, b) in bytes.iter_mut().enumerate() {
+			*b = u8::from_str_radix(&s[(i * 2)..(i * 2 + 2)], 16).map_err(|_| invalid())?;
+		}
+
+		Ok(Self(bytes))
+	}
+}
+
+/// Loaded document not matching its expected digest.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("integrity check failed: expected digest {expected}, found {found}")]
+pub struct IntegrityMismatch {
+	/// Expected digest.
+	pub expected: Digest,
+
+	/// Digest of the loaded document.
+	pub found: Digest,
+}
+
+/// Raw content of a document unavailable for its integrity check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("integrity check failed: raw content of the document unavailable")]
+pub struct MissingRawContent;
+
+/// Loader checking the integrity of the loaded documents.
+///
+/// The [raw content](super::RemoteDocument::raw_content) of each loaded
+/// document with a registered digest is compared with it, and the loader
+/// fails with an [`IntegrityMismatch`] error if it differs, or a
+/// [`MissingRawContent`] error if the inner loader does not provide the raw
+/// content. Documents without registered digest are returned unchecked: use
+/// a [`RestrictedLoader`](super::RestrictedLoader) to reject them.
+///
+/// ```
+/// use json_ld_core::loader::{integrity::Digest, IntegrityLoader, NoLoader};
+/// use static_iref::iri;
+///
+/// let context = r#"{ "@context": { "name": "http://schema.org/name" } }"#;
+///
+/// let mut loader = IntegrityLoader::new(NoLoader);
+/// loader.register(
+///   iri!("https://example.org/context.jsonld").to_owned(),
+///   Digest::of(context.as_bytes()),
+/// );
+/// ```
+pub struct IntegrityLoader<L> {
+	inner: L,
+	digests: HashMap<IriBuf, Digest>,
+}
+
+impl<L> IntegrityLoader<L> {
+	/// Creates a new loader without registered digest, delegating to
+	/// `inner`.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			digests: HashMap::new(),
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Registers the expected digest of the document at the given URL.
+	///
+	/// Replaces and returns the previous digest, if any.
+	pub fn register(&mut self, url: IriBuf, digest: Digest) -> Option<Digest> {
+		self.digests.insert(url, digest)
+	}
+
+	/// Returns the expected digest of the given document, if any.
+	pub fn get_digest(&self, url: &Iri) -> Option<&Digest> {
+		self.digests.get(url)
+	}
+}
+
+impl<L: Loader> Loader for IntegrityLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.check(url, self.inner.load(url).await)
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.check(url, self.inner.load_with_profile(url, profile).await)
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for IntegrityLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let result = self.inner.load_dyn(url).await;
+			self.check(url, result)
+		})
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let result = self.inner.load_with_profile_dyn(url, profile).await;
+			self.check(url, result)
+		})
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+impl<L> IntegrityLoader<L> {
+	/// Checks the document loaded from `url` against its expected digest,
+	/// if any.
+	fn check(&self, url: &Iri, result: LoadingResult<IriBuf>) -> LoadingResult<IriBuf> {
+		let document = result?;
+
+		if let Some(expected) = self.digests.get(url) {
+			let content = document
+				.raw_content()
+				.ok_or_else(|| LoadError::new(url.to_owned(), MissingRawContent))?;
+			let found = Digest::of(content);
+			if found != *expected {
+				return Err(LoadError::new(
+					url.to_owned(),
+					IntegrityMismatch {
+						expected: *expected,
+						found,
+					},
+				));
+			}
+		}
+
+		Ok(document)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn check() {
+		let url = iri!("http://example.org/context.jsonld");
+		let content = r#"{"@context": {"name": "http://schema.org/name", "@version": 1.1}}"#;
+		let inner =
+			std::collections::HashMap::from([(url.to_owned(), fixture::document(url, content))]);
+
+		// `sha256sum` of the content.
+		let digest: Digest = "f0bfd67cccb7758a5974d92b63b42ea6896c506e247eb35c7583943be4123b58"
+			.parse()
+			.unwrap();
+		assert_eq!(Digest::of(content.as_bytes()), digest);
+		assert_eq!(digest.to_string().parse::<Digest>().unwrap(), digest);
+
+		let mut loader = IntegrityLoader::new(inner);
+		loader.register(url.to_owned(), digest);
+		assert!(loader.load(url).await.is_ok());
+
+		loader.register(url.to_owned(), Digest::new([0; 32]));
+		let error = loader.load(url).await.unwrap_err();
+		let mismatch = error.cause.downcast_ref::<IntegrityMismatch>().unwrap();
+		assert_eq!(mismatch.found, digest);
+
+		// The same document, without its raw content.
+		let mut loader = IntegrityLoader::new(fixture::static_loader(&[(url, content)]));
+		loader.register(url.to_owned(), digest);
+		let error = loader.load(url).await.unwrap_err();
+		assert!(error.cause.is::<MissingRawContent>());
+	}
+}
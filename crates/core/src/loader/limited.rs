@@ -0,0 +1,128 @@
+//! Loader bounding the size of the loaded documents.
+use super::{DynLoader, Loader, TooLarge};
+use crate::{Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use json_syntax::Print;
+use std::sync::Arc;
+
+/// Loader rejecting the documents of another loader exceeding a given size,
+/// with a [`TooLarge`] error.
+///
+/// The size of a document is the size (in bytes) of its
+/// [raw content](super::RemoteDocument::raw_content), or of its compact JSON
+/// serialization if the inner loader does not provide it. Since the document
+/// is only measured once loaded, this does not prevent the inner loader from
+/// reading an oversized response: the file system and HTTP loaders enforce
+/// their own limit while reading (see
+/// [`FsLoader::set_max_file_size`](super::FsLoader::set_max_file_size)), and
+/// this wrapper is meant to bound the other loaders, such as in-memory
+/// loaders filled from untrusted sources.
+///
+/// ```
+/// use json_ld_core::loader::{LimitedLoader, StaticLoader};
+///
+/// let loader = LimitedLoader::new(StaticLoader::new(), 1024 * 1024);
+/// ```
+pub struct LimitedLoader<L> {
+	inner: L,
+	max_bytes: u64,
+}
+
+impl<L> LimitedLoader<L> {
+	/// Creates a new loader rejecting the documents of `inner` larger than
+	/// `max_bytes`.
+	pub fn new(inner: L, max_bytes: u64) -> Self {
+		Self { inner, max_bytes }
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns the maximum document size, in bytes.
+	pub fn max_bytes(&self) -> u64 {
+		self.max_bytes
+	}
+
+	/// Checks the size of the document loaded from `url`.
+	fn check(&self, url: &Iri, result: LoadingResult<IriBuf>) -> LoadingResult<IriBuf> {
+		let document = result?;
+
+		let len = match document.raw_content() {
+			Some(content) => content.len(),
+			None => document.document().compact_print().to_string().len(),
+		};
+
+		if len as u64 > self.max_bytes {
+			return Err(LoadError::new(url.to_owned(), TooLarge(self.max_bytes)));
+		}
+
+		Ok(document)
+	}
+}
+
+impl<L: Loader> Loader for LimitedLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.check(url, self.inner.load(url).await)
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.check(url, self.inner.load_with_profile(url, profile).await)
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for LimitedLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let result = self.inner.load_dyn(url).await;
+			self.check(url, result)
+		})
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let result = self.inner.load_with_profile_dyn(url, profile).await;
+			self.check(url, result)
+		})
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn limit() {
+		let inner = fixture::static_loader(&[
+			(iri!("http://example.org/small"), "{}"),
+			(
+				iri!("http://example.org/large"),
+				r#"{"@context": {"name": "http://schema.org/name"}}"#,
+			),
+		]);
+
+		let loader = LimitedLoader::new(inner, 16);
+		assert!(loader.load(iri!("http://example.org/small")).await.is_ok());
+		assert!(loader
+			.load(iri!("http://example.org/large"))
+			.await
+			.unwrap_err()
+			.is_too_large());
+	}
+}
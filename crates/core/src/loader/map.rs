@@ -1,6 +1,7 @@
 use super::{Loader, RemoteDocument};
 use crate::{LoadError, LoadingResult};
 use iref::{Iri, IriBuf};
+use json_syntax::Parse;
 use std::collections::{BTreeMap, HashMap};
 
 /// Error returned using [`HashMap`] or [`BTreeMap`] as a [`Loader`] with the
@@ -13,7 +14,7 @@ impl Loader for HashMap<IriBuf, RemoteDocument> {
 	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
 		match self.get(url) {
 			Some(document) => Ok(document.clone()),
-			None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound).rejected_by("HashMap")),
 		}
 	}
 }
@@ -22,7 +23,115 @@ impl Loader for BTreeMap<IriBuf, RemoteDocument> {
 	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
 		match self.get(url) {
 			Some(document) => Ok(document.clone()),
-			None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound).rejected_by("BTreeMap")),
 		}
 	}
 }
+
+/// In-memory loader.
+///
+/// Maps IRIs to JSON documents kept in memory, for instance context
+/// documents bundled in the binary. Loading a document never does any I/O.
+///
+/// ```
+/// use json_ld_core::loader::StaticLoader;
+/// use static_iref::iri;
+///
+/// let mut loader = StaticLoader::new();
+/// loader
+///   .insert_str(
+///     iri!("https://example.com/context.jsonld").to_owned(),
+///     r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name" } }"#,
+///   )
+///   .unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct StaticLoader {
+	documents: HashMap<IriBuf, json_syntax::Value>,
+}
+
+impl StaticLoader {
+	/// Creates an empty loader.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of documents.
+	pub fn len(&self) -> usize {
+		self.documents.len()
+	}
+
+	/// Checks if the loader has no documents.
+	pub fn is_empty(&self) -> bool {
+		self.documents.is_empty()
+	}
+
+	/// Checks if a document is bound to the given IRI.
+	pub fn contains(&self, iri: &Iri) -> bool {
+		self.documents.contains_key(iri)
+	}
+
+	/// Binds the given JSON document to `iri`.
+	///
+	/// Returns the document previously bound to this IRI, if any.
+	pub fn insert(&mut self, iri: IriBuf, json: json_syntax::Value) -> Option<json_syntax::Value> {
+		self.documents.insert(iri, json)
+	}
+
+	/// Parses the given JSON document and binds it to `iri`.
+	///
+	/// Returns the document previously bound to this IRI, if any.
+	pub fn insert_str(
+		&mut self,
+		iri: IriBuf,
+		content: &str,
+	) -> Result<Option<json_syntax::Value>, json_syntax::parse::Error> {
+		let (json, _) = json_syntax::Value::parse_str(content)?;
+		Ok(self.insert(iri, json))
+	}
+}
+
+impl Loader for StaticLoader {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.documents.get(url) {
+			Some(json) => Ok(RemoteDocument::new(
+				Some(url.to_owned()),
+				Some("application/ld+json".parse().unwrap()),
+				json.clone(),
+			)),
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound).rejected_by("StaticLoader")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn static_loader() {
+		let url = iri!("http://example.org/context.jsonld");
+		let mut loader = StaticLoader::new();
+		loader
+			.insert_str(
+				url.to_owned(),
+				r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name" } }"#,
+			)
+			.unwrap();
+
+		let document = loader.load(url).await.unwrap();
+		assert_eq!(document.url(), Some(&url.to_owned()));
+		assert!(document
+			.document()
+			.as_object()
+			.unwrap()
+			.get_unique("@context")
+			.unwrap()
+			.is_some());
+		assert!(loader
+			.load(iri!("http://example.org/other.jsonld"))
+			.await
+			.is_err())
+	}
+}
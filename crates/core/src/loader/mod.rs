@@ -3,16 +3,62 @@ use iref::{Iri, IriBuf};
 use mime::Mime;
 use rdf_types::vocabulary::{IriVocabulary, IriVocabularyMut};
 use static_iref::iri;
-use std::{borrow::Cow, hash::Hash};
+use std::{borrow::Cow, fmt, hash::Hash, sync::Arc};
 
+pub mod blocking;
+pub mod cache;
 pub mod chain;
+pub mod context;
+pub mod dynamic;
+pub mod file_cache;
 pub mod fs;
+pub mod instrumented;
+pub mod integrity;
+pub mod limited;
 pub mod map;
 pub mod none;
+pub mod pinned;
+pub mod processed;
+pub mod pruning;
+pub mod resolver;
+pub mod restricted;
+pub mod retry;
+pub mod router;
+pub mod single_flight;
+pub mod snapshot;
+pub mod stats;
 
+#[cfg(test)]
+mod fixture;
+
+pub use blocking::{Blocking, SyncLoader};
+pub use cache::{CacheMetadata, HttpCache};
 pub use chain::ChainLoader;
+pub use context::{ContextLoader, WithContextLoader};
+pub use dynamic::DynLoader;
+pub use file_cache::FileCacheLoader;
 pub use fs::FsLoader;
+pub use instrumented::InstrumentedLoader;
+pub use integrity::IntegrityLoader;
+pub use limited::LimitedLoader;
+pub use map::StaticLoader;
 pub use none::NoLoader;
+pub use pinned::PinnedLoader;
+pub use processed::{ProcessedContextProvider, WithProcessedContexts};
+pub use pruning::PruningLoader;
+pub use resolver::{Resolver, ResolverLoader};
+pub use restricted::{RestrictedLoader, Restrictions};
+pub use retry::{RetryLoader, RetryOptions};
+pub use router::RouterLoader;
+pub use single_flight::SingleFlightLoader;
+pub use snapshot::{RecordingLoader, ReplayLoader, Snapshot};
+pub use stats::LoaderStats;
+
+#[cfg(feature = "chaos")]
+pub mod chaos;
+
+#[cfg(feature = "chaos")]
+pub use chaos::{ChaosLoader, ChaosOptions};
 
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
@@ -20,6 +66,21 @@ pub mod reqwest;
 #[cfg(feature = "reqwest")]
 pub use self::reqwest::ReqwestLoader;
 
+#[cfg(feature = "ureq")]
+pub mod ureq;
+
+#[cfg(feature = "ureq")]
+pub use self::ureq::UreqLoader;
+
+#[cfg(feature = "hyper")]
+pub mod hyper;
+
+#[cfg(feature = "hyper")]
+pub use self::hyper::HyperLoader;
+
+#[cfg(any(feature = "reqwest", feature = "ureq", feature = "hyper"))]
+mod negotiation;
+
 pub type LoadingResult<I = IriBuf> = Result<RemoteDocument<I>, LoadError>;
 
 pub type RemoteContextReference<I = IriBuf> = RemoteDocumentReference<I, json_ld_syntax::Context>;
@@ -27,11 +88,19 @@ pub type RemoteContextReference<I = IriBuf> = RemoteDocumentReference<I, json_ld
 /// Remote document, loaded or not.
 ///
 /// Either an IRI or the actual document content.
+///
+/// New kinds of references may be added in minor releases.
 #[derive(Clone)]
+#[non_exhaustive]
 pub enum RemoteDocumentReference<I = IriBuf, T = json_syntax::Value> {
 	/// IRI to the remote document.
 	Iri(I),
 
+	/// IRI to the remote document, to be requested with the given profiles.
+	///
+	/// See [`Loader::load_with_profile`].
+	IriWithProfile(I, Vec<Profile>),
+
 	/// Remote document content.
 	Loaded(RemoteDocument<I, T>),
 }
@@ -44,6 +113,18 @@ impl<I, T> RemoteDocumentReference<I, T> {
 	pub fn iri(iri: I) -> Self {
 		Self::Iri(iri)
 	}
+
+	/// Creates an IRI to a document to be requested with the given `profile`.
+	///
+	/// HTTP loaders send the profile in the `Accept` header
+	/// (`application/ld+json;profile="..."`), which some servers use to
+	/// return a specific form of the document (for instance
+	/// [compacted](StandardProfile::Compacted) or
+	/// [framed](StandardProfile::Framed)). The profiles actually returned are
+	/// listed in the [`RemoteDocument::profile`] of the loaded document.
+	pub fn iri_with_profile(iri: I, profile: Profile) -> Self {
+		Self::IriWithProfile(iri, vec![profile])
+	}
 }
 
 impl<I> RemoteDocumentReference<I> {
@@ -58,6 +139,9 @@ impl<I> RemoteDocumentReference<I> {
 	{
 		match self {
 			Self::Iri(r) => Ok(loader.load_with(vocabulary, r).await?.map(Into::into)),
+			Self::IriWithProfile(r, profile) => {
+				load_with_profile_in(loader, vocabulary, r, &profile).await
+			}
 			Self::Loaded(doc) => Ok(doc),
 		}
 	}
@@ -84,11 +168,31 @@ impl<I> RemoteDocumentReference<I> {
 					.await?
 					.map(Into::into),
 			)),
+			Self::IriWithProfile(r, profile) => Ok(Cow::Owned(
+				load_with_profile_in(loader, vocabulary, r.clone(), profile).await?,
+			)),
 			Self::Loaded(doc) => Ok(Cow::Borrowed(doc)),
 		}
 	}
 }
 
+/// Loads the document behind the given IRI with the given `vocabulary`,
+/// requesting the given profiles.
+async fn load_with_profile_in<V>(
+	loader: &impl Loader,
+	vocabulary: &mut V,
+	url: V::Iri,
+	profile: &[Profile],
+) -> LoadingResult<V::Iri>
+where
+	V: IriVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+{
+	let lexical_url = vocabulary.iri(&url).unwrap();
+	let document = loader.load_with_profile(lexical_url, profile).await?;
+	Ok(document.map_iris(|i| vocabulary.insert_owned(i)))
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ContextLoadError {
 	#[error(transparent)]
@@ -98,9 +202,17 @@ pub enum ContextLoadError {
 	ContextExtractionFailed(#[from] ExtractContextError),
 }
 
+impl ContextLoadError {
+	/// Returns the code of this error.
+	pub fn code(&self) -> json_ld_syntax::ErrorCode {
+		json_ld_syntax::ErrorCode::LoadingRemoteContextFailed
+	}
+}
+
 impl<I> RemoteContextReference<I> {
 	/// Loads the remote context with the given `vocabulary` and `loader`.
 	///
+	/// A [`Self::Iri`] is requested with the [`CONTEXT_PROFILE`].
 	/// If the context is already [`Self::Loaded`], simply returns the inner
 	/// [`RemoteContext`].
 	pub async fn load_context_with<V, L: Loader>(
@@ -113,10 +225,14 @@ impl<I> RemoteContextReference<I> {
 		I: Clone + Eq + Hash,
 	{
 		match self {
-			Self::Iri(r) => Ok(loader
-				.load_with(vocabulary, r)
+			Self::Iri(r) => Ok(load_with_profile_in(loader, vocabulary, r, CONTEXT_PROFILE)
 				.await?
 				.try_map(|d| d.into_ld_context())?),
+			Self::IriWithProfile(r, profile) => {
+				Ok(load_with_profile_in(loader, vocabulary, r, &profile)
+					.await?
+					.try_map(|d| d.into_ld_context())?)
+			}
 			Self::Loaded(doc) => Ok(doc),
 		}
 	}
@@ -138,8 +254,12 @@ impl<I> RemoteContextReference<I> {
 	{
 		match self {
 			Self::Iri(r) => Ok(Cow::Owned(
-				loader
-					.load_with(vocabulary, r.clone())
+				load_with_profile_in(loader, vocabulary, r.clone(), CONTEXT_PROFILE)
+					.await?
+					.try_map(|d| d.into_ld_context())?,
+			)),
+			Self::IriWithProfile(r, profile) => Ok(Cow::Owned(
+				load_with_profile_in(loader, vocabulary, r.clone(), profile)
 					.await?
 					.try_map(|d| d.into_ld_context())?,
 			)),
@@ -154,8 +274,17 @@ impl<I> RemoteContextReference<I> {
 #[derive(Debug, Clone)]
 pub struct RemoteDocument<I = IriBuf, T = json_syntax::Value> {
 	/// The final URL of the loaded document, after eventual redirection.
+	///
+	/// This is the `documentUrl` of the JSON-LD API, used as base IRI of
+	/// the document.
 	pub url: Option<I>,
 
+	/// The URL the document was requested with, if it differs from the
+	/// final [`url`](Self::url).
+	///
+	/// Set by the HTTP loaders when they follow redirections.
+	requested_url: Option<I>,
+
 	/// The HTTP `Content-Type` header value of the loaded document, exclusive
 	/// of any optional parameters.
 	pub content_type: Option<Mime>,
@@ -171,8 +300,20 @@ pub struct RemoteDocument<I = IriBuf, T = json_syntax::Value> {
 	/// [RFC 8288]: https://www.rfc-editor.org/rfc/rfc8288
 	pub context_url: Option<I>,
 
+	/// Values of the `profile` parameter of the response `Content-Type`.
 	pub profile: HashSet<Profile<I>>,
 
+	/// HTTP caching metadata of the response, if provided by the loader.
+	cache: CacheMetadata,
+
+	/// Whether the document was served from a cache instead of being
+	/// downloaded, if reported by the loader.
+	from_cache: bool,
+
+	/// Content of the document as fetched, before parsing, if provided by
+	/// the loader.
+	raw_content: Option<Arc<[u8]>>,
+
 	/// The retrieved document.
 	pub document: T,
 }
@@ -212,9 +353,13 @@ impl<I, T> RemoteDocument<I, T> {
 	) -> Self {
 		Self {
 			url,
+			requested_url: None,
 			content_type,
 			context_url,
 			profile,
+			cache: CacheMetadata::default(),
+			from_cache: false,
+			raw_content: None,
 			document,
 		}
 	}
@@ -223,9 +368,13 @@ impl<I, T> RemoteDocument<I, T> {
 	pub fn map<U>(self, f: impl Fn(T) -> U) -> RemoteDocument<I, U> {
 		RemoteDocument {
 			url: self.url,
+			requested_url: self.requested_url,
 			content_type: self.content_type,
 			context_url: self.context_url,
 			profile: self.profile,
+			cache: self.cache,
+			from_cache: self.from_cache,
+			raw_content: self.raw_content,
 			document: f(self.document),
 		}
 	}
@@ -234,9 +383,13 @@ impl<I, T> RemoteDocument<I, T> {
 	pub fn try_map<U, E>(self, f: impl Fn(T) -> Result<U, E>) -> Result<RemoteDocument<I, U>, E> {
 		Ok(RemoteDocument {
 			url: self.url,
+			requested_url: self.requested_url,
 			content_type: self.content_type,
 			context_url: self.context_url,
 			profile: self.profile,
+			cache: self.cache,
+			from_cache: self.from_cache,
+			raw_content: self.raw_content,
 			document: f(self.document)?,
 		})
 	}
@@ -248,6 +401,7 @@ impl<I, T> RemoteDocument<I, T> {
 	{
 		RemoteDocument {
 			url: self.url.map(&mut f),
+			requested_url: self.requested_url.map(&mut f),
 			content_type: self.content_type,
 			context_url: self.context_url.map(&mut f),
 			profile: self
@@ -255,6 +409,9 @@ impl<I, T> RemoteDocument<I, T> {
 				.into_iter()
 				.map(|p| p.map_iri(&mut f))
 				.collect(),
+			cache: self.cache,
+			from_cache: self.from_cache,
+			raw_content: self.raw_content,
 			document: self.document,
 		}
 	}
@@ -264,6 +421,19 @@ impl<I, T> RemoteDocument<I, T> {
 		self.url.as_ref()
 	}
 
+	/// Returns a reference to the URL the document was requested with.
+	///
+	/// This is the same as [`Self::url`] unless the loader followed
+	/// redirections.
+	pub fn requested_url(&self) -> Option<&I> {
+		self.requested_url.as_ref().or(self.url.as_ref())
+	}
+
+	/// Checks if the loader followed redirections to load the document.
+	pub fn is_redirected(&self) -> bool {
+		self.requested_url.is_some()
+	}
+
 	/// Returns the HTTP `Content-Type` header value of the loaded document,
 	/// exclusive of any optional parameters.
 	pub fn content_type(&self) -> Option<&Mime> {
@@ -284,6 +454,22 @@ impl<I, T> RemoteDocument<I, T> {
 		self.context_url.as_ref()
 	}
 
+	/// Returns the values of the `profile` parameter of the response
+	/// `Content-Type`.
+	pub fn profile(&self) -> &HashSet<Profile<I>> {
+		&self.profile
+	}
+
+	/// Checks if the response declared the given profile, for instance to
+	/// check that a document requested in
+	/// [expanded](StandardProfile::Expanded) form was returned as such.
+	pub fn has_profile(&self, profile: &Profile<I>) -> bool
+	where
+		I: Eq + Hash,
+	{
+		self.profile.contains(profile)
+	}
+
 	/// Returns a reference to the content of the document.
 	pub fn document(&self) -> &T {
 		&self.document
@@ -294,6 +480,20 @@ impl<I, T> RemoteDocument<I, T> {
 		&mut self.document
 	}
 
+	/// Returns the content of the document as fetched, before parsing, if
+	/// provided by the loader.
+	///
+	/// The HTTP loaders and the [`FsLoader`] provide it. It is not updated
+	/// when the document is modified.
+	pub fn raw_content(&self) -> Option<&[u8]> {
+		self.raw_content.as_deref()
+	}
+
+	/// Sets the content of the document as fetched, before parsing.
+	pub fn set_raw_content(&mut self, raw_content: Option<Arc<[u8]>>) {
+		self.raw_content = raw_content
+	}
+
 	/// Drops the original URL and returns the content of the document.
 	pub fn into_document(self) -> T {
 		self.document
@@ -308,6 +508,20 @@ impl<I, T> RemoteDocument<I, T> {
 	pub fn set_url(&mut self, url: Option<I>) {
 		self.url = url
 	}
+
+	/// Sets the URL the document was requested with.
+	///
+	/// It is only stored if it differs from the final URL of the document.
+	pub fn set_requested_url(&mut self, requested_url: I)
+	where
+		I: PartialEq,
+	{
+		self.requested_url = if self.url.as_ref() == Some(&requested_url) {
+			None
+		} else {
+			Some(requested_url)
+		}
+	}
 }
 
 /// Standard `profile` parameter values defined for the `application/ld+json`.
@@ -361,6 +575,12 @@ impl StandardProfile {
 	}
 }
 
+/// Profiles requested when loading a remote context, as required by the
+/// context processing algorithm.
+///
+/// See: <https://www.w3.org/TR/json-ld11-api/#context-processing-algorithm>
+pub const CONTEXT_PROFILE: &[Profile] = &[Profile::Standard(StandardProfile::Context)];
+
 /// Value for the `profile` parameter defined for the `application/ld+json`.
 ///
 /// Standard values defined by the JSON-LD specification are defined by the
@@ -415,11 +635,16 @@ impl<I> Profile<I> {
 pub type LoadErrorCause = Box<dyn std::error::Error + Send + Sync>;
 
 /// Loading error.
-#[derive(Debug, thiserror::Error)]
-#[error("loading document `{target}` failed: {cause}")]
+///
+/// Besides its cause, the error may carry a [`LoadTrace`] describing how the
+/// loader got to the failure, displayed along with the error.
+#[derive(Debug)]
 pub struct LoadError {
 	pub target: IriBuf,
 	pub cause: LoadErrorCause,
+
+	/// Trace of the failed load, if provided by the loader.
+	trace: Option<Box<LoadTrace>>,
 }
 
 impl LoadError {
@@ -427,10 +652,155 @@ impl LoadError {
 		Self {
 			target,
 			cause: Box::new(cause),
+			trace: None,
+		}
+	}
+
+	/// Sets the trace of the failed load.
+	pub fn with_trace(mut self, trace: LoadTrace) -> Self {
+		self.trace = Some(Box::new(trace));
+		self
+	}
+
+	/// Returns the trace of the failed load, if provided by the loader.
+	pub fn trace(&self) -> Option<&LoadTrace> {
+		self.trace.as_deref()
+	}
+
+	/// Returns the trace of the failed load, creating an empty one if
+	/// necessary.
+	pub fn trace_mut(&mut self) -> &mut LoadTrace {
+		self.trace.get_or_insert_with(Default::default)
+	}
+
+	/// Records the name of the loader that rejected the target IRI.
+	pub fn rejected_by(mut self, loader: &'static str) -> Self {
+		self.trace_mut().rejected_by.push(loader);
+		self
+	}
+
+	/// Checks if the load failed because the document exceeds the maximum
+	/// document size of the loader.
+	pub fn is_too_large(&self) -> bool {
+		self.cause.is::<TooLarge>()
+	}
+}
+
+/// Loaded document exceeding the maximum document size (in bytes) of a
+/// loader.
+///
+/// Shared by all the loaders with a size limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("document exceeds {0} bytes")]
+pub struct TooLarge(pub u64);
+
+impl fmt::Display for LoadError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"loading document `{}` failed: {}",
+			self.target, self.cause
+		)?;
+
+		if let Some(trace) = &self.trace {
+			if !trace.is_empty() {
+				write!(f, " ({trace})")?
+			}
+
+			if let Some(hint) = trace.hint() {
+				write!(f, ". {hint}")?
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl std::error::Error for LoadError {}
+
+/// Trace of a failed load.
+///
+/// Loaders fill the parts of the trace they know about: the built-in loaders
+/// record their name when they reject an IRI, and the HTTP loaders also
+/// record the redirections followed and the last response received. The
+/// [`ChainLoader`] merges the traces of its loaders.
+#[derive(Debug, Default, Clone)]
+pub struct LoadTrace {
+	/// URLs visited after the target IRI, in order, following redirections.
+	pub redirects: Vec<IriBuf>,
+
+	/// HTTP status of the last response received, if any.
+	pub status: Option<u16>,
+
+	/// Content type of the last response received, if any.
+	pub content_type: Option<String>,
+
+	/// Names of the loaders that rejected the target IRI, in order.
+	pub rejected_by: Vec<&'static str>,
+}
+
+impl LoadTrace {
+	/// Checks if the trace is empty.
+	pub fn is_empty(&self) -> bool {
+		self.redirects.is_empty()
+			&& self.status.is_none()
+			&& self.content_type.is_none()
+			&& self.rejected_by.is_empty()
+	}
+
+	/// Returns a suggestion to fix the failed load, if any.
+	///
+	/// When no server answered, the document can be provided locally. When a
+	/// server answered with something else than JSON, the document can be
+	/// pinned.
+	pub fn hint(&self) -> Option<&'static str> {
+		match (self.status, &self.content_type) {
+			(None, _) if !self.rejected_by.is_empty() => Some(
+				"To provide this document locally, use `FsLoader::mount` or `StaticLoader`",
+			),
+			(Some(200..=299), Some(content_type)) if !content_type.contains("json") => Some(
+				"The server did not serve JSON; use `FsLoader::mount` or `StaticLoader` to provide a local copy of this document",
+			),
+			_ => None,
 		}
 	}
 }
 
+impl fmt::Display for LoadTrace {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut separator = "";
+
+		if !self.redirects.is_empty() {
+			write!(f, "redirected to ")?;
+			for (i, url) in self.redirects.iter().enumerate() {
+				if i > 0 {
+					write!(f, ", then ")?
+				}
+
+				write!(f, "`{url}`")?
+			}
+
+			separator = "; "
+		}
+
+		if let Some(status) = self.status {
+			write!(f, "{separator}HTTP status {status}")?;
+			separator = "; "
+		}
+
+		if let Some(content_type) = &self.content_type {
+			write!(f, "{separator}content type `{content_type}`")?;
+			separator = "; "
+		}
+
+		if !self.rejected_by.is_empty() {
+			write!(f, "{separator}rejected by {}", self.rejected_by.join(", "))?
+		}
+
+		Ok(())
+	}
+}
+
 /// Document loader.
 ///
 /// A document loader is required by most processing functions to fetch remote
@@ -450,6 +820,10 @@ impl LoadError {
 ///   - `ReqwestLoader` actually downloading the remote documents using the
 ///     [`reqwest`](https://crates.io/crates/reqwest) library.
 ///     This requires the `reqwest` feature to be enabled.
+///   - `UreqLoader` and `HyperLoader` doing the same using the blocking
+///     [`ureq`](https://crates.io/crates/ureq) client or any `tower` HTTP
+///     service (such as a [`hyper`](https://crates.io/crates/hyper) client).
+///     This requires the `ureq` or `hyper` feature to be enabled.
 pub trait Loader {
 	/// Loads the document behind the given IRI, using the given vocabulary.
 	#[allow(async_fn_in_trait)]
@@ -466,9 +840,65 @@ pub trait Loader {
 	/// Loads the document behind the given IRI.
 	#[allow(async_fn_in_trait)]
 	async fn load(&self, url: &Iri) -> Result<RemoteDocument<IriBuf>, LoadError>;
+
+	/// Loads the document behind the given IRI, requesting the given
+	/// profiles.
+	///
+	/// Loaders supporting content negotiation (such as the HTTP loaders)
+	/// request the profiles with the `Accept` header, and list the profiles
+	/// actually returned in the [`RemoteDocument::profile`] of the loaded
+	/// document. By default, the profiles are ignored and the document is
+	/// loaded with [`Self::load`].
+	#[allow(async_fn_in_trait)]
+	async fn load_with_profile(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+	) -> Result<RemoteDocument<IriBuf>, LoadError> {
+		let _ = profile;
+		self.load(url).await
+	}
+
+	/// Returns the already processed context behind the given IRI, if any.
+	///
+	/// The context processing algorithm calls this function before loading
+	/// a remote context. By default, no processed context is provided. See
+	/// [`WithProcessedContexts`].
+	///
+	/// Loaders wrapping other loaders must forward this function to them.
+	fn processed_context(&self, url: &Iri) -> Option<Arc<crate::Context>> {
+		let _ = url;
+		None
+	}
+}
+
+impl<'l, L: Loader + ?Sized> Loader for &'l L {
+	async fn load_with<V>(&self, vocabulary: &mut V, url: V::Iri) -> LoadingResult<V::Iri>
+	where
+		V: IriVocabularyMut,
+		V::Iri: Clone + Eq + Hash,
+	{
+		L::load_with(self, vocabulary, url).await
+	}
+
+	async fn load(&self, url: &Iri) -> Result<RemoteDocument<IriBuf>, LoadError> {
+		L::load(self, url).await
+	}
+
+	async fn load_with_profile(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+	) -> Result<RemoteDocument<IriBuf>, LoadError> {
+		L::load_with_profile(self, url, profile).await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<crate::Context>> {
+		L::processed_context(self, url)
+	}
 }
 
-impl<'l, L: Loader> Loader for &'l L {
+impl<'l, L: Loader + ?Sized> Loader for &'l mut L {
 	async fn load_with<V>(&self, vocabulary: &mut V, url: V::Iri) -> LoadingResult<V::Iri>
 	where
 		V: IriVocabularyMut,
@@ -480,9 +910,21 @@ impl<'l, L: Loader> Loader for &'l L {
 	async fn load(&self, url: &Iri) -> Result<RemoteDocument<IriBuf>, LoadError> {
 		L::load(self, url).await
 	}
+
+	async fn load_with_profile(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+	) -> Result<RemoteDocument<IriBuf>, LoadError> {
+		L::load_with_profile(self, url, profile).await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<crate::Context>> {
+		L::processed_context(self, url)
+	}
 }
 
-impl<'l, L: Loader> Loader for &'l mut L {
+impl<L: Loader + ?Sized> Loader for Box<L> {
 	async fn load_with<V>(&self, vocabulary: &mut V, url: V::Iri) -> LoadingResult<V::Iri>
 	where
 		V: IriVocabularyMut,
@@ -494,6 +936,44 @@ impl<'l, L: Loader> Loader for &'l mut L {
 	async fn load(&self, url: &Iri) -> Result<RemoteDocument<IriBuf>, LoadError> {
 		L::load(self, url).await
 	}
+
+	async fn load_with_profile(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+	) -> Result<RemoteDocument<IriBuf>, LoadError> {
+		L::load_with_profile(self, url, profile).await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<crate::Context>> {
+		L::processed_context(self, url)
+	}
+}
+
+impl<L: Loader + ?Sized> Loader for Arc<L> {
+	async fn load_with<V>(&self, vocabulary: &mut V, url: V::Iri) -> LoadingResult<V::Iri>
+	where
+		V: IriVocabularyMut,
+		V::Iri: Clone + Eq + Hash,
+	{
+		L::load_with(self, vocabulary, url).await
+	}
+
+	async fn load(&self, url: &Iri) -> Result<RemoteDocument<IriBuf>, LoadError> {
+		L::load(self, url).await
+	}
+
+	async fn load_with_profile(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+	) -> Result<RemoteDocument<IriBuf>, LoadError> {
+		L::load_with_profile(self, url, profile).await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<crate::Context>> {
+		L::processed_context(self, url)
+	}
 }
 
 /// Context extraction error.
@@ -548,3 +1028,88 @@ impl ExtractContext for json_syntax::Value {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use static_iref::iri;
+
+	#[test]
+	fn requested_url() {
+		let mut document = RemoteDocument::new(
+			Some(iri!("https://example.org/final").to_owned()),
+			None,
+			json_syntax::Value::Null,
+		);
+		document.set_requested_url(iri!("https://example.org/final").to_owned());
+		assert!(!document.is_redirected());
+		assert_eq!(document.requested_url(), document.url());
+
+		document.set_requested_url(iri!("https://example.org/requested").to_owned());
+		assert!(document.is_redirected());
+		assert_eq!(
+			document.requested_url().unwrap().as_str(),
+			"https://example.org/requested"
+		);
+		assert_eq!(
+			document.url().unwrap().as_str(),
+			"https://example.org/final"
+		);
+	}
+
+	#[test]
+	fn trace_display() {
+		let error = LoadError::new(
+			iri!("https://example.org/page").to_owned(),
+			std::io::Error::other("invalid content type"),
+		)
+		.with_trace(LoadTrace {
+			redirects: vec![iri!("https://example.org/page.html").to_owned()],
+			status: Some(200),
+			content_type: Some("text/html".to_owned()),
+			rejected_by: vec!["ReqwestLoader"],
+		});
+		assert_eq!(
+			error.to_string(),
+			"loading document `https://example.org/page` failed: invalid content type \
+			(redirected to `https://example.org/page.html`; HTTP status 200; \
+			content type `text/html`; rejected by ReqwestLoader). \
+			The server did not serve JSON; use `FsLoader::mount` or `StaticLoader` \
+			to provide a local copy of this document"
+		);
+	}
+
+	/// Loader returning the requested profiles.
+	struct Negotiating;
+
+	impl Loader for Negotiating {
+		async fn load(&self, url: &Iri) -> LoadingResult {
+			self.load_with_profile(url, &[]).await
+		}
+
+		async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult {
+			Ok(RemoteDocument::new_full(
+				Some(url.to_owned()),
+				Some("application/ld+json".parse().unwrap()),
+				None,
+				profile.iter().cloned().collect(),
+				json_syntax::Value::Object(Default::default()),
+			))
+		}
+	}
+
+	#[async_std::test]
+	async fn profile_request() {
+		let compacted = Profile::Standard(StandardProfile::Compacted);
+		let input: RemoteDocumentReference = RemoteDocumentReference::iri_with_profile(
+			iri!("http://example.org/doc").to_owned(),
+			compacted.clone(),
+		);
+		let document = input.load_with(&mut (), &Negotiating).await.unwrap();
+		assert!(document.has_profile(&compacted));
+
+		let input = RemoteDocumentReference::iri(iri!("http://example.org/doc").to_owned());
+		let document = input.load_with(&mut (), &Negotiating).await.unwrap();
+		assert!(document.profile().is_empty());
+	}
+}
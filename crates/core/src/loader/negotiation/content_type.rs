@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
 use hashbrown::HashMap;
+use http::HeaderValue;
 use mime::Mime;
-use reqwest::header::HeaderValue;
 
 pub struct ContentType {
 	media_type: Mime,
@@ -103,8 +103,11 @@ impl ContentType {
 		}
 	}
 
+	/// Checks if this is `application/ld+json`, `application/json` or any
+	/// media type with a `+json` suffix.
 	pub fn is_json_ld(&self) -> bool {
-		self.media_type == "application/json" || self.media_type == "application/ld+json"
+		self.media_type.essence_str() == "application/json"
+			|| self.media_type.suffix() == Some(mime::JSON)
 	}
 
 	pub fn media_type(&self) -> &Mime {
@@ -0,0 +1,197 @@
+use hashbrown::HashMap;
+use http::HeaderValue;
+use iref::{IriRef, IriRefBuf};
+
+pub struct Link {
+	href: IriRefBuf,
+	params: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl Link {
+	/// Parses a header value holding a single link.
+	#[cfg(test)]
+	pub fn new(value: &HeaderValue) -> Option<Self> {
+		let mut bytes = value.as_bytes();
+		let link = Self::parse(&mut bytes)?;
+		skip_whitespaces(&mut bytes);
+		if bytes.is_empty() {
+			Some(link)
+		} else {
+			None
+		}
+	}
+
+	/// Parses a header value holding a comma separated list of links.
+	///
+	/// Parsing stops at the first invalid link.
+	pub fn parse_all(value: &HeaderValue) -> Vec<Self> {
+		let mut bytes = value.as_bytes();
+		let mut links = Vec::new();
+
+		while let Some(link) = Self::parse(&mut bytes) {
+			links.push(link);
+			skip_whitespaces(&mut bytes);
+			match bytes.split_first() {
+				Some((b',', rest)) => bytes = rest,
+				_ => break,
+			}
+		}
+
+		links
+	}
+
+	/// Parses a link, stopping before the `,` separating it from the next
+	/// one.
+	fn parse(bytes: &mut &[u8]) -> Option<Self> {
+		skip_whitespaces(bytes);
+		let rest = bytes.strip_prefix(b"<")?;
+		let end = rest.iter().position(|b| *b == b'>')?;
+		let href = IriRefBuf::from_vec(rest[..end].to_vec()).ok()?;
+		*bytes = &rest[end + 1..];
+
+		let mut params = HashMap::new();
+		loop {
+			skip_whitespaces(bytes);
+			match bytes.split_first() {
+				Some((b';', rest)) => *bytes = rest,
+				Some((b',', _)) | None => break Some(Self { href, params }),
+				Some(_) => break None,
+			}
+
+			skip_whitespaces(bytes);
+			let key = take_token(bytes).to_ascii_lowercase();
+			if key.is_empty() {
+				return None;
+			}
+
+			skip_whitespaces(bytes);
+			let value = match bytes.split_first() {
+				Some((b'=', rest)) => {
+					*bytes = rest;
+					skip_whitespaces(bytes);
+					match bytes.split_first() {
+						Some((b'"', rest)) => {
+							*bytes = rest;
+							take_quoted(bytes)?
+						}
+						_ => take_token(bytes).to_vec(),
+					}
+				}
+				_ => Vec::new(),
+			};
+
+			// Only the first occurrence of a parameter is considered.
+			params.entry(key).or_insert(value);
+		}
+	}
+
+	pub fn href(&self) -> &IriRef {
+		self.href.as_iri_ref()
+	}
+
+	pub fn rel(&self) -> Option<&[u8]> {
+		self.params.get(b"rel".as_slice()).map(Vec::as_slice)
+	}
+
+	/// Checks if the given relation type is listed in the `rel` parameter.
+	pub fn has_rel(&self, rel: &[u8]) -> bool {
+		self.rel().is_some_and(|value| {
+			value
+				.split(u8::is_ascii_whitespace)
+				.any(|r| r.eq_ignore_ascii_case(rel))
+		})
+	}
+
+	pub fn type_(&self) -> Option<&[u8]> {
+		self.params.get(b"type".as_slice()).map(Vec::as_slice)
+	}
+}
+
+fn skip_whitespaces(bytes: &mut &[u8]) {
+	while let Some((b, rest)) = bytes.split_first() {
+		if b.is_ascii_whitespace() {
+			*bytes = rest
+		} else {
+			break;
+		}
+	}
+}
+
+/// Reads bytes until a separator or whitespace.
+fn take_token<'a>(bytes: &mut &'a [u8]) -> &'a [u8] {
+	let end = bytes
+		.iter()
+		.position(|b| b.is_ascii_whitespace() || matches!(b, b';' | b',' | b'='))
+		.unwrap_or(bytes.len());
+	let (token, rest) = bytes.split_at(end);
+	*bytes = rest;
+	token
+}
+
+/// Reads the rest of a quoted string, after the opening quote.
+fn take_quoted(bytes: &mut &[u8]) -> Option<Vec<u8>> {
+	let mut value = Vec::new();
+	loop {
+		let (b, rest) = bytes.split_first()?;
+		*bytes = rest;
+		match b {
+			b'"' => break Some(value),
+			b'\\' => {
+				let (b, rest) = bytes.split_first()?;
+				*bytes = rest;
+				value.push(*b)
+			}
+			b => value.push(*b),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_link_1() {
+		let link = Link::new(
+			&HeaderValue::from_str(
+				"<http://www.example.org/context>; rel=\"context\"; type=\"application/ld+json\"",
+			)
+			.unwrap(),
+		)
+		.unwrap();
+		assert_eq!(link.href(), "http://www.example.org/context");
+		assert_eq!(link.rel(), Some(b"context".as_slice()));
+		assert_eq!(link.type_(), Some(b"application/ld+json".as_slice()))
+	}
+
+	#[test]
+	fn parse_link_2() {
+		let link = Link::new(&HeaderValue::from_str("<http://www.example.org/context>; rel=\"context\"; type=\"application/ld+json\"; foo=\"bar\"").unwrap()).unwrap();
+		assert_eq!(link.href(), "http://www.example.org/context");
+		assert_eq!(link.rel(), Some(b"context".as_slice()));
+		assert_eq!(link.type_(), Some(b"application/ld+json".as_slice()))
+	}
+
+	#[test]
+	fn parse_link_3() {
+		let link =
+			Link::new(&HeaderValue::from_str("<http://www.example.org/context>").unwrap()).unwrap();
+		assert_eq!(link.href(), "http://www.example.org/context")
+	}
+
+	#[test]
+	fn parse_link_list() {
+		let links = Link::parse_all(
+			&HeaderValue::from_str(
+				"<http://www.example.org/a>; rel=alternate; type=\"application/ld+json\", <http://www.example.org/context>;rel=\"http://www.w3.org/ns/json-ld#context other\"",
+			)
+			.unwrap(),
+		);
+		assert_eq!(links.len(), 2);
+		assert_eq!(links[0].href(), "http://www.example.org/a");
+		assert!(links[0].has_rel(b"alternate"));
+		assert_eq!(links[0].type_(), Some(b"application/ld+json".as_slice()));
+		assert_eq!(links[1].href(), "http://www.example.org/context");
+		assert!(links[1].has_rel(b"http://www.w3.org/ns/json-ld#context"))
+	}
+}
@@ -0,0 +1,303 @@
+//! HTTP content negotiation shared by the HTTP loaders.
+use super::{CacheMetadata, RemoteDocument, TooLarge};
+use crate::{LoadTrace, Profile};
+use hashbrown::HashSet;
+use http::header::{
+	HeaderMap, AGE, CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, DATE, ETAG, EXPIRES, LINK,
+};
+use http::StatusCode;
+use iref::{Iri, IriBuf};
+use json_syntax::Parse;
+use mime::Mime;
+use std::time::{Duration, SystemTime};
+
+mod content_type;
+mod link;
+
+use content_type::*;
+use link::*;
+
+/// Content negotiation error.
+#[derive(Debug)]
+pub enum Error {
+	/// No JSON-LD content type, and no alternate JSON-LD document.
+	InvalidContentType,
+
+	/// Multiple context `Link` headers.
+	MultipleContextLinkHeaders,
+}
+
+/// Outcome of the content negotiation of a successful response.
+pub enum Negotiated {
+	/// The response body is a JSON-LD document.
+	Document(Metadata),
+
+	/// The response points to an alternate JSON-LD document.
+	Alternate(IriBuf),
+}
+
+/// Metadata of a JSON-LD response.
+pub struct Metadata {
+	content_type: Mime,
+	context_url: Option<IriBuf>,
+	profile: HashSet<Profile>,
+	cache: CacheMetadata,
+}
+
+impl Metadata {
+	/// Parses the response body and builds the remote document.
+	pub fn into_document(
+		self,
+		url: IriBuf,
+		body: &[u8],
+	) -> Result<RemoteDocument, json_syntax::parse::Error<std::io::Error>> {
+		let decoder = utf8_decode::Decoder::new(body.iter().copied());
+		let (document, _) = json_syntax::Value::parse_utf8(decoder)?;
+
+		let mut document = RemoteDocument::new_full(
+			Some(url),
+			Some(self.content_type),
+			self.context_url,
+			self.profile,
+			document,
+		);
+		document.set_cache_metadata(self.cache);
+		document.set_raw_content(Some(body.into()));
+
+		Ok(document)
+	}
+}
+
+/// Converts the given IRI into the URI to request, percent-encoding its
+/// non-ASCII characters as described by
+/// [RFC 3987 section 3.1](https://www.rfc-editor.org/rfc/rfc3987#section-3.1).
+#[cfg(any(feature = "ureq", feature = "hyper"))]
+pub fn to_uri(iri: &Iri) -> String {
+	let mut uri = String::with_capacity(iri.as_str().len());
+	for c in iri.as_str().chars() {
+		if c.is_ascii() {
+			uri.push(c)
+		} else {
+			for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+				uri.push_str(&format!("%{byte:02X}"))
+			}
+		}
+	}
+
+	uri
+}
+
+/// Checks the `Content-Length` of a response against the maximum response
+/// size, if any, so that oversized bodies are rejected before being read.
+pub fn check_content_length(headers: &HeaderMap, limit: Option<usize>) -> Result<(), TooLarge> {
+	let len = headers
+		.get(CONTENT_LENGTH)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.parse::<u64>().ok());
+
+	match (len, limit) {
+		(Some(len), Some(limit)) if len > limit as u64 => Err(TooLarge(limit as u64)),
+		_ => Ok(()),
+	}
+}
+
+/// Negotiates the content of a successful response to a request on `url`,
+/// from its headers.
+///
+/// When no JSON-LD content type is served, the loader follows the
+/// `alternate` link of type `application/ld+json`, if any.
+pub fn negotiate(url: &Iri, headers: &HeaderMap) -> Result<Negotiated, Error> {
+	let mut content_types = headers
+		.get_all(CONTENT_TYPE)
+		.into_iter()
+		.filter_map(ContentType::new);
+
+	match content_types.find(ContentType::is_json_ld) {
+		Some(content_type) => {
+			let mut context_url = None;
+			if *content_type.media_type() != "application/ld+json" {
+				for link in links(headers) {
+					if link.has_rel(b"http://www.w3.org/ns/json-ld#context") {
+						if context_url.is_some() {
+							return Err(Error::MultipleContextLinkHeaders);
+						}
+
+						context_url = Some(link.href().resolved(url));
+					}
+				}
+			}
+
+			let mut profile = HashSet::new();
+			for p in content_type
+				.profile()
+				.into_iter()
+				.flat_map(|p| p.split(|b| *b == b' '))
+			{
+				if let Ok(p) = std::str::from_utf8(p) {
+					if let Ok(iri) = Iri::new(p) {
+						profile.insert(Profile::new(iri));
+					}
+				}
+			}
+
+			Ok(Negotiated::Document(Metadata {
+				content_type: content_type.into_media_type(),
+				context_url,
+				profile,
+				cache: cache_metadata(headers),
+			}))
+		}
+		None => {
+			log::debug!("no valid media type found");
+			for link in links(headers) {
+				if link.has_rel(b"alternate") && link.type_() == Some(b"application/ld+json") {
+					log::debug!("link found");
+					return Ok(Negotiated::Alternate(link.href().resolved(url)));
+				}
+			}
+
+			Err(Error::InvalidContentType)
+		}
+	}
+}
+
+/// Records the status and content type of a response in the given trace.
+pub fn trace_response(trace: &mut LoadTrace, status: StatusCode, headers: &HeaderMap) {
+	trace.status = Some(status.as_u16());
+	trace.content_type = headers
+		.get(CONTENT_TYPE)
+		.and_then(|value| value.to_str().ok())
+		.map(ToOwned::to_owned);
+}
+
+/// Builds the `Accept` header value requesting the given profiles.
+pub fn accept_header(request_profile: &[Profile]) -> String {
+	let mut json_ld_params = String::new();
+
+	if !request_profile.is_empty() {
+		json_ld_params.push_str("; profile=");
+
+		if request_profile.len() > 1 {
+			json_ld_params.push('"');
+		}
+
+		for (i, p) in request_profile.iter().enumerate() {
+			if i > 0 {
+				json_ld_params.push(' ');
+			}
+
+			json_ld_params.push_str(p.iri().as_str());
+		}
+
+		if request_profile.len() > 1 {
+			json_ld_params.push('"');
+		}
+	}
+
+	format!("application/ld+json{json_ld_params}, application/json")
+}
+
+/// Returns the links of all the `Link` headers.
+fn links(headers: &HeaderMap) -> impl Iterator<Item = Link> + '_ {
+	headers.get_all(LINK).into_iter().flat_map(Link::parse_all)
+}
+
+/// Extracts the caching metadata of a response from its headers.
+pub fn cache_metadata(headers: &HeaderMap) -> CacheMetadata {
+	let mut cache = CacheMetadata {
+		fetched_at: Some(SystemTime::now()),
+		..Default::default()
+	};
+
+	for value in headers.get_all(CACHE_CONTROL) {
+		let Ok(value) = value.to_str() else { continue };
+		for directive in value.split(',') {
+			let directive = directive.trim();
+			match directive.split_once('=') {
+				Some((name, seconds)) => {
+					let Ok(seconds) = seconds.trim().trim_matches('"').parse() else {
+						continue;
+					};
+
+					let name = name.trim();
+					if name.eq_ignore_ascii_case("max-age") {
+						cache.max_age = Some(Duration::from_secs(seconds))
+					} else if name.eq_ignore_ascii_case("s-maxage") {
+						cache.s_maxage = Some(Duration::from_secs(seconds))
+					}
+				}
+				None if directive.eq_ignore_ascii_case("no-cache") => cache.no_cache = true,
+				None if directive.eq_ignore_ascii_case("no-store") => cache.no_store = true,
+				_ => (),
+			}
+		}
+	}
+
+	// An invalid expiration date stands for a date in the past.
+	if let Some(expires) = headers.get(EXPIRES) {
+		let date = http_date(headers.get(DATE)).or(cache.fetched_at);
+		cache.expires_after = Some(
+			http_date(Some(expires))
+				.zip(date)
+				.and_then(|(expires, date)| expires.duration_since(date).ok())
+				.unwrap_or_default(),
+		)
+	}
+
+	cache.age = headers
+		.get(AGE)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.trim().parse().ok())
+		.map(Duration::from_secs);
+
+	cache.etag = headers
+		.get(ETAG)
+		.and_then(|value| value.to_str().ok())
+		.map(ToOwned::to_owned);
+
+	cache
+}
+
+/// Parses the HTTP date of the given header value, if any.
+fn http_date(value: Option<&http::HeaderValue>) -> Option<SystemTime> {
+	httpdate::parse_http_date(value?.to_str().ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use http::HeaderValue;
+
+	#[test]
+	fn freshness_headers() {
+		let mut headers = HeaderMap::new();
+		headers.insert(
+			CACHE_CONTROL,
+			HeaderValue::from_static("public, s-maxage=600"),
+		);
+		headers.insert(
+			DATE,
+			HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+		);
+		headers.insert(
+			EXPIRES,
+			HeaderValue::from_static("Sun, 06 Nov 1994 08:50:37 GMT"),
+		);
+
+		let cache = cache_metadata(&headers);
+		assert_eq!(cache.freshness_lifetime(), Some(Duration::from_secs(60)));
+		assert_eq!(
+			cache.shared_freshness_lifetime(),
+			Some(Duration::from_secs(600))
+		);
+
+		let now = cache.fetched_at.unwrap();
+		assert!(cache.is_stale(now + Duration::from_secs(60)));
+		assert!(!cache.is_stale_shared(now + Duration::from_secs(60)));
+
+		headers.insert(EXPIRES, HeaderValue::from_static("0"));
+		let cache = cache_metadata(&headers);
+		assert_eq!(cache.expires_after, Some(Duration::ZERO));
+		assert!(cache.is_stale(now))
+	}
+}
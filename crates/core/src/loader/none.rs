@@ -18,6 +18,6 @@ pub struct CannotLoad;
 impl Loader for NoLoader {
 	#[inline(always)]
 	async fn load(&self, url: &Iri) -> LoadingResult {
-		Err(LoadError::new(url.to_owned(), CannotLoad))
+		Err(LoadError::new(url.to_owned(), CannotLoad).rejected_by("NoLoader"))
 	}
 }
@@ -0,0 +1,333 @@
+//! Context version pinning.
+//!
+//! Long running services usually depend on a fixed set of remote contexts
+//! whose content is expected to never change. The [`PinnedLoader`] wraps
+//! another loader and compares each loaded context with a known
+//! [`ContextPin`], reporting any difference instead of silently processing
+//! documents with a modified context.
+use crate::{warning, Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf};
+use json_ld_syntax::context::definition::Version;
+use json_syntax::Print;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::{DynLoader, Loader};
+
+/// Expected content of a remote context.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContextPin {
+	/// Expected `@version` entry.
+	pub version: Option<Version>,
+
+	/// Fingerprint of each term definition.
+	pub terms: BTreeMap<String, u64>,
+}
+
+impl ContextPin {
+	/// Computes the pin of the given context document.
+	///
+	/// The document is expected to be an object with a `@context` entry. If
+	/// the context is an array, definitions are merged in order.
+	pub fn of(document: &json_syntax::Value) -> Self {
+		let mut result = Self::default();
+
+		if let Some(object) = document.as_object() {
+			if let Some(context) = object.get_unique("@context").ok().flatten() {
+				result.add_context(context)
+			}
+		}
+
+		result
+	}
+
+	fn add_context(&mut self, context: &json_syntax::Value) {
+		match context {
+			json_syntax::Value::Array(items) => {
+				for item in items {
+					self.add_context(item)
+				}
+			}
+			json_syntax::Value::Object(definition) => {
+				for entry in definition.iter() {
+					match entry.key.as_str() {
+						"@version" => {
+							if let json_syntax::Value::Number(n) = &entry.value {
+								if n.as_str() == Version::V1_1.into_str() {
+									self.version = Some(Version::V1_1)
+								}
+							}
+						}
+						key if key.starts_with('@') => (),
+						key => {
+							self.terms.insert(key.to_owned(), fingerprint(&entry.value));
+						}
+					}
+				}
+			}
+			_ => (),
+		}
+	}
+
+	/// Compares this pin with the pin of a loaded context, returning the
+	/// mismatch if any.
+	pub fn compare(&self, url: IriBuf, found: &Self) -> Option<PinMismatch> {
+		let version_changed = self.version.is_some() != found.version.is_some();
+
+		let mut changed_terms = Vec::new();
+		let mut removed_terms = Vec::new();
+		for (term, fingerprint) in &self.terms {
+			match found.terms.get(term) {
+				Some(f) if f == fingerprint => (),
+				Some(_) => changed_terms.push(term.clone()),
+				None => removed_terms.push(term.clone()),
+			}
+		}
+
+		let added_terms: Vec<_> = found
+			.terms
+			.keys()
+			.filter(|term| !self.terms.contains_key(*term))
+			.cloned()
+			.collect();
+
+		if version_changed
+			|| !changed_terms.is_empty()
+			|| !removed_terms.is_empty()
+			|| !added_terms.is_empty()
+		{
+			Some(PinMismatch {
+				url,
+				expected_version: self.version,
+				found_version: found.version,
+				changed_terms,
+				added_terms,
+				removed_terms,
+			})
+		} else {
+			None
+		}
+	}
+}
+
+/// Computes the fingerprint of a JSON value.
+///
+/// The value is first canonicalized so that the fingerprint does not depend
+/// on the order of object entries or the formatting of the document. The
+/// fingerprint is stable across executions and platforms, so it can be stored.
+pub fn fingerprint(value: &json_syntax::Value) -> u64 {
+	const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+	const FNV_PRIME: u64 = 0x100000001b3;
+
+	let mut value = value.clone();
+	value.canonicalize();
+
+	value
+		.compact_print()
+		.to_string()
+		.bytes()
+		.fold(FNV_OFFSET_BASIS, |hash, b| {
+			(hash ^ b as u64).wrapping_mul(FNV_PRIME)
+		})
+}
+
+/// Difference between a pinned context and its loaded content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinMismatch {
+	/// URL of the context.
+	pub url: IriBuf,
+
+	/// Pinned `@version`.
+	pub expected_version: Option<Version>,
+
+	/// Loaded `@version`.
+	pub found_version: Option<Version>,
+
+	/// Terms whose definition changed.
+	pub changed_terms: Vec<String>,
+
+	/// Terms not present in the pin.
+	pub added_terms: Vec<String>,
+
+	/// Pinned terms missing from the loaded context.
+	pub removed_terms: Vec<String>,
+}
+
+impl fmt::Display for PinMismatch {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "context `{}` differs from its pin", self.url)?;
+
+		if self.expected_version.is_some() != self.found_version.is_some() {
+			write!(
+				f,
+				", version {} instead of {}",
+				self.found_version.map(Version::into_str).unwrap_or("1.0"),
+				self.expected_version
+					.map(Version::into_str)
+					.unwrap_or("1.0")
+			)?;
+		}
+
+		for (label, terms) in [
+			("changed", &self.changed_terms),
+			("added", &self.added_terms),
+			("removed", &self.removed_terms),
+		] {
+			if !terms.is_empty() {
+				write!(f, ", {label} terms: {}", terms.join(", "))?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl std::error::Error for PinMismatch {}
+
+impl<N> contextual::DisplayWithContext<N> for PinMismatch {
+	fn fmt_with(&self, _vocabulary: &N, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+/// What to do when a loaded context differs from its pin.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PinPolicy {
+	/// Report the mismatch to the warning handler and return the document.
+	#[default]
+	Warn,
+
+	/// Fail with a [`PinMismatch`] error.
+	Deny,
+}
+
+/// Loader checking loaded contexts against pins.
+///
+/// Documents without pin are returned unchecked. Mismatches are either
+/// reported to a [`warning::Handler`] or turned into loading errors
+/// depending on the configured [`PinPolicy`].
+pub struct PinnedLoader<L, W = ()> {
+	loader: L,
+	pins: HashMap<IriBuf, ContextPin>,
+	policy: PinPolicy,
+	warnings: Mutex<W>,
+}
+
+impl<L> PinnedLoader<L> {
+	/// Creates a new pinned loader, without pins, ignoring warnings.
+	pub fn new(loader: L) -> Self {
+		Self {
+			loader,
+			pins: HashMap::new(),
+			policy: PinPolicy::default(),
+			warnings: Mutex::new(()),
+		}
+	}
+}
+
+impl<L, W> PinnedLoader<L, W> {
+	/// Sets the warning handler receiving mismatches with the
+	/// [`PinPolicy::Warn`] policy.
+	pub fn with_warnings<H>(self, warnings: H) -> PinnedLoader<L, H> {
+		PinnedLoader {
+			loader: self.loader,
+			pins: self.pins,
+			policy: self.policy,
+			warnings: Mutex::new(warnings),
+		}
+	}
+
+	/// Sets the mismatch policy.
+	pub fn with_policy(mut self, policy: PinPolicy) -> Self {
+		self.policy = policy;
+		self
+	}
+
+	/// Pins the context at the given URL.
+	///
+	/// Replaces and returns the previous pin, if any.
+	pub fn pin(&mut self, url: IriBuf, pin: ContextPin) -> Option<ContextPin> {
+		self.pins.insert(url, pin)
+	}
+
+	/// Returns the pin of the given context, if any.
+	pub fn get_pin(&self, url: &Iri) -> Option<&ContextPin> {
+		self.pins.get(url)
+	}
+
+	/// Consumes the loader and returns the inner warning handler.
+	pub fn into_warnings(self) -> W {
+		self.warnings.into_inner().unwrap()
+	}
+}
+
+impl<L, W> Loader for PinnedLoader<L, W>
+where
+	L: Loader,
+	W: warning::Handler<(), PinMismatch>,
+{
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.check(url, self.loader.load(url).await)
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.check(url, self.loader.load_with_profile(url, profile).await)
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.loader.processed_context(url)
+	}
+}
+
+impl<L, W> DynLoader for PinnedLoader<L, W>
+where
+	L: DynLoader,
+	W: warning::Handler<(), PinMismatch> + Send,
+{
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let result = self.loader.load_dyn(url).await;
+			self.check(url, result)
+		})
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let result = self.loader.load_with_profile_dyn(url, profile).await;
+			self.check(url, result)
+		})
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.loader.processed_context_dyn(url)
+	}
+}
+
+impl<L, W> PinnedLoader<L, W>
+where
+	W: warning::Handler<(), PinMismatch>,
+{
+	/// Compares the context loaded from `url` with its pin, if any.
+	fn check(&self, url: &Iri, result: LoadingResult<IriBuf>) -> LoadingResult<IriBuf> {
+		let document = result?;
+
+		if let Some(pin) = self.pins.get(url) {
+			let found = ContextPin::of(document.document());
+			if let Some(mismatch) = pin.compare(url.to_owned(), &found) {
+				match self.policy {
+					PinPolicy::Warn => self.warnings.lock().unwrap().handle(&(), mismatch),
+					PinPolicy::Deny => return Err(LoadError::new(url.to_owned(), mismatch)),
+				}
+			}
+		}
+
+		Ok(document)
+	}
+}
@@ -0,0 +1,153 @@
+//! Pre-processed contexts.
+//!
+//! Processing a context is costly, in particular for large remote contexts.
+//! Applications always using the same contexts can process them once and
+//! provide the result to the context processing algorithm, which then skips
+//! both the loading and the processing of these contexts.
+use super::{DynLoader, Loader};
+use crate::{Context, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::Arc,
+};
+
+/// Provider of already processed contexts.
+///
+/// Each provided context must be the result of processing the remote context
+/// behind its IRI on top of a newly-initialized context (with
+/// `Context::new(None)`), in `json-ld-1.1` processing mode. It is only used
+/// when the remote context is processed on top of a newly-initialized
+/// context, which is the case of the first context of a document, in
+/// `json-ld-1.1` mode. The provided context is used as is: it is not checked
+/// against the actual remote context.
+pub trait ProcessedContextProvider {
+	/// Returns the processed context behind the given IRI, if any.
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>>;
+}
+
+impl ProcessedContextProvider for HashMap<IriBuf, Arc<Context>> {
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.get(url).cloned()
+	}
+}
+
+impl ProcessedContextProvider for BTreeMap<IriBuf, Arc<Context>> {
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.get(url).cloned()
+	}
+}
+
+/// Loader providing already processed contexts.
+///
+/// Documents are loaded by the inner loader, while the context processing
+/// algorithm gets the contexts known by the [`ProcessedContextProvider`]
+/// without loading nor processing them.
+///
+/// ```
+/// use std::{collections::HashMap, sync::Arc};
+/// use json_ld_core::{loader::WithProcessedContexts, Context, NoLoader};
+/// use static_iref::iri;
+///
+/// let mut contexts = HashMap::new();
+/// contexts.insert(
+///   iri!("https://example.com/context.jsonld").to_owned(),
+///   Arc::new(Context::new(None))
+/// );
+///
+/// let loader = WithProcessedContexts::new(contexts, NoLoader);
+/// ```
+///
+/// The loaders of this crate wrapping another loader forward the processed
+/// contexts of their inner loader, so this loader can be wrapped like any
+/// other.
+pub struct WithProcessedContexts<P, L> {
+	provider: P,
+	loader: L,
+}
+
+impl<P, L> WithProcessedContexts<P, L> {
+	/// Creates a new loader providing the processed contexts of `provider`,
+	/// and loading documents with `loader`.
+	pub fn new(provider: P, loader: L) -> Self {
+		Self { provider, loader }
+	}
+
+	/// Returns the processed contexts provider.
+	pub fn provider(&self) -> &P {
+		&self.provider
+	}
+
+	/// Returns the inner loader.
+	pub fn loader(&self) -> &L {
+		&self.loader
+	}
+}
+
+impl<P: ProcessedContextProvider, L: Loader> Loader for WithProcessedContexts<P, L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.loader.load(url).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.loader.load_with_profile(url, profile).await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.provider
+			.processed_context(url)
+			.or_else(|| self.loader.processed_context(url))
+	}
+}
+
+impl<P, L> DynLoader for WithProcessedContexts<P, L>
+where
+	P: ProcessedContextProvider + Send + Sync,
+	L: DynLoader,
+{
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		self.loader.load_dyn(url)
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		self.loader.load_with_profile_dyn(url, profile)
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.provider
+			.processed_context(url)
+			.or_else(|| self.loader.processed_context_dyn(url))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{
+		ChainLoader, InstrumentedLoader, NoLoader, RouterLoader, SingleFlightLoader,
+	};
+	use static_iref::iri;
+
+	#[test]
+	fn wrapped() {
+		let url = iri!("https://example.com/context.jsonld");
+		let mut contexts: HashMap<IriBuf, Arc<Context>> = HashMap::new();
+		contexts.insert(url.to_owned(), Arc::new(Context::new(None)));
+
+		let loader = WithProcessedContexts::new(contexts, NoLoader);
+		let loader =
+			InstrumentedLoader::new(SingleFlightLoader::new(ChainLoader::new(NoLoader, loader)));
+		let loader =
+			RouterLoader::new(NoLoader).route(iri!("https://example.com/").to_owned(), loader);
+
+		assert!(loader.processed_context(url).is_some());
+		assert!(loader
+			.processed_context(iri!("https://example.org/context.jsonld"))
+			.is_none())
+	}
+}
@@ -0,0 +1,259 @@
+//! Context pruning loader.
+//!
+//! Some remote contexts define thousands of terms (such as the schema.org
+//! context), while the documents using them only need a handful. Processing
+//! every term definition dominates the cost of expanding such small
+//! documents. The [`PruningLoader`] removes from the loaded contexts the term
+//! definitions that the document cannot possibly use before they are
+//! processed.
+use super::{DynLoader, Loader, RemoteDocument};
+use crate::{Context, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use hashbrown::HashSet;
+use iref::{Iri, IriBuf};
+use std::sync::{Arc, Mutex};
+
+/// Loader removing from the loaded contexts the terms not needed by a
+/// document.
+///
+/// A term is kept if its name appears (as a key, a string value or the
+/// prefix of a compact IRI) in the document, in a keyword entry (such as
+/// `@vocab`) or kept term definition of a context loaded so far, or if the
+/// term may be used as a prefix (its IRI ends with `/` or `#`, or it is
+/// defined with `@prefix`). Keywords (`@vocab`, `@base`, etc.) are always
+/// kept, and term definitions are kept whole, scoped contexts included.
+///
+/// Since the terms referenced by a context are remembered, the contexts it
+/// imports (with `@import`) keep the terms it needs. However a context is
+/// pruned when it is loaded: a term only referenced by the definitions of
+/// a context loaded afterwards (for instance the next one in a `@context`
+/// array) is removed, and the expansion result may differ. Only enable
+/// pruning for contexts that do not depend on each other this way.
+///
+/// Pruned contexts must only be used to expand the document given to
+/// [`PruningLoader::new`], not to compact documents.
+pub struct PruningLoader<L> {
+	inner: L,
+	terms: Option<Mutex<HashSet<String>>>,
+}
+
+impl<L> PruningLoader<L> {
+	/// Creates a new loader pruning contexts for the given document.
+	pub fn new(inner: L, document: &json_syntax::Value) -> Self {
+		let mut terms = HashSet::new();
+		collect_terms(document, &mut terms);
+		Self {
+			inner,
+			terms: Some(Mutex::new(terms)),
+		}
+	}
+
+	/// Creates a loader that does not prune anything.
+	pub fn disabled(inner: L) -> Self {
+		Self { inner, terms: None }
+	}
+
+	/// Returns the terms kept so far, if pruning is enabled.
+	///
+	/// These are the terms used by the document, and the ones referenced by
+	/// the contexts loaded so far.
+	pub fn terms(&self) -> Option<HashSet<String>> {
+		self.terms
+			.as_ref()
+			.map(|terms| terms.lock().unwrap().clone())
+	}
+
+	/// Prunes the context of the given loaded document.
+	fn prune(&self, mut document: RemoteDocument) -> RemoteDocument {
+		if let Some(terms) = &self.terms {
+			if let Some(object) = document.document_mut().as_object_mut() {
+				if let Ok(Some(context)) = object.get_unique_mut("@context") {
+					let mut terms = terms.lock().unwrap();
+					*terms = prune_context(context, &terms)
+				}
+			}
+		}
+
+		document
+	}
+}
+
+impl<L: Loader> Loader for PruningLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		Ok(self.prune(self.inner.load(url).await?))
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		Ok(self.prune(self.inner.load_with_profile(url, profile).await?))
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for PruningLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move { Ok(self.prune(self.inner.load_dyn(url).await?)) })
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(
+			async move { Ok(self.prune(self.inner.load_with_profile_dyn(url, profile).await?)) },
+		)
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+/// Removes the term definitions of `context` that are not needed to expand
+/// a document using the given `terms`.
+///
+/// Returns the needed terms: the given ones, and the ones referenced by the
+/// keyword entries and the kept term definitions of `context`.
+///
+/// See [`PruningLoader`] for the exact rules.
+pub fn prune_context(context: &mut json_syntax::Value, terms: &HashSet<String>) -> HashSet<String> {
+	let mut needed = terms.clone();
+	for_each_keyword_entry(context, &mut |value| collect_terms(value, &mut needed));
+
+	loop {
+		let mut changed = false;
+
+		for_each_definition(context, &mut |key, value| {
+			if needed.contains(key) || is_prefix_definition(value) {
+				let mut refs = HashSet::new();
+				collect_terms(value, &mut refs);
+				for r in refs {
+					changed |= needed.insert(r)
+				}
+
+				changed |= needed.insert(key.to_owned())
+			}
+		});
+
+		if !changed {
+			break;
+		}
+	}
+
+	retain_definitions(context, &|key| key.starts_with('@') || needed.contains(key));
+	needed
+}
+
+fn for_each_keyword_entry(context: &json_syntax::Value, f: &mut impl FnMut(&json_syntax::Value)) {
+	match context {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				for_each_keyword_entry(item, f)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			for entry in object.iter() {
+				if entry.key.starts_with('@') {
+					f(&entry.value)
+				}
+			}
+		}
+		_ => (),
+	}
+}
+
+fn for_each_definition(
+	context: &json_syntax::Value,
+	f: &mut impl FnMut(&str, &json_syntax::Value),
+) {
+	match context {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				for_each_definition(item, f)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			for entry in object.iter() {
+				if !entry.key.starts_with('@') {
+					f(entry.key.as_str(), &entry.value)
+				}
+			}
+		}
+		_ => (),
+	}
+}
+
+fn retain_definitions(context: &mut json_syntax::Value, keep: &impl Fn(&str) -> bool) {
+	match context {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				retain_definitions(item, keep)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			let mut retained = json_syntax::Object::new();
+			for entry in std::mem::take(object) {
+				if keep(entry.key.as_str()) {
+					retained.insert(entry.key, entry.value);
+				}
+			}
+
+			*object = retained
+		}
+		_ => (),
+	}
+}
+
+fn is_prefix_definition(value: &json_syntax::Value) -> bool {
+	fn is_prefix_iri(iri: &str) -> bool {
+		iri.ends_with('/') || iri.ends_with('#')
+	}
+
+	match value {
+		json_syntax::Value::String(iri) => is_prefix_iri(iri),
+		json_syntax::Value::Object(object) => {
+			object
+				.get_unique("@prefix")
+				.ok()
+				.flatten()
+				.and_then(json_syntax::Value::as_boolean)
+				.unwrap_or(false)
+				|| object
+					.get_unique("@id")
+					.ok()
+					.flatten()
+					.and_then(json_syntax::Value::as_str)
+					.is_some_and(is_prefix_iri)
+		}
+		_ => false,
+	}
+}
+
+fn collect_terms(value: &json_syntax::Value, terms: &mut HashSet<String>) {
+	match value {
+		json_syntax::Value::String(s) => insert_term(s, terms),
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				collect_terms(item, terms)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			for entry in object.iter() {
+				insert_term(entry.key.as_str(), terms);
+				collect_terms(&entry.value, terms)
+			}
+		}
+		_ => (),
+	}
+}
+
+fn insert_term(s: &str, terms: &mut HashSet<String>) {
+	if let Some((prefix, _)) = s.split_once(':') {
+		terms.insert(prefix.to_owned());
+	}
+
+	terms.insert(s.to_owned());
+}
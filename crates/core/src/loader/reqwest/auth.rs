@@ -0,0 +1,112 @@
+use futures::future::BoxFuture;
+use iref::Iri;
+use std::fmt;
+
+/// Error returned by a [`TokenProvider`].
+pub type TokenError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Bearer token provider.
+///
+/// Called before each request of a [`ReqwestLoader`](super::ReqwestLoader)
+/// with the requested URL. The returned token, if any, is sent in the
+/// `Authorization: Bearer` header of the request. Since the provider is
+/// asynchronous, it can fetch or refresh the token on demand.
+///
+/// This trait is implemented by any function taking an IRI and returning a
+/// boxed future.
+///
+/// ```
+/// use futures::future::BoxFuture;
+/// use iref::Iri;
+/// use json_ld_core::loader::reqwest::{TokenError, TokenProvider};
+/// use json_ld_core::loader::ReqwestLoader;
+///
+/// struct ExampleToken(String);
+///
+/// impl TokenProvider for ExampleToken {
+///   fn token<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, Result<Option<String>, TokenError>> {
+///     Box::pin(async move {
+///       Ok(url
+///         .as_str()
+///         .starts_with("https://example.com/")
+///         .then(|| self.0.clone()))
+///     })
+///   }
+/// }
+///
+/// let loader = ReqwestLoader::builder()
+///   .token_provider(ExampleToken("secret".to_owned()))
+///   .build()
+///   .unwrap();
+/// ```
+pub trait TokenProvider: Send + Sync {
+	/// Returns the token to send with the request on the given URL, if any.
+	fn token<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, Result<Option<String>, TokenError>>;
+}
+
+/// Origin of a URL: its scheme, host and port.
+///
+/// The [extra headers](super::Options::headers) of a
+/// [`ReqwestLoader`](super::ReqwestLoader) are scoped to an origin, so that
+/// credentials are not sent to the other hosts the loader contacts.
+///
+/// ```
+/// use json_ld_core::loader::reqwest::Origin;
+/// use static_iref::iri;
+///
+/// let origin = Origin::of(iri!("https://example.com/contexts/v1.jsonld")).unwrap();
+/// assert_eq!(Origin::of(iri!("https://EXAMPLE.com:443/other")), Some(origin));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Origin {
+	scheme: String,
+	host: String,
+	port: Option<u16>,
+}
+
+impl Origin {
+	/// Returns the origin of the given URL, if it has an authority.
+	///
+	/// The scheme and host are compared case-insensitively, and the default
+	/// port of the `http` and `https` schemes is implied.
+	pub fn of(url: &Iri) -> Option<Self> {
+		let authority = url.authority()?;
+		let scheme = url.scheme().as_str().to_ascii_lowercase();
+
+		let port = match authority.port().map(|port| port.as_str()) {
+			Some(port) if !port.is_empty() => Some(port.parse().ok()?),
+			_ => match scheme.as_str() {
+				"http" => Some(80),
+				"https" => Some(443),
+				_ => None,
+			},
+		};
+
+		Some(Self {
+			scheme,
+			host: authority.host().as_str().to_ascii_lowercase(),
+			port,
+		})
+	}
+}
+
+impl fmt::Display for Origin {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}://{}", self.scheme, self.host)?;
+
+		if let Some(port) = self.port {
+			write!(f, ":{port}")?
+		}
+
+		Ok(())
+	}
+}
+
+impl<F> TokenProvider for F
+where
+	F: Send + Sync + for<'a> Fn(&'a Iri) -> BoxFuture<'a, Result<Option<String>, TokenError>>,
+{
+	fn token<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, Result<Option<String>, TokenError>> {
+		(self)(url)
+	}
+}
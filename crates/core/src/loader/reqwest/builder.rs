@@ -0,0 +1,351 @@
+use super::{Options, Origin, ReqwestLoader, TokenProvider};
+use crate::{
+	loader::{restricted, HttpCache, Restrictions},
+	Profile,
+};
+use hashbrown::{HashMap, HashSet};
+use iref::Iri;
+use reqwest::{
+	dns::{Addrs, Name, Resolve, Resolving},
+	header::{HeaderMap, HeaderName, HeaderValue},
+	redirect,
+};
+use reqwest_middleware::ClientWithMiddleware;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default maximum number of HTTP redirections, as in `reqwest`.
+const DEFAULT_MAX_HTTP_REDIRECTS: usize = 10;
+
+/// HTTP client used by a [`ReqwestLoaderBuilder`].
+enum Client {
+	/// Client built from the builder settings.
+	Settings {
+		connect_timeout: Option<Duration>,
+		read_timeout: Option<Duration>,
+		timeout: Option<Duration>,
+		max_http_redirects: Option<usize>,
+		proxies: Vec<reqwest::Proxy>,
+	},
+
+	/// Pre-built client.
+	Custom(ClientWithMiddleware),
+}
+
+/// [`ReqwestLoader`] builder.
+///
+/// ```
+/// use std::time::Duration;
+/// use json_ld_core::loader::ReqwestLoader;
+///
+/// let loader = ReqwestLoader::builder()
+///   .connect_timeout(Duration::from_secs(5))
+///   .timeout(Duration::from_secs(30))
+///   .max_http_redirects(4)
+///   .build()
+///   .unwrap();
+/// ```
+pub struct ReqwestLoaderBuilder {
+	request_profile: Vec<Profile>,
+	max_redirections: usize,
+	max_response_bytes: Option<usize>,
+	cache: Option<Arc<HttpCache>>,
+	headers: HashMap<Origin, HeaderMap>,
+	token_provider: Option<Arc<dyn TokenProvider>>,
+	restrictions: Option<Restrictions>,
+	client: Client,
+}
+
+impl Default for ReqwestLoaderBuilder {
+	fn default() -> Self {
+		let options = Options::default();
+		Self {
+			request_profile: options.request_profile,
+			max_redirections: options.max_redirections,
+			max_response_bytes: options.max_response_bytes,
+			cache: options.cache,
+			headers: options.headers,
+			token_provider: options.token_provider,
+			restrictions: None,
+			client: Client::Settings {
+				connect_timeout: None,
+				read_timeout: None,
+				timeout: None,
+				max_http_redirects: None,
+				proxies: Vec::new(),
+			},
+		}
+	}
+}
+
+impl ReqwestLoaderBuilder {
+	/// Creates a builder starting from the default options.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a profile to the `Accept` header of requests.
+	pub fn request_profile(mut self, profile: Profile) -> Self {
+		self.request_profile.push(profile);
+		self
+	}
+
+	/// Sets the maximum number of `Link` header redirections.
+	///
+	/// See [`Options::max_redirections`].
+	pub fn max_redirections(mut self, value: usize) -> Self {
+		self.max_redirections = value;
+		self
+	}
+
+	/// Sets the maximum size (in bytes) of a response body.
+	///
+	/// See [`Options::max_response_bytes`].
+	pub fn max_response_bytes(mut self, value: usize) -> Self {
+		self.max_response_bytes = Some(value);
+		self
+	}
+
+	/// Sets the HTTP cache, which can be shared with other loaders.
+	///
+	/// See [`Options::cache`].
+	pub fn cache(mut self, cache: Arc<HttpCache>) -> Self {
+		self.cache = Some(cache);
+		self
+	}
+
+	/// Adds a header sent with every request to the given origin.
+	///
+	/// See [`Options::headers`].
+	pub fn header(mut self, origin: Origin, name: HeaderName, value: HeaderValue) -> Self {
+		self.headers.entry(origin).or_default().append(name, value);
+		self
+	}
+
+	/// Sets the bearer token provider.
+	///
+	/// See [`Options::token_provider`].
+	pub fn token_provider(mut self, provider: impl TokenProvider + 'static) -> Self {
+		self.token_provider = Some(Arc::new(provider));
+		self
+	}
+
+	/// Restricts the URLs requested by the loader.
+	///
+	/// The restrictions are checked for the requested URL and each `Link`
+	/// header alternate. Unless a pre-built [`client`](Self::client) is
+	/// given, they are also checked for each HTTP redirection, and the
+	/// addresses resolved from host names are filtered with
+	/// [`Restrictions::check_address`], so that a public name resolving to a
+	/// private address cannot be reached. With a [`proxy`](Self::proxy),
+	/// host names are resolved by the proxy, which must filter the addresses
+	/// itself.
+	pub fn restrictions(mut self, restrictions: Restrictions) -> Self {
+		self.restrictions = Some(restrictions);
+		self
+	}
+
+	/// Sets the timeout of the connection phase of requests.
+	///
+	/// Ignored if a pre-built [`client`](Self::client) is given.
+	pub fn connect_timeout(mut self, value: Duration) -> Self {
+		if let Client::Settings {
+			connect_timeout, ..
+		} = &mut self.client
+		{
+			*connect_timeout = Some(value)
+		}
+		self
+	}
+
+	/// Sets the timeout of each read on the connection.
+	///
+	/// Ignored if a pre-built [`client`](Self::client) is given.
+	pub fn read_timeout(mut self, value: Duration) -> Self {
+		if let Client::Settings { read_timeout, .. } = &mut self.client {
+			*read_timeout = Some(value)
+		}
+		self
+	}
+
+	/// Sets the total timeout of each request, from the connection to the
+	/// end of the response body.
+	///
+	/// Ignored if a pre-built [`client`](Self::client) is given.
+	pub fn timeout(mut self, value: Duration) -> Self {
+		if let Client::Settings { timeout, .. } = &mut self.client {
+			*timeout = Some(value)
+		}
+		self
+	}
+
+	/// Sets the maximum number of HTTP redirections followed for each
+	/// request.
+	///
+	/// Defaults to the `reqwest` default (10). Ignored if a pre-built
+	/// [`client`](Self::client) is given.
+	pub fn max_http_redirects(mut self, value: usize) -> Self {
+		if let Client::Settings {
+			max_http_redirects, ..
+		} = &mut self.client
+		{
+			*max_http_redirects = Some(value)
+		}
+		self
+	}
+
+	/// Adds a proxy used by requests.
+	///
+	/// Ignored if a pre-built [`client`](Self::client) is given.
+	pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+		if let Client::Settings { proxies, .. } = &mut self.client {
+			proxies.push(proxy)
+		}
+		self
+	}
+
+	/// Uses the given pre-built HTTP client.
+	///
+	/// Timeouts, HTTP redirections and proxies set on this builder are then
+	/// ignored: they must be configured on the client.
+	pub fn client(self, client: reqwest::Client) -> Self {
+		self.client_with_middleware(reqwest_middleware::ClientBuilder::new(client).build())
+	}
+
+	/// Uses the given pre-built HTTP client, with middlewares.
+	///
+	/// See [`Self::client`].
+	pub fn client_with_middleware(mut self, client: ClientWithMiddleware) -> Self {
+		self.client = Client::Custom(client);
+		self
+	}
+
+	/// Builds the loader.
+	///
+	/// Fails if the HTTP client cannot be built.
+	pub fn build(self) -> Result<ReqwestLoader, reqwest::Error> {
+		let client = match self.client {
+			Client::Settings {
+				connect_timeout,
+				read_timeout,
+				timeout,
+				max_http_redirects,
+				proxies,
+			} => {
+				let mut builder = reqwest::Client::builder();
+
+				if let Some(value) = connect_timeout {
+					builder = builder.connect_timeout(value)
+				}
+
+				if let Some(value) = read_timeout {
+					builder = builder.read_timeout(value)
+				}
+
+				if let Some(value) = timeout {
+					builder = builder.timeout(value)
+				}
+
+				if self.restrictions.is_some() || !self.headers.is_empty() {
+					builder = builder.redirect(redirect_policy(
+						self.restrictions.clone(),
+						self.headers.keys().cloned().collect(),
+						max_http_redirects.unwrap_or(DEFAULT_MAX_HTTP_REDIRECTS),
+					));
+				} else if let Some(value) = max_http_redirects {
+					builder = builder.redirect(redirect::Policy::limited(value))
+				}
+
+				if let Some(restrictions) = &self.restrictions {
+					if !restrictions.allows_private_addresses() && proxies.is_empty() {
+						builder =
+							builder.dns_resolver(Arc::new(RestrictedResolver(restrictions.clone())))
+					}
+				}
+
+				for proxy in proxies {
+					builder = builder.proxy(proxy)
+				}
+
+				reqwest_middleware::ClientBuilder::new(builder.build()?).build()
+			}
+			Client::Custom(client) => client,
+		};
+
+		let mut loader = ReqwestLoader::new_using(Options {
+			request_profile: self.request_profile,
+			max_redirections: self.max_redirections,
+			max_response_bytes: self.max_response_bytes,
+			cache: self.cache,
+			headers: self.headers,
+			token_provider: self.token_provider,
+			client,
+		});
+		loader.restrictions = self.restrictions;
+		Ok(loader)
+	}
+}
+
+/// Redirection policy following at most `max` redirections allowed by the
+/// given restrictions, if any.
+///
+/// Redirections leaving an origin with scoped headers are not followed, and
+/// left to the loader.
+fn redirect_policy(
+	restrictions: Option<Restrictions>,
+	scoped: HashSet<Origin>,
+	max: usize,
+) -> redirect::Policy {
+	redirect::Policy::custom(move |attempt| {
+		if attempt.previous().len() >= max {
+			return attempt.error("too many redirects");
+		}
+
+		let origin = attempt
+			.previous()
+			.first()
+			.and_then(|url| Origin::of(Iri::new(url.as_str()).ok()?));
+		let target = Iri::new(attempt.url().as_str()).ok().map(|url| {
+			let allowed = match &restrictions {
+				Some(restrictions) => restrictions.check(url),
+				None => Ok(()),
+			};
+
+			(Origin::of(url), allowed)
+		});
+
+		match target {
+			Some((target_origin, Ok(()))) => {
+				if origin.as_ref().is_some_and(|o| scoped.contains(o)) && target_origin != origin {
+					attempt.stop()
+				} else {
+					attempt.follow()
+				}
+			}
+			Some((_, Err(e))) => attempt.error(e),
+			None => attempt.error("invalid redirection URL"),
+		}
+	})
+}
+
+/// DNS resolver discarding the addresses forbidden by the restrictions.
+struct RestrictedResolver(Restrictions);
+
+impl Resolve for RestrictedResolver {
+	fn resolve(&self, name: Name) -> Resolving {
+		let restrictions = self.0.clone();
+		Box::pin(async move {
+			let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+				.await?
+				.filter(|addr| restrictions.check_address(addr.ip()).is_ok())
+				.collect();
+
+			if addrs.is_empty() {
+				return Err(restricted::Error::PrivateAddress(name.as_str().to_owned()).into());
+			}
+
+			Ok::<Addrs, Box<dyn std::error::Error + Send + Sync>>(Box::new(addrs.into_iter()))
+		})
+	}
+}
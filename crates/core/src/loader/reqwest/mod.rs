@@ -1,24 +1,30 @@
 //! Simple document and context loader based on [`reqwest`](https://crates.io/crates/reqwest)
 use crate::LoadError;
+use crate::LoadTrace;
 use crate::LoadingResult;
 use crate::Profile;
 
-use super::{Loader, RemoteDocument};
-use hashbrown::HashSet;
+use super::negotiation::{
+	self, accept_header, cache_metadata, negotiate, trace_response, Negotiated,
+};
+use super::{DynLoader, HttpCache, Loader, Restrictions, TooLarge};
+use futures::future::BoxFuture;
+use hashbrown::HashMap;
 use iref::{Iri, IriBuf};
-use json_syntax::Parse;
 use reqwest::{
-	header::{ACCEPT, CONTENT_TYPE, LINK},
+	header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, IF_NONE_MATCH, LOCATION},
 	StatusCode,
 };
 use reqwest_middleware::ClientWithMiddleware;
 use std::string::FromUtf8Error;
+use std::sync::Arc;
+use std::time::SystemTime;
 
-mod content_type;
-mod link;
+mod auth;
+mod builder;
 
-use content_type::*;
-use link::*;
+pub use auth::{Origin, TokenError, TokenProvider};
+pub use builder::ReqwestLoaderBuilder;
 
 /// Loader options.
 pub struct Options {
@@ -33,11 +39,59 @@ pub struct Options {
 	/// Defaults to 8.
 	///
 	/// Note: this only controls how many times the loader will use a `Link`
-	/// HTTP header to find the target JSON-LD document. The number of allowed
-	/// regular HTTP redirections is controlled by the HTTP
+	/// HTTP header to find the target JSON-LD document (or follow an HTTP
+	/// redirection to another origin, see [`Self::headers`]). The number of
+	/// allowed regular HTTP redirections is controlled by the HTTP
 	/// [`client`](Self::client).
 	pub max_redirections: usize,
 
+	/// Maximum size (in bytes) of a response body.
+	///
+	/// The limit applies to the body as returned by the HTTP client, that is
+	/// after decompression if the client is configured to decompress
+	/// responses. The body is read incrementally and the loader fails with
+	/// a [`TooLarge`](super::TooLarge) error as soon as it exceeds the limit
+	/// (or right away if the `Content-Length` exceeds it), so a small
+	/// compressed payload expanding into gigabytes is never fully
+	/// decompressed in memory.
+	///
+	/// Defaults to `None` (no limit).
+	pub max_response_bytes: Option<usize>,
+
+	/// HTTP cache.
+	///
+	/// If set, fresh cached documents are returned without network query,
+	/// stale documents are revalidated with an `If-None-Match` request when
+	/// they have an entity tag, and loaded documents are stored in the
+	/// cache. Documents are cached by URL and `Accept` header, so the
+	/// documents loaded with [`ReqwestLoader::load_with_profile`] (such as
+	/// remote contexts) are cached apart from the others.
+	///
+	/// Defaults to `None` (no cache).
+	pub cache: Option<Arc<HttpCache>>,
+
+	/// Extra headers sent with the requests to each origin.
+	///
+	/// Can be used to pass an API key. The headers of an origin are only
+	/// sent to this origin, and replace the ones set by the loader with the
+	/// same name. HTTP redirections to another origin are followed by the
+	/// loader itself (instead of the HTTP client) when the
+	/// [`client`](Self::client) is built by the [`ReqwestLoaderBuilder`], so
+	/// that they do not carry the headers of the previous origin.
+	///
+	/// Defaults to no headers.
+	pub headers: HashMap<Origin, HeaderMap>,
+
+	/// Bearer token provider.
+	///
+	/// If set, the provider is called before each request and the token it
+	/// returns is sent in the `Authorization` header. Like any header, it is
+	/// also sent when following `Link` header redirections: the provider
+	/// should only return a token for the URLs it is meant for.
+	///
+	/// Defaults to `None`.
+	pub token_provider: Option<Arc<dyn TokenProvider>>,
+
 	/// HTTP client.
 	pub client: ClientWithMiddleware,
 }
@@ -47,6 +101,10 @@ impl Default for Options {
 		Self {
 			request_profile: Vec::new(),
 			max_redirections: 8,
+			max_response_bytes: None,
+			cache: None,
+			headers: HashMap::new(),
+			token_provider: None,
 			client: reqwest_middleware::ClientBuilder::new(reqwest::Client::default()).build(),
 		}
 	}
@@ -70,21 +128,51 @@ pub enum Error {
 	#[error("too many redirections")]
 	TooManyRedirections,
 
+	#[error("token provider failed: {0}")]
+	Token(TokenError),
+
+	#[error("invalid token")]
+	InvalidToken,
+
 	#[error("JSON parse error: {0}")]
 	Parse(json_syntax::parse::Error<std::io::Error>),
 }
 
+impl From<negotiation::Error> for Error {
+	fn from(value: negotiation::Error) -> Self {
+		match value {
+			negotiation::Error::InvalidContentType => Self::InvalidContentType,
+			negotiation::Error::MultipleContextLinkHeaders => Self::MultipleContextLinkHeaders,
+		}
+	}
+}
+
 /// `reqwest`-based loader.
 ///
 /// Only works with the [`tokio`](https://tokio.rs/) runtime.
 ///
-/// The loader will follow indirections and `Link` headers.
+/// The loader will follow indirections and `Link` headers. When a plain JSON
+/// document (`application/json` or any `+json` media type other than
+/// `application/ld+json`) is served with a `Link` header of relation type
+/// `http://www.w3.org/ns/json-ld#context`, the linked context is stored in
+/// the [`RemoteDocument::context_url`] and applied during expansion.
+///
+/// As required by the JSON-LD API, the [`RemoteDocument::url`] of a loaded
+/// document (used as its base IRI) is its final URL, after the HTTP
+/// redirections followed by the client and the `Link` header redirections.
+/// The IRI it was requested with is available through
+/// [`RemoteDocument::requested_url`].
 ///
 /// Loaded documents are not cached: a new network query is made each time
 /// an URL is loaded even if it has already been queried before.
+///
+/// A loader built with [`ReqwestLoaderBuilder::restrictions`] checks every
+/// URL it requests against the given [`Restrictions`], including the HTTP
+/// redirections and the `Link` header alternates.
 pub struct ReqwestLoader {
 	options: Options,
 	accept_header: String,
+	restrictions: Option<Restrictions>,
 }
 
 impl Default for ReqwestLoader {
@@ -99,146 +187,214 @@ impl ReqwestLoader {
 		Self::default()
 	}
 
+	/// Returns a builder to configure the loader and its HTTP client
+	/// (timeouts, redirections, proxies).
+	pub fn builder() -> ReqwestLoaderBuilder {
+		ReqwestLoaderBuilder::new()
+	}
+
 	/// Creates a new leader with the given options.
 	pub fn new_using(options: Options) -> Self {
-		let mut json_ld_params = String::new();
+		Self {
+			accept_header: accept_header(&options.request_profile),
+			options,
+			restrictions: None,
+		}
+	}
 
-		if !options.request_profile.is_empty() {
-			json_ld_params.push_str("; profile=");
+	/// Checks that the given URL is allowed by the restrictions of the
+	/// loader, if any.
+	fn check(&self, url: &Iri) -> Result<(), LoadError> {
+		match &self.restrictions {
+			Some(restrictions) => restrictions
+				.check(url)
+				.map_err(|e| LoadError::new(url.to_owned(), e)),
+			None => Ok(()),
+		}
+	}
 
-			if options.request_profile.len() > 1 {
-				json_ld_params.push('"');
-			}
+	/// Loads the document behind the given IRI, requesting the given
+	/// profiles instead of [`Options::request_profile`].
+	///
+	/// This can be used to request a specific representation of the document
+	/// (for instance [expanded](crate::StandardProfile::Expanded)). The
+	/// profiles actually returned by the server are listed in the
+	/// [`RemoteDocument::profile`] of the loaded document.
+	pub async fn load_with_profile(
+		&self,
+		url: &Iri,
+		request_profile: &[Profile],
+	) -> LoadingResult<IriBuf> {
+		self.fetch(
+			url,
+			&accept_header(request_profile),
+			self.options.cache.as_deref(),
+		)
+		.await
+	}
 
-			for (i, p) in options.request_profile.iter().enumerate() {
-				if i > 0 {
-					json_ld_params.push(' ');
-				}
+	/// Loads the document behind the given IRI using the given `Accept`
+	/// header value, and the given cache.
+	///
+	/// On failure, the error carries the trace of the load.
+	async fn fetch(
+		&self,
+		url: &Iri,
+		accept_header: &str,
+		cache: Option<&HttpCache>,
+	) -> LoadingResult<IriBuf> {
+		let mut trace = LoadTrace::default();
+		self.fetch_traced(url, accept_header, cache, &mut trace)
+			.await
+			.map_err(|e| e.with_trace(trace).rejected_by("ReqwestLoader"))
+	}
 
-				json_ld_params.push_str(p.iri().as_str());
+	/// Loads the document behind the given IRI using the given `Accept`
+	/// header value, and the given cache, recording the redirections and
+	/// responses in `trace`.
+	async fn fetch_traced(
+		&self,
+		url: &Iri,
+		accept_header: &str,
+		cache: Option<&HttpCache>,
+		trace: &mut LoadTrace,
+	) -> LoadingResult<IriBuf> {
+		let requested = url;
+		self.check(requested)?;
+
+		let mut cached = cache.and_then(|cache| cache.get(requested, accept_header));
+		if let Some(mut document) = cached.take() {
+			if !document.is_stale(SystemTime::now()) {
+				log::debug!("cached: {}", requested);
+				document.set_from_cache(true);
+				return Ok(document);
 			}
 
-			if options.request_profile.len() > 1 {
-				json_ld_params.push('"');
+			if document.cache_metadata().etag.is_some() {
+				cached = Some(document)
 			}
 		}
 
-		Self {
-			options,
-			accept_header: format!("application/ld+json{json_ld_params}, application/json"),
-		}
-	}
-}
-
-/// HTTP body parse error.
-#[derive(Debug, thiserror::Error)]
-pub enum ParseError {
-	/// Invalid encoding.
-	#[error("invalid encoding")]
-	InvalidEncoding(FromUtf8Error),
-
-	/// JSON parse error.
-	#[error("JSON parse error: {0}")]
-	Json(json_ld_syntax::parse::Error),
-}
-
-impl Loader for ReqwestLoader {
-	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
 		let mut redirection_number = 0;
-		let mut url = url.to_owned();
+		let mut url = requested.to_owned();
 		'next_url: loop {
 			if redirection_number > self.options.max_redirections {
 				return Err(LoadError::new(url.clone(), Error::TooManyRedirections));
 			}
 
 			log::debug!("downloading: {}", url);
-			let request = self
+			let mut request = self
 				.options
 				.client
 				.get(url.as_str())
-				.header(ACCEPT, &self.accept_header);
+				.header(ACCEPT, accept_header);
+
+			if let Some(headers) = Origin::of(&url).and_then(|o| self.options.headers.get(&o)) {
+				request = request.headers(headers.clone())
+			}
+
+			if let Some(provider) = &self.options.token_provider {
+				let token = provider
+					.token(&url)
+					.await
+					.map_err(|e| LoadError::new(url.clone(), Error::Token(e)))?;
+
+				if let Some(token) = token {
+					let mut value = HeaderValue::try_from(format!("Bearer {token}"))
+						.map_err(|_| LoadError::new(url.clone(), Error::InvalidToken))?;
+					value.set_sensitive(true);
+					request = request.header(AUTHORIZATION, value)
+				}
+			}
+
+			if redirection_number == 0 {
+				if let Some(etag) = cached
+					.as_ref()
+					.and_then(|d| d.cache_metadata().etag.as_ref())
+				{
+					request = request.header(IF_NONE_MATCH, etag)
+				}
+			}
 
 			let response = request
 				.send()
 				.await
 				.map_err(|e| LoadError::new(url.clone(), e))?;
 
+			// The client normalizes the requested URL (for instance adding a
+			// trailing `/` to an empty path), which is not a redirection.
+			if reqwest::Url::parse(url.as_str()).ok().as_ref() != Some(response.url()) {
+				// Regular HTTP redirections followed by the client: the final
+				// URL of the response is the base URL of the document.
+				if let Ok(final_url) = IriBuf::new(response.url().to_string()) {
+					trace.redirects.push(final_url.clone());
+					url = final_url
+				}
+			}
+
+			trace_response(trace, response.status(), response.headers());
 			match response.status() {
-				StatusCode::OK => {
-					let mut content_types = response
-						.headers()
-						.get_all(CONTENT_TYPE)
-						.into_iter()
-						.filter_map(ContentType::new);
-
-					match content_types.find(ContentType::is_json_ld) {
-						Some(content_type) => {
-							let mut context_url = None;
-							if *content_type.media_type() != "application/ld+json" {
-								for link in response.headers().get_all(LINK).into_iter() {
-									if let Some(link) = Link::new(link) {
-										if link.rel()
-											== Some(b"http://www.w3.org/ns/json-ld#context")
-										{
-											if context_url.is_some() {
-												return Err(LoadError::new(
-													url,
-													Error::MultipleContextLinkHeaders,
-												));
-											}
-
-											context_url = Some(link.href().resolved(&url));
-										}
-									}
-								}
-							}
-
-							let mut profile = HashSet::new();
-							for p in content_type
-								.profile()
-								.into_iter()
-								.flat_map(|p| p.split(|b| *b == b' '))
-							{
-								if let Ok(p) = std::str::from_utf8(p) {
-									if let Ok(iri) = Iri::new(p) {
-										profile.insert(Profile::new(iri));
-									}
-								}
-							}
-
-							let bytes = response.bytes().await.map_err(|e| {
+				StatusCode::OK => match negotiate(&url, response.headers()) {
+					Ok(Negotiated::Document(metadata)) => {
+						let bytes = match self.options.max_response_bytes {
+							Some(limit) => read_limited(&url, response, limit).await?,
+							None => response.bytes().await.map_err(|e| {
 								LoadError::new(url.clone(), Error::Reqwest(e.into()))
-							})?;
-
-							let decoder = utf8_decode::Decoder::new(bytes.iter().copied());
-							let (document, _) = json_syntax::Value::parse_utf8(decoder)
-								.map_err(|e| LoadError::new(url.clone(), Error::Parse(e)))?;
-
-							break Ok(RemoteDocument::new_full(
-								Some(url),
-								Some(content_type.into_media_type()),
-								context_url,
-								profile,
-								document,
-							));
+							})?,
+						};
+
+						let mut document = metadata
+							.into_document(url.clone(), &bytes)
+							.map_err(|e| LoadError::new(url, Error::Parse(e)))?;
+						document.set_requested_url(requested.to_owned());
+
+						if let Some(cache) = cache {
+							cache.insert(requested.to_owned(), accept_header, document.clone())
 						}
-						None => {
-							log::debug!("no valid media type found");
-							for link in response.headers().get_all(LINK).into_iter() {
-								if let Some(link) = Link::new(link) {
-									if link.rel() == Some(b"alternate")
-										&& link.type_() == Some(b"application/ld+json")
-									{
-										log::debug!("link found");
-										url = link.href().resolved(&url);
-										redirection_number += 1;
-										continue 'next_url;
-									}
-								}
-							}
-
-							break Err(LoadError::new(url, Error::InvalidContentType));
+
+						break Ok(document);
+					}
+					Ok(Negotiated::Alternate(alternate)) => {
+						self.check(&alternate)?;
+						trace.redirects.push(alternate.clone());
+						url = alternate;
+						redirection_number += 1;
+						continue 'next_url;
+					}
+					Err(e) => break Err(LoadError::new(url, Error::from(e))),
+				},
+				StatusCode::NOT_MODIFIED if cached.is_some() => {
+					log::debug!("not modified: {}", url);
+					let mut document = cached.take().unwrap();
+					document
+						.cache
+						.revalidate(cache_metadata(response.headers()));
+
+					if let Some(cache) = cache {
+						cache.insert(requested.to_owned(), accept_header, document.clone())
+					}
+
+					document.set_from_cache(true);
+					break Ok(document);
+				}
+				code if code.is_redirection() => {
+					// Redirection to another origin, left to the loader by the
+					// client so that it does not carry the scoped headers.
+					let location = response
+						.headers()
+						.get(LOCATION)
+						.and_then(|value| value.to_str().ok())
+						.and_then(|value| iref::IriRef::new(value).ok());
+
+					match location {
+						Some(location) => {
+							let location = location.resolved(&url);
+							self.check(&location)?;
+							trace.redirects.push(location.clone());
+							url = location;
+							redirection_number += 1;
 						}
+						None => break Err(LoadError::new(url, Error::QueryFailed(code))),
 					}
 				}
 				code => break Err(LoadError::new(url, Error::QueryFailed(code))),
@@ -246,3 +402,320 @@ impl Loader for ReqwestLoader {
 		}
 	}
 }
+
+/// HTTP body parse error.
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+	/// Invalid encoding.
+	#[error("invalid encoding")]
+	InvalidEncoding(FromUtf8Error),
+
+	/// JSON parse error.
+	#[error("JSON parse error: {0}")]
+	Json(json_ld_syntax::parse::Error),
+}
+
+impl Loader for ReqwestLoader {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.fetch(url, &self.accept_header, self.options.cache.as_deref())
+			.await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.fetch(url, &accept_header(profile), self.options.cache.as_deref())
+			.await
+	}
+}
+
+impl DynLoader for ReqwestLoader {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(Loader::load(self, url))
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(Loader::load_with_profile(self, url, profile))
+	}
+}
+
+/// Reads the body of the given response to a request on `url`, failing if
+/// it is longer than `limit` bytes.
+async fn read_limited(
+	url: &IriBuf,
+	mut response: reqwest::Response,
+	limit: usize,
+) -> Result<bytes::Bytes, LoadError> {
+	let too_large = || LoadError::new(url.clone(), TooLarge(limit as u64));
+
+	if response
+		.content_length()
+		.is_some_and(|len| len > limit as u64)
+	{
+		return Err(too_large());
+	}
+
+	let mut body = Vec::new();
+	while let Some(chunk) = response
+		.chunk()
+		.await
+		.map_err(|e| LoadError::new(url.clone(), Error::Reqwest(e.into())))?
+	{
+		if body.len() + chunk.len() > limit {
+			return Err(too_large());
+		}
+
+		body.extend_from_slice(&chunk)
+	}
+
+	Ok(body.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture::http::{Response, Server};
+	use static_iref::iri;
+	use std::time::{Duration, Instant};
+
+	/// Returns the HTTP client error causing the given load error, if any.
+	fn client_error(error: &LoadError) -> Option<&reqwest::Error> {
+		match error.cause.downcast_ref() {
+			Some(reqwest_middleware::Error::Reqwest(e)) => Some(e),
+			_ => None,
+		}
+	}
+
+	#[tokio::test]
+	async fn cache() {
+		let server = Server::start(|request| match request.path.as_str() {
+			"/fresh" => Response::json_ld("{}").header("Cache-Control", "max-age=60"),
+			"/no-store" => Response::json_ld("{}")
+				.header("Cache-Control", "no-store, max-age=60")
+				.header("ETag", "\"v1\""),
+			_ if request.header("If-None-Match") == Some("\"v1\"") => Response::new(304),
+			_ => Response::json_ld("{}")
+				.header("Cache-Control", "no-cache")
+				.header("ETag", "\"v1\""),
+		});
+
+		let loader = ReqwestLoader::builder()
+			.cache(Arc::new(HttpCache::new()))
+			.build()
+			.unwrap();
+
+		for (path, from_cache) in [
+			("/fresh", true),
+			("/no-store", false),
+			("/revalidated", true),
+		] {
+			let url = server.url(path);
+			assert!(!loader.load(&url).await.unwrap().is_from_cache());
+			assert_eq!(loader.load(&url).await.unwrap().is_from_cache(), from_cache);
+		}
+
+		let requests: Vec<String> = server
+			.requests()
+			.iter()
+			.map(|r| format!("{} {}", r.path, r.header("If-None-Match").unwrap_or("-")))
+			.collect();
+
+		assert_eq!(
+			requests,
+			[
+				"/fresh -",
+				"/no-store -",
+				"/no-store -",
+				"/revalidated -",
+				"/revalidated \"v1\""
+			]
+		)
+	}
+
+	#[tokio::test]
+	async fn redirect() {
+		let server = Server::start(|request| match request.path.as_str() {
+			"/old" => Response::redirect("/new"),
+			_ => Response::json_ld("{}"),
+		});
+
+		let loader = ReqwestLoader::new();
+		let document = loader.load(&server.url("/old")).await.unwrap();
+		assert!(document.is_redirected());
+		assert_eq!(document.url(), Some(&server.url("/new")));
+		assert_eq!(document.requested_url(), Some(&server.url("/old")));
+
+		// The client adds the missing `/` path, which is not a redirection.
+		let document = loader.load(&server.url("")).await.unwrap();
+		assert!(!document.is_redirected());
+		assert_eq!(document.url(), Some(&server.url("")));
+	}
+
+	#[tokio::test]
+	async fn max_response_bytes() {
+		let body = r#"{ "@context": { "name": "http://schema.org/name" } }"#;
+		let server = Server::start(move |request| match request.path.as_str() {
+			"/streamed" => Response::json_ld(body).without_content_length(),
+			_ => Response::json_ld(body),
+		});
+
+		let loader = ReqwestLoader::builder()
+			.max_response_bytes(16)
+			.build()
+			.unwrap();
+
+		for path in ["/sized", "/streamed"] {
+			let error = loader.load(&server.url(path)).await.unwrap_err();
+			assert!(error.is_too_large())
+		}
+
+		let loader = ReqwestLoader::builder()
+			.max_response_bytes(body.len())
+			.build()
+			.unwrap();
+		assert!(loader.load(&server.url("/streamed")).await.is_ok())
+	}
+
+	#[tokio::test]
+	async fn max_response_bytes_decompressed() {
+		use flate2::{write::GzEncoder, Compression};
+		use std::io::Write;
+
+		// A few kilobytes of gzip expanding into a 4MiB document.
+		let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+		encoder.write_all(b"{\"padding\": \"").unwrap();
+		encoder.write_all(&vec![b' '; 4 * 1024 * 1024]).unwrap();
+		encoder.write_all(b"\"}").unwrap();
+		let bomb = encoder.finish().unwrap();
+		assert!(bomb.len() < 64 * 1024);
+
+		let server = Server::start(move |_| {
+			Response::json_ld("")
+				.header("Content-Encoding", "gzip")
+				.body(bomb.clone())
+		});
+
+		let loader = ReqwestLoader::builder()
+			.max_response_bytes(64 * 1024)
+			.build()
+			.unwrap();
+		let error = loader.load(&server.url("/bomb")).await.unwrap_err();
+		assert!(error.is_too_large())
+	}
+
+	#[tokio::test]
+	async fn scoped_headers() {
+		let other = Server::start(|_| Response::json_ld("{}"));
+		let location = other.url("/new").to_string();
+		let server = Server::start(move |_| Response::redirect(&location));
+
+		let loader = ReqwestLoader::builder()
+			.header(
+				Origin::of(&server.url("/")).unwrap(),
+				reqwest::header::HeaderName::from_static("x-api-key"),
+				HeaderValue::from_static("secret"),
+			)
+			.build()
+			.unwrap();
+
+		let document = loader.load(&server.url("/old")).await.unwrap();
+		assert_eq!(document.url(), Some(&other.url("/new")));
+		assert_eq!(server.requests()[0].header("x-api-key"), Some("secret"));
+		assert_eq!(other.requests()[0].header("x-api-key"), None);
+	}
+
+	#[tokio::test]
+	async fn timeouts() {
+		let server = Server::start(|_| {
+			std::thread::sleep(Duration::from_secs(2));
+			Response::json_ld("{}")
+		});
+
+		for builder in [
+			ReqwestLoader::builder().timeout(Duration::from_millis(100)),
+			ReqwestLoader::builder().read_timeout(Duration::from_millis(100)),
+		] {
+			let loader = builder.build().unwrap();
+			let start = Instant::now();
+			let error = loader.load(&server.url("/slow")).await.unwrap_err();
+			assert!(client_error(&error).is_some_and(reqwest::Error::is_timeout));
+			assert!(start.elapsed() < Duration::from_secs(1))
+		}
+
+		// Nothing listens on port 9 of this documentation address, and the
+		// connection attempt is dropped.
+		let loader = ReqwestLoader::builder()
+			.connect_timeout(Duration::from_millis(100))
+			.build()
+			.unwrap();
+		let start = Instant::now();
+		let error = loader
+			.load(iri!("http://192.0.2.1:9/context.jsonld"))
+			.await
+			.unwrap_err();
+		assert!(client_error(&error).is_some());
+		assert!(start.elapsed() < Duration::from_secs(1))
+	}
+
+	#[tokio::test]
+	async fn max_http_redirects() {
+		// `/n` redirects to `/n-1`, and `/0` serves the document.
+		let server = Server::start(|request| match request.path[1..].parse::<u32>() {
+			Ok(n) if n > 0 => Response::redirect(&format!("/{}", n - 1)),
+			_ => Response::json_ld("{}"),
+		});
+
+		let loader = ReqwestLoader::builder()
+			.max_http_redirects(2)
+			.build()
+			.unwrap();
+		assert!(loader.load(&server.url("/2")).await.is_ok());
+
+		let error = loader.load(&server.url("/3")).await.unwrap_err();
+		assert!(client_error(&error).is_some_and(reqwest::Error::is_redirect))
+	}
+
+	#[tokio::test]
+	async fn proxy() {
+		let proxy = Server::start(|_| Response::json_ld("{}"));
+
+		let loader = ReqwestLoader::builder()
+			.proxy(reqwest::Proxy::http(proxy.url("").as_str()).unwrap())
+			.build()
+			.unwrap();
+		loader
+			.load(iri!("http://example.org/context.jsonld"))
+			.await
+			.unwrap();
+
+		// Requests sent to a proxy carry the absolute URL.
+		assert_eq!(
+			proxy.requests()[0].path,
+			"http://example.org/context.jsonld"
+		)
+	}
+
+	#[tokio::test]
+	async fn client() {
+		let server = Server::start(|_| Response::json_ld("{}"));
+
+		let client = reqwest::Client::builder()
+			.user_agent("custom-client")
+			.build()
+			.unwrap();
+		let loader = ReqwestLoader::builder()
+			.timeout(Duration::ZERO)
+			.client(client)
+			.build()
+			.unwrap();
+
+		// The timeout set on the builder is ignored.
+		loader.load(&server.url("/context.jsonld")).await.unwrap();
+		assert_eq!(
+			server.requests()[0].header("User-Agent"),
+			Some("custom-client")
+		)
+	}
+}
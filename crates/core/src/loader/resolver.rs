@@ -0,0 +1,148 @@
+//! Resolution of IRIs that are not HTTP URLs, such as DIDs.
+use super::Loader;
+use crate::{Context, LoadingResult, Profile};
+use iref::{Iri, IriBuf};
+use std::sync::Arc;
+
+/// Resolver of the IRIs of a given scheme.
+///
+/// Many DID documents and verifiable credentials contexts are identified by
+/// `did:` URIs, that cannot be fetched over HTTP. Implement this trait to
+/// plug a DID resolver (or the resolver of any other scheme) in a
+/// [`ResolverLoader`].
+pub trait Resolver {
+	/// Scheme of the IRIs resolved by this resolver, such as `did`.
+	///
+	/// The scheme is compared with the scheme of loaded IRIs ignoring case.
+	fn scheme(&self) -> &str;
+
+	/// Resolves the document identified by the given IRI.
+	#[allow(async_fn_in_trait)]
+	async fn resolve(&self, url: &Iri) -> LoadingResult<IriBuf>;
+}
+
+impl<'r, R: Resolver> Resolver for &'r R {
+	fn scheme(&self) -> &str {
+		R::scheme(self)
+	}
+
+	async fn resolve(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		R::resolve(self, url).await
+	}
+}
+
+/// Loader delegating the IRIs of a given scheme to a [`Resolver`].
+///
+/// IRIs with the scheme of the resolver are resolved by the resolver, other
+/// IRIs (such as HTTP IRIs) are loaded by the inner loader. Resolvers of
+/// different schemes can be stacked by nesting resolver loaders.
+///
+/// ```
+/// use iref::{Iri, IriBuf};
+/// use json_ld_core::loader::{NoLoader, Resolver, ResolverLoader};
+/// use json_ld_core::{LoadingResult, RemoteDocument};
+///
+/// struct DidResolver;
+///
+/// impl Resolver for DidResolver {
+///   fn scheme(&self) -> &str {
+///     "did"
+///   }
+///
+///   async fn resolve(&self, url: &Iri) -> LoadingResult<IriBuf> {
+///     // ... resolve the DID document ...
+///     let document = json_syntax::Value::Object(Default::default());
+///     Ok(RemoteDocument::new(Some(url.to_owned()), None, document))
+///   }
+/// }
+///
+/// let loader = ResolverLoader::new(DidResolver, NoLoader);
+/// ```
+pub struct ResolverLoader<R, L> {
+	resolver: R,
+	inner: L,
+}
+
+impl<R, L> ResolverLoader<R, L> {
+	/// Creates a new loader resolving the IRIs of the scheme of `resolver`
+	/// with it, and loading other IRIs with `inner`.
+	pub fn new(resolver: R, inner: L) -> Self {
+		Self { resolver, inner }
+	}
+
+	/// Returns the resolver.
+	pub fn resolver(&self) -> &R {
+		&self.resolver
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+}
+
+impl<R: Resolver, L> ResolverLoader<R, L> {
+	/// Checks if the given IRI is handled by the resolver.
+	fn resolves(&self, url: &Iri) -> bool {
+		url.scheme()
+			.as_str()
+			.eq_ignore_ascii_case(self.resolver.scheme())
+	}
+}
+
+impl<R: Resolver, L: Loader> Loader for ResolverLoader<R, L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		if self.resolves(url) {
+			self.resolver.resolve(url).await
+		} else {
+			self.inner.load(url).await
+		}
+	}
+
+	/// The profiles are only forwarded to the inner loader, the resolver
+	/// ignoring them.
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		if self.resolves(url) {
+			self.resolver.resolve(url).await
+		} else {
+			self.inner.load_with_profile(url, profile).await
+		}
+	}
+
+	/// Resolved IRIs have no processed context.
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		if self.resolves(url) {
+			None
+		} else {
+			self.inner.processed_context(url)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, NoLoader};
+	use static_iref::iri;
+
+	struct DidResolver;
+
+	impl Resolver for DidResolver {
+		fn scheme(&self) -> &str {
+			"did"
+		}
+
+		async fn resolve(&self, url: &Iri) -> LoadingResult {
+			Ok(fixture::document(url, &format!("\"{url}\"")))
+		}
+	}
+
+	#[async_std::test]
+	async fn scheme() {
+		let loader = ResolverLoader::new(DidResolver, NoLoader);
+
+		let document = loader.load(iri!("DID:example:123")).await.unwrap();
+		assert_eq!(document.document().as_str(), Some("DID:example:123"));
+		assert!(loader.load(iri!("https://example.org/")).await.is_err())
+	}
+}
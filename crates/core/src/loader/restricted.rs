@@ -0,0 +1,485 @@
+//! Loader restricting the loaded URLs.
+use super::{DynLoader, Loader, RemoteDocument};
+use crate::{Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+
+/// Loading error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The URL scheme is not allowed.
+	#[error("forbidden URL scheme `{0}`")]
+	ForbiddenScheme(String),
+
+	/// The URL has no host, or its host is not allowed.
+	#[error("forbidden host `{0}`")]
+	ForbiddenHost(String),
+
+	/// The URL host is a loopback, private or link-local address.
+	#[error("forbidden private address `{0}`")]
+	PrivateAddress(String),
+}
+
+/// URL restrictions of a [`RestrictedLoader`].
+///
+/// By default:
+///   - only the `http` and `https` schemes are allowed,
+///   - any host is allowed, unless [`Self::allow_host`] is used to
+///     allowlist hosts,
+///   - `localhost` and loopback, private, link-local and unspecified IP
+///     addresses are rejected, unless
+///     [`Self::set_allow_private_addresses`] is used.
+///
+/// IPv4 hosts are parsed like browsers do, so that the decimal, octal,
+/// hexadecimal and shortened forms of a private address (`2130706433`,
+/// `0x7f.1`, `127.1`) are also rejected.
+#[derive(Debug, Clone)]
+pub struct Restrictions {
+	schemes: HashSet<String>,
+	hosts: HashSet<String>,
+	allow_private_addresses: bool,
+}
+
+impl Default for Restrictions {
+	fn default() -> Self {
+		Self {
+			schemes: ["http".to_owned(), "https".to_owned()].into(),
+			hosts: HashSet::new(),
+			allow_private_addresses: false,
+		}
+	}
+}
+
+impl Restrictions {
+	/// Creates the default restrictions.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Allows the given URL scheme (case insensitive).
+	pub fn allow_scheme(&mut self, scheme: &str) {
+		self.schemes.insert(scheme.to_ascii_lowercase());
+	}
+
+	/// Disallows the given URL scheme (case insensitive).
+	pub fn deny_scheme(&mut self, scheme: &str) {
+		self.schemes.remove(&scheme.to_ascii_lowercase());
+	}
+
+	/// Adds the given host to the allowlist (case insensitive).
+	///
+	/// Once a host is allowlisted, URLs with any other host are rejected.
+	pub fn allow_host(&mut self, host: &str) {
+		self.hosts.insert(host.to_ascii_lowercase());
+	}
+
+	/// Sets whether `localhost` and loopback, private, link-local and
+	/// unspecified IP addresses are allowed.
+	pub fn set_allow_private_addresses(&mut self, value: bool) {
+		self.allow_private_addresses = value
+	}
+
+	/// Checks if `localhost` and loopback, private, link-local and
+	/// unspecified IP addresses are allowed.
+	pub fn allows_private_addresses(&self) -> bool {
+		self.allow_private_addresses
+	}
+
+	/// Checks that the given URL can be loaded.
+	pub fn check(&self, url: &Iri) -> Result<(), Error> {
+		let scheme = url.scheme().as_str().to_ascii_lowercase();
+		if !self.schemes.contains(&scheme) {
+			return Err(Error::ForbiddenScheme(scheme));
+		}
+
+		let host = match url.authority() {
+			Some(authority) => authority.host().as_str().to_ascii_lowercase(),
+			None => String::new(),
+		};
+
+		if host.is_empty() || (!self.hosts.is_empty() && !self.hosts.contains(&host)) {
+			return Err(Error::ForbiddenHost(host));
+		}
+
+		if !self.allow_private_addresses && is_private_host(&host) {
+			return Err(Error::PrivateAddress(host));
+		}
+
+		Ok(())
+	}
+
+	/// Checks that the given address, resolved from the host of an URL, can
+	/// be connected to.
+	pub fn check_address(&self, ip: IpAddr) -> Result<(), Error> {
+		if !self.allow_private_addresses && is_private_ip(ip) {
+			return Err(Error::PrivateAddress(ip.to_string()));
+		}
+
+		Ok(())
+	}
+}
+
+/// Loader rejecting the URLs that are not explicitly allowed by its
+/// [`Restrictions`] before delegating to another loader.
+///
+/// This protects services expanding untrusted JSON-LD documents against
+/// server-side request forgery.
+///
+/// The inner loader follows the HTTP redirections and `Link` header
+/// alternates itself: this loader can only reject the loaded document
+/// afterwards if its final URL is not allowed. Host names are not resolved
+/// either: a public name resolving to a private address is not detected.
+/// To enforce the restrictions on each request, redirections and resolved
+/// addresses included, give them to the HTTP loader itself, for instance
+/// with [`ReqwestLoaderBuilder::restrictions`](super::reqwest::ReqwestLoaderBuilder::restrictions).
+///
+/// ```
+/// use json_ld_core::loader::{NoLoader, RestrictedLoader};
+///
+/// let mut loader = RestrictedLoader::new(NoLoader);
+/// loader.allow_host("schema.org");
+/// loader.allow_host("www.w3.org");
+/// ```
+pub struct RestrictedLoader<L> {
+	inner: L,
+	restrictions: Restrictions,
+}
+
+impl<L> RestrictedLoader<L> {
+	/// Creates a new loader restricting the loads of `inner` with the default
+	/// rules.
+	pub fn new(inner: L) -> Self {
+		Self::with_restrictions(inner, Restrictions::default())
+	}
+
+	/// Creates a new loader restricting the loads of `inner` with the given
+	/// rules.
+	pub fn with_restrictions(inner: L, restrictions: Restrictions) -> Self {
+		Self {
+			inner,
+			restrictions,
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns the restrictions.
+	pub fn restrictions(&self) -> &Restrictions {
+		&self.restrictions
+	}
+
+	/// Allows the given URL scheme (case insensitive).
+	pub fn allow_scheme(&mut self, scheme: &str) {
+		self.restrictions.allow_scheme(scheme)
+	}
+
+	/// Disallows the given URL scheme (case insensitive).
+	pub fn deny_scheme(&mut self, scheme: &str) {
+		self.restrictions.deny_scheme(scheme)
+	}
+
+	/// Adds the given host to the allowlist (case insensitive).
+	///
+	/// Once a host is allowlisted, URLs with any other host are rejected.
+	pub fn allow_host(&mut self, host: &str) {
+		self.restrictions.allow_host(host)
+	}
+
+	/// Sets whether `localhost` and loopback, private, link-local and
+	/// unspecified IP addresses are allowed.
+	pub fn set_allow_private_addresses(&mut self, value: bool) {
+		self.restrictions.set_allow_private_addresses(value)
+	}
+
+	/// Checks that the given URL can be loaded.
+	pub fn check(&self, url: &Iri) -> Result<(), Error> {
+		self.restrictions.check(url)
+	}
+
+	/// Checks that the final URL of the given loaded document is allowed.
+	fn check_loaded(&self, url: &Iri, document: RemoteDocument) -> LoadingResult<IriBuf> {
+		if let Some(final_url) = document.url() {
+			self.check(final_url)
+				.map_err(|e| LoadError::new(url.to_owned(), e).rejected_by("RestrictedLoader"))?;
+		}
+
+		Ok(document)
+	}
+}
+
+impl<L: Loader> Loader for RestrictedLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.check(url)
+			.map_err(|e| LoadError::new(url.to_owned(), e).rejected_by("RestrictedLoader"))?;
+		let document = self.inner.load(url).await?;
+		self.check_loaded(url, document)
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.check(url)
+			.map_err(|e| LoadError::new(url.to_owned(), e).rejected_by("RestrictedLoader"))?;
+		let document = self.inner.load_with_profile(url, profile).await?;
+		self.check_loaded(url, document)
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.check(url)
+			.ok()
+			.and_then(|()| self.inner.processed_context(url))
+	}
+}
+
+impl<L: DynLoader> DynLoader for RestrictedLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			self.check(url)
+				.map_err(|e| LoadError::new(url.to_owned(), e).rejected_by("RestrictedLoader"))?;
+			let document = self.inner.load_dyn(url).await?;
+			self.check_loaded(url, document)
+		})
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			self.check(url)
+				.map_err(|e| LoadError::new(url.to_owned(), e).rejected_by("RestrictedLoader"))?;
+			let document = self.inner.load_with_profile_dyn(url, profile).await?;
+			self.check_loaded(url, document)
+		})
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.check(url)
+			.ok()
+			.and_then(|()| self.inner.processed_context_dyn(url))
+	}
+}
+
+/// Checks if the given (lowercase) host is `localhost` or a non-public IP
+/// address.
+fn is_private_host(host: &str) -> bool {
+	let host = percent_decode(host);
+	let host = host.strip_suffix('.').unwrap_or(&host);
+	if host == "localhost" || host.ends_with(".localhost") {
+		return true;
+	}
+
+	let ip = match host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+		Some(ipv6) => ipv6.parse().ok().map(IpAddr::V6),
+		None => parse_ipv4(host).map(IpAddr::V4),
+	};
+
+	ip.is_some_and(is_private_ip)
+}
+
+/// Decodes the percent-encoded bytes of the given host, as done by browsers
+/// before parsing it.
+fn percent_decode(host: &str) -> String {
+	let bytes = host.as_bytes();
+	let mut result = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		let decoded = match bytes[i] {
+			b'%' if i + 2 < bytes.len()
+				&& bytes[i + 1].is_ascii_hexdigit()
+				&& bytes[i + 2].is_ascii_hexdigit() =>
+			{
+				std::str::from_utf8(&bytes[i + 1..i + 3])
+					.ok()
+					.and_then(|hex| u8::from_str_radix(hex, 16).ok())
+			}
+			_ => None,
+		};
+
+		match decoded {
+			Some(b) => {
+				result.push(b);
+				i += 3
+			}
+			None => {
+				result.push(bytes[i]);
+				i += 1
+			}
+		}
+	}
+
+	String::from_utf8_lossy(&result).to_ascii_lowercase()
+}
+
+/// Parses the given host as an IPv4 address following the [WHATWG URL
+/// standard][1], which accepts one to four decimal, octal (`0` prefix) or
+/// hexadecimal (`0x` prefix) parts, the last one filling the remaining
+/// bytes.
+///
+/// [1]: https://url.spec.whatwg.org/#concept-ipv4-parser
+fn parse_ipv4(host: &str) -> Option<Ipv4Addr> {
+	let mut parts: Vec<&str> = host.split('.').collect();
+	if parts.len() > 1 && parts.last() == Some(&"") {
+		parts.pop();
+	}
+
+	if parts.len() > 4 {
+		return None;
+	}
+
+	let numbers = parts
+		.into_iter()
+		.map(parse_ipv4_number)
+		.collect::<Option<Vec<u64>>>()?;
+	let (last, init) = numbers.split_last()?;
+	if init.iter().any(|n| *n > 255) || *last >= 256u64.pow(5 - numbers.len() as u32) {
+		return None;
+	}
+
+	let mut ip = *last;
+	for (i, n) in init.iter().enumerate() {
+		ip += n << (8 * (3 - i))
+	}
+
+	Some(Ipv4Addr::from(ip as u32))
+}
+
+/// Parses a part of an IPv4 address.
+fn parse_ipv4_number(part: &str) -> Option<u64> {
+	let (digits, radix) =
+		if let Some(hex) = part.strip_prefix("0x").or_else(|| part.strip_prefix("0X")) {
+			(hex, 16)
+		} else if part.len() > 1 && part.starts_with('0') {
+			(&part[1..], 8)
+		} else {
+			(part, 10)
+		};
+
+	if digits.is_empty() {
+		// `0x` alone is zero, but an empty part is not a number.
+		return (radix == 16).then_some(0);
+	}
+
+	if !digits.chars().all(|c| c.is_digit(radix)) {
+		return None;
+	}
+
+	// Saturate, since any value that large is rejected anyway.
+	Some(u64::from_str_radix(digits, radix).unwrap_or(u64::MAX))
+}
+
+/// Checks if the given IP address is not public.
+fn is_private_ip(ip: IpAddr) -> bool {
+	match ip {
+		IpAddr::V4(ip) => is_private_ipv4(ip),
+		IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+			Some(ip) => is_private_ipv4(ip),
+			None => is_private_ipv6(ip),
+		},
+	}
+}
+
+fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+	let [a, b, ..] = ip.octets();
+	ip.is_loopback()
+		|| ip.is_private()
+		|| ip.is_link_local()
+		|| ip.is_unspecified()
+		|| ip.is_broadcast()
+		|| a == 0
+		// Shared address space (100.64.0.0/10).
+		|| (a == 100 && (b & 0xc0) == 64)
+}
+
+fn is_private_ipv6(ip: Ipv6Addr) -> bool {
+	let first = ip.segments()[0];
+	ip.is_loopback()
+		|| ip.is_unspecified()
+		// Unique local addresses (fc00::/7).
+		|| (first & 0xfe00) == 0xfc00
+		// Link-local addresses (fe80::/10).
+		|| (first & 0xffc0) == 0xfe80
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, NoLoader};
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn check() {
+		let inner = fixture::static_loader(&[(iri!("https://example.org/doc"), "{}")]);
+
+		let mut loader = RestrictedLoader::new(inner);
+		assert!(loader.load(iri!("https://example.org/doc")).await.is_ok());
+		assert!(matches!(
+			loader.check(iri!("file:///etc/passwd")),
+			Err(Error::ForbiddenScheme(_))
+		));
+		assert!(matches!(
+			loader.check(iri!("http://169.254.169.254/latest/meta-data")),
+			Err(Error::PrivateAddress(_))
+		));
+		assert!(matches!(
+			loader.check(iri!("http://[::1]:8080/")),
+			Err(Error::PrivateAddress(_))
+		));
+
+		loader.allow_host("schema.org");
+		assert!(loader.check(iri!("https://schema.org/")).is_ok());
+		assert!(matches!(
+			loader.check(iri!("https://example.org/doc")),
+			Err(Error::ForbiddenHost(_))
+		));
+	}
+
+	#[test]
+	fn ipv4_forms() {
+		let loader = RestrictedLoader::new(NoLoader);
+		for url in [
+			iri!("http://2130706433/"),
+			iri!("http://0x7f.1/"),
+			iri!("http://127.1/"),
+			iri!("http://0177.0.0.1/"),
+			iri!("http://0xA9FEA9FE/"),
+			iri!("http://127.0.0.1./"),
+			iri!("http://%31%32%37.0.0.1/"),
+		] {
+			assert!(
+				matches!(loader.check(url), Err(Error::PrivateAddress(_))),
+				"{url}"
+			)
+		}
+
+		assert!(loader.check(iri!("http://8.8.8.8/")).is_ok());
+		assert!(loader.check(iri!("http://134744072/")).is_ok());
+		assert!(loader.check(iri!("http://1.2.3.4.5/")).is_ok());
+	}
+
+	#[async_std::test]
+	async fn redirected() {
+		struct Redirecting;
+
+		impl Loader for Redirecting {
+			async fn load(&self, _url: &Iri) -> LoadingResult {
+				Ok(fixture::document(iri!("http://127.0.0.1/doc"), "{}"))
+			}
+		}
+
+		let loader = RestrictedLoader::new(Redirecting);
+		let error = loader
+			.load(iri!("https://example.org/doc"))
+			.await
+			.unwrap_err();
+		assert!(error.cause.downcast_ref::<Error>().is_some());
+		assert!(loader
+			.processed_context(iri!("http://localhost/"))
+			.is_none())
+	}
+}
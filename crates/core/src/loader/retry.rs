@@ -0,0 +1,286 @@
+//! Retry of transient load failures, and per-host rate limiting.
+use super::{DynLoader, Loader};
+use crate::{Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Retry strategy of a [`RetryLoader`].
+#[derive(Debug, Clone)]
+pub struct RetryOptions {
+	/// Maximum number of retries of a failed load.
+	///
+	/// Defaults to 3.
+	pub max_retries: u32,
+
+	/// Delay before the first retry.
+	///
+	/// Defaults to 500 milliseconds.
+	pub initial_backoff: Duration,
+
+	/// Factor by which the delay is multiplied after each retry.
+	///
+	/// Defaults to 2.
+	pub backoff_factor: f64,
+
+	/// Maximum delay before a retry.
+	///
+	/// Defaults to 30 seconds.
+	pub max_backoff: Duration,
+
+	/// HTTP statuses of the failures worth retrying.
+	///
+	/// Defaults to 429 (Too Many Requests), 502 (Bad Gateway), 503 (Service
+	/// Unavailable) and 504 (Gateway Timeout).
+	pub retry_statuses: Vec<u16>,
+
+	/// Minimum interval between two loads from the same host, if any.
+	///
+	/// Defaults to `None` (no rate limit).
+	pub min_interval_per_host: Option<Duration>,
+}
+
+impl Default for RetryOptions {
+	fn default() -> Self {
+		Self {
+			max_retries: 3,
+			initial_backoff: Duration::from_millis(500),
+			backoff_factor: 2.0,
+			max_backoff: Duration::from_secs(30),
+			retry_statuses: vec![429, 502, 503, 504],
+			min_interval_per_host: None,
+		}
+	}
+}
+
+/// Loader retrying the transient failures of another loader, with
+/// exponential backoff.
+///
+/// A failure is transient if the [trace](LoadError::trace) of the error
+/// holds one of the [`RetryOptions::retry_statuses`], as recorded by the
+/// HTTP loaders. Other failures are returned immediately. The `n`-th retry
+/// is delayed by `initial_backoff * backoff_factor^(n - 1)`, bounded by
+/// `max_backoff`. Once the retries are exhausted, the last error is
+/// returned.
+///
+/// With [`RetryOptions::min_interval_per_host`], loads (including retries)
+/// are also spaced out per host, so that a batch of documents referencing
+/// the same remote contexts does not trigger the rate limit of the server
+/// in the first place.
+///
+/// Delays are measured by the [`async_io`] timer (the one of `async-std`
+/// and `smol`), which also works with other runtimes.
+///
+/// ```
+/// use json_ld_core::loader::{NoLoader, RetryLoader, RetryOptions};
+/// use std::time::Duration;
+///
+/// let loader = RetryLoader::new(NoLoader, RetryOptions {
+///   max_retries: 5,
+///   min_interval_per_host: Some(Duration::from_millis(100)),
+///   ..Default::default()
+/// });
+/// ```
+pub struct RetryLoader<L> {
+	inner: L,
+	options: RetryOptions,
+
+	/// Earliest start of the next load, for each host.
+	next_loads: Mutex<HashMap<String, Instant>>,
+}
+
+impl<L> RetryLoader<L> {
+	/// Creates a new loader retrying the transient failures of `inner`.
+	pub fn new(inner: L, options: RetryOptions) -> Self {
+		Self {
+			inner,
+			options,
+			next_loads: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns the retry strategy.
+	pub fn options(&self) -> &RetryOptions {
+		&self.options
+	}
+
+	/// Checks if the given error is a transient failure, worth retrying.
+	pub fn is_transient(&self, error: &LoadError) -> bool {
+		error
+			.trace()
+			.and_then(|trace| trace.status)
+			.is_some_and(|status| self.options.retry_statuses.contains(&status))
+	}
+
+	/// Returns the delay before the given retry, starting from `0`.
+	fn backoff(&self, retry: u32) -> Duration {
+		let factor = self
+			.options
+			.backoff_factor
+			.powi(retry.try_into().unwrap_or(i32::MAX));
+		Duration::try_from_secs_f64(self.options.initial_backoff.as_secs_f64() * factor)
+			.map_or(self.options.max_backoff, |backoff| {
+				backoff.min(self.options.max_backoff)
+			})
+	}
+
+	/// Waits until the host of the given IRI can be loaded from, according
+	/// to the rate limit.
+	async fn throttle(&self, url: &Iri) {
+		let (Some(interval), Some(authority)) =
+			(self.options.min_interval_per_host, url.authority())
+		else {
+			return;
+		};
+
+		let wait = {
+			let mut next_loads = self.next_loads.lock().unwrap();
+			let now = Instant::now();
+			let next_load = next_loads
+				.entry(authority.host().as_str().to_ascii_lowercase())
+				.or_insert(now);
+			let start = (*next_load).max(now);
+			*next_load = start + interval;
+			start - now
+		};
+
+		if !wait.is_zero() {
+			async_io::Timer::after(wait).await;
+		}
+	}
+
+	/// Calls `load` until it succeeds, fails with a non transient error, or
+	/// the retries are exhausted.
+	async fn retry<F>(&self, url: &Iri, load: impl Fn() -> F) -> LoadingResult<IriBuf>
+	where
+		F: Future<Output = LoadingResult<IriBuf>>,
+	{
+		let mut retry = 0;
+		loop {
+			self.throttle(url).await;
+
+			match load().await {
+				Err(e) if retry < self.options.max_retries && self.is_transient(&e) => {
+					let backoff = self.backoff(retry);
+					if !backoff.is_zero() {
+						async_io::Timer::after(backoff).await;
+					}
+
+					retry += 1
+				}
+				result => break result,
+			}
+		}
+	}
+}
+
+impl<L: Loader> Loader for RetryLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.retry(url, || self.inner.load(url)).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.retry(url, || self.inner.load_with_profile(url, profile))
+			.await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for RetryLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.retry(url, move || self.inner.load_dyn(url)))
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.retry(url, move || self.inner.load_with_profile_dyn(url, profile)))
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, LoadTrace};
+	use static_iref::iri;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// Loader answering `503 Service Unavailable` to the given number of
+	/// loads, counting the loads.
+	struct Unavailable {
+		failures: usize,
+		loads: AtomicUsize,
+	}
+
+	impl Loader for Unavailable {
+		async fn load(&self, url: &Iri) -> LoadingResult {
+			if self.loads.fetch_add(1, Ordering::SeqCst) < self.failures {
+				return Err(
+					LoadError::new(url.to_owned(), std::io::Error::other("unavailable"))
+						.with_trace(LoadTrace {
+							status: Some(503),
+							..Default::default()
+						}),
+				);
+			}
+
+			Ok(fixture::document(url, "{}"))
+		}
+	}
+
+	fn retry(failures: usize, min_interval_per_host: Option<Duration>) -> RetryLoader<Unavailable> {
+		RetryLoader::new(
+			Unavailable {
+				failures,
+				loads: AtomicUsize::new(0),
+			},
+			RetryOptions {
+				max_retries: 2,
+				initial_backoff: Duration::ZERO,
+				min_interval_per_host,
+				..Default::default()
+			},
+		)
+	}
+
+	#[async_std::test]
+	async fn retries() {
+		let url = iri!("http://example.org/context.jsonld");
+
+		let loader = retry(2, None);
+		assert!(loader.load(url).await.is_ok());
+		assert_eq!(loader.inner().loads.load(Ordering::SeqCst), 3);
+
+		let loader = retry(3, None);
+		let error = loader.load(url).await.unwrap_err();
+		assert!(loader.is_transient(&error));
+		assert_eq!(loader.inner().loads.load(Ordering::SeqCst), 3);
+	}
+
+	#[async_std::test]
+	async fn rate_limit() {
+		let url = iri!("http://example.org/context.jsonld");
+		let interval = Duration::from_millis(20);
+		let loader = retry(1, Some(interval));
+		let start = Instant::now();
+		assert!(loader.load(url).await.is_ok());
+		assert!(start.elapsed() >= interval)
+	}
+}
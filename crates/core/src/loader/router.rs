@@ -0,0 +1,256 @@
+//! Dispatch of loads by IRI prefix.
+use super::Loader;
+use crate::{Context, LoadError, LoadingResult, Profile};
+use iref::{Iri, IriBuf};
+use std::sync::Arc;
+
+/// Loader dispatching each IRI to the loader registered for its prefix.
+///
+/// Routes are registered with [`Self::route`], binding an IRI prefix to a
+/// loader. Prefixes are matched like the mount points of an
+/// [`FsLoader`](super::FsLoader): the scheme and authority must be equal,
+/// and the path of the prefix must be a prefix of the path of the IRI. When
+/// several prefixes match, the longest one is used. IRIs matching no prefix
+/// are loaded by the default loader.
+///
+/// Each route has its own loader type, so online and offline loaders can be
+/// combined without writing any dispatch code:
+///
+/// ```
+/// use json_ld_core::loader::{NoLoader, RouterLoader, StaticLoader};
+/// use json_ld_core::FsLoader;
+/// use static_iref::iri;
+///
+/// let mut vendored = FsLoader::new();
+/// vendored.mount(iri!("https://example.com/").to_owned(), "contexts");
+///
+/// let loader = RouterLoader::new(NoLoader)
+///   .route(iri!("https://example.com/").to_owned(), vendored)
+///   .route(iri!("https://example.com/test/").to_owned(), StaticLoader::new());
+/// ```
+///
+/// Unlike a [`ChainLoader`](super::ChainLoader), a router never tries
+/// another loader when the selected one fails.
+pub struct RouterLoader<R, D> {
+	routes: R,
+	default: D,
+}
+
+impl<D> RouterLoader<NoRoute, D> {
+	/// Creates a new router without routes, loading every IRI with
+	/// `default`.
+	pub fn new(default: D) -> Self {
+		Self {
+			routes: NoRoute,
+			default,
+		}
+	}
+}
+
+impl<R, D> RouterLoader<R, D> {
+	/// Routes the IRIs starting with `prefix` to the given loader.
+	///
+	/// If the same prefix is registered twice, the last route is used.
+	pub fn route<L>(self, prefix: IriBuf, loader: L) -> RouterLoader<Route<L, R>, D> {
+		RouterLoader {
+			routes: Route {
+				prefix,
+				loader,
+				next: self.routes,
+			},
+			default: self.default,
+		}
+	}
+
+	/// Returns the registered routes.
+	pub fn routes(&self) -> &R {
+		&self.routes
+	}
+
+	/// Returns the default loader.
+	pub fn default_loader(&self) -> &D {
+		&self.default
+	}
+}
+
+impl<R: Routes, D: Loader> Loader for RouterLoader<R, D> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.routes.find(url) {
+			Some((_, index)) => self.routes.load_at(index, url, &[]).await,
+			None => self.default.load(url).await,
+		}
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		match self.routes.find(url) {
+			Some((_, index)) => self.routes.load_at(index, url, profile).await,
+			None => self.default.load_with_profile(url, profile).await,
+		}
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		match self.routes.find(url) {
+			Some((_, index)) => self.routes.processed_context_at(index, url),
+			None => self.default.processed_context(url),
+		}
+	}
+}
+
+/// Set of routes of a [`RouterLoader`].
+///
+/// Implemented by [`NoRoute`] and [`Route`]. Routes are indexed from the
+/// last registered one.
+pub trait Routes {
+	/// Returns the prefix length and index of the route matching the given
+	/// IRI with the longest prefix, if any.
+	fn find(&self, url: &Iri) -> Option<(usize, usize)>;
+
+	/// Loads the given IRI with the route at the given index, requesting the
+	/// given profiles (if any).
+	///
+	/// Fails with [`NoSuchRoute`] if there is no route at this index.
+	#[allow(async_fn_in_trait)]
+	async fn load_at(&self, index: usize, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf>;
+
+	/// Returns the processed context provided by the route at the given
+	/// index for the given IRI, if any.
+	///
+	/// See [`Loader::processed_context`].
+	fn processed_context_at(&self, index: usize, url: &Iri) -> Option<Arc<Context>> {
+		let _ = (index, url);
+		None
+	}
+}
+
+/// Error returned when loading with a route index out of bounds.
+#[derive(Debug, thiserror::Error)]
+#[error("no route at index {0}")]
+pub struct NoSuchRoute(pub usize);
+
+/// Empty set of routes.
+pub struct NoRoute;
+
+impl Routes for NoRoute {
+	fn find(&self, _url: &Iri) -> Option<(usize, usize)> {
+		None
+	}
+
+	async fn load_at(
+		&self,
+		index: usize,
+		url: &Iri,
+		_profile: &[Profile],
+	) -> LoadingResult<IriBuf> {
+		Err(LoadError::new(url.to_owned(), NoSuchRoute(index)).rejected_by("RouterLoader"))
+	}
+}
+
+/// Route, followed by the previously registered routes.
+pub struct Route<L, R> {
+	prefix: IriBuf,
+	loader: L,
+	next: R,
+}
+
+impl<L, R> Route<L, R> {
+	/// Returns the prefix of the route.
+	pub fn prefix(&self) -> &Iri {
+		&self.prefix
+	}
+
+	/// Returns the loader of the route.
+	pub fn loader(&self) -> &L {
+		&self.loader
+	}
+
+	/// Returns the previously registered routes.
+	pub fn next(&self) -> &R {
+		&self.next
+	}
+}
+
+impl<L: Loader, R: Routes> Routes for Route<L, R> {
+	fn find(&self, url: &Iri) -> Option<(usize, usize)> {
+		let next = self.next.find(url).map(|(len, index)| (len, index + 1));
+
+		match url.as_iri_ref().suffix(&self.prefix) {
+			Some(_) => {
+				let len = self.prefix.as_str().len();
+				match next {
+					Some((next_len, _)) if next_len > len => next,
+					_ => Some((len, 0)),
+				}
+			}
+			None => next,
+		}
+	}
+
+	async fn load_at(&self, index: usize, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		if index == 0 {
+			if profile.is_empty() {
+				self.loader.load(url).await
+			} else {
+				self.loader.load_with_profile(url, profile).await
+			}
+		} else {
+			self.next.load_at(index - 1, url, profile).await
+		}
+	}
+
+	fn processed_context_at(&self, index: usize, url: &Iri) -> Option<Arc<Context>> {
+		if index == 0 {
+			self.loader.processed_context(url)
+		} else {
+			self.next.processed_context_at(index - 1, url)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, NoLoader};
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn longest_prefix() {
+		let vendored =
+			fixture::static_loader(&[(iri!("https://example.com/a"), r#"{ "from": "vendored" }"#)]);
+		let tests = fixture::static_loader(&[(
+			iri!("https://example.com/test/a"),
+			r#"{ "from": "tests" }"#,
+		)]);
+
+		let loader = RouterLoader::new(NoLoader)
+			.route(iri!("https://example.com/test/").to_owned(), tests)
+			.route(iri!("https://example.com/").to_owned(), vendored);
+
+		let a = loader.load(iri!("https://example.com/a")).await.unwrap();
+		assert_eq!(fixture::from(&a), "vendored");
+		let test_a = loader
+			.load(iri!("https://example.com/test/a"))
+			.await
+			.unwrap();
+		assert_eq!(fixture::from(&test_a), "tests");
+
+		// Routed to the longest prefix only, and to the default loader.
+		assert!(loader
+			.load(iri!("https://example.com/test/b"))
+			.await
+			.is_err());
+		let error = loader
+			.load(iri!("https://example.org/a"))
+			.await
+			.unwrap_err();
+		assert_eq!(error.trace().unwrap().rejected_by, ["NoLoader"]);
+	}
+
+	#[async_std::test]
+	async fn no_such_route() {
+		let error = NoRoute
+			.load_at(0, iri!("https://example.com/a"), &[])
+			.await
+			.unwrap_err();
+		assert!(error.cause.is::<NoSuchRoute>())
+	}
+}
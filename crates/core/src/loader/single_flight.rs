@@ -0,0 +1,205 @@
+//! Deduplication of concurrent loads.
+use super::{DynLoader, Loader, RemoteDocument};
+use crate::{Context, LoadError, LoadTrace, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// Failure of a load shared with the concurrent loads of the same document.
+///
+/// Only the load that actually called the inner loader gets the original
+/// error. The concurrent loads waiting for it get this error instead, holding
+/// the message of the original error, along with its trace.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{0}")]
+pub struct SharedLoadError(pub String);
+
+/// Outcome of a load, shared with the concurrent loads of the same document.
+type Outcome = Result<RemoteDocument, (SharedLoadError, Option<LoadTrace>)>;
+
+/// Slot receiving the outcome of a load in flight.
+type Slot = Arc<futures::lock::Mutex<Option<Outcome>>>;
+
+/// Loader merging concurrent loads of the same document.
+///
+/// When many tasks share a loader, the same context is often requested by
+/// several of them at once. This loader only calls the inner loader once per
+/// IRI (and [requested profiles](Loader::load_with_profile)) in flight:
+/// concurrent loads of the same IRI wait for this call and get a copy of its
+/// result.
+///
+/// Results are not kept once the load completes, so later loads call the
+/// inner loader again. Combine this loader with a cache to also avoid
+/// sequential loads.
+///
+/// ```
+/// use json_ld_core::loader::{NoLoader, SingleFlightLoader};
+///
+/// let loader = SingleFlightLoader::new(NoLoader);
+/// ```
+pub struct SingleFlightLoader<L> {
+	inner: L,
+	in_flight: Mutex<HashMap<(IriBuf, Vec<Profile>), Slot>>,
+}
+
+impl<L> SingleFlightLoader<L> {
+	/// Creates a new loader merging the concurrent loads made to `inner`.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			in_flight: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns the number of IRIs currently being loaded.
+	pub fn in_flight(&self) -> usize {
+		self.in_flight.lock().unwrap().len()
+	}
+}
+
+impl<L> SingleFlightLoader<L> {
+	/// Runs the given load of `url` with the given profiles, unless a
+	/// concurrent load of the same document completes first.
+	async fn merge(
+		&self,
+		url: &Iri,
+		profile: &[Profile],
+		load: impl Future<Output = LoadingResult<IriBuf>>,
+	) -> LoadingResult<IriBuf> {
+		let key = (url.to_owned(), profile.to_vec());
+		let slot = self
+			.in_flight
+			.lock()
+			.unwrap()
+			.entry(key.clone())
+			.or_insert_with(|| Arc::new(futures::lock::Mutex::new(None)))
+			.clone();
+
+		let mut outcome = slot.lock().await;
+		if let Some(result) = outcome.as_ref() {
+			return result.clone().map_err(|(e, trace)| {
+				let error = LoadError::new(url.to_owned(), e);
+				match trace {
+					Some(trace) => error.with_trace(trace),
+					None => error,
+				}
+			});
+		}
+
+		// No load completed before this one: call the inner loader. If this
+		// load is cancelled, the next waiting load takes over.
+		let result = load.await;
+		*outcome = Some(match &result {
+			Ok(document) => Ok(document.clone()),
+			Err(e) => Err((SharedLoadError(e.cause.to_string()), e.trace().cloned())),
+		});
+
+		let mut in_flight = self.in_flight.lock().unwrap();
+		if in_flight
+			.get(&key)
+			.is_some_and(|current| Arc::ptr_eq(current, &slot))
+		{
+			in_flight.remove(&key);
+		}
+
+		result
+	}
+}
+
+impl<L: Loader> Loader for SingleFlightLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.merge(url, &[], self.inner.load(url)).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.merge(url, profile, self.inner.load_with_profile(url, profile))
+			.await
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for SingleFlightLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.merge(url, &[], self.inner.load_dyn(url)))
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(self.merge(url, profile, self.inner.load_with_profile_dyn(url, profile)))
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// Slow loader counting the loads.
+	#[derive(Default)]
+	struct Slow(AtomicUsize);
+
+	impl Loader for Slow {
+		async fn load(&self, url: &Iri) -> LoadingResult {
+			self.0.fetch_add(1, Ordering::SeqCst);
+			async_std::task::sleep(std::time::Duration::from_millis(50)).await;
+			Ok(fixture::document(url, "{}"))
+		}
+	}
+
+	#[async_std::test]
+	async fn merge_concurrent_loads() {
+		let loader = SingleFlightLoader::new(Slow::default());
+		let url = iri!("http://example.org/context.jsonld");
+
+		let (a, b) = futures::join!(loader.load(url), loader.load(url));
+		assert_eq!(a.unwrap().document(), b.unwrap().document());
+		assert_eq!(loader.inner().0.load(Ordering::SeqCst), 1);
+		assert_eq!(loader.in_flight(), 0);
+
+		loader.load(url).await.unwrap();
+		assert_eq!(loader.inner().0.load(Ordering::SeqCst), 2);
+	}
+
+	/// Slow loader rejecting every IRI.
+	struct Rejecting;
+
+	impl Loader for Rejecting {
+		async fn load(&self, url: &Iri) -> LoadingResult {
+			async_std::task::sleep(std::time::Duration::from_millis(50)).await;
+			Err(
+				LoadError::new(url.to_owned(), SharedLoadError("rejected".to_owned()))
+					.rejected_by("Rejecting"),
+			)
+		}
+	}
+
+	#[async_std::test]
+	async fn shared_trace() {
+		let loader = SingleFlightLoader::new(Rejecting);
+		let url = iri!("http://example.org/context.jsonld");
+
+		let (a, b) = futures::join!(loader.load(url), loader.load(url));
+		for error in [a.unwrap_err(), b.unwrap_err()] {
+			assert_eq!(error.trace().unwrap().rejected_by, ["Rejecting"])
+		}
+	}
+}
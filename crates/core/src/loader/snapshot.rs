@@ -0,0 +1,326 @@
+//! Record and replay of loaded documents.
+//!
+//! Processing a document may require many remote contexts. The
+//! [`RecordingLoader`] captures every document loaded during a run into a
+//! [`Snapshot`], that can be saved to a file and served offline by a
+//! [`ReplayLoader`]. This makes test runs and bug reproductions independent
+//! of the network.
+use super::file_cache::{decode_entry, encode_entry};
+use super::{DynLoader, Loader, RemoteDocument};
+use crate::{Context, LoadError, LoadingResult, Profile};
+use futures::future::BoxFuture;
+use iref::{Iri, IriBuf};
+use json_syntax::{Parse, Print};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Snapshot reading error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The snapshot file could not be read.
+	#[error("unable to read snapshot: {0}")]
+	IO(std::io::Error),
+
+	/// The snapshot is not a valid JSON array of entries.
+	#[error("invalid snapshot")]
+	Invalid,
+}
+
+/// Set of loaded documents, indexed by the IRI they were loaded from and the
+/// [profiles they were requested with](Loader::load_with_profile).
+///
+/// A snapshot is serialized as a JSON array of entries, sorted by IRI, each
+/// holding the document along with its URL, content type, context URL and
+/// profiles.
+#[derive(Debug, Default, Clone)]
+pub struct Snapshot {
+	documents: BTreeMap<(IriBuf, Vec<Profile>), RemoteDocument>,
+}
+
+impl Snapshot {
+	/// Creates an empty snapshot.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of documents.
+	pub fn len(&self) -> usize {
+		self.documents.len()
+	}
+
+	/// Checks if the snapshot is empty.
+	pub fn is_empty(&self) -> bool {
+		self.documents.is_empty()
+	}
+
+	/// Returns the document loaded from the given `url`, if any.
+	pub fn get(&self, url: &Iri) -> Option<&RemoteDocument> {
+		self.get_with_profile(url, &[])
+	}
+
+	/// Returns the document loaded from the given `url` with the given
+	/// requested profiles, if any.
+	pub fn get_with_profile(&self, url: &Iri, profile: &[Profile]) -> Option<&RemoteDocument> {
+		self.documents.get(&(url.to_owned(), profile.to_vec()))
+	}
+
+	/// Inserts the document loaded from the given `url`.
+	///
+	/// Replaces and returns the previous document, if any.
+	pub fn insert(&mut self, url: IriBuf, document: RemoteDocument) -> Option<RemoteDocument> {
+		self.insert_with_profile(url, Vec::new(), document)
+	}
+
+	/// Inserts the document loaded from the given `url` with the given
+	/// requested profiles.
+	///
+	/// Replaces and returns the previous document, if any.
+	pub fn insert_with_profile(
+		&mut self,
+		url: IriBuf,
+		profile: Vec<Profile>,
+		document: RemoteDocument,
+	) -> Option<RemoteDocument> {
+		self.documents.insert((url, profile), document)
+	}
+
+	/// Returns an iterator over the IRIs, requested profiles and documents of
+	/// the snapshot, sorted by IRI.
+	pub fn iter(&self) -> impl Iterator<Item = (&IriBuf, &[Profile], &RemoteDocument)> {
+		self.documents
+			.iter()
+			.map(|((url, profile), document)| (url, profile.as_slice(), document))
+	}
+
+	/// Serializes the snapshot.
+	pub fn to_json(&self) -> json_syntax::Value {
+		json_syntax::Value::Array(
+			self.documents
+				.iter()
+				.map(|((url, profile), document)| {
+					json_syntax::Value::Object(encode_entry(url, profile, document))
+				})
+				.collect(),
+		)
+	}
+
+	/// Deserializes a snapshot.
+	pub fn from_json(json: &json_syntax::Value) -> Result<Self, Error> {
+		let mut result = Self::new();
+
+		for entry in json.as_array().ok_or(Error::Invalid)? {
+			let (url, profile, document) = entry
+				.as_object()
+				.and_then(decode_entry)
+				.ok_or(Error::Invalid)?;
+			result.insert_with_profile(url, profile, document);
+		}
+
+		Ok(result)
+	}
+
+	/// Reads a snapshot from the given file.
+	pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+		let content = std::fs::read_to_string(path).map_err(Error::IO)?;
+		let (json, _) = json_syntax::Value::parse_str(&content).map_err(|_| Error::Invalid)?;
+		Self::from_json(&json)
+	}
+
+	/// Writes the snapshot to the given file.
+	pub fn write(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+		std::fs::write(path, self.to_json().pretty_print().to_string())
+	}
+}
+
+/// Loader recording the documents loaded by another loader.
+///
+/// Documents are recorded under the IRI and profiles they were requested
+/// with, the IRI possibly differing from their URL after redirections.
+/// Failed loads are not recorded.
+///
+/// ```
+/// use json_ld_core::loader::{NoLoader, RecordingLoader, ReplayLoader};
+///
+/// let loader = RecordingLoader::new(NoLoader);
+/// // ... process documents with `loader` ...
+/// let replay = ReplayLoader::new(loader.into_snapshot());
+/// ```
+pub struct RecordingLoader<L> {
+	inner: L,
+	snapshot: Mutex<Snapshot>,
+}
+
+impl<L> RecordingLoader<L> {
+	/// Creates a new loader recording the documents loaded by `inner`.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			snapshot: Mutex::new(Snapshot::new()),
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a copy of the documents recorded so far.
+	pub fn snapshot(&self) -> Snapshot {
+		self.snapshot.lock().unwrap().clone()
+	}
+
+	/// Returns the recorded documents.
+	pub fn into_snapshot(self) -> Snapshot {
+		self.snapshot.into_inner().unwrap()
+	}
+}
+
+impl<L: Loader> Loader for RecordingLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		let document = self.inner.load(url).await?;
+		self.snapshot
+			.lock()
+			.unwrap()
+			.insert(url.to_owned(), document.clone());
+		Ok(document)
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		let document = self.inner.load_with_profile(url, profile).await?;
+		self.snapshot.lock().unwrap().insert_with_profile(
+			url.to_owned(),
+			profile.to_vec(),
+			document.clone(),
+		);
+		Ok(document)
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context(url)
+	}
+}
+
+impl<L: DynLoader> DynLoader for RecordingLoader<L> {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let document = self.inner.load_dyn(url).await?;
+			self.snapshot
+				.lock()
+				.unwrap()
+				.insert(url.to_owned(), document.clone());
+			Ok(document)
+		})
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(async move {
+			let document = self.inner.load_with_profile_dyn(url, profile).await?;
+			self.snapshot.lock().unwrap().insert_with_profile(
+				url.to_owned(),
+				profile.to_vec(),
+				document.clone(),
+			);
+			Ok(document)
+		})
+	}
+
+	fn processed_context_dyn(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.inner.processed_context_dyn(url)
+	}
+}
+
+/// Document absent from the replayed snapshot.
+#[derive(Debug, thiserror::Error)]
+#[error("document not recorded")]
+pub struct NotRecorded;
+
+/// Loader serving the documents of a [`Snapshot`], without any I/O.
+///
+/// Loading a document absent from the snapshot fails with a
+/// [`NotRecorded`] error.
+pub struct ReplayLoader {
+	snapshot: Snapshot,
+}
+
+impl ReplayLoader {
+	/// Creates a new loader serving the documents of the given snapshot.
+	pub fn new(snapshot: Snapshot) -> Self {
+		Self { snapshot }
+	}
+
+	/// Creates a new loader serving the documents of the snapshot stored in
+	/// the given file.
+	pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+		Snapshot::read(path).map(Self::new)
+	}
+
+	/// Returns the replayed snapshot.
+	pub fn snapshot(&self) -> &Snapshot {
+		&self.snapshot
+	}
+}
+
+impl Loader for ReplayLoader {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.load_with_profile(url, &[]).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		match self.snapshot.get_with_profile(url, profile) {
+			Some(document) => Ok(document.clone()),
+			None => Err(LoadError::new(url.to_owned(), NotRecorded)),
+		}
+	}
+}
+
+impl DynLoader for ReplayLoader {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(Loader::load(self, url))
+	}
+
+	fn load_with_profile_dyn<'a>(
+		&'a self,
+		url: &'a Iri,
+		profile: &'a [Profile],
+	) -> BoxFuture<'a, LoadingResult<IriBuf>> {
+		Box::pin(Loader::load_with_profile(self, url, profile))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use static_iref::iri;
+
+	#[async_std::test]
+	async fn record_and_replay() {
+		let url = iri!("http://example.org/context.jsonld");
+		let inner =
+			fixture::static_loader(&[(url, r#"{"@context": {"name": "http://schema.org/name"}}"#)]);
+
+		let recorder = RecordingLoader::new(inner);
+		let expected = recorder.load(url).await.unwrap();
+
+		let directory = fixture::TempDir::new("snapshot");
+		let path = directory.path().join("snapshot.json");
+		recorder.snapshot().write(&path).unwrap();
+		let snapshot = Snapshot::read(&path).unwrap();
+		assert_eq!(snapshot.len(), 1);
+
+		let replay = ReplayLoader::new(snapshot);
+		let replayed = replay.load(url).await.unwrap();
+		assert_eq!(replayed.url(), expected.url());
+		assert_eq!(replayed.document(), expected.document());
+
+		assert!(replay
+			.load(iri!("http://example.org/other.jsonld"))
+			.await
+			.is_err())
+	}
+}
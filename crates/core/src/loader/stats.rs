@@ -0,0 +1,201 @@
+//! Loading statistics.
+use super::instrumented::LoadEvent;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Counters of the loads performed by one or more loaders.
+///
+/// The counters are updated by an [`InstrumentedLoader`](super::InstrumentedLoader)
+/// given these statistics with
+/// [`with_stats`](super::InstrumentedLoader::with_stats), or with the
+/// `record_*` functions. They are atomic, so the statistics can be shared,
+/// behind an [`Arc`](std::sync::Arc), by several loaders and read at any
+/// time.
+///
+/// With the `metrics` feature, every update is also reported to the
+/// [`metrics`](https://crates.io/crates/metrics) facade, as the
+/// `json_ld_loader_loads_total`, `json_ld_loader_cache_hits_total` and
+/// `json_ld_loader_bytes_total` counters, and the
+/// `json_ld_loader_latency_seconds` histogram.
+///
+/// ```
+/// use std::sync::Arc;
+/// use json_ld_core::loader::{InstrumentedLoader, LoaderStats, NoLoader};
+///
+/// let stats = Arc::new(LoaderStats::new());
+/// let loader = InstrumentedLoader::new(NoLoader).with_stats(stats.clone());
+///
+/// assert_eq!(stats.loads(), 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct LoaderStats {
+	loads: AtomicU64,
+	cache_hits: AtomicU64,
+	bytes: AtomicU64,
+	latency: AtomicU64,
+}
+
+impl LoaderStats {
+	/// Creates new zeroed statistics.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of loads, successful or not.
+	pub fn loads(&self) -> u64 {
+		self.loads.load(Ordering::Relaxed)
+	}
+
+	/// Returns the number of loads [served from a
+	/// cache](super::RemoteDocument::is_from_cache), revalidated or not.
+	pub fn cache_hits(&self) -> u64 {
+		self.cache_hits.load(Ordering::Relaxed)
+	}
+
+	/// Returns the number of downloaded bytes.
+	///
+	/// This is the size of the [raw content](super::RemoteDocument::raw_content)
+	/// of the documents not served from a cache, after decompression if the
+	/// HTTP client decompresses responses.
+	pub fn bytes(&self) -> u64 {
+		self.bytes.load(Ordering::Relaxed)
+	}
+
+	/// Returns the cumulative duration of the loads.
+	pub fn latency(&self) -> Duration {
+		Duration::from_nanos(self.latency.load(Ordering::Relaxed))
+	}
+
+	/// Returns the mean duration of a load, if any load was performed.
+	pub fn mean_latency(&self) -> Option<Duration> {
+		match self.loads() {
+			0 => None,
+			n => Some(Duration::from_nanos(
+				self.latency.load(Ordering::Relaxed) / n,
+			)),
+		}
+	}
+
+	/// Resets every counter to zero.
+	pub fn reset(&self) {
+		self.loads.store(0, Ordering::Relaxed);
+		self.cache_hits.store(0, Ordering::Relaxed);
+		self.bytes.store(0, Ordering::Relaxed);
+		self.latency.store(0, Ordering::Relaxed);
+	}
+
+	/// Records the given load.
+	///
+	/// A loaded document reported as [served from a
+	/// cache](super::RemoteDocument::is_from_cache) by a caching loader, such
+	/// as the [`HttpCache`](super::HttpCache) or the
+	/// [`FileCacheLoader`](super::FileCacheLoader), is counted as a cache
+	/// hit. Other documents count for the size of their [raw
+	/// content](super::RemoteDocument::raw_content), if the loader provides
+	/// it.
+	pub fn record(&self, event: &LoadEvent) {
+		self.record_load(event.duration);
+
+		if let Ok(document) = event.result {
+			if document.is_from_cache() {
+				self.record_cache_hit()
+			} else if let Some(content) = document.raw_content() {
+				self.record_bytes(content.len())
+			}
+		}
+	}
+
+	/// Records a load that took the given time.
+	pub fn record_load(&self, latency: Duration) {
+		self.loads.fetch_add(1, Ordering::Relaxed);
+		self.latency
+			.fetch_add(latency.as_nanos() as u64, Ordering::Relaxed);
+
+		#[cfg(feature = "metrics")]
+		{
+			metrics::counter!("json_ld_loader_loads_total").increment(1);
+			metrics::histogram!("json_ld_loader_latency_seconds").record(latency.as_secs_f64());
+		}
+	}
+
+	/// Records a load served from a cache.
+	pub fn record_cache_hit(&self) {
+		self.cache_hits.fetch_add(1, Ordering::Relaxed);
+
+		#[cfg(feature = "metrics")]
+		metrics::counter!("json_ld_loader_cache_hits_total").increment(1);
+	}
+
+	/// Records downloaded bytes.
+	pub fn record_bytes(&self, len: usize) {
+		self.bytes.fetch_add(len as u64, Ordering::Relaxed);
+
+		#[cfg(feature = "metrics")]
+		metrics::counter!("json_ld_loader_bytes_total").increment(len as u64);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::{fixture, InstrumentedLoader, Loader, RemoteDocument};
+	use static_iref::iri;
+	use std::collections::HashMap;
+	use std::sync::Arc;
+
+	#[test]
+	fn record() {
+		let stats = LoaderStats::new();
+		assert_eq!(stats.mean_latency(), None);
+
+		stats.record_load(Duration::from_millis(10));
+		stats.record_load(Duration::from_millis(30));
+		stats.record_cache_hit();
+		stats.record_bytes(100);
+		stats.record_bytes(50);
+
+		assert_eq!(stats.loads(), 2);
+		assert_eq!(stats.cache_hits(), 1);
+		assert_eq!(stats.bytes(), 150);
+		assert_eq!(stats.latency(), Duration::from_millis(40));
+		assert_eq!(stats.mean_latency(), Some(Duration::from_millis(20)));
+
+		stats.reset();
+		assert_eq!(stats.loads(), 0);
+		assert_eq!(stats.latency(), Duration::ZERO)
+	}
+
+	#[async_std::test]
+	async fn instrumented() {
+		let fresh = iri!("http://example.org/fresh");
+		let cached = iri!("http://example.org/cached");
+		let no_content = iri!("http://example.org/no-content");
+
+		let mut cached_document = fixture::document(cached, "{}");
+		cached_document.set_from_cache(true);
+
+		let documents = HashMap::from([
+			(fresh.to_owned(), fixture::document(fresh, r#"{ "a": 1 }"#)),
+			(cached.to_owned(), cached_document),
+			(
+				no_content.to_owned(),
+				RemoteDocument::new(Some(no_content.to_owned()), None, fixture::json("{}")),
+			),
+		]);
+
+		let stats = Arc::new(LoaderStats::new());
+		let loader = InstrumentedLoader::new(documents).with_stats(stats.clone());
+
+		assert!(loader.load(fresh).await.is_ok());
+		assert!(loader.load(cached).await.is_ok());
+		assert!(loader.load(no_content).await.is_ok());
+		assert!(loader
+			.load(iri!("http://example.org/missing"))
+			.await
+			.is_err());
+
+		assert_eq!(stats.loads(), 4);
+		assert_eq!(stats.cache_hits(), 1);
+		assert_eq!(stats.bytes(), 10)
+	}
+}
@@ -0,0 +1,279 @@
+//! Blocking document and context loader based on [`ureq`](https://crates.io/crates/ureq)
+use super::negotiation::{
+	self, accept_header, check_content_length, negotiate, to_uri, trace_response, Negotiated,
+};
+use super::{Loader, SyncLoader, TooLarge};
+use crate::{LoadError, LoadTrace, LoadingResult, Profile};
+use http::{header::ACCEPT, StatusCode};
+use iref::{Iri, IriBuf};
+use std::sync::Arc;
+use ureq::ResponseExt;
+
+/// Loader options.
+pub struct Options {
+	/// One or more IRIs to use in the request as a profile parameter.
+	///
+	/// (See [IANA Considerations](https://www.w3.org/TR/json-ld11/#iana-considerations)).
+	pub request_profile: Vec<Profile>,
+
+	/// Maximum number of allowed `Link` header redirections before the loader
+	/// fails.
+	///
+	/// Defaults to 8.
+	///
+	/// Note: this only controls how many times the loader will use a `Link`
+	/// HTTP header to find the target JSON-LD document. The number of allowed
+	/// regular HTTP redirections is controlled by the HTTP
+	/// [`agent`](Self::agent).
+	pub max_redirections: usize,
+
+	/// Maximum size (in bytes) of a response body.
+	///
+	/// The body is read incrementally and the loader fails with a
+	/// [`TooLarge`](super::TooLarge) error as soon as it exceeds the limit
+	/// (or right away if the `Content-Length` exceeds it).
+	///
+	/// Defaults to `None` (no limit).
+	pub max_response_bytes: Option<usize>,
+
+	/// HTTP agent.
+	///
+	/// The agent should be configured to not treat HTTP error status codes
+	/// as errors, so that they are reported as [`Error::QueryFailed`].
+	pub agent: ureq::Agent,
+}
+
+impl Default for Options {
+	fn default() -> Self {
+		Self {
+			request_profile: Vec::new(),
+			max_redirections: 8,
+			max_response_bytes: None,
+			agent: ureq::Agent::config_builder()
+				.http_status_as_error(false)
+				.build()
+				.into(),
+		}
+	}
+}
+
+/// Loading error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("internal error: {0}")]
+	Ureq(ureq::Error),
+
+	#[error("query failed: status code {0}")]
+	QueryFailed(StatusCode),
+
+	#[error("invalid content type")]
+	InvalidContentType,
+
+	#[error("multiple context link headers")]
+	MultipleContextLinkHeaders,
+
+	#[error("too many redirections")]
+	TooManyRedirections,
+
+	#[error("JSON parse error: {0}")]
+	Parse(json_syntax::parse::Error<std::io::Error>),
+}
+
+impl From<negotiation::Error> for Error {
+	fn from(value: negotiation::Error) -> Self {
+		match value {
+			negotiation::Error::InvalidContentType => Self::InvalidContentType,
+			negotiation::Error::MultipleContextLinkHeaders => Self::MultipleContextLinkHeaders,
+		}
+	}
+}
+
+/// `ureq`-based loader.
+///
+/// Unlike [`ReqwestLoader`](super::ReqwestLoader), this loader is blocking
+/// and does not require any async runtime. It implements [`SyncLoader`],
+/// and [`Loader`] by sending the blocking requests from a thread pool, so
+/// that it does not block the async runtime.
+///
+/// The loader performs the same content negotiation as the
+/// [`ReqwestLoader`](super::ReqwestLoader), following `Link` headers. IRIs
+/// are converted into URIs before being requested.
+///
+/// Loaded documents are not cached: a new network query is made each time
+/// an URL is loaded even if it has already been queried before.
+pub struct UreqLoader {
+	options: Arc<Options>,
+	accept_header: String,
+}
+
+impl Default for UreqLoader {
+	fn default() -> Self {
+		Self::new_using(Options::default())
+	}
+}
+
+impl UreqLoader {
+	/// Creates a new loader with the default options.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new loader with the given options.
+	pub fn new_using(options: Options) -> Self {
+		Self {
+			accept_header: accept_header(&options.request_profile),
+			options: Arc::new(options),
+		}
+	}
+
+	/// Loads the document behind the given IRI, requesting the given
+	/// profiles instead of [`Options::request_profile`].
+	///
+	/// See [`ReqwestLoader::load_with_profile`](super::ReqwestLoader::load_with_profile).
+	pub fn load_with_profile(
+		&self,
+		url: &Iri,
+		request_profile: &[Profile],
+	) -> LoadingResult<IriBuf> {
+		fetch(&self.options, url, &accept_header(request_profile))
+	}
+
+	/// Loads the document behind the given IRI using the given `Accept`
+	/// header value, from a thread pool.
+	async fn fetch_unblocked(&self, url: &Iri, accept_header: String) -> LoadingResult<IriBuf> {
+		let options = self.options.clone();
+		let url = url.to_owned();
+		blocking::unblock(move || fetch(&options, &url, &accept_header)).await
+	}
+}
+
+/// Loads the document behind the given IRI using the given `Accept` header
+/// value.
+///
+/// On failure, the error carries the trace of the load.
+fn fetch(options: &Options, url: &Iri, accept_header: &str) -> LoadingResult<IriBuf> {
+	let mut trace = LoadTrace::default();
+	fetch_traced(options, url, accept_header, &mut trace)
+		.map_err(|e| e.with_trace(trace).rejected_by("UreqLoader"))
+}
+
+/// Loads the document behind the given IRI using the given `Accept` header
+/// value, recording the redirections and responses in `trace`.
+fn fetch_traced(
+	options: &Options,
+	url: &Iri,
+	accept_header: &str,
+	trace: &mut LoadTrace,
+) -> LoadingResult<IriBuf> {
+	let requested = url;
+	let mut redirection_number = 0;
+	let mut url = url.to_owned();
+	loop {
+		if redirection_number > options.max_redirections {
+			return Err(LoadError::new(url, Error::TooManyRedirections));
+		}
+
+		log::debug!("downloading: {}", url);
+		let uri = to_uri(&url);
+		let mut response = options
+			.agent
+			.get(&uri)
+			.header(ACCEPT, accept_header)
+			.call()
+			.map_err(|e| LoadError::new(url.clone(), Error::Ureq(e)))?;
+
+		// Regular HTTP redirections are followed by the agent: the final
+		// URI of the response is the base URL of the document.
+		if uri.parse::<http::Uri>().ok().as_ref() != Some(response.get_uri()) {
+			if let Ok(final_url) = IriBuf::new(response.get_uri().to_string()) {
+				trace.redirects.push(final_url.clone());
+				url = final_url
+			}
+		}
+
+		trace_response(trace, response.status(), response.headers());
+		match response.status() {
+			StatusCode::OK => match negotiate(&url, response.headers()) {
+				Ok(Negotiated::Document(metadata)) => {
+					let limit = options.max_response_bytes;
+					check_content_length(response.headers(), limit)
+						.map_err(|e| LoadError::new(url.clone(), e))?;
+					let bytes = response
+						.body_mut()
+						.with_config()
+						.limit(limit.map(|l| l as u64).unwrap_or(u64::MAX))
+						.read_to_vec()
+						.map_err(|e| match (e, limit) {
+							(ureq::Error::BodyExceedsLimit(_), Some(limit)) => {
+								LoadError::new(url.clone(), TooLarge(limit as u64))
+							}
+							(e, _) => LoadError::new(url.clone(), Error::Ureq(e)),
+						})?;
+
+					let mut document = metadata
+						.into_document(url.clone(), &bytes)
+						.map_err(|e| LoadError::new(url, Error::Parse(e)))?;
+					document.set_requested_url(requested.to_owned());
+					break Ok(document);
+				}
+				Ok(Negotiated::Alternate(alternate)) => {
+					trace.redirects.push(alternate.clone());
+					url = alternate;
+					redirection_number += 1;
+				}
+				Err(e) => break Err(LoadError::new(url, Error::from(e))),
+			},
+			code => break Err(LoadError::new(url, Error::QueryFailed(code))),
+		}
+	}
+}
+
+impl SyncLoader for UreqLoader {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		fetch(&self.options, url, &self.accept_header)
+	}
+}
+
+impl Loader for UreqLoader {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.fetch_unblocked(url, self.accept_header.clone()).await
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.fetch_unblocked(url, accept_header(profile)).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture::http::{Response, Server};
+
+	#[test]
+	fn redirect() {
+		let server = Server::start(|request| match request.path.as_str() {
+			"/old" => Response::redirect("/new"),
+			_ => Response::json_ld("{}"),
+		});
+
+		let loader = UreqLoader::new();
+		let document = loader.load_sync(&server.url("/old")).unwrap();
+		assert!(document.is_redirected());
+		assert_eq!(document.url(), Some(&server.url("/new")));
+		assert_eq!(document.requested_url(), Some(&server.url("/old")));
+
+		let document = loader.load_sync(&server.url("/new")).unwrap();
+		assert!(!document.is_redirected());
+	}
+
+	#[async_std::test]
+	async fn iri_to_uri() {
+		let server = Server::start(|_| Response::json_ld("{}"));
+
+		let loader = UreqLoader::new();
+		let url = server.url("/café");
+		let document = Loader::load(&loader, &url).await.unwrap();
+		assert_eq!(document.url(), Some(&url));
+		assert_eq!(server.requests()[0].path, "/caf%C3%A9");
+	}
+}
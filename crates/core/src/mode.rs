@@ -1,20 +1,32 @@
 use std::convert::TryFrom;
 use std::fmt;
+use std::str::FromStr;
 
 /// Processing mode.
 ///
 /// This is a property of the context processing and compaction options.
 /// New features defined in JSON-LD 1.1 are available unless the processing mode is set to [`ProcessingMode::JsonLd1_0`].
+///
+/// The string form of a processing mode is `json-ld-1.0` or `json-ld-1.1`,
+/// as in the `processingMode` option of the JSON-LD API.
 #[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProcessingMode {
 	/// JSON-LD 1.0.
+	#[cfg_attr(feature = "serde", serde(rename = "json-ld-1.0"))]
 	JsonLd1_0,
 
 	/// JSON-LD 1.1.
 	#[default]
+	#[cfg_attr(feature = "serde", serde(rename = "json-ld-1.1"))]
 	JsonLd1_1,
 }
 
+/// Invalid processing mode name.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("invalid processing mode `{0}`")]
+pub struct InvalidProcessingMode(pub String);
+
 impl ProcessingMode {
 	/// Returns the name of the processing mode.
 	#[inline(always)]
@@ -38,6 +50,14 @@ impl<'a> TryFrom<&'a str> for ProcessingMode {
 	}
 }
 
+impl FromStr for ProcessingMode {
+	type Err = InvalidProcessingMode;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::try_from(s).map_err(|_| InvalidProcessingMode(s.to_owned()))
+	}
+}
+
 impl fmt::Display for ProcessingMode {
 	#[inline(always)]
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
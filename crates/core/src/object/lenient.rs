@@ -0,0 +1,281 @@
+//! Lenient conversion of "almost expanded" JSON documents.
+//!
+//! Documents claiming to be in expanded form often deviate slightly from it,
+//! for instance by not wrapping single values into arrays, or by using bare
+//! strings instead of value objects. The functions of this module normalize
+//! such common shapes into proper expanded JSON-LD so that they can then be
+//! converted using [`TryFromJson`](super::TryFromJson). Each fix applied is
+//! reported to a warning handler.
+//!
+//! Only those two deviations are fixed. In particular, strings are only
+//! wrapped into value objects where a value object is allowed, that is as
+//! property values and list items. Any other malformed value is left as is,
+//! and reported by the conversion.
+use super::push_pointer_segment;
+use crate::warning;
+use std::fmt;
+
+/// Fix applied while normalizing an almost expanded JSON document.
+///
+/// Each fix carries the JSON pointer to the fixed value in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+	/// A single value has been wrapped into an array.
+	WrappedInArray(String),
+
+	/// A bare string has been wrapped into a value object.
+	WrappedInValueObject(String),
+}
+
+impl Fix {
+	/// Returns the JSON pointer to the fixed value in the input.
+	pub fn pointer(&self) -> &str {
+		match self {
+			Self::WrappedInArray(p) => p,
+			Self::WrappedInValueObject(p) => p,
+		}
+	}
+}
+
+impl fmt::Display for Fix {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::WrappedInArray(p) => write!(f, "value at `{p}` wrapped into an array"),
+			Self::WrappedInValueObject(p) => {
+				write!(f, "string at `{p}` wrapped into a value object")
+			}
+		}
+	}
+}
+
+impl<N> contextual::DisplayWithContext<N> for Fix {
+	fn fmt_with(&self, _vocabulary: &N, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+/// Normalizes an almost expanded JSON-LD document.
+///
+/// A single top-level object is wrapped into an array.
+pub fn normalize_document<N>(
+	vocabulary: &N,
+	value: json_syntax::Value,
+	warnings: &mut impl warning::Handler<N, Fix>,
+) -> json_syntax::Value {
+	let mut pointer = String::new();
+	normalize_array(vocabulary, value, &mut pointer, warnings, normalize_object)
+}
+
+/// Normalizes an almost expanded JSON-LD object (node, value or list object).
+pub fn normalize_object<N>(
+	vocabulary: &N,
+	value: json_syntax::Value,
+	warnings: &mut impl warning::Handler<N, Fix>,
+) -> json_syntax::Value {
+	let mut pointer = String::new();
+	normalize_object_at(vocabulary, value, &mut pointer, warnings)
+}
+
+type NormalizeItem<N, H> = fn(&N, json_syntax::Value, &mut String, &mut H) -> json_syntax::Value;
+
+fn normalize_array<N, H: warning::Handler<N, Fix>>(
+	vocabulary: &N,
+	value: json_syntax::Value,
+	pointer: &mut String,
+	warnings: &mut H,
+	normalize_item: NormalizeItem<N, H>,
+) -> json_syntax::Value {
+	let items = match value {
+		json_syntax::Value::Array(items) => items,
+		item => {
+			warnings.handle(vocabulary, Fix::WrappedInArray(pointer.clone()));
+			vec![item]
+		}
+	};
+
+	let len = pointer.len();
+	let items = items
+		.into_iter()
+		.enumerate()
+		.map(|(i, item)| {
+			push_pointer_segment(pointer, &i.to_string());
+			let item = normalize_item(vocabulary, item, pointer, warnings);
+			pointer.truncate(len);
+			item
+		})
+		.collect();
+
+	json_syntax::Value::Array(items)
+}
+
+fn normalize_object_at<N, H: warning::Handler<N, Fix>>(
+	vocabulary: &N,
+	value: json_syntax::Value,
+	pointer: &mut String,
+	warnings: &mut H,
+) -> json_syntax::Value {
+	let object = match value {
+		json_syntax::Value::Object(object) => object,
+		other => return other,
+	};
+
+	if object.get_unique("@value").ok().flatten().is_some() {
+		return json_syntax::Value::Object(object);
+	}
+
+	let len = pointer.len();
+	let mut result = json_syntax::Object::default();
+	for entry in object {
+		push_pointer_segment(pointer, &entry.key);
+		let value = match entry.key.as_str() {
+			"@list" => normalize_array(vocabulary, entry.value, pointer, warnings, normalize_item),
+			"@graph" | "@included" => normalize_array(
+				vocabulary,
+				entry.value,
+				pointer,
+				warnings,
+				normalize_object_at,
+			),
+			"@type" => normalize_array(vocabulary, entry.value, pointer, warnings, keep),
+			"@reverse" => normalize_properties(vocabulary, entry.value, pointer, warnings),
+			key if key.starts_with('@') => entry.value,
+			_ => normalize_array(vocabulary, entry.value, pointer, warnings, normalize_item),
+		};
+		pointer.truncate(len);
+
+		result.insert(entry.key, value);
+	}
+
+	json_syntax::Value::Object(result)
+}
+
+fn normalize_properties<N, H: warning::Handler<N, Fix>>(
+	vocabulary: &N,
+	value: json_syntax::Value,
+	pointer: &mut String,
+	warnings: &mut H,
+) -> json_syntax::Value {
+	match value {
+		json_syntax::Value::Object(object) => {
+			let len = pointer.len();
+			let mut result = json_syntax::Object::default();
+			for entry in object {
+				push_pointer_segment(pointer, &entry.key);
+				let value =
+					normalize_array(vocabulary, entry.value, pointer, warnings, normalize_item);
+				pointer.truncate(len);
+
+				result.insert(entry.key, value);
+			}
+
+			json_syntax::Value::Object(result)
+		}
+		other => other,
+	}
+}
+
+fn normalize_item<N, H: warning::Handler<N, Fix>>(
+	vocabulary: &N,
+	value: json_syntax::Value,
+	pointer: &mut String,
+	warnings: &mut H,
+) -> json_syntax::Value {
+	match value {
+		json_syntax::Value::Object(_) => normalize_object_at(vocabulary, value, pointer, warnings),
+		value @ json_syntax::Value::String(_) => {
+			warnings.handle(vocabulary, Fix::WrappedInValueObject(pointer.clone()));
+			let mut object = json_syntax::Object::default();
+			object.insert("@value".into(), value);
+			json_syntax::Value::Object(object)
+		}
+		other => other,
+	}
+}
+
+fn keep<N, H>(
+	_vocabulary: &N,
+	value: json_syntax::Value,
+	_pointer: &mut String,
+	_warnings: &mut H,
+) -> json_syntax::Value {
+	value
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use crate::ExpandedDocument;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	struct Fixes(Vec<Fix>);
+
+	impl<N> warning::Handler<N, Fix> for Fixes {
+		fn handle(&mut self, _vocabulary: &N, fix: Fix) {
+			self.0.push(fix)
+		}
+	}
+
+	#[test]
+	fn normalize() {
+		let mut fixes = Fixes(Vec::new());
+		let value = normalize_document(
+			&(),
+			fixture::json(
+				r#"{
+					"@id": "http://example.org/a",
+					"@type": "http://example.org/T",
+					"http://example.org/p": "a",
+					"http://example.org/l": { "@list": ["b", 1] },
+					"@graph": "http://example.org/g"
+				}"#,
+			),
+			&mut fixes,
+		);
+
+		assert_eq!(
+			value,
+			fixture::json(
+				r#"[{
+					"@id": "http://example.org/a",
+					"@type": ["http://example.org/T"],
+					"http://example.org/p": [{ "@value": "a" }],
+					"http://example.org/l": [{ "@list": [{ "@value": "b" }, 1] }],
+					"@graph": ["http://example.org/g"]
+				}]"#
+			)
+		);
+
+		assert_eq!(
+			fixes.0,
+			[
+				Fix::WrappedInArray("".to_owned()),
+				Fix::WrappedInArray("/0/@type".to_owned()),
+				Fix::WrappedInArray("/0/http:~1~1example.org~1p".to_owned()),
+				Fix::WrappedInValueObject("/0/http:~1~1example.org~1p/0".to_owned()),
+				Fix::WrappedInArray("/0/http:~1~1example.org~1l".to_owned()),
+				Fix::WrappedInValueObject("/0/http:~1~1example.org~1l/0/@list/0".to_owned()),
+				Fix::WrappedInArray("/0/@graph".to_owned()),
+			]
+		)
+	}
+
+	#[test]
+	fn lenient_json() {
+		let document: ExpandedDocument = ExpandedDocument::try_from_lenient_json_in(
+			no_vocabulary_mut(),
+			fixture::json(r#"{ "@id": "http://example.org/a", "http://example.org/p": "a" }"#),
+			(),
+		)
+		.unwrap();
+		assert_eq!(document.len(), 1);
+
+		// Only strings are wrapped into value objects.
+		let result: Result<ExpandedDocument, _> = ExpandedDocument::try_from_lenient_json_in(
+			no_vocabulary_mut(),
+			fixture::json(r#"{ "http://example.org/p": 1 }"#),
+			(),
+		);
+		assert!(result.is_err())
+	}
+}
@@ -58,6 +58,11 @@ impl<T, B> List<T, B> {
 		self.entry.push(object)
 	}
 
+	/// Checks if this list directly contains a list object.
+	pub fn has_nested_list(&self) -> bool {
+		self.entry.iter().any(|item| item.is_list())
+	}
+
 	pub fn pop(&mut self) -> Option<IndexedObject<T, B>> {
 		self.entry.pop()
 	}
@@ -141,7 +146,8 @@ impl<T: Eq + Hash, B: Eq + Hash> List<T, B> {
 		object: json_syntax::Object,
 		list_entry: json_syntax::object::Entry,
 	) -> Result<Self, InvalidExpandedJson> {
-		let list = Vec::try_from_json_in(vocabulary, list_entry.value)?;
+		let list =
+			Vec::try_from_json_in(vocabulary, list_entry.value).map_err(|e| e.at_key("@list"))?;
 
 		match object.into_iter().next() {
 			Some(_) => Err(InvalidExpandedJson::UnexpectedEntry),
@@ -10,6 +10,7 @@ use rdf_types::{BlankIdBuf, Generator, Subject, Vocabulary, VocabularyMut};
 use smallvec::SmallVec;
 use std::hash::Hash;
 
+pub mod lenient;
 pub mod list;
 mod mapped_eq;
 pub mod node;
@@ -450,6 +451,64 @@ impl<T, B> Relabel<T, B> for Object<T, B> {
 	}
 }
 
+impl<T: Eq + Hash, B: Eq + Hash> Object<T, B> {
+	/// Removes the duplicate property values of every node in this object.
+	///
+	/// See [`Node::dedup_values`].
+	pub fn dedup_values(&mut self) {
+		match self {
+			Self::Node(n) => n.dedup_values(),
+			Self::List(l) => {
+				for item in l.iter_mut() {
+					item.inner_mut().dedup_values()
+				}
+			}
+			Self::Value(_) => (),
+		}
+	}
+
+	/// Splices the items of every list nested in another list into the
+	/// enclosing list, in this object and every nested node.
+	///
+	/// Empty nested lists are removed. The index of a nested list is lost.
+	pub fn flatten_nested_lists(&mut self) {
+		match self {
+			Self::Node(n) => n.flatten_nested_lists(),
+			Self::List(l) => {
+				let items = std::mem::take(l.entry_mut());
+				for mut item in items {
+					item.inner_mut().flatten_nested_lists();
+					let (object, index) = item.into_parts();
+					match object {
+						Self::List(nested) => l.entry_mut().extend(nested.into_entry()),
+						object => l.push(Indexed::new(object, index)),
+					}
+				}
+			}
+			Self::Value(_) => (),
+		}
+	}
+
+	/// Sorts the properties of every node in this object by identifier.
+	///
+	/// See [`Node::sort_properties`].
+	pub fn sort_properties(&mut self)
+	where
+		T: Ord,
+		B: Ord,
+	{
+		match self {
+			Self::Node(n) => n.sort_properties(),
+			Self::List(l) => {
+				for item in l.iter_mut() {
+					item.inner_mut().sort_properties()
+				}
+			}
+			Self::Value(_) => (),
+		}
+	}
+}
+
 impl<T: Eq + Hash, B: Eq + Hash> Indexed<Object<T, B>> {
 	pub fn equivalent(&self, other: &Self) -> bool {
 		self.index() == other.index() && self.inner().equivalent(other.inner())
@@ -802,8 +861,8 @@ impl<T, B, V: TryFromJson<T, B>> TryFromJson<T, B> for Vec<V> {
 			json_syntax::Value::Array(items) => {
 				let mut result = Vec::new();
 
-				for item in items {
-					result.push(V::try_from_json_in(vocabulary, item)?)
+				for (i, item) in items.into_iter().enumerate() {
+					result.push(V::try_from_json_in(vocabulary, item).map_err(|e| e.at_index(i))?)
 				}
 
 				Ok(result)
@@ -822,8 +881,9 @@ impl<T, B, V: Eq + Hash + TryFromJson<T, B>> TryFromJson<T, B> for IndexSet<V> {
 			json_syntax::Value::Array(items) => {
 				let mut result = IndexSet::new();
 
-				for item in items {
-					result.insert(V::try_from_json_in(vocabulary, item)?);
+				for (i, item) in items.into_iter().enumerate() {
+					result
+						.insert(V::try_from_json_in(vocabulary, item).map_err(|e| e.at_index(i))?);
 				}
 
 				Ok(result)
@@ -885,20 +945,55 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Object<T, B> {
 ///
 /// This can be raised when trying to directly convert a JSON value into an
 /// expanded JSON-LD object without using the expansion algorithm.
-#[derive(Debug)]
+///
+/// Errors raised inside a JSON object or array are wrapped into the
+/// [`Self::At`] variant, locating the faulty value in the input using a
+/// [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901). Use
+/// [`Self::inner`] to match on the error regardless of its location.
+///
+/// New kinds of errors may be added in minor releases.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum InvalidExpandedJson {
+	#[error("invalid object")]
 	InvalidObject,
+
+	#[error("invalid list")]
 	InvalidList,
+
+	#[error("invalid index")]
 	InvalidIndex,
+
+	#[error("invalid identifier")]
 	InvalidId,
+
+	#[error("invalid value type")]
 	InvalidValueType,
+
+	#[error("invalid literal")]
 	InvalidLiteral,
+
+	#[error("invalid language")]
 	InvalidLanguage,
+
+	#[error("invalid direction")]
 	InvalidDirection,
+
+	#[error("not expanded")]
 	NotExpanded,
+
+	#[error("unexpected entry")]
 	UnexpectedEntry,
+
+	#[error("duplicate key `{0}`")]
 	DuplicateKey(json_syntax::object::Key),
+
+	#[error("unexpected {0}, expected {1}")]
 	Unexpected(json_syntax::Kind, json_syntax::Kind),
+
+	/// Error located at the given JSON pointer.
+	#[error("{1} at `{0}`")]
+	At(String, Box<Self>),
 }
 
 impl InvalidExpandedJson {
@@ -909,6 +1004,58 @@ impl InvalidExpandedJson {
 	) -> Self {
 		InvalidExpandedJson::DuplicateKey(a.key)
 	}
+
+	/// Locates this error under the given object `key`.
+	pub fn at_key(self, key: &str) -> Self {
+		let mut segment = String::with_capacity(key.len() + 1);
+		push_pointer_segment(&mut segment, key);
+		self.prefixed(segment)
+	}
+
+	/// Locates this error under the given array `index`.
+	pub fn at_index(self, index: usize) -> Self {
+		self.prefixed(format!("/{index}"))
+	}
+
+	fn prefixed(self, mut pointer: String) -> Self {
+		match self {
+			Self::At(suffix, e) => {
+				pointer.push_str(&suffix);
+				Self::At(pointer, e)
+			}
+			e => Self::At(pointer, Box::new(e)),
+		}
+	}
+
+	/// Returns the JSON pointer to the faulty value in the input.
+	///
+	/// The empty pointer designates the input value itself.
+	pub fn pointer(&self) -> &str {
+		match self {
+			Self::At(pointer, _) => pointer,
+			_ => "",
+		}
+	}
+
+	/// Returns the error without its location.
+	pub fn inner(&self) -> &Self {
+		match self {
+			Self::At(_, e) => e,
+			e => e,
+		}
+	}
+}
+
+/// Appends the given `segment` to a JSON pointer, escaping it.
+pub(crate) fn push_pointer_segment(pointer: &mut String, segment: &str) {
+	pointer.push('/');
+	for c in segment.chars() {
+		match c {
+			'~' => pointer.push_str("~0"),
+			'/' => pointer.push_str("~1"),
+			c => pointer.push(c),
+		}
+	}
 }
 
 impl<T, B> Any<T, B> for Object<T, B> {
@@ -1163,3 +1310,78 @@ impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> IntoJsonWithContext<N> for Objec
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::fixture;
+	use crate::ExpandedDocument;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	#[test]
+	fn invalid_expanded_json_pointer() {
+		let result: Result<ExpandedDocument, _> = ExpandedDocument::try_from_json_in(
+			no_vocabulary_mut(),
+			fixture::json(r#"[{ "http://example.org/p": [{ "@value": "a", "@type": 1 }] }]"#),
+		);
+		let error = result.unwrap_err();
+		assert_eq!(error.pointer(), "/0/http:~1~1example.org~1p/0/@type");
+		assert!(matches!(
+			error.inner(),
+			InvalidExpandedJson::InvalidValueType
+		))
+	}
+
+	#[test]
+	fn dedup() {
+		let expanded = |json| -> ExpandedDocument {
+			ExpandedDocument::try_from_json_in(no_vocabulary_mut(), fixture::json(json)).unwrap()
+		};
+
+		let mut document = expanded(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/p": [
+					{ "@value": "x" },
+					{ "@id": "http://example.org/b" },
+					{ "@value": "x", "@language": "en" },
+					{ "@value": "x" },
+					{ "@id": "http://example.org/b" },
+					{ "http://example.org/q": [{ "@value": "y" }] },
+					{ "http://example.org/q": [{ "@value": "y" }] },
+					{ "@list": [] },
+					{ "@list": [] }
+				],
+				"@reverse": {
+					"http://example.org/r": [
+						{ "@id": "http://example.org/c" },
+						{ "@id": "http://example.org/c" }
+					]
+				}
+			}]"#,
+		);
+		document.dedup();
+
+		// Anonymous nodes and lists are kept.
+		assert_eq!(
+			document,
+			expanded(
+				r#"[{
+					"@id": "http://example.org/a",
+					"http://example.org/p": [
+						{ "@value": "x" },
+						{ "@id": "http://example.org/b" },
+						{ "@value": "x", "@language": "en" },
+						{ "http://example.org/q": [{ "@value": "y" }] },
+						{ "http://example.org/q": [{ "@value": "y" }] },
+						{ "@list": [] },
+						{ "@list": [] }
+					],
+					"@reverse": {
+						"http://example.org/r": [{ "@id": "http://example.org/c" }]
+					}
+				}]"#
+			)
+		)
+	}
+}
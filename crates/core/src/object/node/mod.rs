@@ -601,6 +601,28 @@ impl<T: Eq + Hash, B: Eq + Hash> Node<T, B> {
 		self.properties.insert_all(prop, values)
 	}
 
+	/// Associates the given graphs to the node through the given property.
+	///
+	/// Each graph is inserted as a graph object, identified by the given
+	/// identifier or anonymous. Once compacted with a term whose container
+	/// mapping is `["@graph", "@id"]`, the property value is a map from graph
+	/// identifiers (or `@none`) to graph contents.
+	pub fn insert_graphs(
+		&mut self,
+		prop: Id<T, B>,
+		graphs: impl IntoIterator<Item = (Option<Id<T, B>>, Graph<T, B>)>,
+	) where
+		T: Clone,
+		B: Clone,
+	{
+		for (id, graph) in graphs {
+			let mut node = Self::new();
+			node.id = id;
+			node.graph = Some(graph);
+			self.insert(prop.clone(), Indexed::new(Object::node(node), None))
+		}
+	}
+
 	pub fn reverse_properties_or_insert(
 		&mut self,
 		props: ReverseProperties<T, B>,
@@ -631,6 +653,232 @@ impl<T: Eq + Hash, B: Eq + Hash> Node<T, B> {
 			false
 		}
 	}
+	/// Removes the `@included` entry of this node and of every nested node.
+	pub fn drop_included(&mut self) {
+		self.included = None;
+
+		for (_, values) in self.properties.iter_mut() {
+			for value in values.iter_mut() {
+				if let Some(node) = value.inner_mut().as_node_mut() {
+					node.drop_included()
+				}
+			}
+		}
+	}
+
+	/// Replaces node references by the nodes of the `@included` entry they
+	/// refer to, in this node and every nested node.
+	///
+	/// Each included node is inlined at most once, at the first reference
+	/// found. Included nodes that are not referenced stay in the `@included`
+	/// entry. The `@index` of an inlined node is replaced by the index of the
+	/// reference.
+	pub fn inline_included(&mut self)
+	where
+		T: Clone,
+		B: Clone,
+	{
+		let mut kept = Included::new();
+		let mut by_id = hashbrown::HashMap::new();
+
+		for mut node in self.included.take().unwrap_or_default() {
+			node.inner_mut().inline_included();
+			match node.id.clone() {
+				Some(id) => {
+					by_id.insert(id, node);
+				}
+				None => {
+					kept.insert(node);
+				}
+			}
+		}
+
+		self.inline_references(&mut by_id);
+
+		kept.extend(by_id.into_values());
+		if !kept.is_empty() {
+			self.included = Some(kept)
+		}
+	}
+
+	fn inline_references(&mut self, by_id: &mut hashbrown::HashMap<Id<T, B>, IndexedNode<T, B>>)
+	where
+		T: Clone,
+		B: Clone,
+	{
+		for (_, values) in self.properties.iter_mut() {
+			for value in values.iter_mut() {
+				if let Some(node) = value.inner_mut().as_node_mut() {
+					let included = match &node.id {
+						Some(id) if node.is_empty() => by_id.remove(id),
+						_ => None,
+					};
+
+					match included {
+						Some(included) => *node = included.into_inner(),
+						None => {
+							node.inline_included();
+							node.inline_references(by_id)
+						}
+					}
+				}
+			}
+		}
+	}
+
+	/// Removes, for each property of this node and of every nested node,
+	/// the values equivalent to a preceding value of the same property.
+	///
+	/// Equivalence is defined by [`Object::equivalent`](crate::Object::equivalent):
+	/// value objects are equivalent if they have the same value, type,
+	/// language, direction and index, identified node objects if they are
+	/// equal. Lists and anonymous nodes denote distinct blank nodes and are
+	/// always kept. Duplicates are found by hashing, in linear time.
+	pub fn dedup_values(&mut self) {
+		for (_, values) in self.properties.iter_mut() {
+			for value in values.iter_mut() {
+				value.inner_mut().dedup_values()
+			}
+
+			values.dedup_except(|value| match value.inner() {
+				Object::Value(_) => false,
+				Object::Node(node) => node.id.is_none(),
+				Object::List(_) => true,
+			})
+		}
+
+		if let Some(reverse_properties) = self.reverse_properties.as_mut() {
+			for (_, nodes) in reverse_properties.iter_mut() {
+				for node in nodes.iter_mut() {
+					node.inner_mut().dedup_values()
+				}
+
+				nodes.dedup_except(|node| node.id.is_none())
+			}
+		}
+
+		if let Some(graph) = self.graph.take() {
+			self.graph = Some(
+				graph
+					.into_iter()
+					.map(|mut object| {
+						object.inner_mut().dedup_values();
+						object
+					})
+					.collect(),
+			)
+		}
+
+		if let Some(included) = self.included.take() {
+			self.included = Some(
+				included
+					.into_iter()
+					.map(|mut node| {
+						node.inner_mut().dedup_values();
+						node
+					})
+					.collect(),
+			)
+		}
+	}
+
+	/// Splices the items of every list nested in another list into the
+	/// enclosing list, in this node and every nested node.
+	///
+	/// See [`Object::flatten_nested_lists`](crate::Object::flatten_nested_lists).
+	pub fn flatten_nested_lists(&mut self) {
+		for (_, values) in self.properties.iter_mut() {
+			for value in values.iter_mut() {
+				value.inner_mut().flatten_nested_lists()
+			}
+		}
+
+		if let Some(reverse_properties) = self.reverse_properties.as_mut() {
+			for (_, nodes) in reverse_properties.iter_mut() {
+				for node in nodes.iter_mut() {
+					node.inner_mut().flatten_nested_lists()
+				}
+			}
+		}
+
+		if let Some(graph) = self.graph.take() {
+			self.graph = Some(
+				graph
+					.into_iter()
+					.map(|mut object| {
+						object.inner_mut().flatten_nested_lists();
+						object
+					})
+					.collect(),
+			)
+		}
+
+		if let Some(included) = self.included.take() {
+			self.included = Some(
+				included
+					.into_iter()
+					.map(|mut node| {
+						node.inner_mut().flatten_nested_lists();
+						node
+					})
+					.collect(),
+			)
+		}
+	}
+
+	/// Sorts the properties and reverse properties of this node and of every
+	/// nested node by identifier.
+	///
+	/// The values of each property, the types, and the graph and included
+	/// nodes keep their order. Together with
+	/// [`ExpandedDocument::sort_by_id`](crate::ExpandedDocument::sort_by_id),
+	/// this gives a serialization that does not depend on the order of the
+	/// entries in the original document.
+	pub fn sort_properties(&mut self)
+	where
+		T: Ord,
+		B: Ord,
+	{
+		self.properties.sort();
+		for (_, values) in self.properties.iter_mut() {
+			for value in values.iter_mut() {
+				value.inner_mut().sort_properties()
+			}
+		}
+
+		if let Some(reverse_properties) = self.reverse_properties.as_mut() {
+			reverse_properties.sort();
+			for (_, nodes) in reverse_properties.iter_mut() {
+				for node in nodes.iter_mut() {
+					node.inner_mut().sort_properties()
+				}
+			}
+		}
+
+		if let Some(graph) = self.graph.take() {
+			self.graph = Some(
+				graph
+					.into_iter()
+					.map(|mut object| {
+						object.inner_mut().sort_properties();
+						object
+					})
+					.collect(),
+			)
+		}
+
+		if let Some(included) = self.included.take() {
+			self.included = Some(
+				included
+					.into_iter()
+					.map(|mut node| {
+						node.inner_mut().sort_properties();
+						node
+					})
+					.collect(),
+			)
+		}
+	}
 }
 
 impl<T, B> Relabel<T, B> for Node<T, B> {
@@ -1240,7 +1488,9 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Node<T, B> {
 			.remove_unique("@id")
 			.map_err(InvalidExpandedJson::duplicate_key)?
 		{
-			Some(entry) => Some(Id::try_from_json_in(vocabulary, entry.value)?),
+			Some(entry) => {
+				Some(Id::try_from_json_in(vocabulary, entry.value).map_err(|e| e.at_key("@id"))?)
+			}
 			None => None,
 		};
 
@@ -1248,7 +1498,9 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Node<T, B> {
 			.remove_unique("@type")
 			.map_err(InvalidExpandedJson::duplicate_key)?
 		{
-			Some(entry) => Some(Vec::try_from_json_in(vocabulary, entry.value)?),
+			Some(entry) => Some(
+				Vec::try_from_json_in(vocabulary, entry.value).map_err(|e| e.at_key("@type"))?,
+			),
 			None => None,
 		};
 
@@ -1256,7 +1508,10 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Node<T, B> {
 			.remove_unique("@graph")
 			.map_err(InvalidExpandedJson::duplicate_key)?
 		{
-			Some(entry) => Some(IndexSet::try_from_json_in(vocabulary, entry.value)?),
+			Some(entry) => Some(
+				IndexSet::try_from_json_in(vocabulary, entry.value)
+					.map_err(|e| e.at_key("@graph"))?,
+			),
 			None => None,
 		};
 
@@ -1264,7 +1519,10 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Node<T, B> {
 			.remove_unique("@included")
 			.map_err(InvalidExpandedJson::duplicate_key)?
 		{
-			Some(entry) => Some(IndexSet::try_from_json_in(vocabulary, entry.value)?),
+			Some(entry) => Some(
+				IndexSet::try_from_json_in(vocabulary, entry.value)
+					.map_err(|e| e.at_key("@included"))?,
+			),
 			None => None,
 		};
 
@@ -1272,10 +1530,10 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Node<T, B> {
 			.remove_unique("@reverse")
 			.map_err(InvalidExpandedJson::duplicate_key)?
 		{
-			Some(entry) => Some(ReverseProperties::try_from_json_in(
-				vocabulary,
-				entry.value,
-			)?),
+			Some(entry) => Some(
+				ReverseProperties::try_from_json_in(vocabulary, entry.value)
+					.map_err(|e| e.at_key("@reverse"))?,
+			),
 			None => None,
 		};
 
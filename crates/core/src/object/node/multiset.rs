@@ -74,6 +74,25 @@ impl<T, S> Multiset<T, S> {
 		&self.data
 	}
 
+	/// Removes every value equal to a preceding value, except the values
+	/// for which `distinct` returns `true`, which are always kept.
+	///
+	/// The first occurrence of each value is kept, in order.
+	pub fn dedup_except(&mut self, distinct: impl Fn(&T) -> bool)
+	where
+		T: Eq + Hash,
+	{
+		let mut seen = hashbrown::HashSet::with_capacity(self.data.len());
+		let keep: Vec<bool> = self
+			.data
+			.iter()
+			.map(|value| distinct(value) || seen.insert(value))
+			.collect();
+
+		let mut keep = keep.into_iter();
+		self.data.retain(|_| keep.next().unwrap())
+	}
+
 	// pub fn into_stripped(self) -> Multiset<locspan::Stripped<T>, S> {
 	// 	Multiset { data: unsafe { core::mem::transmute(self.data) }, hasher: self.hasher }
 	// }
@@ -11,6 +11,11 @@ use std::hash::{Hash, Hasher};
 pub type PropertyObjects<T, B> = Multiset<IndexedObject<T, B>>;
 
 /// Properties of a node object, and their associated objects.
+///
+/// Properties are iterated in insertion order, which is the order in which
+/// the expansion algorithm processed them, unless a property is
+/// [removed](Self::remove) (the last property then takes its place) or the
+/// map is [sorted](Self::sort).
 #[derive(Educe, Debug, Clone)]
 #[educe(
 	PartialEq(bound = "T: Eq + Hash, B: Eq + Hash"),
@@ -61,6 +66,17 @@ impl<T, B> Properties<T, B> {
 	pub fn clear(&mut self) {
 		self.0.clear()
 	}
+
+	/// Sorts the properties by identifier.
+	///
+	/// The order of the objects associated to each property is unchanged.
+	pub fn sort(&mut self)
+	where
+		T: Ord,
+		B: Ord,
+	{
+		self.0.sort_keys()
+	}
 }
 
 impl<T: Eq + Hash, B: Eq + Hash> Properties<T, B> {
@@ -219,7 +235,8 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Properties<T, B> {
 
 		for entry in object {
 			let prop = Id::from_string_in(vocabulary, entry.key.to_string());
-			let objects: Vec<IndexedObject<T, B>> = Vec::try_from_json_in(vocabulary, entry.value)?;
+			let objects: Vec<IndexedObject<T, B>> =
+				Vec::try_from_json_in(vocabulary, entry.value).map_err(|e| e.at_key(&entry.key))?;
 			result.insert_all(prop, objects)
 		}
 
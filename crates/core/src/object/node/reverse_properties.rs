@@ -14,6 +14,9 @@ use std::hash::{Hash, Hasher};
 pub type ReversePropertyNodes<T = IriBuf, B = BlankIdBuf> = Multiset<IndexedNode<T, B>>;
 
 /// Reverse properties of a node object, and their associated nodes.
+///
+/// Reverse properties are iterated in insertion order, unless one is
+/// [removed](Self::remove) or the map is [sorted](Self::sort).
 #[derive(Educe, Debug, Clone)]
 #[educe(
 	PartialEq(bound = "T: Eq + Hash, B: Eq + Hash"),
@@ -66,6 +69,18 @@ impl<T, B> ReverseProperties<T, B> {
 	pub fn clear(&mut self) {
 		self.0.clear()
 	}
+
+	/// Sorts the reverse properties by identifier.
+	///
+	/// The order of the nodes associated to each reverse property is
+	/// unchanged.
+	pub fn sort(&mut self)
+	where
+		T: Ord,
+		B: Ord,
+	{
+		self.0.sort_keys()
+	}
 }
 
 impl<T: Eq + Hash, B: Eq + Hash> ReverseProperties<T, B> {
@@ -224,7 +239,8 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for ReverseProperties<T
 
 		for entry in object {
 			let prop = Id::from_string_in(vocabulary, entry.key.to_string());
-			let nodes: Vec<IndexedNode<T, B>> = Vec::try_from_json_in(vocabulary, entry.value)?;
+			let nodes: Vec<IndexedNode<T, B>> =
+				Vec::try_from_json_in(vocabulary, entry.value).map_err(|e| e.at_key(&entry.key))?;
 			result.insert_all(prop, nodes)
 		}
 
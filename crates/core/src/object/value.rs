@@ -272,13 +272,14 @@ impl<T> Value<T> {
 					iri => match Iri::new(iri) {
 						Ok(iri) => {
 							let ty = vocabulary.insert(iri);
-							let lit = value_entry.value.try_into()?;
+							let lit = Literal::try_from(value_entry.value)
+								.map_err(|e| e.at_key("@value"))?;
 							Ok(Self::Literal(lit, Some(ty)))
 						}
-						Err(_) => Err(InvalidExpandedJson::InvalidValueType),
+						Err(_) => Err(InvalidExpandedJson::InvalidValueType.at_key("@type")),
 					},
 				},
-				_ => Err(InvalidExpandedJson::InvalidValueType),
+				_ => Err(InvalidExpandedJson::InvalidValueType.at_key("@type")),
 			},
 			None => {
 				let language = object
@@ -298,7 +299,8 @@ impl<T> Value<T> {
 						direction,
 					)?))
 				} else {
-					let lit = value_entry.value.try_into()?;
+					let lit =
+						Literal::try_from(value_entry.value).map_err(|e| e.at_key("@value"))?;
 					Ok(Self::Literal(lit, None))
 				}
 			}
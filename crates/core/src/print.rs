@@ -7,7 +7,11 @@ pub use json_syntax::print::{Options, Print, Printed};
 
 use crate::{object, ExpandedDocument, Id, Indexed, Object};
 use contextual::WithContext;
-use rdf_types::vocabulary::{IriVocabulary, Vocabulary};
+use iref::IriBuf;
+use rdf_types::{
+	vocabulary::{IriVocabulary, Vocabulary},
+	BlankIdBuf,
+};
 
 pub trait PrintWithSizeAndVocabulary<V> {
 	fn fmt_with_size_and(
@@ -662,3 +666,91 @@ impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> PrintWithSizeAndContext<N>
 		)
 	}
 }
+
+/// Prints the given value without vocabulary.
+///
+/// Used to implement [`Print`] for the types using [`IriBuf`] and
+/// [`BlankIdBuf`] identifiers, which do not need a vocabulary to be
+/// displayed.
+fn fmt_without_vocabulary<T>(
+	value: &T,
+	f: &mut std::fmt::Formatter,
+	options: &Options,
+	indent: usize,
+) -> std::fmt::Result
+where
+	T: PrecomputeSizeWithContext<()> + PrintWithSizeAndContext<()>,
+{
+	let mut sizes = Vec::new();
+	value.contextual_pre_compute_size(&(), options, &mut sizes);
+	let mut index = 0;
+	value.contextual_fmt_with_size(&(), f, options, indent, &sizes, &mut index)
+}
+
+impl Print for ExpandedDocument<IriBuf, BlankIdBuf> {
+	fn fmt_with(
+		&self,
+		f: &mut std::fmt::Formatter,
+		options: &Options,
+		indent: usize,
+	) -> std::fmt::Result {
+		fmt_without_vocabulary(self, f, options, indent)
+	}
+}
+
+impl std::fmt::Display for ExpandedDocument<IriBuf, BlankIdBuf> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Display::fmt(&self.compact_print(), f)
+	}
+}
+
+impl Print for Indexed<Object<IriBuf, BlankIdBuf>> {
+	fn fmt_with(
+		&self,
+		f: &mut std::fmt::Formatter,
+		options: &Options,
+		indent: usize,
+	) -> std::fmt::Result {
+		fmt_without_vocabulary(self, f, options, indent)
+	}
+}
+
+impl std::fmt::Display for Indexed<Object<IriBuf, BlankIdBuf>> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Display::fmt(&self.compact_print(), f)
+	}
+}
+
+impl Print for object::Node<IriBuf, BlankIdBuf> {
+	fn fmt_with(
+		&self,
+		f: &mut std::fmt::Formatter,
+		options: &Options,
+		indent: usize,
+	) -> std::fmt::Result {
+		fmt_without_vocabulary(self, f, options, indent)
+	}
+}
+
+impl std::fmt::Display for object::Node<IriBuf, BlankIdBuf> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Display::fmt(&self.compact_print(), f)
+	}
+}
+
+impl Print for Indexed<object::Node<IriBuf, BlankIdBuf>> {
+	fn fmt_with(
+		&self,
+		f: &mut std::fmt::Formatter,
+		options: &Options,
+		indent: usize,
+	) -> std::fmt::Result {
+		fmt_without_vocabulary(self, f, options, indent)
+	}
+}
+
+impl std::fmt::Display for Indexed<object::Node<IriBuf, BlankIdBuf>> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Display::fmt(&self.compact_print(), f)
+	}
+}
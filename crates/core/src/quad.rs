@@ -35,7 +35,10 @@ impl<T, B> LdQuads<T, B> for ExpandedDocument<T, B> {
 	fn quads(&self) -> Quads<T, B> {
 		let mut stack = SmallVec::new();
 		stack.push(QuadsFrame::IndexedObjectSet(None, self.iter()));
-		Quads { stack }
+		Quads {
+			stack,
+			object_index: None,
+		}
 	}
 }
 
@@ -43,7 +46,10 @@ impl<T, B> LdQuads<T, B> for FlattenedDocument<T, B> {
 	fn quads(&self) -> Quads<T, B> {
 		let mut stack = SmallVec::new();
 		stack.push(QuadsFrame::IndexedNodeSlice(None, self.iter()));
-		Quads { stack }
+		Quads {
+			stack,
+			object_index: None,
+		}
 	}
 }
 
@@ -55,7 +61,10 @@ impl<T: Eq + Hash, B: Eq + Hash> LdQuads<T, B> for NodeMap<T, B> {
 			stack.push(QuadsFrame::NodeMapGraph(id, graph.nodes()));
 		}
 
-		Quads { stack }
+		Quads {
+			stack,
+			object_index: None,
+		}
 	}
 }
 
@@ -63,6 +72,7 @@ const STACK_LEN: usize = 6;
 
 pub struct Quads<'a, T, B> {
 	stack: SmallVec<[QuadsFrame<'a, T, B>; STACK_LEN]>,
+	object_index: Option<&'a str>,
 }
 
 enum QuadsFrame<'a, T, B> {
@@ -116,6 +126,12 @@ enum QuadsFrame<'a, T, B> {
 }
 
 impl<'a, T, B> Quads<'a, T, B> {
+	/// Returns the `@index` of the object of the last quad returned by the
+	/// iterator, if any.
+	pub fn object_index(&self) -> Option<&'a str> {
+		self.object_index
+	}
+
 	fn push_object(&mut self, graph: Option<&'a Id<T, B>>, object: &'a Indexed<Object<T, B>>) {
 		match object.inner() {
 			Object::Node(node) => self.push_node(graph, node),
@@ -164,6 +180,7 @@ impl<'a, T, B> Iterator for Quads<'a, T, B> {
 	type Item = QuadRef<'a, T, B>;
 
 	fn next(&mut self) -> Option<Self::Item> {
+		self.object_index = None;
 		while let Some(last) = self.stack.last_mut() {
 			match last {
 				QuadsFrame::NodeMapGraph(graph, nodes) => {
@@ -264,6 +281,7 @@ impl<'a, T, B> Iterator for Quads<'a, T, B> {
 					match objects.next() {
 						Some(object) => {
 							self.push_object(graph, object);
+							self.object_index = object.index();
 							return Some(QuadRef(
 								graph,
 								subject,
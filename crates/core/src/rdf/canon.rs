@@ -0,0 +1,486 @@
+//! RDF dataset canonicalization.
+//!
+//! Implements the [RDFC-1.0] algorithm, which gives the blank nodes of a
+//! dataset labels (`c14n0`, `c14n1`, ...) depending only on the content of
+//! the dataset. Isomorphic datasets, whatever their original blank node
+//! labels, have the same canonical form.
+//!
+//! Canonicalizing a specially crafted dataset (with many indistinguishable
+//! blank nodes) can take exponential time: the size of untrusted datasets
+//! should be bounded before canonicalizing them.
+//!
+//! [RDFC-1.0]: https://www.w3.org/TR/rdf-canon/
+use super::{sort_quads, Quad, ValidId, Value};
+use contextual::WithContext;
+use rdf_types::{
+	vocabulary::BlankIdVocabularyMut, BlankIdBuf, RdfDisplayWithContext, Term, Vocabulary,
+};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+
+/// Computes the canonical label (without the `_:` prefix) of each blank node
+/// of the given quads.
+pub fn canonical_labels<V: Vocabulary>(
+	vocabulary: &V,
+	quads: &[Quad<V::Iri, V::BlankId, V::Literal>],
+) -> HashMap<V::BlankId, String>
+where
+	V::BlankId: Clone + Eq + Hash,
+	ValidId<V::Iri, V::BlankId>: RdfDisplayWithContext<V>,
+	Value<V::Iri, V::BlankId, V::Literal>: RdfDisplayWithContext<V>,
+{
+	let mut blank_ids = Vec::new();
+	let mut indexes = HashMap::new();
+	let mut id = |id: &ValidId<V::Iri, V::BlankId>| match id {
+		ValidId::Blank(b) => Component::Blank(*indexes.entry(b.clone()).or_insert_with(|| {
+			blank_ids.push(b.clone());
+			blank_ids.len() - 1
+		})),
+		other => Component::Other(other.with(vocabulary).rdf_display().to_string()),
+	};
+
+	let mut canonical_quads = Vec::with_capacity(quads.len());
+	let mut seen = HashSet::new();
+	for rdf_types::Quad(s, p, o, g) in quads {
+		let quad = CanonicalQuad {
+			subject: id(s),
+			predicate: p.with(vocabulary).rdf_display().to_string(),
+			object: match o {
+				Term::Id(o) => id(o),
+				literal => Component::Other(literal.with(vocabulary).rdf_display().to_string()),
+			},
+			graph: g.as_ref().map(&mut id),
+		};
+
+		if seen.insert(quad.clone()) {
+			canonical_quads.push(quad)
+		}
+	}
+
+	let labels = Canonicalization::new(&canonical_quads, blank_ids.len()).run();
+	blank_ids.into_iter().zip(labels).collect()
+}
+
+/// Relabels the blank nodes of the given quads with their canonical labels,
+/// then sorts the quads in canonical order (see [`sort_quads`]) and removes
+/// the duplicates.
+pub fn canonicalize_quads<V>(
+	vocabulary: &mut V,
+	quads: &mut Vec<Quad<V::Iri, V::BlankId, V::Literal>>,
+) where
+	V: Vocabulary + BlankIdVocabularyMut,
+	V::Iri: PartialEq,
+	V::BlankId: Clone + Eq + Hash,
+	V::Literal: PartialEq,
+	ValidId<V::Iri, V::BlankId>: RdfDisplayWithContext<V>,
+	Value<V::Iri, V::BlankId, V::Literal>: RdfDisplayWithContext<V>,
+{
+	let relabeling: HashMap<_, _> = canonical_labels(vocabulary, quads)
+		.into_iter()
+		.map(|(b, label)| {
+			let canonical = BlankIdBuf::new(format!("_:{label}")).unwrap();
+			(b, vocabulary.insert_blank_id(&canonical))
+		})
+		.collect();
+
+	let relabel = |id: &mut ValidId<V::Iri, V::BlankId>| {
+		if let ValidId::Blank(b) = id {
+			*b = relabeling[b].clone()
+		}
+	};
+
+	for rdf_types::Quad(s, _, o, g) in quads.iter_mut() {
+		relabel(s);
+		if let Term::Id(o) = o {
+			relabel(o)
+		}
+
+		if let Some(g) = g {
+			relabel(g)
+		}
+	}
+
+	sort_quads(vocabulary, quads);
+	quads.dedup()
+}
+
+/// Returns the canonical N-Quads serialization of the given quads: blank
+/// nodes are relabeled with their canonical labels, and the quads are sorted
+/// and deduplicated.
+pub fn canonical_nquads<V: Vocabulary>(
+	vocabulary: &V,
+	quads: &[Quad<V::Iri, V::BlankId, V::Literal>],
+) -> String
+where
+	V::BlankId: Clone + Eq + Hash,
+	ValidId<V::Iri, V::BlankId>: RdfDisplayWithContext<V>,
+	Value<V::Iri, V::BlankId, V::Literal>: RdfDisplayWithContext<V>,
+{
+	let labels = canonical_labels(vocabulary, quads);
+	let id = |id: &ValidId<V::Iri, V::BlankId>| match id {
+		ValidId::Blank(b) => format!("_:{}", labels[b]),
+		other => other.with(vocabulary).rdf_display().to_string(),
+	};
+
+	let mut lines: Vec<String> = quads
+		.iter()
+		.map(|rdf_types::Quad(s, p, o, g)| {
+			let p = p.with(vocabulary).rdf_display();
+			let o = match o {
+				Term::Id(o) => id(o),
+				literal => literal.with(vocabulary).rdf_display().to_string(),
+			};
+
+			match g {
+				Some(g) => format!("{} {p} {o} {} .\n", id(s), id(g)),
+				None => format!("{} {p} {o} .\n", id(s)),
+			}
+		})
+		.collect();
+
+	lines.sort_unstable();
+	lines.dedup();
+	lines.concat()
+}
+
+/// Subject, object or graph of a quad: a blank node (by index) or any other
+/// term in N-Quads form.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Component {
+	Blank(usize),
+	Other(String),
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CanonicalQuad {
+	subject: Component,
+	predicate: String,
+	object: Component,
+	graph: Option<Component>,
+}
+
+impl CanonicalQuad {
+	/// Serializes the quad in N-Quads, labeling the blank nodes with the
+	/// given function.
+	fn to_nquads<'a>(&self, label: impl Fn(usize) -> &'a str) -> String {
+		let component = |c: &Component| match c {
+			Component::Blank(n) => format!("_:{}", label(*n)),
+			Component::Other(s) => s.clone(),
+		};
+
+		match &self.graph {
+			Some(g) => format!(
+				"{} {} {} {} .\n",
+				component(&self.subject),
+				self.predicate,
+				component(&self.object),
+				component(g)
+			),
+			None => format!(
+				"{} {} {} .\n",
+				component(&self.subject),
+				self.predicate,
+				component(&self.object)
+			),
+		}
+	}
+}
+
+/// Blank node identifier issuer.
+#[derive(Clone)]
+struct Issuer {
+	prefix: &'static str,
+	issued: HashMap<usize, String>,
+
+	/// Blank nodes in issuance order.
+	order: Vec<usize>,
+}
+
+impl Issuer {
+	fn new(prefix: &'static str) -> Self {
+		Self {
+			prefix,
+			issued: HashMap::new(),
+			order: Vec::new(),
+		}
+	}
+
+	fn get(&self, n: usize) -> Option<&str> {
+		self.issued.get(&n).map(String::as_str)
+	}
+
+	fn issue(&mut self, n: usize) -> &str {
+		let count = self.order.len();
+		let order = &mut self.order;
+		let prefix = self.prefix;
+		self.issued.entry(n).or_insert_with(|| {
+			order.push(n);
+			format!("{prefix}{count}")
+		})
+	}
+}
+
+/// Canonicalization state.
+struct Canonicalization<'a> {
+	/// Quads mentioning each blank node.
+	blank_quads: Vec<Vec<&'a CanonicalQuad>>,
+
+	canonical: Issuer,
+}
+
+impl<'a> Canonicalization<'a> {
+	fn new(quads: &'a [CanonicalQuad], blank_count: usize) -> Self {
+		let mut blank_quads = vec![Vec::new(); blank_count];
+		for quad in quads {
+			let mut blanks: Vec<usize> =
+				[Some(&quad.subject), Some(&quad.object), quad.graph.as_ref()]
+					.into_iter()
+					.flatten()
+					.filter_map(|c| match c {
+						Component::Blank(n) => Some(*n),
+						Component::Other(_) => None,
+					})
+					.collect();
+
+			blanks.sort_unstable();
+			blanks.dedup();
+			for n in blanks {
+				blank_quads[n].push(quad)
+			}
+		}
+
+		Self {
+			blank_quads,
+			canonical: Issuer::new("c14n"),
+		}
+	}
+
+	/// Runs the canonicalization algorithm, returning the canonical label of
+	/// each blank node.
+	fn run(mut self) -> Vec<String> {
+		let mut hash_to_blanks: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+		for n in 0..self.blank_quads.len() {
+			hash_to_blanks
+				.entry(self.hash_first_degree(n))
+				.or_default()
+				.push(n)
+		}
+
+		for blanks in hash_to_blanks.values() {
+			if let [n] = blanks.as_slice() {
+				self.canonical.issue(*n);
+			}
+		}
+
+		for blanks in hash_to_blanks.values() {
+			if blanks.len() == 1 {
+				continue;
+			}
+
+			let mut hash_paths = Vec::new();
+			for &n in blanks {
+				if self.canonical.get(n).is_some() {
+					continue;
+				}
+
+				let mut issuer = Issuer::new("b");
+				issuer.issue(n);
+				hash_paths.push(self.hash_n_degree(n, issuer))
+			}
+
+			hash_paths.sort_by(|(a, _), (b, _)| a.cmp(b));
+			for (_, issuer) in hash_paths {
+				for n in issuer.order {
+					self.canonical.issue(n);
+				}
+			}
+		}
+
+		(0..self.blank_quads.len())
+			.map(|n| self.canonical.get(n).unwrap().to_owned())
+			.collect()
+	}
+
+	/// Hash First Degree Quads algorithm.
+	fn hash_first_degree(&self, n: usize) -> String {
+		let mut lines: Vec<String> = self.blank_quads[n]
+			.iter()
+			.map(|quad| quad.to_nquads(|m| if m == n { "a" } else { "z" }))
+			.collect();
+
+		lines.sort_unstable();
+		hash(&lines.concat())
+	}
+
+	/// Hash Related Blank Node algorithm.
+	fn hash_related(
+		&self,
+		related: usize,
+		quad: &CanonicalQuad,
+		issuer: &Issuer,
+		position: &str,
+	) -> String {
+		let mut input = position.to_owned();
+		if position != "g" {
+			// The predicate is in N-Quads form, between `<` and `>`.
+			input.push_str(&quad.predicate);
+		}
+
+		match self.canonical.get(related).or_else(|| issuer.get(related)) {
+			Some(id) => {
+				input.push_str("_:");
+				input.push_str(id)
+			}
+			None => input.push_str(&self.hash_first_degree(related)),
+		}
+
+		hash(&input)
+	}
+
+	/// Hash N-Degree Quads algorithm.
+	fn hash_n_degree(&self, n: usize, mut issuer: Issuer) -> (String, Issuer) {
+		let mut hash_to_related: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+		for quad in &self.blank_quads[n] {
+			for (component, position) in [
+				(Some(&quad.subject), "s"),
+				(Some(&quad.object), "o"),
+				(quad.graph.as_ref(), "g"),
+			] {
+				if let Some(Component::Blank(related)) = component {
+					if *related != n {
+						hash_to_related
+							.entry(self.hash_related(*related, quad, &issuer, position))
+							.or_default()
+							.push(*related)
+					}
+				}
+			}
+		}
+
+		let mut data = String::new();
+		for (related_hash, mut blanks) in hash_to_related {
+			data.push_str(&related_hash);
+
+			let mut chosen: Option<(String, Issuer)> = None;
+			for permutation in permutohedron::Heap::new(&mut blanks) {
+				if let Some(path) = self.permutation_path(&permutation, &issuer, chosen.as_ref()) {
+					chosen = Some(path)
+				}
+			}
+
+			let (chosen_path, chosen_issuer) = chosen.unwrap();
+			data.push_str(&chosen_path);
+			issuer = chosen_issuer
+		}
+
+		(hash(&data), issuer)
+	}
+
+	/// Computes the path of the given permutation of related blank nodes,
+	/// unless it is not smaller than the chosen path.
+	fn permutation_path(
+		&self,
+		permutation: &[usize],
+		issuer: &Issuer,
+		chosen: Option<&(String, Issuer)>,
+	) -> Option<(String, Issuer)> {
+		let is_worse = |path: &str| {
+			chosen.is_some_and(|(chosen, _)| path.len() >= chosen.len() && path > chosen.as_str())
+		};
+
+		let mut issuer = issuer.clone();
+		let mut path = String::new();
+		let mut recursion = Vec::new();
+		for &related in permutation {
+			match self.canonical.get(related) {
+				Some(id) => {
+					path.push_str("_:");
+					path.push_str(id)
+				}
+				None => {
+					if issuer.get(related).is_none() {
+						recursion.push(related)
+					}
+
+					path.push_str("_:");
+					path.push_str(issuer.issue(related))
+				}
+			}
+
+			if is_worse(&path) {
+				return None;
+			}
+		}
+
+		for related in recursion {
+			let (related_hash, related_issuer) = self.hash_n_degree(related, issuer.clone());
+			path.push_str("_:");
+			path.push_str(issuer.issue(related));
+			path.push('<');
+			path.push_str(&related_hash);
+			path.push('>');
+			issuer = related_issuer;
+
+			if is_worse(&path) {
+				return None;
+			}
+		}
+
+		match chosen {
+			Some((chosen, _)) if path.as_str() >= chosen.as_str() => None,
+			_ => Some((path, issuer)),
+		}
+	}
+}
+
+/// Hex-encoded SHA-256 hash of the given string.
+fn hash(input: &str) -> String {
+	Sha256::digest(input.as_bytes())
+		.iter()
+		.map(|b| format!("{b:02x}"))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rdf_types::Literal;
+	use static_iref::iri;
+
+	type TestQuad = Quad<iref::IriBuf, BlankIdBuf, Literal>;
+
+	fn blank(label: &str) -> ValidId<iref::IriBuf, BlankIdBuf> {
+		ValidId::Blank(BlankIdBuf::new(format!("_:{label}")).unwrap())
+	}
+
+	fn iri(iri: &iref::Iri) -> ValidId<iref::IriBuf, BlankIdBuf> {
+		ValidId::Iri(iri.to_owned())
+	}
+
+	/// Two blank nodes pointing at each other, one of them being typed.
+	fn cycle(a: &str, b: &str) -> Vec<TestQuad> {
+		let p = iri!("http://example.org/p");
+		let ty = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+		let class = iri!("http://example.org/Class");
+		vec![
+			rdf_types::Quad(blank(a), iri(p), Term::Id(blank(b)), None),
+			rdf_types::Quad(blank(b), iri(p), Term::Id(blank(a)), None),
+			rdf_types::Quad(blank(a), iri(ty), Term::Id(iri(class)), None),
+		]
+	}
+
+	#[test]
+	fn isomorphic_datasets() {
+		let mut x = cycle("x", "y");
+		let mut y = cycle("b1", "b0");
+		y.reverse();
+
+		assert_eq!(canonical_nquads(&(), &x), canonical_nquads(&(), &y));
+
+		canonicalize_quads(&mut (), &mut x);
+		canonicalize_quads(&mut (), &mut y);
+		assert_eq!(x, y);
+		assert!(canonical_nquads(&(), &x).contains("_:c14n0"))
+	}
+}
@@ -0,0 +1,166 @@
+//! Recovery of the `@index` of property values preserved through RDF.
+//!
+//! See [`Quads::with_index_property`](super::Quads::with_index_property).
+use super::{RDF_OBJECT, RDF_PREDICATE, RDF_SUBJECT};
+use crate::object::node::Graph;
+use crate::{ExpandedDocument, Id, IndexedObject, Object, ValidId};
+use rdf_types::vocabulary::IriVocabularyMut;
+use std::hash::Hash;
+
+/// Reified quad holding the `@index` of its object.
+struct Annotation<T, B> {
+	subject: Id<T, B>,
+	predicate: Id<T, B>,
+	object: IndexedObject<T, B>,
+	index: String,
+}
+
+/// Properties of a reified quad.
+struct Properties<T> {
+	subject: T,
+	predicate: T,
+	object: T,
+	index: T,
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<T, B> {
+	/// Restores the `@index` of property values preserved through the given
+	/// index property, using the given vocabulary.
+	///
+	/// This is the reverse of
+	/// [`Quads::with_index_property`](super::Quads::with_index_property), to
+	/// be applied on a document converted back from RDF. Each node describing
+	/// a reified quad with an index (and nothing else) is removed, and its
+	/// index is set on the matching property value of the subject node of
+	/// the same graph. Reified quads without matching value are kept.
+	pub fn restore_rdf_indexes_with<V: IriVocabularyMut<Iri = T>>(
+		&mut self,
+		vocabulary: &mut V,
+		index_property: &T,
+	) {
+		let properties = Properties {
+			subject: vocabulary.insert(RDF_SUBJECT),
+			predicate: vocabulary.insert(RDF_PREDICATE),
+			object: vocabulary.insert(RDF_OBJECT),
+			index: index_property.clone(),
+		};
+
+		let objects = std::mem::take(self).into_objects();
+		*self = restore_in(objects, &properties).into()
+	}
+
+	/// Restores the `@index` of property values preserved through the given
+	/// index property.
+	///
+	/// See [`Self::restore_rdf_indexes_with`].
+	pub fn restore_rdf_indexes(&mut self, index_property: &T)
+	where
+		(): IriVocabularyMut<Iri = T>,
+	{
+		self.restore_rdf_indexes_with(rdf_types::vocabulary::no_vocabulary_mut(), index_property)
+	}
+}
+
+fn restore_in<T: Clone + Eq + Hash, B: Clone + Eq + Hash>(
+	objects: Graph<T, B>,
+	properties: &Properties<T>,
+) -> Graph<T, B> {
+	let mut annotations = Vec::new();
+	let mut result = Vec::with_capacity(objects.len());
+
+	for mut object in objects {
+		match annotation(&object, properties) {
+			Some(annotation) => annotations.push((annotation, object)),
+			None => {
+				if let Some(node) = object.inner_mut().as_node_mut() {
+					if let Some(graph) = node.graph_entry_mut() {
+						*graph = restore_in(std::mem::take(graph), properties)
+					}
+				}
+
+				result.push(object)
+			}
+		}
+	}
+
+	for (annotation, object) in annotations {
+		if !apply(&mut result, annotation) {
+			result.push(object)
+		}
+	}
+
+	result.into_iter().collect()
+}
+
+/// Reads the reified quad described by the given object, if any.
+fn annotation<T: Clone + Eq + Hash, B: Clone + Eq + Hash>(
+	object: &IndexedObject<T, B>,
+	properties: &Properties<T>,
+) -> Option<Annotation<T, B>> {
+	let node = object.inner().as_node()?;
+
+	if object.index().is_some()
+		|| node.id.is_none()
+		|| node.types.is_some()
+		|| node.graph_entry().is_some()
+		|| node.included_entry().is_some()
+		|| node.reverse_properties_entry().is_some()
+		|| node.properties().len() != 4
+	{
+		return None;
+	}
+
+	let single = |property: &T| {
+		let mut values = node
+			.properties()
+			.get(&ValidId::<T, B>::Iri(property.clone()));
+		let value = values.next()?;
+		match values.next() {
+			Some(_) => None,
+			None => Some(value),
+		}
+	};
+
+	let reference = |property: &T| single(property)?.inner().as_node()?.id.clone();
+
+	Some(Annotation {
+		subject: reference(&properties.subject)?,
+		predicate: reference(&properties.predicate)?,
+		object: single(&properties.object)?.clone(),
+		index: single(&properties.index)?
+			.inner()
+			.as_value()?
+			.as_str()?
+			.to_owned(),
+	})
+}
+
+/// Sets the index of the annotated value, returning `false` if it is not
+/// found.
+fn apply<T: Eq + Hash, B: Eq + Hash>(
+	objects: &mut [IndexedObject<T, B>],
+	annotation: Annotation<T, B>,
+) -> bool {
+	for object in objects {
+		if let Object::Node(node) = object.inner_mut() {
+			if node.id.as_ref() != Some(&annotation.subject) {
+				continue;
+			}
+
+			for (property, values) in node.properties_mut().iter_mut() {
+				if *property != annotation.predicate {
+					continue;
+				}
+
+				for value in values.iter_mut() {
+					if value.index().is_none() && *value == annotation.object {
+						value.set_index(Some(annotation.index));
+						return true;
+					}
+				}
+			}
+		}
+	}
+
+	false
+}
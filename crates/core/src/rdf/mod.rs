@@ -1,6 +1,8 @@
-use std::str::FromStr;
+use std::{fmt, hash::Hash, str::FromStr};
 
-use crate::{object::value, Direction, Id, Indexed, IndexedObject, Node, Object, ValidId};
+use crate::{
+	object::value, Direction, ExpandedDocument, Id, Indexed, IndexedObject, Node, Object, ValidId,
+};
 use iref::{Iri, IriBuf};
 use json_syntax::Print;
 use langtag::LangTagBuf;
@@ -11,8 +13,13 @@ use rdf_types::{
 use smallvec::SmallVec;
 use static_iref::iri;
 
+pub mod canon;
+mod index;
 mod quad;
+pub mod sink;
+pub mod validation;
 pub use quad::*;
+pub use sink::QuadSink;
 
 pub const RDF_TYPE: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
 pub const RDF_FIRST: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
@@ -20,6 +27,9 @@ pub const RDF_REST: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest
 pub const RDF_VALUE: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#value");
 pub const RDF_DIRECTION: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#direction");
 pub const RDF_JSON: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON");
+pub const RDF_SUBJECT: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#subject");
+pub const RDF_PREDICATE: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#predicate");
+pub const RDF_OBJECT: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#object");
 /// IRI of the `http://www.w3.org/1999/02/22-rdf-syntax-ns#nil` value.
 pub const RDF_NIL: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil");
 
@@ -28,6 +38,93 @@ pub const XSD_INTEGER: &Iri = iri!("http://www.w3.org/2001/XMLSchema#integer");
 pub const XSD_DOUBLE: &Iri = iri!("http://www.w3.org/2001/XMLSchema#double");
 pub const XSD_STRING: &Iri = iri!("http://www.w3.org/2001/XMLSchema#string");
 
+/// How lists of lists are serialized into RDF.
+///
+/// Empty lists are serialized as `rdf:nil`, including when nested, unless
+/// nested lists are [flattened](Self::Flatten).
+///
+/// The RDF serialization of an expanded document always keeps nested lists.
+/// Use [`Self::apply`] on the document beforehand to select another
+/// behavior.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NestedLists {
+	/// Nested lists are serialized as nested `rdf:List` structures, as
+	/// specified by JSON-LD 1.1.
+	#[default]
+	Keep,
+
+	/// The items of nested lists are spliced into the enclosing list, and
+	/// empty nested lists are dropped.
+	///
+	/// See [`ExpandedDocument::flatten_nested_lists`](crate::ExpandedDocument::flatten_nested_lists).
+	Flatten,
+
+	/// Serialization fails if the document contains a list of lists.
+	Reject,
+}
+
+impl NestedLists {
+	/// Prepares the given document for its RDF serialization, flattening or
+	/// rejecting its nested lists as required.
+	pub fn apply<T: Eq + Hash, B: Eq + Hash>(
+		self,
+		document: &mut ExpandedDocument<T, B>,
+	) -> Result<(), NestedListsRejected> {
+		match self {
+			Self::Keep => Ok(()),
+			Self::Flatten => {
+				document.flatten_nested_lists();
+				Ok(())
+			}
+			Self::Reject => {
+				if document.has_nested_lists() {
+					Err(NestedListsRejected)
+				} else {
+					Ok(())
+				}
+			}
+		}
+	}
+}
+
+/// Error raised by [`NestedLists::apply`] when a document contains a list of
+/// lists, rejected by [`NestedLists::Reject`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("nested lists are not allowed")]
+pub struct NestedListsRejected;
+
+/// Selects the graphs from which RDF quads are produced.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum GraphFilter<I> {
+	/// All graphs.
+	#[default]
+	All,
+
+	/// Only the default graph.
+	DefaultGraph,
+
+	/// Only named graphs.
+	NamedGraphs,
+
+	/// Only the named graphs with the given IRIs.
+	Graphs(Vec<I>),
+}
+
+impl<I: PartialEq> GraphFilter<I> {
+	/// Checks if the given graph is selected by this filter.
+	pub fn accepts<B>(&self, graph: Option<&Id<I, B>>) -> bool {
+		match self {
+			Self::All => true,
+			Self::DefaultGraph => graph.is_none(),
+			Self::NamedGraphs => graph.is_some(),
+			Self::Graphs(iris) => match graph {
+				Some(Id::Valid(ValidId::Iri(iri))) => iris.contains(iri),
+				_ => false,
+			},
+		}
+	}
+}
+
 /// JSON-LD to RDF triple.
 pub type Triple<T, B, L> = rdf_types::Triple<ValidId<T, B>, ValidId<T, B>, Value<T, B, L>>;
 
@@ -44,7 +141,12 @@ impl<T: Clone, B: Clone> Id<T, B> {
 ///
 /// Used by the RDF serializer to decide how to encode
 /// [`Direction`](crate::Direction)s.
+///
+/// The string form of a direction representation method is `i18n-datatype`
+/// or `compound-literal`, as in the `rdfDirection` option of the JSON-LD
+/// API.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RdfDirection {
 	/// Encode direction in the string value type IRI using the
 	/// `https://www.w3.org/ns/i18n#` prefix.
@@ -53,6 +155,7 @@ pub enum RdfDirection {
 	/// `https://www.w3.org/ns/i18n#language_direction` or simply
 	/// `https://www.w3.org/ns/i18n#direction` otherwise where `direction` is
 	/// either `rtl` or `ltr`.
+	#[cfg_attr(feature = "serde", serde(rename = "i18n-datatype"))]
 	I18nDatatype,
 
 	/// Encode the direction using a compound literal value.
@@ -64,10 +167,28 @@ pub enum RdfDirection {
 	/// _:b http://www.w3.org/1999/02/22-rdf-syntax-ns#direction direction
 	/// ```
 	/// where `direction` is either `rtl` or `ltr`.
+	#[cfg_attr(feature = "serde", serde(rename = "compound-literal"))]
 	CompoundLiteral,
 }
 
-#[derive(Debug, Clone)]
+impl RdfDirection {
+	/// Returns the name of the direction representation method.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::I18nDatatype => "i18n-datatype",
+			Self::CompoundLiteral => "compound-literal",
+		}
+	}
+}
+
+impl fmt::Display for RdfDirection {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("invalid RDF direction `{0}`")]
 pub struct InvalidRdfDirection(pub String);
 
 impl FromStr for RdfDirection {
@@ -1,13 +1,16 @@
-use super::{RdfDirection, ValidId, Value};
+use super::{GraphFilter, RdfDirection, ValidId, Value};
 use crate::{flattening::NodeMap, ExpandedDocument, FlattenedDocument, LdQuads};
+use contextual::WithContext;
 use rdf_types::vocabulary::IriVocabularyMut;
 use rdf_types::{
 	vocabulary::{BlankIdVocabulary, IriVocabulary, LiteralVocabulary, LiteralVocabularyMut},
-	Generator, Triple, Vocabulary,
+	Generator, Literal, LiteralType, RdfDisplay, RdfDisplayWithContext, Triple, Vocabulary,
 };
 use std::borrow::Cow;
 use std::convert::TryInto;
 use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 pub type Quad<T, B, L> =
 	rdf_types::Quad<ValidId<T, B>, ValidId<T, B>, Value<T, B, L>, ValidId<T, B>>;
@@ -31,6 +34,18 @@ type VocabularyCompoundLiteral<'a, N> = Compound<
 	<N as LiteralVocabulary>::Literal,
 >;
 
+type VocabularyTriple<N> = super::Triple<
+	<N as IriVocabulary>::Iri,
+	<N as BlankIdVocabulary>::BlankId,
+	<N as LiteralVocabulary>::Literal,
+>;
+
+/// Pending triples of a reified quad, in reverse order.
+struct Annotation<'a, N: Vocabulary> {
+	graph: Option<&'a ValidId<N::Iri, N::BlankId>>,
+	triples: Vec<VocabularyTriple<N>>,
+}
+
 /// Iterator over the RDF Quads of a JSON-LD document.
 pub struct Quads<'a, N: Vocabulary, G: Generator<N>> {
 	vocabulary: &'a mut N,
@@ -39,17 +54,60 @@ pub struct Quads<'a, N: Vocabulary, G: Generator<N>> {
 	compound_value: Option<VocabularyCompoundLiteral<'a, N>>,
 	quads: crate::quad::Quads<'a, N::Iri, N::BlankId>,
 	produce_generalized_rdf: bool,
+	graph_filter: Option<&'a GraphFilter<N::Iri>>,
+	index_property: Option<&'a N::Iri>,
+	annotation: Option<Annotation<'a, N>>,
+	dropped: Option<&'a AtomicUsize>,
 }
 
 impl<'a, N: Vocabulary, G: Generator<N>> Quads<'a, N, G> {
 	pub fn cloned(self) -> ClonedQuads<'a, N, G> {
 		ClonedQuads { inner: self }
 	}
+
+	/// Only produces the quads of the graphs selected by the given filter.
+	///
+	/// Quads of other graphs are skipped before being converted to RDF.
+	pub fn with_graph_filter(self, filter: &'a GraphFilter<N::Iri>) -> Self {
+		Self {
+			graph_filter: Some(filter),
+			..self
+		}
+	}
+
+	/// Preserves the `@index` of property values through the given property.
+	///
+	/// Each quad whose object has an `@index` is reified with a fresh blank
+	/// node `_:r`, followed by these triples:
+	/// ```nquads
+	/// _:r http://www.w3.org/1999/02/22-rdf-syntax-ns#subject subject
+	/// _:r http://www.w3.org/1999/02/22-rdf-syntax-ns#predicate predicate
+	/// _:r http://www.w3.org/1999/02/22-rdf-syntax-ns#object object
+	/// _:r property "index"
+	/// ```
+	///
+	/// See [`ExpandedDocument::restore_rdf_indexes_with`] to recover the
+	/// indexes from a document converted back from RDF.
+	pub fn with_index_property(self, property: &'a N::Iri) -> Self {
+		Self {
+			index_property: Some(property),
+			..self
+		}
+	}
+
+	/// Counts in `counter` the quads skipped because their predicate is a
+	/// blank node identifier, when generalized RDF is not produced.
+	pub fn count_dropped(self, counter: &'a AtomicUsize) -> Self {
+		Self {
+			dropped: Some(counter),
+			..self
+		}
+	}
 }
 
 impl<'a, N: Vocabulary + IriVocabularyMut, G: Generator<N>> Iterator for Quads<'a, N, G>
 where
-	N::Iri: Clone,
+	N::Iri: Clone + PartialEq,
 	N::BlankId: Clone,
 	N::Literal: Clone,
 	N: LiteralVocabularyMut,
@@ -58,6 +116,20 @@ where
 
 	fn next(&mut self) -> Option<Self::Item> {
 		loop {
+			if let Some(annotation) = &mut self.annotation {
+				match annotation.triples.pop() {
+					Some(Triple(subject, property, object)) => {
+						break Some(rdf_types::Quad(
+							Cow::Owned(subject),
+							Cow::Owned(property),
+							object,
+							annotation.graph,
+						))
+					}
+					None => self.annotation = None,
+				}
+			}
+
 			if let Some(compound_value) = &mut self.compound_value {
 				match compound_value.triples.next(
 					self.vocabulary,
@@ -73,6 +145,10 @@ where
 								compound_value.graph,
 							));
 						}
+
+						if let Some(counter) = self.dropped {
+							counter.fetch_add(1, Ordering::Relaxed);
+						}
 					}
 					None => self.compound_value = None,
 				}
@@ -80,6 +156,12 @@ where
 
 			match self.quads.next() {
 				Some(crate::quad::QuadRef(graph, subject, property, object)) => {
+					if let Some(filter) = self.graph_filter {
+						if !filter.accepts(graph) {
+							continue;
+						}
+					}
+
 					let rdf_graph: Option<&'a ValidId<N::Iri, N::BlankId>> =
 						match graph.map(|r| r.try_into()) {
 							Some(Ok(r)) => Some(r),
@@ -104,6 +186,10 @@ where
 
 					if !self.produce_generalized_rdf && (*rdf_property).is_blank() {
 						// Skip gRDF quad.
+						if let Some(counter) = self.dropped {
+							counter.fetch_add(1, Ordering::Relaxed);
+						}
+
 						continue;
 					}
 
@@ -117,6 +203,46 @@ where
 							});
 						}
 
+						if let (Some(index_property), Some(index)) =
+							(self.index_property, self.quads.object_index())
+						{
+							let id = self.generator.next(self.vocabulary);
+							let ty = self.vocabulary.insert(super::XSD_STRING);
+							let index = self.vocabulary.insert_owned_literal(Literal::new(
+								index.to_owned(),
+								LiteralType::Any(ty),
+							));
+
+							let mut triples = vec![
+								Triple(
+									id.clone(),
+									ValidId::Iri(self.vocabulary.insert(super::RDF_SUBJECT)),
+									Value::Id(rdf_subject.clone()),
+								),
+								Triple(
+									id.clone(),
+									ValidId::Iri(self.vocabulary.insert(super::RDF_PREDICATE)),
+									Value::Id((*rdf_property).clone()),
+								),
+								Triple(
+									id.clone(),
+									ValidId::Iri(self.vocabulary.insert(super::RDF_OBJECT)),
+									compound_value.value.clone(),
+								),
+								Triple(
+									id,
+									ValidId::Iri(index_property.clone()),
+									Value::Literal(index),
+								),
+							];
+							triples.reverse();
+
+							self.annotation = Some(Annotation {
+								graph: rdf_graph,
+								triples,
+							});
+						}
+
 						break Some(rdf_types::Quad(
 							Cow::Borrowed(rdf_subject),
 							rdf_property,
@@ -139,7 +265,7 @@ pub struct ClonedQuads<'a, N: Vocabulary, G: Generator<N>> {
 
 impl<'a, N: Vocabulary + IriVocabularyMut, G: Generator<N>> Iterator for ClonedQuads<'a, N, G>
 where
-	N::Iri: Clone,
+	N::Iri: Clone + PartialEq,
 	N::BlankId: Clone,
 	N::Literal: Clone,
 	N: LiteralVocabularyMut,
@@ -153,6 +279,31 @@ where
 	}
 }
 
+/// Sorts the given quads in canonical order.
+///
+/// Quads are sorted by subject, predicate, object and graph, each compared
+/// through its N-Quads representation, with the default graph first. Unlike
+/// the order in which the quads are generated, this order does not depend
+/// on the vocabulary nor on the internal ordering of the document, so the
+/// quads of a document with canonical blank node labels are always sorted
+/// the same way. Use [`canon::canonicalize_quads`](super::canon::canonicalize_quads)
+/// to relabel the blank nodes first.
+pub fn sort_quads<V: Vocabulary>(vocabulary: &V, quads: &mut [Quad<V::Iri, V::BlankId, V::Literal>])
+where
+	ValidId<V::Iri, V::BlankId>: RdfDisplayWithContext<V>,
+	Value<V::Iri, V::BlankId, V::Literal>: RdfDisplayWithContext<V>,
+{
+	quads.sort_by_cached_key(|rdf_types::Quad(s, p, o, g)| {
+		(
+			s.with(vocabulary).rdf_display().to_string(),
+			p.with(vocabulary).rdf_display().to_string(),
+			o.with(vocabulary).rdf_display().to_string(),
+			g.as_ref()
+				.map(|g| g.with(vocabulary).rdf_display().to_string()),
+		)
+	})
+}
+
 pub trait RdfQuads<T, B> {
 	fn rdf_quads_full<'a, V: Vocabulary<Iri = T, BlankId = B>, G: Generator<V>>(
 		&'a self,
@@ -202,6 +353,10 @@ impl<T, B> RdfQuads<T, B> for ExpandedDocument<T, B> {
 			compound_value: None,
 			quads: self.quads(),
 			produce_generalized_rdf,
+			graph_filter: None,
+			index_property: None,
+			annotation: None,
+			dropped: None,
 		}
 	}
 }
@@ -221,10 +376,33 @@ impl<T, B> RdfQuads<T, B> for FlattenedDocument<T, B> {
 			compound_value: None,
 			quads: self.quads(),
 			produce_generalized_rdf,
+			graph_filter: None,
+			index_property: None,
+			annotation: None,
+			dropped: None,
 		}
 	}
 }
 
+/// Produces the quads of a shared document without cloning it.
+impl<T, B, D: RdfQuads<T, B>> RdfQuads<T, B> for Arc<D> {
+	fn rdf_quads_full<'a, V: Vocabulary<Iri = T, BlankId = B>, G: Generator<V>>(
+		&'a self,
+		vocabulary: &'a mut V,
+		generator: &'a mut G,
+		rdf_direction: Option<RdfDirection>,
+		produce_generalized_rdf: bool,
+	) -> Quads<'a, V, G> {
+		D::rdf_quads_full(
+			self,
+			vocabulary,
+			generator,
+			rdf_direction,
+			produce_generalized_rdf,
+		)
+	}
+}
+
 impl<T: Eq + Hash, B: Eq + Hash> RdfQuads<T, B> for NodeMap<T, B> {
 	fn rdf_quads_full<'a, V: Vocabulary<Iri = T, BlankId = B>, G: Generator<V>>(
 		&'a self,
@@ -240,6 +418,10 @@ impl<T: Eq + Hash, B: Eq + Hash> RdfQuads<T, B> for NodeMap<T, B> {
 			compound_value: None,
 			quads: self.quads(),
 			produce_generalized_rdf,
+			graph_filter: None,
+			index_property: None,
+			annotation: None,
+			dropped: None,
 		}
 	}
 }
@@ -0,0 +1,197 @@
+//! RDF quad sinks.
+//!
+//! A [`QuadSink`] receives the RDF quads produced from a JSON-LD document,
+//! one graph at a time, making it possible to load documents into RDF stores
+//! without collecting the whole dataset first.
+use super::{Quad, ValidId, Value};
+use contextual::WithContext;
+use rdf_types::{dataset::IndexedBTreeDataset, RdfDisplay, RdfDisplayWithContext, Term};
+use std::convert::Infallible;
+use std::fmt::Write;
+
+/// RDF quad sink.
+///
+/// Quads are pushed using [`insert`](Self::insert), always within a graph
+/// opened with [`begin_graph`](Self::begin_graph) and closed with
+/// [`end_graph`](Self::end_graph). The same graph may be opened more than
+/// once.
+pub trait QuadSink<T, B, L> {
+	/// Error type.
+	type Error;
+
+	/// Starts a sequence of quads in the given graph (`None` is the default
+	/// graph).
+	fn begin_graph(&mut self, _name: Option<&ValidId<T, B>>) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
+	/// Inserts a quad in the current graph.
+	fn insert(&mut self, quad: Quad<T, B, L>) -> Result<(), Self::Error>;
+
+	/// Ends the current sequence of quads.
+	fn end_graph(&mut self) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
+impl<'a, T, B, L, S: QuadSink<T, B, L>> QuadSink<T, B, L> for &'a mut S {
+	type Error = S::Error;
+
+	fn begin_graph(&mut self, name: Option<&ValidId<T, B>>) -> Result<(), Self::Error> {
+		S::begin_graph(*self, name)
+	}
+
+	fn insert(&mut self, quad: Quad<T, B, L>) -> Result<(), Self::Error> {
+		S::insert(*self, quad)
+	}
+
+	fn end_graph(&mut self) -> Result<(), Self::Error> {
+		S::end_graph(*self)
+	}
+}
+
+impl<T, B, L> QuadSink<T, B, L> for Vec<Quad<T, B, L>> {
+	type Error = Infallible;
+
+	fn insert(&mut self, quad: Quad<T, B, L>) -> Result<(), Self::Error> {
+		self.push(quad);
+		Ok(())
+	}
+}
+
+impl<T, B, L> QuadSink<T, B, L> for IndexedBTreeDataset<Term<ValidId<T, B>, L>>
+where
+	T: Clone + Ord,
+	B: Clone + Ord,
+	L: Clone + Ord,
+{
+	type Error = Infallible;
+
+	fn insert(&mut self, rdf_types::Quad(s, p, o, g): Quad<T, B, L>) -> Result<(), Self::Error> {
+		self.extend(std::iter::once(rdf_types::Quad(
+			Term::Id(s),
+			Term::Id(p),
+			o,
+			g.map(Term::Id),
+		)));
+		Ok(())
+	}
+}
+
+/// Pushes the given quads into a sink, opening a new graph each time the
+/// graph of the current quad differs from the previous one.
+pub fn drain_into<T, B, L, S>(
+	quads: impl IntoIterator<Item = Quad<T, B, L>>,
+	sink: &mut S,
+) -> Result<(), S::Error>
+where
+	T: Clone + PartialEq,
+	B: Clone + PartialEq,
+	S: QuadSink<T, B, L>,
+{
+	let mut current: Option<Option<ValidId<T, B>>> = None;
+
+	for rdf_types::Quad(s, p, o, g) in quads {
+		if current.as_ref() != Some(&g) {
+			if current.is_some() {
+				sink.end_graph()?;
+			}
+
+			sink.begin_graph(g.as_ref())?;
+		}
+
+		current = Some(g.clone());
+		sink.insert(rdf_types::Quad(s, p, o, g))?;
+	}
+
+	if current.is_some() {
+		sink.end_graph()?;
+	}
+
+	Ok(())
+}
+
+/// SPARQL `INSERT DATA` request generator.
+///
+/// Each received graph is written as a block of the request, using the
+/// vocabulary to display the terms. Literals are escaped following the
+/// N-Triples syntax, which is also valid SPARQL.
+pub struct SparqlInsert<'v, V> {
+	vocabulary: &'v V,
+	default_graph: Option<String>,
+	request: String,
+	in_named_graph: bool,
+}
+
+impl<'v, V> SparqlInsert<'v, V> {
+	/// Starts a new request.
+	pub fn new(vocabulary: &'v V) -> Self {
+		Self {
+			vocabulary,
+			default_graph: None,
+			request: "INSERT DATA {\n".to_string(),
+			in_named_graph: false,
+		}
+	}
+
+	/// Starts a new request where quads of the default graph are inserted
+	/// into the given `graph` instead.
+	pub fn new_in_graph<G: RdfDisplayWithContext<V>>(vocabulary: &'v V, graph: &G) -> Self {
+		let mut result = Self::new(vocabulary);
+		result.default_graph = Some(graph.with(vocabulary).rdf_display().to_string());
+		result
+	}
+
+	/// Ends the request and returns it.
+	pub fn into_string(mut self) -> String {
+		self.request.push('}');
+		self.request
+	}
+}
+
+impl<'v, V, T, B, L> QuadSink<T, B, L> for SparqlInsert<'v, V>
+where
+	ValidId<T, B>: RdfDisplayWithContext<V>,
+	Value<T, B, L>: RdfDisplayWithContext<V>,
+{
+	type Error = Infallible;
+
+	fn begin_graph(&mut self, name: Option<&ValidId<T, B>>) -> Result<(), Self::Error> {
+		let name = match name {
+			Some(name) => Some(name.with(self.vocabulary).rdf_display().to_string()),
+			None => self.default_graph.clone(),
+		};
+
+		if let Some(name) = name {
+			writeln!(self.request, "\tGRAPH {name} {{").unwrap();
+			self.in_named_graph = true
+		}
+
+		Ok(())
+	}
+
+	fn insert(&mut self, rdf_types::Quad(s, p, o, _): Quad<T, B, L>) -> Result<(), Self::Error> {
+		if self.in_named_graph {
+			self.request.push('\t')
+		}
+
+		writeln!(
+			self.request,
+			"\t{} {} {} .",
+			s.with(self.vocabulary).rdf_display(),
+			p.with(self.vocabulary).rdf_display(),
+			o.with(self.vocabulary).rdf_display()
+		)
+		.unwrap();
+		Ok(())
+	}
+
+	fn end_graph(&mut self) -> Result<(), Self::Error> {
+		if self.in_named_graph {
+			self.request.push_str("\t}\n");
+			self.in_named_graph = false
+		}
+
+		Ok(())
+	}
+}
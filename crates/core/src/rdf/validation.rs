@@ -0,0 +1,337 @@
+//! Well-formedness check of produced RDF.
+//!
+//! Lenient expansion may let through values that are not valid RDF terms.
+//! Relative IRIs and ill-formed language tags are silently dropped when
+//! producing quads, and literals of XSD datatypes are emitted whatever their
+//! lexical form. The [`validate_document`] function reports the former on an
+//! expanded document, and [`validate_quad`] the latter on each quad, so that
+//! such values can be detected before the quads reach an RDF store.
+use super::{Quad, Value};
+use crate::object::{self, value};
+use crate::{ExpandedDocument, Id};
+use rdf_types::{LiteralTypeRef, Vocabulary};
+use std::fmt;
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Problem found in an expanded document or an RDF quad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+	/// The given reference is not a valid absolute IRI, typically a relative
+	/// IRI left by expansion.
+	InvalidIri(String),
+
+	/// The given language tag is not a well-formed BCP47 tag.
+	InvalidLanguageTag(String),
+
+	/// The lexical form of a literal is not valid for its XSD datatype.
+	InvalidLexicalForm {
+		/// Lexical form.
+		value: String,
+
+		/// Datatype IRI.
+		datatype: String,
+	},
+}
+
+impl fmt::Display for Issue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::InvalidIri(iri) => write!(f, "invalid IRI `{iri}`"),
+			Self::InvalidLanguageTag(tag) => write!(f, "invalid language tag `{tag}`"),
+			Self::InvalidLexicalForm { value, datatype } => {
+				write!(
+					f,
+					"invalid lexical form `{value}` for datatype <{datatype}>"
+				)
+			}
+		}
+	}
+}
+
+/// Validation report.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+	/// Number of checked quads.
+	pub quads: usize,
+
+	/// Issues found in the expanded document, whose values are dropped from
+	/// the produced quads.
+	pub dropped: Vec<Issue>,
+
+	/// Issues found, with the position of the quad in which they appear.
+	pub issues: Vec<(usize, Issue)>,
+}
+
+impl Report {
+	/// Checks that no issue has been found.
+	pub fn is_valid(&self) -> bool {
+		self.dropped.is_empty() && self.issues.is_empty()
+	}
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} quads, {} issues",
+			self.quads,
+			self.dropped.len() + self.issues.len()
+		)?;
+
+		for issue in &self.dropped {
+			write!(f, "\ndropped: {issue}")?;
+		}
+
+		for (i, issue) in &self.issues {
+			write!(f, "\nquad {i}: {issue}")?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Validates the given quads.
+pub fn validate<'a, V: Vocabulary>(
+	vocabulary: &V,
+	quads: impl IntoIterator<Item = &'a Quad<V::Iri, V::BlankId, V::Literal>>,
+) -> Report
+where
+	V::Iri: 'a,
+	V::BlankId: 'a,
+	V::Literal: 'a,
+{
+	let mut report = Report::default();
+
+	for (i, quad) in quads.into_iter().enumerate() {
+		let mut issues = Vec::new();
+		validate_quad(vocabulary, quad, &mut issues);
+		report
+			.issues
+			.extend(issues.into_iter().map(|issue| (i, issue)));
+		report.quads += 1
+	}
+
+	report
+}
+
+/// Validates a single quad, pushing the found issues into `issues`.
+///
+/// Subjects, predicates and graphs are valid by construction, so only the
+/// lexical form of literal objects is checked.
+pub fn validate_quad<V: Vocabulary>(
+	vocabulary: &V,
+	rdf_types::Quad(_, _, o, _): &Quad<V::Iri, V::BlankId, V::Literal>,
+	issues: &mut Vec<Issue>,
+) {
+	if let Value::Literal(l) = o {
+		let literal = vocabulary.literal(l).unwrap();
+		if let LiteralTypeRef::Any(ty) = literal.type_ {
+			let datatype = vocabulary.iri(ty).unwrap().as_str();
+			if is_valid_lexical_form(datatype, literal.value) == Some(false) {
+				issues.push(Issue::InvalidLexicalForm {
+					value: literal.value.to_owned(),
+					datatype: datatype.to_owned(),
+				})
+			}
+		}
+	}
+}
+
+/// Validates the given expanded document, returning the issues preventing
+/// some of its values from being turned into RDF.
+pub fn validate_document<T, B>(document: &ExpandedDocument<T, B>) -> Vec<Issue> {
+	let mut issues = Vec::new();
+
+	for fragment in document.traverse() {
+		if let Some(Id::Invalid(id)) = fragment.as_id() {
+			issues.push(Issue::InvalidIri(id))
+		}
+
+		if let object::FragmentRef::ValueFragment(value::FragmentRef::Value(
+			value::EntryValueRef::Language(tag),
+		)) = fragment
+		{
+			if tag.as_well_formed().is_none() {
+				issues.push(Issue::InvalidLanguageTag(tag.as_str().to_owned()))
+			}
+		}
+	}
+
+	issues
+}
+
+/// Checks the lexical form of a literal with the given datatype.
+///
+/// Returns `None` if the datatype is not one of the checked XSD datatypes.
+pub fn is_valid_lexical_form(datatype: &str, value: &str) -> Option<bool> {
+	let valid = match datatype.strip_prefix(XSD)? {
+		"boolean" => matches!(value, "true" | "false" | "1" | "0"),
+		"decimal" => is_decimal(value),
+		"double" | "float" => is_double(value),
+		"integer" => integer(value).is_some(),
+		"nonNegativeInteger" => integer(value).is_some_and(|(neg, zero)| !neg || zero),
+		"positiveInteger" => integer(value).is_some_and(|(neg, zero)| !neg && !zero),
+		"nonPositiveInteger" => integer(value).is_some_and(|(neg, zero)| neg || zero),
+		"negativeInteger" => integer(value).is_some_and(|(neg, zero)| neg && !zero),
+		"long" => in_range(value, i64::MIN.into(), i64::MAX.into()),
+		"int" => in_range(value, i32::MIN.into(), i32::MAX.into()),
+		"short" => in_range(value, i16::MIN.into(), i16::MAX.into()),
+		"byte" => in_range(value, i8::MIN.into(), i8::MAX.into()),
+		"unsignedLong" => in_range(value, 0, u64::MAX.into()),
+		"unsignedInt" => in_range(value, 0, u32::MAX.into()),
+		"unsignedShort" => in_range(value, 0, u16::MAX.into()),
+		"unsignedByte" => in_range(value, 0, u8::MAX.into()),
+		"date" => strip_timezone(value).is_some_and(is_date),
+		"time" => strip_timezone(value).is_some_and(is_time),
+		"dateTime" => strip_timezone(value)
+			.and_then(|v| v.split_once('T'))
+			.is_some_and(|(date, time)| is_date(date) && is_time(time)),
+		_ => return None,
+	};
+
+	Some(valid)
+}
+
+fn is_digits(s: &str) -> bool {
+	!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Removes the sign of a number, returning whether it is negative.
+fn strip_sign(s: &str) -> (bool, &str) {
+	match s.as_bytes().first() {
+		Some(b'-') => (true, &s[1..]),
+		Some(b'+') => (false, &s[1..]),
+		_ => (false, s),
+	}
+}
+
+/// Parses an integer, returning whether it is negative and whether it is
+/// zero.
+fn integer(s: &str) -> Option<(bool, bool)> {
+	let (negative, digits) = strip_sign(s);
+	is_digits(digits).then(|| (negative, digits.bytes().all(|b| b == b'0')))
+}
+
+fn in_range(s: &str, min: i128, max: i128) -> bool {
+	integer(s).is_some() && s.parse::<i128>().is_ok_and(|n| (min..=max).contains(&n))
+}
+
+fn is_decimal(s: &str) -> bool {
+	let (_, s) = strip_sign(s);
+	match s.split_once('.') {
+		Some((int, frac)) => {
+			(int.is_empty() || is_digits(int))
+				&& (frac.is_empty() || is_digits(frac))
+				&& !(int.is_empty() && frac.is_empty())
+		}
+		None => is_digits(s),
+	}
+}
+
+fn is_double(s: &str) -> bool {
+	if matches!(s, "INF" | "+INF" | "-INF" | "NaN") {
+		return true;
+	}
+
+	match s.split_once(['e', 'E']) {
+		Some((mantissa, exponent)) => is_decimal(mantissa) && integer(exponent).is_some(),
+		None => is_decimal(s),
+	}
+}
+
+/// Removes the optional timezone (`Z` or `(+|-)hh:mm`) at the end of a date
+/// or time, returning `None` if it is invalid.
+fn strip_timezone(s: &str) -> Option<&str> {
+	if let Some(s) = s.strip_suffix('Z') {
+		return Some(s);
+	}
+
+	let bytes = s.as_bytes();
+	let len = bytes.len();
+	if len >= 6 && matches!(bytes[len - 6], b'+' | b'-') && bytes[len - 3] == b':' {
+		let (hours, minutes) = (&s[len - 5..len - 3], &s[len - 2..]);
+		let valid = is_digits(hours)
+			&& is_digits(minutes)
+			&& hours <= "14"
+			&& minutes <= "59"
+			&& (hours < "14" || minutes == "00");
+		return valid.then(|| &s[..len - 6]);
+	}
+
+	Some(s)
+}
+
+/// Checks a `-?YYYY-MM-DD` date without timezone.
+fn is_date(s: &str) -> bool {
+	let s = s.strip_prefix('-').unwrap_or(s);
+	let mut parts = s.splitn(3, '-');
+	let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next()) else {
+		return false;
+	};
+
+	year.len() >= 4
+		&& is_digits(year)
+		&& (year.len() == 4 || !year.starts_with('0'))
+		&& two_digits(month).is_some_and(|m| (1..=12).contains(&m))
+		&& two_digits(day).is_some_and(|d| (1..=31).contains(&d))
+}
+
+/// Checks a `hh:mm:ss(.s+)?` time without timezone.
+fn is_time(s: &str) -> bool {
+	let (s, fraction) = match s.split_once('.') {
+		Some((s, fraction)) => (s, Some(fraction)),
+		None => (s, None),
+	};
+
+	let mut parts = s.splitn(3, ':');
+	let (Some(h), Some(m), Some(sec)) = (parts.next(), parts.next(), parts.next()) else {
+		return false;
+	};
+
+	match (two_digits(h), two_digits(m), two_digits(sec)) {
+		(Some(24), Some(0), Some(0)) => fraction.map_or(true, |f| f.bytes().all(|b| b == b'0')),
+		(Some(h), Some(m), Some(sec)) => {
+			h < 24 && m < 60 && sec < 60 && fraction.map_or(true, is_digits)
+		}
+		_ => false,
+	}
+}
+
+fn two_digits(s: &str) -> Option<u8> {
+	if s.len() == 2 && is_digits(s) {
+		s.parse().ok()
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn xsd(name: &str, value: &str) -> Option<bool> {
+		is_valid_lexical_form(&format!("{XSD}{name}"), value)
+	}
+
+	#[test]
+	fn lexical_forms() {
+		assert_eq!(xsd("boolean", "true"), Some(true));
+		assert_eq!(xsd("boolean", "yes"), Some(false));
+		assert_eq!(xsd("integer", "-12"), Some(true));
+		assert_eq!(xsd("integer", "1.0"), Some(false));
+		assert_eq!(xsd("nonNegativeInteger", "-0"), Some(true));
+		assert_eq!(xsd("positiveInteger", "0"), Some(false));
+		assert_eq!(xsd("byte", "128"), Some(false));
+		assert_eq!(xsd("decimal", ".5"), Some(true));
+		assert_eq!(xsd("decimal", "."), Some(false));
+		assert_eq!(xsd("double", "1.5E-3"), Some(true));
+		assert_eq!(xsd("double", "1e"), Some(false));
+		assert_eq!(xsd("date", "2024-02-29-05:00"), Some(true));
+		assert_eq!(xsd("date", "2024-13-01"), Some(false));
+		assert_eq!(xsd("dateTime", "2024-02-29T12:30:00.5Z"), Some(true));
+		assert_eq!(xsd("dateTime", "2024-02-29 12:30:00"), Some(false));
+		assert_eq!(xsd("time", "25:00:00"), Some(false));
+		assert_eq!(xsd("string", ""), None);
+	}
+}
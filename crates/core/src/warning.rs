@@ -1,4 +1,76 @@
 use contextual::{DisplayWithContext, WithContext};
+use std::fmt;
+
+/// Location of a warning in the processed document.
+///
+/// The location is given as a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901)
+/// to the value that triggered the warning. Warnings raised while defining
+/// a term are located relative to the context definition defining the term.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+	pointer: String,
+}
+
+impl Location {
+	/// Creates a new location from a JSON pointer.
+	pub fn new(pointer: String) -> Self {
+		Self { pointer }
+	}
+
+	/// Returns the location of the document root.
+	pub fn root() -> Self {
+		Self::new(String::new())
+	}
+
+	/// Returns the location of the entry of this object with the given key.
+	pub fn key(&self, key: &str) -> Self {
+		let mut result = self.clone();
+		result.push_key(key);
+		result
+	}
+
+	/// Returns the location of the item of this array at the given index.
+	pub fn index(&self, i: usize) -> Self {
+		let mut result = self.clone();
+		result.push_index(i);
+		result
+	}
+
+	/// Appends an object key to this location.
+	pub fn push_key(&mut self, key: &str) {
+		self.pointer.push('/');
+		for c in key.chars() {
+			match c {
+				'~' => self.pointer.push_str("~0"),
+				'/' => self.pointer.push_str("~1"),
+				c => self.pointer.push(c),
+			}
+		}
+	}
+
+	/// Appends an array index to this location.
+	pub fn push_index(&mut self, i: usize) {
+		self.pointer.push('/');
+		self.pointer.push_str(&i.to_string())
+	}
+
+	/// Returns the JSON pointer to the location.
+	///
+	/// The empty string designates the document root.
+	pub fn pointer(&self) -> &str {
+		&self.pointer
+	}
+}
+
+impl fmt::Display for Location {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.pointer.is_empty() {
+			f.write_str("document root")
+		} else {
+			write!(f, "`{}`", self.pointer)
+		}
+	}
+}
 
 /// Warning handler.
 ///
@@ -8,6 +80,16 @@ use contextual::{DisplayWithContext, WithContext};
 pub trait Handler<N, W> {
 	/// Handle a warning with the given `vocabulary`.
 	fn handle(&mut self, vocabulary: &N, warning: W);
+
+	/// Handle a warning raised at the given location.
+	///
+	/// Processing algorithms call this method instead of [`Self::handle`]
+	/// when they know where the warning was raised. By default, the location
+	/// is ignored and the warning is passed to [`Self::handle`].
+	fn handle_at(&mut self, vocabulary: &N, warning: W, location: &Location) {
+		let _ = location;
+		self.handle(vocabulary, warning)
+	}
 }
 
 impl<N, W> Handler<N, W> for () {
@@ -18,6 +100,10 @@ impl<'a, N, W, H: Handler<N, W>> Handler<N, W> for &'a mut H {
 	fn handle(&mut self, vocabulary: &N, warning: W) {
 		H::handle(*self, vocabulary, warning)
 	}
+
+	fn handle_at(&mut self, vocabulary: &N, warning: W, location: &Location) {
+		H::handle_at(*self, vocabulary, warning, location)
+	}
 }
 
 /// Prints warnings that can be displayed without vocabulary on the standard
@@ -28,6 +114,10 @@ impl<N, W: std::fmt::Display> Handler<N, W> for Print {
 	fn handle(&mut self, _vocabulary: &N, warning: W) {
 		eprintln!("{warning}")
 	}
+
+	fn handle_at(&mut self, _vocabulary: &N, warning: W, location: &Location) {
+		eprintln!("{warning} (at {location})")
+	}
 }
 
 /// Prints warnings with a given vocabulary on the standard output.
@@ -37,4 +127,8 @@ impl<N, W: DisplayWithContext<N>> Handler<N, W> for PrintWith {
 	fn handle(&mut self, vocabulary: &N, warning: W) {
 		eprintln!("{}", warning.with(vocabulary))
 	}
+
+	fn handle_at(&mut self, vocabulary: &N, warning: W, location: &Location) {
+		eprintln!("{} (at {location})", warning.with(vocabulary))
+	}
 }
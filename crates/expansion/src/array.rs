@@ -1,4 +1,6 @@
-use crate::{expand_element, ActiveProperty, Error, Expanded, Loader, Options, WarningHandler};
+use crate::{
+	expand_element, ActiveProperty, Error, Expanded, Loader, Options, Path, WarningHandler,
+};
 use json_ld_core::{context::TermDefinitionRef, object, Context, Environment, Object};
 use json_ld_syntax::ContainerKind;
 use json_syntax::Array;
@@ -12,8 +14,9 @@ pub(crate) async fn expand_array<N, L, W>(
 	active_property: ActiveProperty<'_>,
 	active_property_definition: Option<TermDefinitionRef<'_, N::Iri, N::BlankId>>,
 	element: &Array,
+	path: Path<'_>,
 	base_url: Option<&N::Iri>,
-	options: Options,
+	options: &Options,
 	from_map: bool,
 ) -> Result<Expanded<N::Iri, N::BlankId>, Error>
 where
@@ -35,7 +38,7 @@ where
 	}
 
 	// For each item in element:
-	for item in element.iter() {
+	for (i, item) in element.iter().enumerate() {
 		// Initialize `expanded_item` to the result of using this algorithm
 		// recursively, passing `active_context`, `active_property`, `item` as element,
 		// `base_url`, the `frame_expansion`, `ordered`, and `from_map` flags.
@@ -48,6 +51,7 @@ where
 			active_context,
 			active_property,
 			item,
+			Path::Index(&path, i),
 			base_url,
 			options,
 			from_map,
@@ -1,5 +1,5 @@
 use super::expand_element;
-use crate::{ActiveProperty, Error, Loader, Options, WarningHandler};
+use crate::{ActiveProperty, Error, Loader, Options, Path, Warning, WarningHandler};
 use json_ld_core::{Context, Environment, ExpandedDocument, IndexedObject, Object};
 use json_syntax::Value;
 use rdf_types::VocabularyMut;
@@ -10,12 +10,22 @@ use std::hash::Hash;
 /// Note that you probably do not want to use this function directly,
 /// but instead use the [`Document::expand`](crate::Document::expand) method on
 /// a `Value` instance.
+///
+/// If `declared` is true, the document has been served with a JSON media type
+/// (`application/ld+json`, `application/json` or any `+json` suffix), as
+/// expected from a JSON-LD document, and will not be checked with
+/// [`looks_like_json_ld`].
 pub(crate) async fn expand<'a, N, L, W>(
-	env: Environment<'a, N, L, W>,
+	Environment {
+		vocabulary,
+		loader,
+		warnings,
+	}: Environment<'a, N, L, W>,
 	document: &'a Value,
 	active_context: Context<N::Iri, N::BlankId>,
 	base_url: Option<&'a N::Iri>,
 	options: Options,
+	declared: bool,
 ) -> Result<ExpandedDocument<N::Iri, N::BlankId>, Error>
 where
 	N: VocabularyMut,
@@ -24,34 +34,93 @@ where
 	L: Loader,
 	W: WarningHandler<N>,
 {
+	if !declared
+		&& active_context.is_empty()
+		&& active_context.vocabulary().is_none()
+		&& !looks_like_json_ld(document)
+	{
+		if options.reject_non_json_ld {
+			return Err(Error::NotJsonLd);
+		}
+
+		warnings.handle_at(
+			vocabulary,
+			Warning::ProbablyNotJsonLd,
+			&Path::Root.location(),
+		);
+	}
+
+	let relabel = options.relabel_non_conforming_blank_ids;
 	let expanded = expand_element(
-		env,
+		Environment {
+			vocabulary,
+			loader,
+			warnings,
+		},
 		&active_context,
 		ActiveProperty::None,
 		document,
+		Path::Root,
 		base_url,
-		options,
+		&options,
 		false,
 	)
 	.await?;
-	if expanded.len() == 1 {
+
+	let mut result = if expanded.len() == 1 {
 		let obj = expanded.into_iter().next().unwrap();
 		match obj.into_unnamed_graph() {
-			Ok(graph) => Ok(ExpandedDocument::from(graph)),
+			Ok(graph) => ExpandedDocument::from(graph),
 			Err(obj) => {
 				let mut result = ExpandedDocument::new();
 				if filter_top_level_item(&obj) {
 					result.insert(obj);
 				}
-				Ok(result)
+				result
 			}
 		}
 	} else {
-		Ok(expanded.into_iter().filter(filter_top_level_item).collect())
+		expanded.into_iter().filter(filter_top_level_item).collect()
+	};
+
+	if relabel {
+		let relabeling = result.relabel_non_conforming_blank_ids_with(vocabulary);
+		for (original, b) in relabeling.iter() {
+			let b = vocabulary.insert_blank_id(b);
+			warnings.handle_at(
+				vocabulary,
+				Warning::RelabeledBlankId(original.to_owned(), b),
+				&Path::Root.location(),
+			)
+		}
 	}
+
+	Ok(result)
 }
 
 pub(crate) fn filter_top_level_item<T, B>(item: &IndexedObject<T, B>) -> bool {
 	// Remove dangling values.
 	!matches!(item.inner(), Object::Value(_))
 }
+
+/// Checks if the given document looks like JSON-LD.
+///
+/// A document looks like JSON-LD if it contains at least one keyword or
+/// compact/absolute IRI key (containing a `:`), or if it has no key at all.
+/// Otherwise, without context, all its keys would be dropped by expansion.
+pub(crate) fn looks_like_json_ld(document: &Value) -> bool {
+	fn visit(value: &Value, has_keys: &mut bool) -> bool {
+		match value {
+			Value::Array(items) => items.iter().any(|item| visit(item, has_keys)),
+			Value::Object(object) => object.iter().any(|entry| {
+				*has_keys = true;
+				let key = entry.key.as_str();
+				key.starts_with('@') || key.contains(':') || visit(&entry.value, has_keys)
+			}),
+			_ => false,
+		}
+	}
+
+	let mut has_keys = false;
+	visit(document, &mut has_keys) || !has_keys
+}
@@ -1,14 +1,14 @@
 use crate::{
 	expand_array, expand_iri, expand_literal, expand_node, expand_value, Error, Expanded,
-	GivenLiteralValue, LiteralValue, Loader, Options, Warning, WarningHandler,
+	GivenLiteralValue, LiteralValue, Loader, Options, Path, ValueMut, Warning, WarningHandler,
 };
 use json_ld_context_processing::{Options as ProcessingOptions, Process};
 use json_ld_core::{object, Context, Environment, Id, Indexed, Object, Term, ValidId};
 use json_ld_syntax::{Keyword, Nullable};
 use json_syntax::{object::Entry, Value};
 use mown::Mown;
-use rdf_types::VocabularyMut;
-use std::{borrow::Cow, hash::Hash};
+use rdf_types::{vocabulary::IriVocabulary, VocabularyMut};
+use std::hash::Hash;
 
 pub(crate) struct ExpandedEntry<'a, T, B>(pub &'a str, pub Term<T, B>, pub &'a Value);
 
@@ -18,12 +18,12 @@ pub(crate) enum ActiveProperty<'a> {
 }
 
 impl<'a> ActiveProperty<'a> {
-	// pub fn as_str(&self) -> Option<&'a str> {
-	// 	match self {
-	// 		Self::Some(Meta(s, _)) => Some(s),
-	// 		Self::None => None
-	// 	}
-	// }
+	pub fn as_str(&self) -> Option<&'a str> {
+		match self {
+			Self::Some(s) => Some(*s),
+			Self::None => None,
+		}
+	}
 
 	pub fn is_some(&self) -> bool {
 		matches!(self, Self::Some(_))
@@ -74,8 +74,9 @@ pub(crate) async fn expand_element<'a, N, L, W>(
 	active_context: &'a Context<N::Iri, N::BlankId>,
 	active_property: ActiveProperty<'a>,
 	element: &'a Value,
+	path: Path<'a>,
 	base_url: Option<&'a N::Iri>,
-	options: Options,
+	options: &'a Options,
 	from_map: bool,
 ) -> ElementExpansionResult<N::Iri, N::BlankId>
 where
@@ -114,6 +115,7 @@ where
 				active_property,
 				active_property_definition,
 				element,
+				path,
 				base_url,
 				options,
 				from_map,
@@ -134,15 +136,14 @@ where
 			for Entry { key, value } in element.entries() {
 				match expand_iri(
 					&mut env,
+					path.key(key),
 					active_context,
 					Nullable::Some(key.as_str().into()),
 					false,
 					Some(options.policy.vocab),
 				)? {
-					Some(Term::Keyword(Keyword::Value)) => {
-						preliminary_value_entry = Some(value.clone())
-					}
-					Some(Term::Keyword(Keyword::Id)) => preliminary_id_entry = Some(value.clone()),
+					Some(Term::Keyword(Keyword::Value)) => preliminary_value_entry = Some(value),
+					Some(Term::Keyword(Keyword::Id)) => preliminary_id_entry = Some(value),
 					_ => (),
 				}
 			}
@@ -194,9 +195,8 @@ where
 				.get_unique("@context")
 				.map_err(Error::duplicate_key_ref)?
 			{
-				use json_ld_syntax::TryFromJson;
 				let local_context =
-					json_ld_syntax::context::Context::try_from_json(local_context.clone())?;
+					json_ld_syntax::context::Context::try_from_json_ref(local_context)?;
 
 				active_context = Mown::Owned(
 					local_context
@@ -212,16 +212,15 @@ where
 				);
 			}
 
-			let entries: Cow<[Entry]> = if options.ordered {
-				Cow::Owned(element.entries().to_vec())
-			} else {
-				Cow::Borrowed(element.entries())
-			};
+			// Entries are ordered, if need be, by `expand_node`, so they can be
+			// borrowed as they are.
+			let entries = element.entries();
 
 			let mut type_entries: Vec<&Entry> = Vec::new();
 			for entry @ Entry { key, .. } in entries.iter() {
 				let expanded_key = expand_iri(
 					&mut env,
+					path.key(key),
 					active_context.as_ref(),
 					Nullable::Some(key.as_str().into()),
 					false,
@@ -289,14 +288,15 @@ where
 			// `element` expanding to `@type` (if any), ordering entries lexicographically by
 			// key.
 			// Both the key and value of the matched entry are IRI expanded.
-			let input_type = if let Some(Entry { value, .. }) = type_entries.first() {
-				let value = Value::force_as_array(value);
-				if let Some(input_type) = value.last() {
+			let input_type = if let Some(Entry { key, value }) = type_entries.first() {
+				let items = Value::force_as_array(value);
+				if let Some(input_type) = items.last() {
 					input_type
 						.as_string()
 						.map(|input_type_str| {
 							expand_iri(
 								&mut env,
+								path.key(key).item(value, items.len() - 1),
 								active_context.as_ref(),
 								Nullable::Some(input_type_str.into()),
 								false,
@@ -318,12 +318,18 @@ where
 			let mut set_entry = None;
 			let mut value_entry = None;
 			for Entry { key, value } in entries.iter() {
+				let entry_path = path.key(key);
 				if key.is_empty() {
-					env.warnings.handle(env.vocabulary, Warning::EmptyTerm);
+					env.warnings.handle_at(
+						env.vocabulary,
+						Warning::EmptyTerm,
+						&entry_path.location(),
+					);
 				}
 
 				let expanded_key = expand_iri(
 					&mut env,
+					entry_path,
 					active_context.as_ref(),
 					Nullable::Some(key.as_str().into()),
 					false,
@@ -332,16 +338,19 @@ where
 
 				if let Some(expanded_key) = expanded_key {
 					match &expanded_key {
-						Term::Keyword(Keyword::Value) => value_entry = Some(value.clone()),
+						Term::Keyword(Keyword::Value) => value_entry = Some(value),
 						Term::Keyword(Keyword::List) => {
 							if active_property.is_some() && active_property != Keyword::Graph {
-								list_entry = Some(value.clone())
+								list_entry = Some((key.as_str(), value))
 							}
 						}
-						Term::Keyword(Keyword::Set) => set_entry = Some(value.clone()),
+						Term::Keyword(Keyword::Set) => set_entry = Some((key.as_str(), value)),
 						Term::Id(Id::Valid(ValidId::Blank(id))) => {
-							env.warnings
-								.handle(env.vocabulary, Warning::BlankNodeIdProperty(id.clone()));
+							env.warnings.handle_at(
+								env.vocabulary,
+								Warning::BlankNodeIdProperty(id.clone()),
+								&entry_path.location(),
+							);
 						}
 						_ => (),
 					}
@@ -350,7 +359,7 @@ where
 				}
 			}
 
-			if let Some(list_entry) = list_entry {
+			if let Some((list_key, list_entry)) = list_entry {
 				// List objects.
 				let mut index = None;
 				for ExpandedEntry(_, expanded_key, value) in expanded_entries {
@@ -369,8 +378,8 @@ where
 				// base URL, and the ordered flags, ensuring that the
 				// result is an array..
 				let mut result = Vec::new();
-				let list_entry = Value::force_as_array(&list_entry);
-				for item in list_entry {
+				let list_path = path.key(list_key);
+				for (i, item) in Value::force_as_array(list_entry).iter().enumerate() {
 					let e = Box::pin(expand_element(
 						Environment {
 							vocabulary: env.vocabulary,
@@ -380,6 +389,7 @@ where
 						active_context.as_ref(),
 						active_property,
 						item,
+						list_path.item(list_entry, i),
 						base_url,
 						options,
 						false,
@@ -392,7 +402,7 @@ where
 					Object::List(object::List::new(result)),
 					index,
 				)))
-			} else if let Some(set_entry) = set_entry {
+			} else if let Some((set_key, set_entry)) = set_entry {
 				// Set objects.
 				for ExpandedEntry(_, expanded_key, _) in expanded_entries {
 					match expanded_key {
@@ -412,7 +422,8 @@ where
 					env,
 					active_context.as_ref(),
 					active_property,
-					&set_entry,
+					set_entry,
+					path.key(set_key),
 					base_url,
 					options,
 					false,
@@ -422,18 +433,19 @@ where
 				// Value objects.
 				let expanded_value = expand_value(
 					&mut env,
+					path,
 					options.policy.vocab,
 					input_type,
 					type_scoped_context,
 					expanded_entries,
-					&value_entry,
+					value_entry,
 				)?;
 
-				if let Some(value) = expanded_value {
-					Ok(Expanded::Object(value))
-				} else {
-					Ok(Expanded::Null)
-				}
+				Ok(expanded_value
+					.and_then(|value| {
+						transform_value(env.vocabulary, options, active_property, value)
+					})
+					.map_or(Expanded::Null, Expanded::Object))
 			} else {
 				// Node objects.
 				let e = expand_node(
@@ -442,6 +454,7 @@ where
 					type_scoped_context,
 					active_property,
 					expanded_entries,
+					path,
 					base_url,
 					options,
 				)
@@ -493,13 +506,53 @@ where
 
 			// Return the result of the Value Expansion algorithm, passing the `active_context`,
 			// `active_property`, and `element` as value.
-			Ok(Expanded::Object(expand_literal(
-				env,
+			let value = expand_literal(
+				Environment {
+					vocabulary: env.vocabulary,
+					loader: env.loader,
+					warnings: env.warnings,
+				},
+				path,
 				options.policy.vocab,
+				options.validate_iris,
 				active_context.as_ref(),
 				active_property,
 				LiteralValue::Given(GivenLiteralValue::new(element)),
-			)?))
+			)?;
+
+			Ok(
+				transform_value(env.vocabulary, options, active_property, value)
+					.map_or(Expanded::Null, Expanded::Object),
+			)
 		}
 	}
 }
+
+/// Calls the value transformer of `options`, if any, on the given expanded
+/// value object.
+///
+/// Returns `None` if the transformer drops the value.
+pub(crate) fn transform_value<N: IriVocabulary>(
+	vocabulary: &N,
+	options: &Options,
+	active_property: ActiveProperty,
+	mut value: Indexed<Object<N::Iri, N::BlankId>>,
+) -> Option<Indexed<Object<N::Iri, N::BlankId>>> {
+	if let Some(transformer) = &options.value_transformer {
+		if let Some(v) = value.inner_mut().as_value_mut() {
+			let v = match v {
+				object::Value::Literal(lit, ty) => {
+					ValueMut::Literal(lit, ty.as_ref().and_then(|ty| vocabulary.iri(ty)))
+				}
+				object::Value::LangString(s) => ValueMut::LangString(s),
+				object::Value::Json(json) => ValueMut::Json(json),
+			};
+
+			if !transformer.transform(active_property.as_str(), v) {
+				return None;
+			}
+		}
+	}
+
+	Some(value)
+}
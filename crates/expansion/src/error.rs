@@ -1,5 +1,9 @@
 use json_ld_context_processing::algorithm::RejectVocab;
 use json_ld_syntax::ErrorCode;
+use json_syntax::Print;
+
+/// Maximum length (in bytes) of the value snippets rendered in errors.
+const SNIPPET_MAX_LEN: usize = 64;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -15,8 +19,8 @@ pub enum Error {
 	#[error("Invalid set or list object")]
 	InvalidSetOrListObject,
 
-	#[error("Invalid `@reverse` property map")]
-	InvalidReversePropertyMap,
+	#[error("Invalid `@reverse` property map: unexpected keyword `{0}`")]
+	InvalidReversePropertyMap(String),
 
 	#[error("Invalid `@type` value")]
 	InvalidTypeValue,
@@ -24,8 +28,8 @@ pub enum Error {
 	#[error("Key `{0}` expansion failed")]
 	KeyExpansionFailed(String),
 
-	#[error("Invalid `@reverse` property value")]
-	InvalidReversePropertyValue,
+	#[error("Invalid value of reverse property `{property}`, expected node objects: {value}")]
+	InvalidReversePropertyValue { property: String, value: String },
 
 	#[error("Invalid `@language` map value")]
 	InvalidLanguageMapValue,
@@ -56,6 +60,38 @@ pub enum Error {
 
 	#[error("Forbidden use of `@vocab`")]
 	ForbiddenVocab,
+
+	#[error("Value of property `{property}` is too long ({length} bytes, maximum is {max})")]
+	LiteralTooLong {
+		property: String,
+		length: usize,
+		max: usize,
+	},
+
+	#[error("Document is probably not JSON-LD: it has no `@context`, no keyword and no absolute IRI key")]
+	NotJsonLd,
+
+	#[error(transparent)]
+	InvalidIri(InvalidIri),
+}
+
+/// Term expanded into an invalid IRI.
+///
+/// See [`Options::validate_iris`](crate::Options::validate_iris).
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Term `{term}` expanded into invalid IRI `{iri}`")]
+pub struct InvalidIri {
+	/// Expanded term.
+	pub term: String,
+
+	/// Invalid IRI.
+	pub iri: String,
+}
+
+impl From<InvalidIri> for Error {
+	fn from(value: InvalidIri) -> Self {
+		Self::InvalidIri(value)
+	}
 }
 
 impl From<RejectVocab> for Error {
@@ -71,10 +107,10 @@ impl Error {
 			Self::ContextProcessing(e) => e.code(),
 			Self::InvalidIndexValue => ErrorCode::InvalidIndexValue,
 			Self::InvalidSetOrListObject => ErrorCode::InvalidSetOrListObject,
-			Self::InvalidReversePropertyMap => ErrorCode::InvalidReversePropertyMap,
+			Self::InvalidReversePropertyMap(_) => ErrorCode::InvalidReversePropertyMap,
 			Self::InvalidTypeValue => ErrorCode::InvalidTypeValue,
 			Self::KeyExpansionFailed(_) => ErrorCode::KeyExpansionFailed,
-			Self::InvalidReversePropertyValue => ErrorCode::InvalidReversePropertyValue,
+			Self::InvalidReversePropertyValue { .. } => ErrorCode::InvalidReversePropertyValue,
 			Self::InvalidLanguageMapValue => ErrorCode::InvalidLanguageMapValue,
 			Self::CollidingKeywords => ErrorCode::CollidingKeywords,
 			Self::InvalidIdValue => ErrorCode::InvalidIdValue,
@@ -85,6 +121,9 @@ impl Error {
 			Self::Literal(e) => e.code(),
 			Self::Value(e) => e.code(),
 			Self::ForbiddenVocab => ErrorCode::InvalidVocabMapping,
+			Self::LiteralTooLong { .. } => ErrorCode::InvalidValueObjectValue,
+			Self::NotJsonLd => ErrorCode::NotJsonLd,
+			Self::InvalidIri(_) => ErrorCode::InvalidIriMapping,
 		}
 	}
 }
@@ -97,6 +136,27 @@ impl Error {
 	) -> Self {
 		Self::DuplicateKey(a.key.clone())
 	}
+
+	/// Creates an [`Error::InvalidReversePropertyValue`] error for the given
+	/// reverse property and JSON value.
+	///
+	/// The value is rendered in compact form and truncated to a few
+	/// characters.
+	pub fn invalid_reverse_property_value(property: String, value: &json_syntax::Value) -> Self {
+		let mut value = value.compact_print().to_string();
+
+		if value.len() > SNIPPET_MAX_LEN {
+			let mut end = SNIPPET_MAX_LEN;
+			while !value.is_char_boundary(end) {
+				end -= 1
+			}
+
+			value.truncate(end);
+			value.push('…')
+		}
+
+		Self::InvalidReversePropertyValue { property, value }
+	}
 }
 
 impl From<json_ld_context_processing::Error> for Error {
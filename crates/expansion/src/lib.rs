@@ -19,20 +19,23 @@ mod expanded;
 mod literal;
 mod node;
 mod options;
+mod path;
+mod transformer;
 mod value;
 mod warning;
 
 pub use error::*;
 pub use expanded::*;
 pub use options::*;
+pub use transformer::*;
 pub use warning::*;
 
 pub(crate) use array::*;
 pub(crate) use document::filter_top_level_item;
 pub(crate) use element::*;
-pub(crate) use json_ld_context_processing::algorithm::expand_iri_simple as expand_iri;
 pub(crate) use literal::*;
 pub(crate) use node::*;
+pub(crate) use path::*;
 pub(crate) use value::*;
 
 /// Result of the document expansion.
@@ -131,6 +134,36 @@ pub trait Expand<Iri> {
 		L: Loader,
 		W: WarningHandler<N>;
 
+	/// Expand the document in the given environment.
+	///
+	/// This is equivalent to [`Expand::expand_full`] with the vocabulary,
+	/// loader and warning handler of `env`.
+	#[allow(async_fn_in_trait)]
+	async fn expand_in<N, L, W>(
+		&self,
+		env: Environment<'_, N, L, W>,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<&N::Iri>,
+		options: Options,
+	) -> ExpansionResult<N::Iri, N::BlankId>
+	where
+		N: VocabularyMut<Iri = Iri>,
+		Iri: Clone + Eq + Hash,
+		N::BlankId: Clone + Eq + Hash,
+		L: Loader,
+		W: WarningHandler<N>,
+	{
+		self.expand_full(
+			env.vocabulary,
+			context,
+			base_url,
+			env.loader,
+			options,
+			env.warnings,
+		)
+		.await
+	}
+
 	/// Expand the input JSON-LD document with the given `vocabulary`
 	/// to interpret identifiers.
 	///
@@ -211,6 +244,7 @@ impl<Iri> Expand<Iri> for Value {
 			context,
 			base_url,
 			options,
+			false,
 		)
 		.await
 	}
@@ -232,7 +266,7 @@ impl<Iri> Expand<Iri> for RemoteDocument<Iri> {
 		base_url: Option<&Iri>,
 		loader: &L,
 		options: Options,
-		warnings_handler: W,
+		mut warnings_handler: W,
 	) -> ExpansionResult<Iri, N::BlankId>
 	where
 		N: VocabularyMut<Iri = Iri>,
@@ -241,15 +275,22 @@ impl<Iri> Expand<Iri> for RemoteDocument<Iri> {
 		L: Loader,
 		W: WarningHandler<N>,
 	{
-		self.document()
-			.expand_full(
+		let declared = self.content_type().is_some_and(|t| {
+			t.essence_str() == "application/json" || t.suffix().is_some_and(|s| s == "json")
+		});
+
+		document::expand(
+			Environment {
 				vocabulary,
-				context,
-				base_url,
 				loader,
-				options,
-				warnings_handler,
-			)
-			.await
+				warnings: &mut warnings_handler,
+			},
+			self.document(),
+			context,
+			base_url,
+			options,
+			declared,
+		)
+		.await
 	}
 }
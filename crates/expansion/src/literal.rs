@@ -1,4 +1,6 @@
-use crate::{expand_iri, node_id_of_term, ActiveProperty, WarningHandler};
+use crate::{
+	expand_iri, node_id_of_term, validate_iri, ActiveProperty, InvalidIri, Path, WarningHandler,
+};
 use json_ld_context_processing::algorithm::{Action, RejectVocab};
 use json_ld_core::{
 	object::value::Literal, Context, Environment, IndexedObject, LangString, Node, Object, Type,
@@ -66,6 +68,12 @@ pub enum LiteralExpansionError {
 
 	#[error("Forbidden use of `@vocab`")]
 	ForbiddenVocab,
+
+	#[error("Invalid `@id` value")]
+	InvalidIdValue,
+
+	#[error(transparent)]
+	InvalidIri(InvalidIri),
 }
 
 impl LiteralExpansionError {
@@ -73,6 +81,8 @@ impl LiteralExpansionError {
 		match self {
 			Self::InvalidTypeValue => ErrorCode::InvalidTypeValue,
 			Self::ForbiddenVocab => ErrorCode::InvalidTypeValue,
+			Self::InvalidIdValue => ErrorCode::InvalidIdValue,
+			Self::InvalidIri(_) => ErrorCode::InvalidIriMapping,
 		}
 	}
 }
@@ -83,13 +93,21 @@ impl From<RejectVocab> for LiteralExpansionError {
 	}
 }
 
+impl From<InvalidIri> for LiteralExpansionError {
+	fn from(value: InvalidIri) -> Self {
+		Self::InvalidIri(value)
+	}
+}
+
 pub(crate) type LiteralExpansionResult<T, B> = Result<ExpandedLiteral<T, B>, LiteralExpansionError>;
 
 /// Expand a literal value.
 /// See <https://www.w3.org/TR/json-ld11-api/#value-expansion>.
 pub(crate) fn expand_literal<N, L, W>(
 	mut env: Environment<N, L, W>,
+	path: Path,
 	vocab_policy: Action,
+	validate_iris: Action,
 	active_context: &Context<N::Iri, N::BlankId>,
 	active_property: ActiveProperty<'_>,
 	value: LiteralValue,
@@ -115,19 +133,20 @@ where
 		// `false` for vocab.
 		Some(Type::Id) if value.is_string() => {
 			let mut node = Node::new();
-			let id = node_id_of_term(
-				expand_iri(
-					&mut env,
-					active_context,
-					Nullable::Some(value.as_str().unwrap().into()),
-					true,
-					None,
-				)
-				.unwrap()
-				.unwrap(),
-			);
-
-			node.id = id;
+			let id = expand_iri(
+				&mut env,
+				path,
+				active_context,
+				Nullable::Some(value.as_str().unwrap().into()),
+				true,
+				None,
+			)?
+			.ok_or(LiteralExpansionError::InvalidIdValue)?;
+
+			if let Some(id) = node_id_of_term(id) {
+				node.id = validate_iri(&mut env, path, validate_iris, value.as_str().unwrap(), id)?;
+			}
+
 			Ok(Object::node(node).into())
 		}
 
@@ -140,15 +159,15 @@ where
 
 			let ty = expand_iri(
 				&mut env,
+				path,
 				active_context,
 				Nullable::Some(value.as_str().unwrap().into()),
 				true,
 				Some(vocab_policy),
 			)?;
 
-			if let Some(ty) = ty {
-				let id = node_id_of_term(ty);
-				node.id = id;
+			if let Some(id) = ty.and_then(node_id_of_term) {
+				node.id = validate_iri(&mut env, path, validate_iris, value.as_str().unwrap(), id)?;
 			}
 
 			Ok(Object::node(node).into())
@@ -1,13 +1,14 @@
 use crate::{
-	expand_element, expand_iri, expand_literal, filter_top_level_item, Action, ActiveProperty,
-	Error, Expanded, ExpandedEntry, LiteralValue, Options, Warning, WarningHandler,
+	expand_element, expand_iri, expand_literal, filter_top_level_item, transform_value,
+	validate_iri, Action, ActiveProperty, Error, Expanded, ExpandedEntry, LiteralValue, Options,
+	Path, Warning, WarningHandler,
 };
 use contextual::WithContext;
 use indexmap::IndexSet;
 use json_ld_context_processing::{Options as ProcessingOptions, Process};
 use json_ld_core::{
-	object, object::value::Literal, Container, Context, Environment, Id, Indexed, IndexedObject,
-	LangString, Loader, Node, Object, ProcessingMode, Term, Type, Value,
+	context::BindingRef, object, object::value::Literal, Container, Context, Environment, Id,
+	Indexed, IndexedObject, LangString, Loader, Node, Object, ProcessingMode, Term, Type, Value,
 };
 use json_ld_syntax::{ContainerKind, Keyword, LenientLangTagBuf, Nullable};
 use json_syntax::object::Entry;
@@ -33,8 +34,9 @@ pub(crate) async fn expand_node<'a, N, L, W>(
 	type_scoped_context: &'a Context<N::Iri, N::BlankId>,
 	active_property: ActiveProperty<'a>,
 	expanded_entries: Vec<ExpandedEntry<'a, N::Iri, N::BlankId>>,
+	path: Path<'a>,
 	base_url: Option<&'a N::Iri>,
-	options: Options,
+	options: &'a Options,
 ) -> Result<Option<Indexed<Node<N::Iri, N::BlankId>>>, Error>
 where
 	N: VocabularyMut,
@@ -47,19 +49,44 @@ where
 	// let mut result = Indexed::new(Node::new(), None);
 	// let mut has_value_object_entries = false;
 
-	let (result, has_value_object_entries) = expand_node_entries(
-		env,
+	let (mut result, mut has_value_object_entries) = expand_node_entries(
+		Environment {
+			vocabulary: env.vocabulary,
+			loader: env.loader,
+			warnings: env.warnings,
+		},
 		Indexed::new(Node::new(), None),
 		false,
 		active_context,
 		type_scoped_context,
 		active_property,
 		expanded_entries,
+		path,
 		base_url,
 		options,
 	)
 	.await?;
 
+	// Extension: restore the missing properties from their default value.
+	if options.materialize_defaults && !has_value_object_entries && !result.is_empty() {
+		let default_entries = default_entries(active_context, &result);
+		if !default_entries.is_empty() {
+			(result, has_value_object_entries) = expand_node_entries(
+				env,
+				result,
+				has_value_object_entries,
+				active_context,
+				type_scoped_context,
+				active_property,
+				default_entries,
+				path,
+				base_url,
+				options,
+			)
+			.await?;
+		}
+	}
+
 	// If result contains the entry @value:
 	// The result must not contain any entries other than @direction, @index,
 	// @language, @type, and @value.
@@ -92,6 +119,48 @@ where
 	Ok(Some(result))
 }
 
+/// Returns the entries of the terms of `active_context` having a default
+/// value, whose property is missing from the given node.
+///
+/// Entries are sorted by term, so that the output does not depend on the
+/// order of the definitions.
+fn default_entries<'a, T, B>(
+	active_context: &'a Context<T, B>,
+	node: &Node<T, B>,
+) -> Vec<ExpandedEntry<'a, T, B>>
+where
+	T: Clone + Eq + Hash,
+	B: Clone + Eq + Hash,
+{
+	let mut candidates = Vec::new();
+	for binding in active_context.definitions() {
+		if let BindingRef::Normal(key, definition) = binding {
+			if let (Some(default), Some(Term::Id(prop))) =
+				(definition.default_value(), &definition.value)
+			{
+				if !definition.reverse_property && !node.properties().contains(prop) {
+					candidates.push((key.as_str(), prop, default))
+				}
+			}
+		}
+	}
+
+	candidates.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+	// Only the first term of each property is used.
+	let mut entries: Vec<ExpandedEntry<T, B>> = Vec::with_capacity(candidates.len());
+	for (key, prop, default) in candidates {
+		if !entries
+			.iter()
+			.any(|ExpandedEntry(_, p, _)| matches!(p, Term::Id(p) if p == prop))
+		{
+			entries.push(ExpandedEntry(key, Term::Id(prop.clone()), default))
+		}
+	}
+
+	entries
+}
+
 /// Type returned by the `expand_node_entries` function.
 ///
 /// It is a tuple containing both the node being expanded
@@ -111,8 +180,9 @@ async fn expand_node_entries<'a, N, L, W>(
 	type_scoped_context: &'a Context<N::Iri, N::BlankId>,
 	active_property: ActiveProperty<'a>,
 	expanded_entries: Vec<ExpandedEntry<'a, N::Iri, N::BlankId>>,
+	path: Path<'a>,
 	base_url: Option<&'a N::Iri>,
-	options: Options,
+	options: &'a Options,
 ) -> NodeEntriesExpensionResult<N::Iri, N::BlankId>
 where
 	N: VocabularyMut,
@@ -124,6 +194,7 @@ where
 	// For each `key` and `value` in `element`, ordered lexicographically by key
 	// if `ordered` is `true`:
 	for ExpandedEntry(key, expanded_key, value) in expanded_entries {
+		let entry_path = path.key(key);
 		match expanded_key {
 			Term::Null => (),
 
@@ -140,7 +211,7 @@ where
 				// If `active_property` equals `@reverse`, an invalid reverse property
 				// map error has been detected and processing is aborted.
 				if active_property == Keyword::Reverse {
-					return Err(Error::InvalidReversePropertyMap);
+					return Err(Error::InvalidReversePropertyMap(key.to_string()));
 				}
 
 				// If `result` already has an `expanded_property` entry, other than
@@ -164,14 +235,26 @@ where
 							// Otherwise, set `expanded_value` to the result of IRI
 							// expanding value using true for document relative and
 							// false for vocab.
-							result.id = expand_iri(
+							let id = expand_iri(
 								&mut env,
+								entry_path,
 								active_context,
 								Nullable::Some(str_value.into()),
 								true,
 								None,
 							)?
 							.and_then(node_id_of_term);
+
+							result.id = match id {
+								Some(id) => validate_iri(
+									&mut env,
+									entry_path,
+									options.validate_iris,
+									str_value,
+									id,
+								)?,
+								None => None,
+							};
 						} else {
 							return Err(Error::InvalidIdValue);
 						}
@@ -181,20 +264,32 @@ where
 						// If value is neither a string nor an array of strings, an
 						// invalid type value error has been detected and processing
 						// is aborted.
-						let value = json_syntax::Value::force_as_array(value);
+						let items = json_syntax::Value::force_as_array(value);
 						// Set `expanded_value` to the result of IRI expanding each
 						// of its values using `type_scoped_context` for active
 						// context, and true for document relative.
-						for ty in value {
+						for (i, ty) in items.iter().enumerate() {
 							if let Some(str_ty) = ty.as_str() {
 								if let Some(ty) = expand_iri(
 									&mut env,
+									entry_path.item(value, i),
 									type_scoped_context,
 									Nullable::Some(str_ty.into()),
 									true,
 									Some(options.policy.vocab),
 								)? {
 									if let Ok(ty) = ty.try_into() {
+										let ty = match validate_iri(
+											&mut env,
+											entry_path.item(value, i),
+											options.validate_iris,
+											str_ty,
+											ty,
+										)? {
+											Some(ty) => ty,
+											None => continue,
+										};
+
 										if let Id::Invalid(_) = &ty {
 											match options.policy.invalid {
 												Action::Keep => (),
@@ -231,6 +326,7 @@ where
 							active_context,
 							ActiveProperty::Some("@graph"),
 							value,
+							entry_path,
 							base_url,
 							options,
 							false,
@@ -265,6 +361,7 @@ where
 							active_context,
 							ActiveProperty::Some("@included"),
 							value,
+							entry_path,
 							base_url,
 							options,
 							false,
@@ -316,15 +413,19 @@ where
 								value: reverse_value,
 							} in reverse_entries
 							{
+								let reverse_path = entry_path.key(reverse_key);
 								match expand_iri(
 									&mut env,
+									reverse_path,
 									active_context,
 									Nullable::Some(reverse_key.as_str().into()),
 									false,
 									Some(options.policy.vocab),
 								)? {
 									Some(Term::Keyword(_)) => {
-										return Err(Error::InvalidReversePropertyMap)
+										return Err(Error::InvalidReversePropertyMap(
+											reverse_key.to_string(),
+										))
 									}
 									Some(Term::Id(reverse_prop))
 										if reverse_prop
@@ -332,6 +433,17 @@ where
 											.as_str()
 											.contains(':') =>
 									{
+										let reverse_prop = match validate_iri(
+											&mut env,
+											reverse_path,
+											options.validate_iris,
+											reverse_key,
+											reverse_prop,
+										)? {
+											Some(reverse_prop) => reverse_prop,
+											None => continue,
+										};
+
 										if !reverse_prop.is_valid() {
 											match options.policy.invalid {
 												Action::Keep => (),
@@ -353,6 +465,7 @@ where
 											active_context,
 											ActiveProperty::Some(reverse_key.as_ref()),
 											reverse_value,
+											reverse_path,
 											base_url,
 											options,
 											false,
@@ -368,6 +481,15 @@ where
 												false
 											};
 
+										if let Some(max) = options.max_literal_length {
+											check_literal_length(
+												env.vocabulary,
+												&reverse_prop,
+												&reverse_expanded_value,
+												max,
+											)?;
+										}
+
 										if is_double_reversed {
 											result.insert_all(
 												reverse_prop,
@@ -381,7 +503,12 @@ where
 													Ok(node) => reverse_expanded_nodes.push(node),
 													Err(_) => {
 														return Err(
-															Error::InvalidReversePropertyValue,
+															Error::invalid_reverse_property_value(
+																reverse_prop
+																	.with(&*env.vocabulary)
+																	.to_string(),
+																reverse_value,
+															),
 														)
 													}
 												}
@@ -419,8 +546,10 @@ where
 						let nesting_key = key;
 						// Recursively repeat steps 3, 8, 13, and 14 using `nesting_key` for active property,
 						// and nested value for element.
-						let value = json_syntax::Value::force_as_array(value);
-						for nested_value in value {
+						let items = json_syntax::Value::force_as_array(value);
+						for (i, nested_value) in items.iter().enumerate() {
+							let nested_path = entry_path.item(value, i);
+
 							// Step 3 again.
 							let mut property_scoped_base_url = None;
 							let property_scoped_context = match active_context.get(nesting_key) {
@@ -471,6 +600,7 @@ where
 									.filter_map(|Entry { key, value }| {
 										expand_iri(
 											&mut env,
+											nested_path.key(key),
 											active_context.as_ref(),
 											Nullable::Some(key.as_str().into()),
 											false,
@@ -498,6 +628,7 @@ where
 										type_scoped_context,
 										active_property,
 										nested_expanded_entries,
+										nested_path,
 										base_url,
 										options,
 									))
@@ -516,6 +647,12 @@ where
 			}
 
 			Term::Id(prop) if prop.with(&*env.vocabulary).as_str().contains(':') => {
+				let prop =
+					match validate_iri(&mut env, entry_path, options.validate_iris, key, prop)? {
+						Some(prop) => prop,
+						None => continue,
+					};
+
 				if let Id::Invalid(name) = &prop {
 					match options.policy.invalid {
 						Action::Keep => (),
@@ -595,8 +732,10 @@ where
 										json_syntax::Value::String(item) => {
 											// If language is @none, or expands to
 											// @none, remove @language from v.
+											let language_path = entry_path.key(language);
 											let language = if expand_iri(
 												&mut env,
+												language_path,
 												active_context,
 												Nullable::Some(language.as_str().into()),
 												false,
@@ -610,12 +749,13 @@ where
 													LenientLangTagBuf::new(language.to_string());
 
 												if let Some(error) = error {
-													env.warnings.handle(
+													env.warnings.handle_at(
 														env.vocabulary,
 														Warning::MalformedLanguageTag(
 															language.to_string().clone(),
 															error,
 														),
+														&language_path.location(),
 													)
 												}
 
@@ -634,17 +774,23 @@ where
 												// TODO warning
 
 												// Append v to expanded value.
-												expanded_value.push(
+												expanded_value.extend(transform_value(
+													env.vocabulary,
+													options,
+													ActiveProperty::Some(key),
 													Object::Value(Value::LangString(v)).into(),
-												)
+												))
 											} else {
-												expanded_value.push(
+												expanded_value.extend(transform_value(
+													env.vocabulary,
+													options,
+													ActiveProperty::Some(key),
 													Object::Value(Value::Literal(
 														Literal::String(item.clone()),
 														None,
 													))
 													.into(),
-												)
+												))
 											}
 										}
 										_ => {
@@ -747,8 +893,10 @@ where
 
 								// Initialize `expanded_index` to the result of IRI
 								// expanding index.
+								let index_path = entry_path.key(index);
 								let expanded_index = match expand_iri(
 									&mut env,
+									index_path,
 									active_context,
 									Nullable::Some(index.as_str().into()),
 									false,
@@ -777,6 +925,7 @@ where
 									map_context.as_ref(),
 									ActiveProperty::Some(key),
 									index_value,
+									index_path,
 									base_url,
 									options,
 									true,
@@ -817,7 +966,9 @@ where
 													loader: env.loader,
 													warnings: env.warnings,
 												},
+												index_path,
 												options.policy.vocab,
+												options.validate_iris,
 												active_context,
 												ActiveProperty::Some(index_key),
 												LiteralValue::Inferred(index.as_str().into()),
@@ -827,6 +978,7 @@ where
 											// of IRI expanding index key.
 											let expanded_index_key = match expand_iri(
 												&mut env,
+												index_path,
 												active_context,
 												Nullable::Some(index_key.into()),
 												false,
@@ -836,6 +988,17 @@ where
 												_ => continue,
 											};
 
+											let expanded_index_key = match validate_iri(
+												&mut env,
+												index_path,
+												options.validate_iris,
+												index_key,
+												expanded_index_key,
+											)? {
+												Some(prop) => prop,
+												None => continue,
+											};
+
 											// Add the key-value pair (expanded index
 											// key-index property values) to item.
 											if let Object::Node(node) = item.inner_mut() {
@@ -868,14 +1031,26 @@ where
 											// result of IRI expanding index using true for
 											// document relative and false for vocab.
 											if let Object::Node(ref mut node) = *item {
-												node.id = expand_iri(
+												let id = expand_iri(
 													&mut env,
+													index_path,
 													active_context,
 													Nullable::Some(index.as_str().into()),
 													true,
 													None,
 												)?
 												.and_then(node_id_of_term);
+
+												node.id = match id {
+													Some(id) => validate_iri(
+														&mut env,
+														index_path,
+														options.validate_iris,
+														index,
+														id,
+													)?,
+													None => None,
+												};
 											}
 										} else if container_mapping.contains(ContainerKind::Type) {
 											// Otherwise, if container mapping includes
@@ -916,6 +1091,7 @@ where
 								active_context,
 								ActiveProperty::Some(key),
 								value,
+								entry_path,
 								base_url,
 								options,
 								false,
@@ -961,6 +1137,10 @@ where
 				}
 
 				if !expanded_value.is_null() {
+					if let Some(max) = options.max_literal_length {
+						check_literal_length(env.vocabulary, &prop, &expanded_value, max)?;
+					}
+
 					// If the term definition associated to key indicates that it
 					// is a reverse property:
 					if is_reverse_property {
@@ -969,7 +1149,12 @@ where
 						for object in expanded_value {
 							match object.try_cast::<Node<N::Iri, N::BlankId>>() {
 								Ok(node) => reverse_expanded_nodes.push(node),
-								Err(_) => return Err(Error::InvalidReversePropertyValue),
+								Err(_) => {
+									return Err(Error::invalid_reverse_property_value(
+										prop.with(&*env.vocabulary).to_string(),
+										value,
+									))
+								}
 							}
 						}
 
@@ -1005,3 +1190,35 @@ where
 
 	Ok((result, has_value_object_entries))
 }
+
+/// Checks that no string literal in `objects` is longer than `max` bytes.
+fn check_literal_length<'a, N: VocabularyMut>(
+	vocabulary: &N,
+	property: &Id<N::Iri, N::BlankId>,
+	objects: impl IntoIterator<Item = &'a IndexedObject<N::Iri, N::BlankId>>,
+	max: usize,
+) -> Result<(), Error>
+where
+	N::Iri: 'a,
+	N::BlankId: 'a,
+{
+	for object in objects {
+		match object.inner() {
+			Object::Value(value) => {
+				if let Some(s) = value.as_str() {
+					if s.len() > max {
+						return Err(Error::LiteralTooLong {
+							property: property.with(vocabulary).to_string(),
+							length: s.len(),
+							max,
+						});
+					}
+				}
+			}
+			Object::List(list) => check_literal_length(vocabulary, property, list.iter(), max)?,
+			Object::Node(_) => (),
+		}
+	}
+
+	Ok(())
+}
@@ -1,9 +1,21 @@
+use crate::ValueTransformer;
 use json_ld_core::ProcessingMode;
+use std::{fmt, str::FromStr, sync::Arc};
 
 pub use json_ld_context_processing::algorithm::Action;
 
 /// Expansion options.
-#[derive(Clone, Copy, Default)]
+///
+/// With the `serde` feature, options are (de)serialized as an object with
+/// camel case keys (such as `processingMode`). Missing keys take their
+/// default value. The [`value_transformer`](Self::value_transformer) is not
+/// serialized.
+#[derive(Clone, Default)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(default, rename_all = "camelCase")
+)]
 pub struct Options {
 	/// Sets the processing mode.
 	pub processing_mode: ProcessingMode,
@@ -16,6 +28,68 @@ pub struct Options {
 	/// If set to true, input document entries are processed lexicographically.
 	/// If false, order is not considered in processing.
 	pub ordered: bool,
+
+	/// Maximum length (in bytes) of string literal values.
+	///
+	/// If set, expansion fails with [`Error::LiteralTooLong`](crate::Error::LiteralTooLong)
+	/// as soon as a property value exceeds this length.
+	pub max_literal_length: Option<usize>,
+
+	/// Reject documents that are probably not JSON-LD.
+	///
+	/// A document without `@context`, without keyword and without absolute
+	/// IRI key, expanded with an empty initial context and not served with a
+	/// JSON media type (`application/ld+json`, `application/json` or any
+	/// `+json` suffix), is probably not JSON-LD. By default a
+	/// [`Warning::ProbablyNotJsonLd`](crate::Warning::ProbablyNotJsonLd) is
+	/// emitted. If this option is set, expansion fails with
+	/// [`Error::NotJsonLd`](crate::Error::NotJsonLd) instead.
+	pub reject_non_json_ld: bool,
+
+	/// Relabel non-conforming blank node identifiers.
+	///
+	/// By default, `@id` values starting with `_:` that are not valid blank
+	/// node identifiers (such as `_:node 1`) are kept as invalid identifiers
+	/// (or dropped, depending on the [`Policy`]). If this option is set, they
+	/// are replaced with conforming blank node identifiers at the end of the
+	/// expansion, and a
+	/// [`Warning::RelabeledBlankId`](crate::Warning::RelabeledBlankId) is
+	/// emitted for each of them.
+	///
+	/// See [`ExpandedDocument::relabel_non_conforming_blank_ids_with`](json_ld_core::ExpandedDocument::relabel_non_conforming_blank_ids_with).
+	pub relabel_non_conforming_blank_ids: bool,
+
+	/// Validation of the produced IRIs.
+	///
+	/// Sloppy contexts may produce invalid IRIs, for instance when a prefix
+	/// or vocabulary mapping is concatenated with a term containing spaces.
+	/// Such IRIs are kept as invalid identifiers by default
+	/// ([`Action::Keep`]). With [`Action::Drop`], the node identifiers,
+	/// types and properties expanding into an invalid IRI are dropped, and a
+	/// [`Warning::InvalidIri`](crate::Warning::InvalidIri) is emitted with
+	/// the term and the IRI. With [`Action::Reject`], expansion fails with
+	/// [`Error::InvalidIri`](crate::Error::InvalidIri).
+	///
+	/// Only invalid identifiers containing a `:` (other than non-conforming
+	/// blank node identifiers) are considered IRIs. Other invalid terms are
+	/// handled by the [`Policy`].
+	pub validate_iris: Action,
+
+	/// If set to `true`, the properties missing from a node object are
+	/// restored from the default value of their term.
+	///
+	/// Default values are declared with the `@default` entry of expanded
+	/// term definitions, an extension to JSON-LD. This is the counterpart of
+	/// the `omit_defaults` compaction option. Defaults are expanded like any
+	/// other entry of the node, and are not added to node references (node
+	/// objects with only an `@id` or `@index`).
+	pub materialize_defaults: bool,
+
+	/// Transformer called on each expanded value object.
+	///
+	/// See [`ValueTransformer`].
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub value_transformer: Option<Arc<dyn ValueTransformer>>,
 }
 
 impl Options {
@@ -29,8 +103,15 @@ impl Options {
 
 impl From<Options> for json_ld_context_processing::Options {
 	fn from(options: Options) -> json_ld_context_processing::Options {
+		(&options).into()
+	}
+}
+
+impl From<&Options> for json_ld_context_processing::Options {
+	fn from(options: &Options) -> json_ld_context_processing::Options {
 		json_ld_context_processing::Options {
 			processing_mode: options.processing_mode,
+			default_values: options.materialize_defaults,
 			..Default::default()
 		}
 	}
@@ -50,7 +131,18 @@ impl From<Options> for json_ld_context_processing::Options {
 /// expanded document, or to forbid them completely by raising an error.
 /// You can define your preferred policy using one of this type variant
 /// with the [`Options::policy`] field.
+///
+/// The string form of a policy is the name of one of the predefined
+/// policies: `strict-vc`, `lossless-ingest` or `permissive-web`. With the
+/// `serde` feature, a policy is serialized as an object with the `invalid`,
+/// `vocab` and `allowUndefined` keys, and can be deserialized from such an
+/// object or from the name of a predefined policy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase", try_from = "PolicyRepr")
+)]
 pub struct Policy {
 	/// How to expand invalid terms.
 	pub invalid: Action,
@@ -63,12 +155,121 @@ pub struct Policy {
 	pub allow_undefined: bool,
 }
 
-impl Default for Policy {
-	fn default() -> Self {
+impl Policy {
+	/// Strict policy for Verifiable Credentials.
+	///
+	/// Every term must be defined by the context: invalid terms, terms only
+	/// expanded through `@vocab` and undefined terms are rejected with an
+	/// error. This guarantees that no claim is silently dropped or
+	/// reinterpreted before being signed or verified.
+	pub fn strict_vc() -> Self {
+		Self {
+			invalid: Action::Reject,
+			vocab: Action::Reject,
+			allow_undefined: false,
+		}
+	}
+
+	/// Lossless ingestion policy.
+	///
+	/// Every key is kept in the expanded document, including invalid terms
+	/// and terms expanded through `@vocab`. This is the default policy.
+	pub fn lossless_ingest() -> Self {
 		Self {
 			invalid: Action::Keep,
 			vocab: Action::Keep,
 			allow_undefined: true,
 		}
 	}
+
+	/// Permissive policy for documents found on the Web.
+	///
+	/// Invalid terms are silently dropped while terms expanded through
+	/// `@vocab` and undefined terms are accepted. Processing never fails
+	/// because of the document vocabulary.
+	pub fn permissive_web() -> Self {
+		Self {
+			invalid: Action::Drop,
+			vocab: Action::Keep,
+			allow_undefined: true,
+		}
+	}
+}
+
+impl Default for Policy {
+	fn default() -> Self {
+		Self::lossless_ingest()
+	}
+}
+
+/// Unknown predefined policy name.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("unknown policy `{0}`")]
+pub struct UnknownPolicy(pub String);
+
+impl FromStr for Policy {
+	type Err = UnknownPolicy;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"strict-vc" => Ok(Self::strict_vc()),
+			"lossless-ingest" => Ok(Self::lossless_ingest()),
+			"permissive-web" => Ok(Self::permissive_web()),
+			_ => Err(UnknownPolicy(s.to_owned())),
+		}
+	}
+}
+
+impl fmt::Display for Policy {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if *self == Self::strict_vc() {
+			f.write_str("strict-vc")
+		} else if *self == Self::lossless_ingest() {
+			f.write_str("lossless-ingest")
+		} else if *self == Self::permissive_web() {
+			f.write_str("permissive-web")
+		} else {
+			write!(
+				f,
+				"invalid: {:?}, vocab: {:?}, allow undefined: {}",
+				self.invalid, self.vocab, self.allow_undefined
+			)
+		}
+	}
+}
+
+/// Serialized form of a [`Policy`]: either the name of a predefined policy,
+/// or an explicit policy.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PolicyRepr {
+	Name(String),
+
+	#[serde(rename_all = "camelCase")]
+	Explicit {
+		invalid: Action,
+		vocab: Action,
+		allow_undefined: bool,
+	},
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PolicyRepr> for Policy {
+	type Error = UnknownPolicy;
+
+	fn try_from(value: PolicyRepr) -> Result<Self, Self::Error> {
+		match value {
+			PolicyRepr::Name(name) => name.parse(),
+			PolicyRepr::Explicit {
+				invalid,
+				vocab,
+				allow_undefined,
+			} => Ok(Self {
+				invalid,
+				vocab,
+				allow_undefined,
+			}),
+		}
+	}
 }
@@ -0,0 +1,138 @@
+use crate::{InvalidIri, Warning, WarningHandler};
+use json_ld_context_processing::algorithm::{expand_iri_simple, Action, IriExpansionResult};
+use json_ld_core::{warning, warning::Location, Context, Environment, Id};
+use json_ld_syntax::{ExpandableRef, Nullable};
+use json_syntax::Value;
+use rdf_types::{vocabulary::BlankIdVocabulary, VocabularyMut};
+
+/// Path from the document root to the value being expanded.
+///
+/// It is only turned into a [`Location`] when a warning is raised, so that
+/// tracking it does not allocate.
+#[derive(Clone, Copy)]
+pub(crate) enum Path<'a> {
+	Root,
+	Key(&'a Path<'a>, &'a str),
+	Index(&'a Path<'a>, usize),
+}
+
+impl<'a> Path<'a> {
+	/// Path to the entry of this object with the given key.
+	pub fn key<'b>(&'b self, key: &'b str) -> Path<'b> {
+		Path::Key(self, key)
+	}
+
+	/// Path to the `i`-th item of `value`, seen as an array.
+	///
+	/// See [`Value::force_as_array`].
+	pub fn item(&self, value: &Value, i: usize) -> Path<'_> {
+		if value.is_array() {
+			Path::Index(self, i)
+		} else {
+			*self
+		}
+	}
+
+	pub fn location(&self) -> Location {
+		match self {
+			Self::Root => Location::root(),
+			Self::Key(parent, key) => {
+				let mut location = parent.location();
+				location.push_key(key);
+				location
+			}
+			Self::Index(parent, i) => {
+				let mut location = parent.location();
+				location.push_index(*i);
+				location
+			}
+		}
+	}
+}
+
+/// Warning handler locating the warnings it receives at the given path.
+struct Located<'a, W> {
+	handler: &'a mut W,
+	path: Path<'a>,
+}
+
+impl<'a, N: BlankIdVocabulary, W: WarningHandler<N>> warning::Handler<N, Warning<N::BlankId>>
+	for Located<'a, W>
+{
+	fn handle(&mut self, vocabulary: &N, warning: Warning<N::BlankId>) {
+		self.handler
+			.handle_at(vocabulary, warning, &self.path.location())
+	}
+}
+
+/// IRI expansion, locating the raised warnings at the given path.
+///
+/// See [`expand_iri_simple`].
+pub(crate) fn expand_iri<N, L, W>(
+	env: &mut Environment<N, L, W>,
+	path: Path,
+	active_context: &Context<N::Iri, N::BlankId>,
+	value: Nullable<ExpandableRef>,
+	document_relative: bool,
+	vocab: Option<Action>,
+) -> IriExpansionResult<N>
+where
+	N: VocabularyMut,
+	N::Iri: Clone,
+	N::BlankId: Clone,
+	W: WarningHandler<N>,
+{
+	let mut warnings = Located {
+		handler: &mut *env.warnings,
+		path,
+	};
+
+	expand_iri_simple::<Warning<N::BlankId>, _, _, _>(
+		&mut Environment::new(env.vocabulary, env.loader, &mut warnings),
+		active_context,
+		value,
+		document_relative,
+		vocab,
+	)
+}
+
+/// Checks that the given identifier, expanded from `term`, is not an invalid
+/// IRI, according to the [`Options::validate_iris`](crate::Options::validate_iris)
+/// `action`.
+///
+/// Returns `None` if the identifier must be dropped.
+pub(crate) fn validate_iri<N, L, W>(
+	env: &mut Environment<N, L, W>,
+	path: Path,
+	action: Action,
+	term: &str,
+	id: Id<N::Iri, N::BlankId>,
+) -> Result<Option<Id<N::Iri, N::BlankId>>, InvalidIri>
+where
+	N: BlankIdVocabulary,
+	W: WarningHandler<N>,
+{
+	if let Id::Invalid(iri) = &id {
+		if iri.contains(':') && !iri.starts_with("_:") {
+			match action {
+				Action::Keep => (),
+				Action::Drop => {
+					env.warnings.handle_at(
+						env.vocabulary,
+						Warning::InvalidIri(term.to_owned(), iri.clone()),
+						&path.location(),
+					);
+					return Ok(None);
+				}
+				Action::Reject => {
+					return Err(InvalidIri {
+						term: term.to_owned(),
+						iri: iri.clone(),
+					})
+				}
+			}
+		}
+	}
+
+	Ok(Some(id))
+}
@@ -0,0 +1,41 @@
+use iref::Iri;
+use json_ld_core::{object::Literal, LangString};
+
+/// Expanded value object, as given to a [`ValueTransformer`].
+pub enum ValueMut<'a> {
+	/// Typed literal value, with its datatype if any.
+	Literal(&'a mut Literal, Option<&'a Iri>),
+
+	/// Language tagged string.
+	LangString(&'a mut LangString),
+
+	/// JSON literal value.
+	Json(&'a mut json_syntax::Value),
+}
+
+/// Value object transformer.
+///
+/// Set in the expansion [`Options`](crate::Options), it is called on each
+/// value object produced by the expansion algorithm before it is inserted in
+/// the expanded document. This can be used to normalize values (such as date
+/// strings) or to remove some of them, without a second pass over the
+/// expanded document.
+///
+/// This trait is implemented by any `Fn(Option<&str>, ValueMut) -> bool`
+/// closure.
+pub trait ValueTransformer: Send + Sync {
+	/// Transforms the given value, expanded from the value of the
+	/// `active_property` term.
+	///
+	/// Returns `false` to drop the value.
+	fn transform(&self, active_property: Option<&str>, value: ValueMut) -> bool;
+}
+
+impl<F> ValueTransformer for F
+where
+	F: Send + Sync + Fn(Option<&str>, ValueMut) -> bool,
+{
+	fn transform(&self, active_property: Option<&str>, value: ValueMut) -> bool {
+		(self)(active_property, value)
+	}
+}
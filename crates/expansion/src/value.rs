@@ -1,4 +1,4 @@
-use crate::{expand_iri, Action, ExpandedEntry, Warning, WarningHandler};
+use crate::{expand_iri, Action, ExpandedEntry, Path, Warning, WarningHandler};
 use json_ld_context_processing::algorithm::RejectVocab;
 use json_ld_core::{
 	object::value::Literal, Context, Environment, Id, Indexed, IndexedObject, LangString, Object,
@@ -60,6 +60,7 @@ pub type ValueExpansionResult<T, B> = Result<Option<IndexedObject<T, B>>, Invali
 /// Expand a value object.
 pub(crate) fn expand_value<N, L, W>(
 	env: &mut Environment<N, L, W>,
+	path: Path,
 	vocab_policy: Action,
 	input_type: Option<Term<N::Iri, N::BlankId>>,
 	type_scoped_context: &Context<N::Iri, N::BlankId>,
@@ -81,7 +82,7 @@ where
 	let mut language = None;
 	let mut direction = None;
 
-	for ExpandedEntry(_, expanded_key, value) in expanded_entries {
+	for ExpandedEntry(key, expanded_key, value) in expanded_entries {
 		match expanded_key {
 			// If expanded property is @language:
 			Term::Keyword(Keyword::Language) => {
@@ -94,7 +95,7 @@ where
 					// TODO warning.
 
 					if value != "@none" {
-						language = Some(value.to_owned());
+						language = Some((key, value.to_owned()));
 					}
 				} else {
 					return Err(InvalidValue::LanguageTaggedString);
@@ -133,6 +134,7 @@ where
 				if let Some(ty_value) = value.as_str() {
 					let expanded_ty = expand_iri(
 						env,
+						path.key(key),
 						type_scoped_context,
 						Nullable::Some(ty_value.into()),
 						true,
@@ -206,13 +208,14 @@ where
 
 		if let Literal::String(s) = result {
 			let lang = match language {
-				Some(language) => {
+				Some((key, language)) => {
 					let (language, error) = LenientLangTagBuf::new(language);
 
 					if let Some(error) = error {
-						env.warnings.handle(
+						env.warnings.handle_at(
 							env.vocabulary,
 							Warning::MalformedLanguageTag(language.to_string(), error),
+							&path.key(key).location(),
 						)
 					}
 
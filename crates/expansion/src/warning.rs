@@ -10,8 +10,25 @@ pub enum Warning<B> {
 	EmptyTerm,
 	BlankNodeIdProperty(B),
 	MalformedLanguageTag(String, InvalidLangTag<String>),
+
+	/// The input document has no `@context`, uses no keyword and no absolute
+	/// IRI as key, and has not been served with a JSON media type: it is
+	/// probably not JSON-LD, and will expand to an empty document.
+	ProbablyNotJsonLd,
+
+	/// The given non-conforming blank node identifier has been replaced
+	/// with the given blank node identifier.
+	RelabeledBlankId(String, B),
+
+	/// The given term expanded into the given invalid IRI, and has been
+	/// dropped.
+	///
+	/// See [`Options::validate_iris`](crate::Options::validate_iris).
+	InvalidIri(String, String),
 }
 
+const PROBABLY_NOT_JSON_LD: &str = "document is probably not JSON-LD (no `@context`, no keyword, no absolute IRI key); provide a context using the `expandContext` option or a `Link` header, or add an `@context` entry to the document";
+
 impl<B> From<MalformedIri> for Warning<B> {
 	fn from(MalformedIri(s): MalformedIri) -> Self {
 		Self::MalformedIri(s)
@@ -27,6 +44,14 @@ impl<B: fmt::Display> fmt::Display for Warning<B> {
 				write!(f, "blank node identifier `{b}` used as property")
 			}
 			Self::MalformedLanguageTag(t, e) => write!(f, "invalid language tag `{t}`: {e}"),
+			Self::ProbablyNotJsonLd => f.write_str(PROBABLY_NOT_JSON_LD),
+			Self::RelabeledBlankId(s, b) => {
+				write!(
+					f,
+					"non-conforming blank node identifier `{s}` relabeled `{b}`"
+				)
+			}
+			Self::InvalidIri(t, iri) => write!(f, "term `{t}` expanded into invalid IRI `{iri}`"),
 		}
 	}
 }
@@ -44,6 +69,15 @@ impl<B, N: BlankIdVocabulary<BlankId = B>> DisplayWithContext<N> for Warning<B>
 				)
 			}
 			Self::MalformedLanguageTag(t, e) => write!(f, "invalid language tag `{t}`: {e}"),
+			Self::ProbablyNotJsonLd => f.write_str(PROBABLY_NOT_JSON_LD),
+			Self::RelabeledBlankId(s, b) => {
+				write!(
+					f,
+					"non-conforming blank node identifier `{s}` relabeled `{}`",
+					vocabulary.blank_id(b).unwrap()
+				)
+			}
+			Self::InvalidIri(t, iri) => write!(f, "term `{t}` expanded into invalid IRI `{iri}`"),
 		}
 	}
 }
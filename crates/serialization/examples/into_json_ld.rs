@@ -1,4 +1,3 @@
-use contextual::WithContext;
 use json_ld_serialization::serialize;
 use json_syntax::Print;
 
@@ -19,5 +18,5 @@ fn main() {
 	};
 
 	let json = serialize(&value).expect("serialization failed");
-	eprintln!("{}", json.with(&()).pretty_print());
+	eprintln!("{}", json.pretty_print());
 }
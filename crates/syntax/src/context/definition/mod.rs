@@ -147,6 +147,28 @@ impl Definition {
 	pub fn get_binding(&self, key: &Key) -> Option<Nullable<&TermDefinition>> {
 		self.bindings.get(key)
 	}
+
+	/// Upgrades this JSON-LD 1.0 context definition to the JSON-LD 1.1
+	/// syntax.
+	///
+	/// Sets `@version` to `1.1` and rewrites simple term definitions into
+	/// expanded term definitions, in this context and in every scoped
+	/// context, so that 1.1 keywords can be added to them. Terms whose simple
+	/// definition makes them prefixes are given `"@prefix": true` so that
+	/// compact IRIs using them keep their meaning. Simple definitions whose
+	/// IRI mapping depends on another term (a term, or a compact IRI with an
+	/// empty suffix such as `"foaf:"`) are left as is, since whether they
+	/// define a prefix depends on the definition of that term, which a scoped
+	/// context may change.
+	pub fn upgrade_to_1_1(&mut self) {
+		self.version = Some(Version::V1_1);
+
+		for (key, binding) in &mut self.bindings.0 {
+			if let Nullable::Some(definition) = binding {
+				definition.upgrade_to_1_1(key)
+			}
+		}
+	}
 }
 
 /// Context bindings.
@@ -2,6 +2,7 @@ use super::{BindingsIter, Definition, EntryValueSubItems, Key, Type, Version, Vo
 use crate::{context::TermDefinition, Direction, LenientLangTagBuf, Nullable};
 
 use iref::IriRef;
+use std::fmt;
 
 impl Definition {
 	pub fn iter(&self) -> Entries {
@@ -137,6 +138,15 @@ impl<'a> EntryValueRef<'a> {
 		}
 	}
 
+	/// Returns the term definition, if this is a term definition entry
+	/// value.
+	pub fn as_definition(&self) -> Option<Nullable<&'a TermDefinition>> {
+		match self {
+			Self::Definition(d) => Some(*d),
+			_ => None,
+		}
+	}
+
 	pub fn sub_items(&self) -> EntryValueSubItems<'a> {
 		match self {
 			Self::Definition(Nullable::Some(TermDefinition::Expanded(e))) => {
@@ -160,7 +170,7 @@ pub enum EntryRef<'a> {
 	Definition(&'a Key, Nullable<&'a TermDefinition>),
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EntryKeyRef<'a> {
 	Base,
 	Import,
@@ -175,6 +185,11 @@ pub enum EntryKeyRef<'a> {
 }
 
 impl<'a> EntryKeyRef<'a> {
+	/// Checks if this is a keyword entry key, as opposed to a term.
+	pub fn is_keyword(&self) -> bool {
+		!matches!(self, Self::Definition(_))
+	}
+
 	pub fn as_str(&self) -> &'a str {
 		match self {
 			Self::Base => "@base",
@@ -191,7 +206,27 @@ impl<'a> EntryKeyRef<'a> {
 	}
 }
 
+impl<'a> fmt::Display for EntryKeyRef<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
+	}
+}
+
 impl<'a> EntryRef<'a> {
+	/// Checks if this is a keyword entry, as opposed to a term definition.
+	pub fn is_keyword(&self) -> bool {
+		!matches!(self, Self::Definition(_, _))
+	}
+
+	/// Returns the term and its definition, if this is a term definition
+	/// entry.
+	pub fn as_definition(&self) -> Option<(&'a Key, Nullable<&'a TermDefinition>)> {
+		match self {
+			Self::Definition(key, d) => Some((key, *d)),
+			_ => None,
+		}
+	}
+
 	pub fn into_key(self) -> EntryKeyRef<'a> {
 		match self {
 			Self::Base(_) => EntryKeyRef::Base,
@@ -92,6 +92,23 @@ impl Context {
 	pub fn iter(&self) -> std::slice::Iter<ContextEntry> {
 		self.as_slice().iter()
 	}
+
+	/// Upgrades the context definitions of this JSON-LD 1.0 context to the
+	/// JSON-LD 1.1 syntax.
+	///
+	/// See [`Definition::upgrade_to_1_1`].
+	pub fn upgrade_to_1_1(&mut self) {
+		let entries = match self {
+			Self::One(c) => std::slice::from_mut(c),
+			Self::Many(list) => list.as_mut_slice(),
+		};
+
+		for entry in entries {
+			if let ContextEntry::Definition(definition) = entry {
+				definition.upgrade_to_1_1()
+			}
+		}
+	}
 }
 
 pub enum IntoIter {
@@ -362,6 +362,7 @@ impl<'a> PrecomputeSize for term_definition::EntryRef<'a> {
 			Self::Prefix(v) => v.pre_compute_size(options, sizes),
 			Self::Propagate(v) => v.pre_compute_size(options, sizes),
 			Self::Protected(v) => v.pre_compute_size(options, sizes),
+			Self::Default(v) => v.pre_compute_size(options, sizes),
 		}
 	}
 }
@@ -388,6 +389,7 @@ impl<'a> PrintWithSize for term_definition::EntryRef<'a> {
 			Self::Prefix(v) => v.fmt_with(f, options, indent),
 			Self::Propagate(v) => v.fmt_with(f, options, indent),
 			Self::Protected(v) => v.fmt_with(f, options, indent),
+			Self::Default(v) => v.fmt_with_size(f, options, indent, sizes, index),
 		}
 	}
 }
@@ -1,10 +1,11 @@
 use crate::{
-	container, context, CompactIri, CompactIriBuf, Container, ContainerKind, Direction, Keyword,
-	LenientLangTag, LenientLangTagBuf, Nullable,
+	container, context, is_keyword, CompactIri, CompactIriBuf, Container, ContainerKind, Direction,
+	Keyword, LenientLangTag, LenientLangTagBuf, Nullable,
 };
 use educe::Educe;
 use iref::{Iri, IriBuf};
 use rdf_types::{BlankId, BlankIdBuf};
+use std::fmt;
 
 mod id;
 mod index;
@@ -16,6 +17,10 @@ pub use index::*;
 pub use nest::*;
 pub use type_::*;
 
+fn is_gen_delim(c: char) -> bool {
+	matches!(c, ':' | '/' | '?' | '#' | '[' | ']' | '@')
+}
+
 /// Term definition.
 #[derive(PartialEq, Eq, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -34,6 +39,41 @@ impl TermDefinition {
 		self.is_expanded()
 	}
 
+	/// Rewrites this definition of `key` into an expanded term definition,
+	/// upgrading its scoped context if any.
+	///
+	/// See [`context::Definition::upgrade_to_1_1`].
+	pub fn upgrade_to_1_1(&mut self, key: &context::definition::Key) {
+		match self {
+			Self::Simple(simple) => {
+				// The prefix flag of a simple term definition depends on the
+				// last character of its IRI mapping, which is only known here
+				// if it does not depend on the definition of another term.
+				let value = simple.as_str();
+				let ends_with_gen_delim = match value.split_once(':') {
+					_ if simple.as_blank_id().is_some() => true,
+					Some((_, "")) => return,
+					Some(_) => value.ends_with(is_gen_delim),
+					None if is_keyword(value) => false,
+					None => return,
+				};
+
+				let prefix = !key.as_str().contains([':', '/']) && ends_with_gen_delim;
+
+				*self = Self::Expanded(Box::new(Expanded {
+					id: Some(Nullable::Some(value.to_owned().into())),
+					prefix: prefix.then_some(true),
+					..Expanded::default()
+				}))
+			}
+			Self::Expanded(expanded) => {
+				if let Some(context) = &mut expanded.context {
+					context.upgrade_to_1_1()
+				}
+			}
+		}
+	}
+
 	pub fn as_expanded(&self) -> ExpandedRef {
 		match self {
 			Self::Simple(term) => ExpandedRef {
@@ -202,6 +242,17 @@ pub struct Expanded {
 		)
 	)]
 	pub protected: Option<bool>,
+
+	/// Default value of the term (`@default` extension).
+	///
+	/// This is not part of the JSON-LD specification. When enabled by the
+	/// processing options, properties equal to their default value are
+	/// omitted by compaction, and restored by expansion.
+	#[cfg_attr(
+		feature = "serde",
+		serde(rename = "@default", default, skip_serializing_if = "Option::is_none")
+	)]
+	pub default: Option<json_syntax::Value>,
 }
 
 impl Expanded {
@@ -222,6 +273,7 @@ impl Expanded {
 			&& self.prefix.is_none()
 			&& self.propagate.is_none()
 			&& self.protected.is_none()
+			&& self.default.is_none()
 	}
 
 	pub fn is_simple_definition(&self) -> bool {
@@ -237,6 +289,7 @@ impl Expanded {
 			&& self.prefix.is_none()
 			&& self.propagate.is_none()
 			&& self.protected.is_none()
+			&& self.default.is_none()
 	}
 
 	pub fn simplify(self) -> Nullable<TermDefinition> {
@@ -265,6 +318,7 @@ impl Expanded {
 			prefix: self.prefix,
 			propagate: self.propagate,
 			protected: self.protected,
+			default: self.default.as_ref(),
 		}
 	}
 
@@ -288,6 +342,7 @@ impl Expanded {
 			prefix: self.prefix,
 			propagate: self.propagate,
 			protected: self.protected,
+			default: self.default.as_ref(),
 		}
 	}
 }
@@ -308,6 +363,7 @@ pub struct ExpandedRef<'a> {
 	pub prefix: Option<bool>,
 	pub propagate: Option<bool>,
 	pub protected: Option<bool>,
+	pub default: Option<&'a json_syntax::Value>,
 }
 
 impl<'a> From<Nullable<&'a TermDefinition>> for ExpandedRef<'a> {
@@ -336,6 +392,7 @@ pub struct Entries<'a> {
 	prefix: Option<bool>,
 	propagate: Option<bool>,
 	protected: Option<bool>,
+	default: Option<&'a json_syntax::Value>,
 }
 
 pub enum EntryRef<'a> {
@@ -351,6 +408,7 @@ pub enum EntryRef<'a> {
 	Prefix(bool),
 	Propagate(bool),
 	Protected(bool),
+	Default(&'a json_syntax::Value),
 }
 
 impl<'a> EntryRef<'a> {
@@ -368,6 +426,7 @@ impl<'a> EntryRef<'a> {
 			Self::Prefix(_) => EntryKey::Prefix,
 			Self::Propagate(_) => EntryKey::Propagate,
 			Self::Protected(_) => EntryKey::Protected,
+			Self::Default(_) => EntryKey::Default,
 		}
 	}
 
@@ -385,6 +444,7 @@ impl<'a> EntryRef<'a> {
 			Self::Prefix(_) => EntryKey::Prefix,
 			Self::Propagate(_) => EntryKey::Propagate,
 			Self::Protected(_) => EntryKey::Protected,
+			Self::Default(_) => EntryKey::Default,
 		}
 	}
 
@@ -406,6 +466,7 @@ impl<'a> EntryRef<'a> {
 			Self::Prefix(e) => EntryValueRef::Prefix(*e),
 			Self::Propagate(e) => EntryValueRef::Propagate(*e),
 			Self::Protected(e) => EntryValueRef::Protected(*e),
+			Self::Default(e) => EntryValueRef::Default(e),
 		}
 	}
 
@@ -427,10 +488,12 @@ impl<'a> EntryRef<'a> {
 			Self::Prefix(e) => (EntryKey::Prefix, EntryValueRef::Prefix(*e)),
 			Self::Propagate(e) => (EntryKey::Propagate, EntryValueRef::Propagate(*e)),
 			Self::Protected(e) => (EntryKey::Protected, EntryValueRef::Protected(*e)),
+			Self::Default(e) => (EntryKey::Default, EntryValueRef::Default(e)),
 		}
 	}
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EntryKey {
 	Id,
 	Type,
@@ -444,28 +507,54 @@ pub enum EntryKey {
 	Prefix,
 	Propagate,
 	Protected,
+	Default,
 }
 
 impl EntryKey {
+	/// Returns the keyword of the entry key.
+	///
+	/// # Panics
+	///
+	/// Panics for the `@default` extension entry, which is not a keyword.
+	#[deprecated(
+		since = "0.22.0",
+		note = "panics on the `@default` entry, use `try_keyword` instead"
+	)]
 	pub fn keyword(&self) -> Keyword {
+		self.try_keyword().expect("`@default` is not a keyword")
+	}
+
+	/// Returns the keyword of the entry key, or `None` for the `@default`
+	/// extension entry, which is not a keyword.
+	pub fn try_keyword(&self) -> Option<Keyword> {
 		match self {
-			Self::Id => Keyword::Id,
-			Self::Type => Keyword::Type,
-			Self::Context => Keyword::Context,
-			Self::Reverse => Keyword::Reverse,
-			Self::Index => Keyword::Index,
-			Self::Language => Keyword::Language,
-			Self::Direction => Keyword::Direction,
-			Self::Container => Keyword::Container,
-			Self::Nest => Keyword::Nest,
-			Self::Prefix => Keyword::Prefix,
-			Self::Propagate => Keyword::Propagate,
-			Self::Protected => Keyword::Protected,
+			Self::Id => Some(Keyword::Id),
+			Self::Type => Some(Keyword::Type),
+			Self::Context => Some(Keyword::Context),
+			Self::Reverse => Some(Keyword::Reverse),
+			Self::Index => Some(Keyword::Index),
+			Self::Language => Some(Keyword::Language),
+			Self::Direction => Some(Keyword::Direction),
+			Self::Container => Some(Keyword::Container),
+			Self::Nest => Some(Keyword::Nest),
+			Self::Prefix => Some(Keyword::Prefix),
+			Self::Propagate => Some(Keyword::Propagate),
+			Self::Protected => Some(Keyword::Protected),
+			Self::Default => None,
 		}
 	}
 
 	pub fn as_str(&self) -> &'static str {
-		self.keyword().into_str()
+		match self.try_keyword() {
+			Some(k) => k.into_str(),
+			None => "@default",
+		}
+	}
+}
+
+impl fmt::Display for EntryKey {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
 	}
 }
 
@@ -482,6 +571,7 @@ pub enum EntryValueRef<'a> {
 	Prefix(bool),
 	Propagate(bool),
 	Protected(bool),
+	Default(&'a json_syntax::Value),
 }
 
 impl<'a> EntryValueRef<'a> {
@@ -554,6 +644,10 @@ impl<'a> Iterator for Entries<'a> {
 			len += 1
 		}
 
+		if self.default.is_some() {
+			len += 1
+		}
+
 		(len, Some(len))
 	}
 
@@ -580,10 +674,15 @@ impl<'a> Iterator for Entries<'a> {
 												Some(value) => Some(EntryRef::Prefix(value)),
 												None => match self.propagate.take() {
 													Some(value) => Some(EntryRef::Propagate(value)),
-													None => self
-														.protected
-														.take()
-														.map(EntryRef::Protected),
+													None => match self.protected.take() {
+														Some(value) => {
+															Some(EntryRef::Protected(value))
+														}
+														None => self
+															.default
+															.take()
+															.map(EntryRef::Default),
+													},
 												},
 											},
 										},
@@ -58,6 +58,14 @@ impl TryFromJson for TermDefinition {
 				let mut def = term_definition::Expanded::new();
 
 				for json_syntax::object::Entry { key, value } in o {
+					// Like `@propagate`, the `@default` extension entry is
+					// parsed here but rejected by context processing unless
+					// enabled.
+					if key.as_str() == "@default" {
+						def.default = Some(value);
+						continue;
+					}
+
 					match Keyword::try_from(key.as_str()) {
 						Ok(Keyword::Id) => def.id = Some(Nullable::try_from_json(value)?),
 						Ok(Keyword::Type) => def.type_ = Some(Nullable::try_from_json(value)?),
@@ -349,3 +357,118 @@ impl TryFromJson for ContextEntry {
 		}
 	}
 }
+
+impl Context {
+	/// Builds a context from a borrowed JSON value.
+	///
+	/// This is equivalent to [`TryFromJson::try_from_json`] on a clone of
+	/// `value`, without cloning the whole JSON tree first: only the leaves
+	/// that end up in the context are copied. This matters for documents
+	/// embedding large contexts.
+	pub fn try_from_json_ref(value: &json_syntax::Value) -> Result<Self, InvalidContext> {
+		match value {
+			json_syntax::Value::Array(a) => {
+				let mut many = Vec::with_capacity(a.len());
+
+				for item in a {
+					many.push(ContextEntry::try_from_json_ref(item)?)
+				}
+
+				Ok(Self::Many(many))
+			}
+			context => Ok(Self::One(ContextEntry::try_from_json_ref(context)?)),
+		}
+	}
+}
+
+impl ContextEntry {
+	/// Builds a context entry from a borrowed JSON value.
+	///
+	/// See [`Context::try_from_json_ref`].
+	pub fn try_from_json_ref(value: &json_syntax::Value) -> Result<Self, InvalidContext> {
+		match value {
+			json_syntax::Value::Object(o) => {
+				let mut def = Definition::new();
+
+				for json_syntax::object::Entry { key, value } in o {
+					match Keyword::try_from(key.as_str()) {
+						Ok(Keyword::Base) => {
+							def.base = Some(Nullable::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Import) => {
+							def.import = Some(IriRefBuf::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Language) => {
+							def.language = Some(Nullable::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Direction) => {
+							def.direction = Some(Nullable::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Propagate) => {
+							def.propagate = Some(bool::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Protected) => {
+							def.protected = Some(bool::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Type) => {
+							def.type_ = Some(definition::Type::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Version) => {
+							def.version = Some(definition::Version::try_from_json(value.clone())?)
+						}
+						Ok(Keyword::Vocab) => {
+							def.vocab = Some(Nullable::try_from_json(value.clone())?)
+						}
+						_ => {
+							let term_def = match value {
+								json_syntax::Value::Null => Nullable::Null,
+								other => Nullable::Some(TermDefinition::try_from_json_ref(other)?),
+							};
+
+							if def
+								.bindings
+								.insert_with(key.clone().into(), term_def)
+								.is_some()
+							{
+								return Err(InvalidContext::DuplicateKey);
+							}
+						}
+					}
+				}
+
+				Ok(Self::Definition(def))
+			}
+			other => Self::try_from_json(other.clone()),
+		}
+	}
+}
+
+impl TermDefinition {
+	/// Builds a term definition from a borrowed JSON value.
+	///
+	/// See [`Context::try_from_json_ref`].
+	pub fn try_from_json_ref(value: &json_syntax::Value) -> Result<Self, InvalidContext> {
+		match value {
+			json_syntax::Value::Object(o) => {
+				let mut entries = json_syntax::Object::new();
+				let mut context = None;
+
+				for json_syntax::object::Entry { key, value } in o {
+					if key.as_str() == "@context" {
+						context = Some(Box::new(Context::try_from_json_ref(value)?))
+					} else {
+						entries.insert(key.clone(), value.clone());
+					}
+				}
+
+				let mut def = Self::try_from_json(json_syntax::Value::Object(entries))?;
+				if let (Self::Expanded(expanded), Some(context)) = (&mut def, context) {
+					expanded.context = Some(context)
+				}
+
+				Ok(def)
+			}
+			other => Self::try_from_json(other.clone()),
+		}
+	}
+}
@@ -171,6 +171,9 @@ pub enum ErrorCode {
 
 	/// Duplicate key in JSON object.
 	DuplicateKey,
+
+	/// The input document is probably not JSON-LD.
+	NotJsonLd,
 }
 
 impl ErrorCode {
@@ -230,6 +233,141 @@ impl ErrorCode {
 			ProcessingModeConflict => "processing mode conflict",
 			ProtectedTermRedefinition => "protected term redefinition",
 			DuplicateKey => "duplicate key",
+			NotJsonLd => "not JSON-LD",
+		}
+	}
+
+	/// Returns the URL of the definition of this error code in the JSON-LD
+	/// 1.1 Processing Algorithms and API specification.
+	///
+	/// Returns `None` for the error codes that are specific to this library
+	/// and not defined by the specification.
+	pub fn spec_uri(&self) -> Option<&'static str> {
+		use ErrorCode::*;
+
+		macro_rules! spec {
+			($anchor:literal) => {
+				Some(concat!(
+					"https://www.w3.org/TR/json-ld11-api/#dom-jsonlderrorcode-",
+					$anchor
+				))
+			};
+		}
+
+		match self {
+			CollidingKeywords => spec!("colliding-keywords"),
+			ConflictingIndexes => spec!("conflicting-indexes"),
+			ContextOverflow => spec!("context-overflow"),
+			CyclicIriMapping => spec!("cyclic-iri-mapping"),
+			InvalidIdValue => spec!("invalid-%40id-value"),
+			InvalidImportValue => spec!("invalid-%40import-value"),
+			InvalidIncludedValue => spec!("invalid-%40included-value"),
+			InvalidIndexValue => spec!("invalid-%40index-value"),
+			InvalidNestValue => spec!("invalid-%40nest-value"),
+			InvalidPrefixValue => spec!("invalid-%40prefix-value"),
+			InvalidPropagateValue => spec!("invalid-%40propagate-value"),
+			InvalidProtectedValue => spec!("invalid-%40protected-value"),
+			InvalidReverseValue => spec!("invalid-%40reverse-value"),
+			InvalidVersionValue => spec!("invalid-%40version-value"),
+			InvalidBaseDirection => spec!("invalid-base-direction"),
+			InvalidBaseIri => spec!("invalid-base-iri"),
+			InvalidContainerMapping => spec!("invalid-container-mapping"),
+			InvalidContextEntry => spec!("invalid-context-entry"),
+			InvalidContextNullification => spec!("invalid-context-nullification"),
+			InvalidDefaultLanguage => spec!("invalid-default-language"),
+			InvalidIriMapping => spec!("invalid-iri-mapping"),
+			InvalidJsonLiteral => spec!("invalid-json-literal"),
+			InvalidKeywordAlias => spec!("invalid-keyword-alias"),
+			InvalidLanguageMapValue => spec!("invalid-language-map-value"),
+			InvalidLanguageMapping => spec!("invalid-language-mapping"),
+			InvalidLanguageTaggedString => spec!("invalid-language-tagged-string"),
+			InvalidLanguageTaggedValue => spec!("invalid-language-tagged-value"),
+			InvalidLocalContext => spec!("invalid-local-context"),
+			InvalidRemoteContext => spec!("invalid-remote-context"),
+			InvalidReverseProperty => spec!("invalid-reverse-property"),
+			InvalidReversePropertyMap => spec!("invalid-reverse-property-map"),
+			InvalidReversePropertyValue => spec!("invalid-reverse-property-value"),
+			InvalidScopedContext => spec!("invalid-scoped-context"),
+			InvalidScriptElement => spec!("invalid-script-element"),
+			InvalidSetOrListObject => spec!("invalid-set-or-list-object"),
+			InvalidTermDefinition => spec!("invalid-term-definition"),
+			InvalidTypeMapping => spec!("invalid-type-mapping"),
+			InvalidTypeValue => spec!("invalid-type-value"),
+			InvalidTypedValue => spec!("invalid-typed-value"),
+			InvalidValueObject => spec!("invalid-value-object"),
+			InvalidValueObjectValue => spec!("invalid-value-object-value"),
+			InvalidVocabMapping => spec!("invalid-vocab-mapping"),
+			IriConfusedWithPrefix => spec!("iri-confused-with-prefix"),
+			KeyExpansionFailed => None,
+			KeywordRedefinition => spec!("keyword-redefinition"),
+			LoadingDocumentFailed => spec!("loading-document-failed"),
+			LoadingRemoteContextFailed => spec!("loading-remote-context-failed"),
+			MultipleContextLinkHeaders => spec!("multiple-context-link-headers"),
+			ProcessingModeConflict => spec!("processing-mode-conflict"),
+			ProtectedTermRedefinition => spec!("protected-term-redefinition"),
+			DuplicateKey => None,
+			NotJsonLd => None,
+		}
+	}
+
+	/// Returns a description of the error, as given by the JSON-LD API
+	/// error code registry.
+	pub fn description(&self) -> &'static str {
+		use ErrorCode::*;
+
+		match self {
+			CollidingKeywords => "Two properties which expand to the same keyword have been detected. This might occur if a keyword and an alias thereof are used at the same time.",
+			ConflictingIndexes => "Multiple conflicting indexes have been found for the same node.",
+			ContextOverflow => "Maximum number of @context URLs exceeded.",
+			CyclicIriMapping => "A cycle in IRI mappings has been detected.",
+			InvalidIdValue => "An @id entry was encountered whose value was not a string.",
+			InvalidImportValue => "An invalid value for @import has been found.",
+			InvalidIncludedValue => "An included block contains an invalid value.",
+			InvalidIndexValue => "An @index entry was encountered whose value was not a string.",
+			InvalidNestValue => "An invalid value for @nest has been found.",
+			InvalidPrefixValue => "An invalid value for @prefix has been found.",
+			InvalidPropagateValue => "An invalid value for @propagate has been found.",
+			InvalidProtectedValue => "An invalid value for @protected has been found.",
+			InvalidReverseValue => "An invalid value for an @reverse entry has been detected, i.e., the value was not a map.",
+			InvalidVersionValue => "The @version entry was used in a context with an out of range value.",
+			InvalidBaseDirection => "The value of @direction is not \"ltr\", \"rtl\", or null and thus invalid.",
+			InvalidBaseIri => "An invalid base IRI has been detected, i.e., it is neither an IRI nor null.",
+			InvalidContainerMapping => "An @container entry was encountered whose value was not one of the following strings: @list, @set, or @index.",
+			InvalidContextEntry => "An entry in a context is invalid due to processing mode incompatibility.",
+			InvalidContextNullification => "An attempt was made to nullify a context containing protected term definitions.",
+			InvalidDefaultLanguage => "The value of the default language is not a string or null and thus invalid.",
+			InvalidIriMapping => "A local context contains a term that has an invalid or missing IRI mapping.",
+			InvalidJsonLiteral => "An invalid JSON literal was detected.",
+			InvalidKeywordAlias => "An invalid keyword alias definition has been encountered.",
+			InvalidLanguageMapValue => "An invalid value in a language map has been detected. It MUST be a string or an array of strings.",
+			InvalidLanguageMapping => "An @language entry in a term definition was encountered whose value was neither a string nor null and thus invalid.",
+			InvalidLanguageTaggedString => "A language-tagged string with an invalid language value was detected.",
+			InvalidLanguageTaggedValue => "A number, true, or false with an associated language tag was detected.",
+			InvalidLocalContext => "An invalid local context was detected.",
+			InvalidRemoteContext => "No valid context document has been found for a referenced remote context.",
+			InvalidReverseProperty => "An invalid reverse property definition has been detected.",
+			InvalidReversePropertyMap => "An invalid reverse property map has been detected. No keywords apart from @context are allowed in reverse property maps.",
+			InvalidReversePropertyValue => "An invalid value for a reverse property has been detected. The value of an inverse property must be a node object.",
+			InvalidScopedContext => "The local context defined within a term definition is invalid.",
+			InvalidScriptElement => "A script element in HTML input which is the target of a fragment identifier does not have an appropriate type attribute.",
+			InvalidSetOrListObject => "A set object or list object with disallowed entries has been detected.",
+			InvalidTermDefinition => "An invalid term definition has been detected.",
+			InvalidTypeMapping => "An @type entry in a term definition was encountered whose value could not be expanded to an IRI.",
+			InvalidTypeValue => "An invalid value for an @type entry has been detected, i.e., the value was neither a string nor an array of strings.",
+			InvalidTypedValue => "A typed value with an invalid type was detected.",
+			InvalidValueObject => "A value object with disallowed entries has been detected.",
+			InvalidValueObjectValue => "An invalid value for the @value entry of a value object has been detected, i.e., it is neither a scalar nor null.",
+			InvalidVocabMapping => "An invalid vocabulary mapping has been detected, i.e., it is neither an IRI nor null.",
+			IriConfusedWithPrefix => "When compacting an IRI would result in an IRI which could be confused with a compact IRI (because its IRI scheme matches a term definition and it has no IRI authority).",
+			KeyExpansionFailed => "Unable to expand a key into a IRI, blank node identifier or keyword using the current key expansion policy.",
+			KeywordRedefinition => "A keyword redefinition has been detected.",
+			LoadingDocumentFailed => "The document could not be loaded or parsed as JSON.",
+			LoadingRemoteContextFailed => "There was a problem encountered loading a remote context.",
+			MultipleContextLinkHeaders => "Multiple HTTP Link Headers (RFC 8288) using the http://www.w3.org/ns/json-ld#context link relation have been detected.",
+			ProcessingModeConflict => "An attempt was made to change the processing mode which is incompatible with the previous specified version.",
+			ProtectedTermRedefinition => "An attempt was made to redefine a protected term.",
+			DuplicateKey => "Duplicate key in JSON object.",
+			NotJsonLd => "The input document has no `@context`, no keyword and no absolute IRI key, and has not been served with a JSON media type: it is probably not JSON-LD.",
 		}
 	}
 }
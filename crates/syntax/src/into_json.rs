@@ -320,6 +320,10 @@ impl IntoJson for context::term_definition::Expanded {
 			object.insert("@protected".into(), protected.into_json());
 		}
 
+		if let Some(default) = self.default {
+			object.insert("@default".into(), default);
+		}
+
 		json_syntax::Value::Object(object)
 	}
 }
@@ -30,6 +30,15 @@ impl LenientLangTag {
 	pub fn as_well_formed(&self) -> Option<&LangTag> {
 		LangTag::new(self.as_str()).ok()
 	}
+
+	/// Compares this language tag with the given string, ignoring case.
+	///
+	/// Language tags are case-insensitive (`en-US` and `en-us` are the same
+	/// tag), which is also how the `PartialEq` implementation compares two
+	/// `LenientLangTag`s.
+	pub fn eq_ignore_case(&self, other: &str) -> bool {
+		case_insensitive_eq(self.as_bytes(), other.as_bytes())
+	}
 }
 
 impl PartialEq for LenientLangTag {
@@ -66,6 +75,9 @@ impl ToOwned for LenientLangTag {
 	}
 }
 
+/// Note that, contrarily to the language tag, `str` is compared and hashed
+/// case-sensitively: looking up a language tag by its `str` form only finds
+/// it if it has the same case.
 impl Borrow<str> for LenientLangTag {
 	fn borrow(&self) -> &str {
 		self.as_str()
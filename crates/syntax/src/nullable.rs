@@ -40,6 +40,17 @@ impl<T> Nullable<T> {
 		}
 	}
 
+	/// Unwraps a non-null value.
+	///
+	/// Panics with the given message if the value is `null`.
+	#[inline(always)]
+	pub fn expect(self, msg: &str) -> T {
+		match self {
+			Nullable::Some(t) => t,
+			Nullable::Null => panic!("{msg}"),
+		}
+	}
+
 	/// Returns a nullabl reference to the inner value.
 	#[inline(always)]
 	pub fn as_ref(&self) -> Nullable<&T> {
@@ -49,6 +60,16 @@ impl<T> Nullable<T> {
 		}
 	}
 
+	/// Returns a nullable mutable reference to the inner value.
+	#[inline(always)]
+	pub fn as_mut(&mut self) -> Nullable<&mut T> {
+		match self {
+			Nullable::Null => Nullable::Null,
+			Nullable::Some(t) => Nullable::Some(t),
+		}
+	}
+
+	/// Returns a nullable reference to the dereferenced inner value.
 	pub fn as_deref(&self) -> Nullable<&T::Target>
 	where
 		T: std::ops::Deref,
@@ -80,6 +101,33 @@ impl<T> Nullable<T> {
 		}
 	}
 
+	/// Map the inner value using the given function, returning a nullable
+	/// value.
+	#[inline(always)]
+	pub fn and_then<F, U>(self, f: F) -> Nullable<U>
+	where
+		F: FnOnce(T) -> Nullable<U>,
+	{
+		match self {
+			Nullable::Null => Nullable::Null,
+			Nullable::Some(t) => f(t),
+		}
+	}
+
+	/// Map the inner value using the given function, or returns `default` if
+	/// the value is `null`.
+	#[inline(always)]
+	pub fn map_or<F, U>(self, default: U, f: F) -> U
+	where
+		F: FnOnce(T) -> U,
+	{
+		match self {
+			Nullable::Null => default,
+			Nullable::Some(t) => f(t),
+		}
+	}
+
+	/// Converts the inner value into `U`.
 	pub fn cast<U>(self) -> Nullable<U>
 	where
 		T: Into<U>,
@@ -90,6 +138,7 @@ impl<T> Nullable<T> {
 		}
 	}
 
+	/// Returns the inner value, or `default` if the value is `null`.
 	pub fn unwrap_or(self, default: T) -> T {
 		match self {
 			Self::Null => default,
@@ -97,6 +146,17 @@ impl<T> Nullable<T> {
 		}
 	}
 
+	/// Returns the inner value, or computes it from `f` if the value is
+	/// `null`.
+	pub fn unwrap_or_else(self, f: impl FnOnce() -> T) -> T {
+		match self {
+			Self::Null => f(),
+			Self::Some(t) => t,
+		}
+	}
+
+	/// Returns the inner value, or the default value of `T` if the value is
+	/// `null`.
 	pub fn unwrap_or_default(self) -> T
 	where
 		T: Default,
@@ -123,6 +183,12 @@ impl<T> From<Option<T>> for Nullable<T> {
 	}
 }
 
+impl<T> From<Nullable<T>> for Option<T> {
+	fn from(value: Nullable<T>) -> Self {
+		value.option()
+	}
+}
+
 impl<'a, T: Clone> Nullable<&'a T> {
 	/// Clone the referenced inner value.
 	#[inline(always)]
@@ -132,6 +198,18 @@ impl<'a, T: Clone> Nullable<&'a T> {
 			Nullable::Some(t) => Nullable::Some((*t).clone()),
 		}
 	}
+
+	/// Copy the referenced inner value.
+	#[inline(always)]
+	pub fn copied(&self) -> Nullable<T>
+	where
+		T: Copy,
+	{
+		match self {
+			Nullable::Null => Nullable::Null,
+			Nullable::Some(t) => Nullable::Some(**t),
+		}
+	}
 }
 
 impl<T: fmt::Display> fmt::Display for Nullable<T> {
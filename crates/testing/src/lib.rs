@@ -1,5 +1,58 @@
 //! This library provides the `test_suite` derive macro
 //! that can generate Rust test suites from a JSON-LD document.
+//!
+//! It is used to run the W3C JSON-LD test suite, but works with any test
+//! manifest: projects publishing contexts or frames can describe their own
+//! tests in a JSON-LD document, with their own vocabulary, and have a Rust
+//! test generated for each of them.
+//!
+//! ```ignore
+//! #[json_ld_testing::test_suite("https://example.org/tests/manifest.jsonld")]
+//! #[mount("https://example.org/tests/", "tests/data")]
+//! #[iri_prefix("rdf" = "http://www.w3.org/1999/02/22-rdf-syntax-ns#")]
+//! #[iri_prefix("ex" = "https://example.org/vocab#")]
+//! #[ignore_test("#t0042", see = "https://example.org/issues/42")]
+//! mod context {
+//!   use iref::Iri;
+//!
+//!   #[iri("ex:ContextTest")]
+//!   pub struct Test {
+//!     #[iri("ex:input")]
+//!     pub input: &'static Iri,
+//!
+//!     #[iri("ex:expect")]
+//!     pub expect: Option<&'static Iri>,
+//!
+//!     #[iri("ex:tag")]
+//!     pub tags: &'static [&'static str],
+//!   }
+//! }
+//!
+//! impl context::Test {
+//!   fn run(self) {
+//!     // ...
+//!   }
+//! }
+//! ```
+//!
+//! The manifest is loaded with an [`FsLoader`] configured by the `mount`
+//! attributes (relative paths are resolved against the directory of the
+//! crate being compiled), then converted to RDF. A test is generated for
+//! each node whose type is bound to a struct or enum of the module by an
+//! `iri` attribute. The test builds the Rust value of the node and calls its
+//! `run` method.
+//!
+//! The `iri` attributes of the types, fields and enum variants accept the
+//! compact IRIs defined with `iri_prefix`. Fields can have the following
+//! types, optionally wrapped in an `Option` or in a `&'static [_]` slice
+//! for multiple values: `bool`, `&'static str`, `&'static Iri`,
+//! `ProcessingMode`, `RdfDirection` or another type of the module. Enums are
+//! discriminated by the `rdf:type` of the node.
+//!
+//! The name of each test function is the name of the module, in snake case,
+//! followed by the fragment of the test IRI, or its last path segment if it
+//! has no fragment. The generated code refers to the `static_iref` and
+//! `json_ld` crates.
 use async_std::task;
 use contextual::{DisplayWithContext, WithContext};
 use iref::{IriBuf, IriRefBuf};
@@ -13,6 +66,7 @@ use rdf_types::{
 	Quad,
 };
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 use std::path::PathBuf;
 use syn::parse::ParseStream;
@@ -233,7 +287,7 @@ fn parse_input(
 	for attr in attrs {
 		if attr.path.is_ident("mount") {
 			let mount: MountAttribute = syn::parse2(attr.tokens).map_err(|e| Box::new(e.into()))?;
-			loader.mount(mount.prefix.as_iri().to_owned(), mount.target)
+			loader.mount(mount.prefix.as_iri().to_owned(), mount_target(mount.target))
 		} else if attr.path.is_ident("iri_prefix") {
 			let attr: PrefixBinding = syn::parse2(attr.tokens).map_err(|e| Box::new(e.into()))?;
 			bindings.insert(attr.prefix, vocabulary.insert(attr.iri.as_iri()));
@@ -487,6 +541,7 @@ enum Error {
 	InvalidTypeField,
 	NoTypeVariants(IndexTerm),
 	MultipleTypeVariants(IndexTerm),
+	NoTestName(IriIndex),
 }
 
 impl From<syn::Error> for Error {
@@ -521,6 +576,9 @@ impl DisplayWithContext<IndexVocabulary> for Error {
 				"multiple type variants defined for `{}`",
 				r.with(vocabulary)
 			),
+			Self::NoTestName(i) => {
+				write!(f, "no test name found in `{}`", vocabulary.iri(i).unwrap())
+			}
 		}
 	}
 }
@@ -580,6 +638,7 @@ async fn generate_test_suite(
 
 	let id = &spec.id;
 	let mut tokens = TokenStream::new();
+	let mut names = HashSet::new();
 	for (test, type_id) in tests {
 		let ty = spec.types.get(type_id).unwrap();
 		let cons = ty.generate(
@@ -590,10 +649,18 @@ async fn generate_test_suite(
 			quote! { #id :: #type_id },
 		)?;
 
-		let func_name = func_name(
-			&spec.prefix,
-			vocabulary.iri(&test).unwrap().fragment().unwrap().as_str(),
-		);
+		let name = test_name(vocabulary.iri(&test).unwrap())
+			.ok_or_else(|| Box::new(Error::NoTestName(test)))?;
+		let mut func_name = func_name(&spec.prefix, &name);
+		if !names.insert(func_name.clone()) {
+			let mut n = 2;
+			while !names.insert(format!("{func_name}_{n}")) {
+				n += 1
+			}
+
+			func_name = format!("{func_name}_{n}")
+		}
+
 		let func_id = quote::format_ident!("{}", func_name);
 
 		tokens.extend(quote! {
@@ -638,6 +705,44 @@ fn test_prefix(name: &str) -> String {
 	result
 }
 
+/// Resolves the target directory of a `mount` attribute.
+///
+/// Relative paths are resolved against the directory of the crate being
+/// compiled, which is not necessarily the working directory of the compiler
+/// (for instance in a workspace).
+fn mount_target(target: PathBuf) -> PathBuf {
+	match std::env::var_os("CARGO_MANIFEST_DIR") {
+		Some(dir) if target.is_relative() => PathBuf::from(dir).join(target),
+		_ => target,
+	}
+}
+
+/// Returns the name of the test identified by the given IRI.
+///
+/// This is the fragment of the IRI, or its last path segment if it has no
+/// fragment, where every character that cannot appear in a Rust identifier
+/// is replaced with `_`.
+fn test_name(iri: &iref::Iri) -> Option<String> {
+	let name = match iri.fragment() {
+		Some(fragment) => fragment.as_str(),
+		None => iri
+			.path()
+			.as_str()
+			.rsplit('/')
+			.find(|segment| !segment.is_empty())?,
+	};
+
+	if name.is_empty() {
+		return None;
+	}
+
+	Some(
+		name.chars()
+			.map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+			.collect(),
+	)
+}
+
 fn func_name(prefix: &str, id: &str) -> String {
 	let mut name = prefix.to_string();
 	name.push_str(id);
@@ -0,0 +1,51 @@
+//! Expands and compacts arbitrary documents against a context using every
+//! kind of container, checking that no input makes the processor panic.
+//!
+//! Run with `cargo fuzz run compact` from the repository root.
+#![no_main]
+use json_ld::{syntax::Parse, JsonLdProcessor, RemoteContextReference, RemoteDocument};
+use libfuzzer_sys::fuzz_target;
+use static_iref::iri;
+use std::collections::HashMap;
+
+const CONTEXT: &str = r#"{
+	"@context": {
+		"@vocab": "http://example.org/",
+		"ref": { "@id": "http://example.org/ref", "@type": "@id" },
+		"byId": { "@id": "http://example.org/byId", "@container": "@id" },
+		"byType": { "@id": "http://example.org/byType", "@container": "@type" },
+		"byIndex": { "@id": "http://example.org/byIndex", "@container": "@index" },
+		"byProperty": { "@id": "http://example.org/byProperty", "@container": "@index", "@index": "p" },
+		"byLanguage": { "@id": "http://example.org/byLanguage", "@container": "@language" },
+		"byGraph": { "@id": "http://example.org/byGraph", "@container": ["@graph", "@index"] },
+		"nested": { "@id": "http://example.org/nested", "@nest": "nest" },
+		"nest": "@nest"
+	}
+}"#;
+
+fuzz_target!(|data: &[u8]| {
+	let Ok(input) = std::str::from_utf8(data) else {
+		return;
+	};
+
+	let Ok((json, _)) = json_ld::syntax::Value::parse_str(input) else {
+		return;
+	};
+
+	let context_iri = iri!("http://example.org/context.jsonld").to_owned();
+	let (context, _) = json_ld::syntax::Value::parse_str(CONTEXT).unwrap();
+	let loader = HashMap::from([(
+		context_iri.clone(),
+		RemoteDocument::new(Some(context_iri.clone()), None, context),
+	)]);
+
+	let document = RemoteDocument::new(None, None, json);
+
+	// Errors are fine, panics are not.
+	futures::executor::block_on(async {
+		let _ = document.expand(&loader).await;
+		let _ = document
+			.compact(RemoteContextReference::iri(context_iri), &loader)
+			.await;
+	})
+});
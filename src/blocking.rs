@@ -0,0 +1,98 @@
+//! Blocking processing functions.
+//!
+//! The JSON-LD algorithms only need to wait when loading remote documents.
+//! Using a [`SyncLoader`], documents can be processed without any async
+//! runtime, for instance in command line tools or build scripts.
+//!
+//! ```
+//! use json_ld::{syntax::Parse, RemoteDocument};
+//!
+//! let (json, _) = json_ld::syntax::Value::parse_str(r#"{
+//!   "@context": { "name": "http://xmlns.com/foaf/0.1/name" },
+//!   "name": "Alice"
+//! }"#).unwrap();
+//!
+//! let expanded = json_ld::blocking::expand(
+//!   &RemoteDocument::new(None, None, json),
+//!   &json_ld::NoLoader
+//! ).unwrap();
+//! ```
+use crate::{
+	Blocking, CompactResult, ExpandResult, FlattenResult, JsonLdProcessor, Options,
+	RemoteContextReference, SyncLoader,
+};
+use iref::IriBuf;
+use rdf_types::{BlankIdBuf, Generator};
+
+/// Expands the document with the given `loader`, blocking the current
+/// thread.
+///
+/// See [`JsonLdProcessor::expand`].
+pub fn expand(
+	document: &impl JsonLdProcessor<IriBuf>,
+	loader: &impl SyncLoader,
+) -> ExpandResult<IriBuf, BlankIdBuf> {
+	futures::executor::block_on(document.expand(&Blocking(loader)))
+}
+
+/// Expands the document with the given `loader` and `options`, blocking the
+/// current thread.
+///
+/// See [`JsonLdProcessor::expand_using`].
+pub fn expand_using(
+	document: &impl JsonLdProcessor<IriBuf>,
+	loader: &impl SyncLoader,
+	options: Options,
+) -> ExpandResult<IriBuf, BlankIdBuf> {
+	futures::executor::block_on(document.expand_using(&Blocking(loader), options))
+}
+
+/// Compacts the document with the given `context` and `loader`, blocking the
+/// current thread.
+///
+/// See [`JsonLdProcessor::compact`].
+pub fn compact(
+	document: &impl JsonLdProcessor<IriBuf>,
+	context: RemoteContextReference,
+	loader: &impl SyncLoader,
+) -> CompactResult {
+	futures::executor::block_on(document.compact(context, &Blocking(loader)))
+}
+
+/// Compacts the document with the given `context`, `loader` and `options`,
+/// blocking the current thread.
+///
+/// See [`JsonLdProcessor::compact_using`].
+pub fn compact_using(
+	document: &impl JsonLdProcessor<IriBuf>,
+	context: RemoteContextReference,
+	loader: &impl SyncLoader,
+	options: Options,
+) -> CompactResult {
+	futures::executor::block_on(document.compact_using(context, &Blocking(loader), options))
+}
+
+/// Flattens the document with the given `generator` and `loader`, blocking
+/// the current thread.
+///
+/// See [`JsonLdProcessor::flatten`].
+pub fn flatten(
+	document: &impl JsonLdProcessor<IriBuf>,
+	generator: &mut impl Generator,
+	loader: &impl SyncLoader,
+) -> FlattenResult<IriBuf, BlankIdBuf> {
+	futures::executor::block_on(document.flatten(generator, &Blocking(loader)))
+}
+
+/// Flattens the document with the given `generator`, `loader` and
+/// `options`, blocking the current thread.
+///
+/// See [`JsonLdProcessor::flatten_using`].
+pub fn flatten_using(
+	document: &impl JsonLdProcessor<IriBuf>,
+	generator: &mut impl Generator,
+	loader: &impl SyncLoader,
+	options: Options,
+) -> FlattenResult<IriBuf, BlankIdBuf> {
+	futures::executor::block_on(document.flatten_using(generator, &Blocking(loader), options))
+}
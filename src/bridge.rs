@@ -0,0 +1,65 @@
+//! Conversion between [`json_syntax::Value`] and [`serde_json::Value`].
+//!
+//! Compacted documents are returned as [`json_syntax::Value`]. Web frameworks
+//! usually expect [`serde_json::Value`] instead. The functions of this module
+//! convert between the two, preserving the order of object entries (the
+//! `preserve_order` feature of `serde_json` is enabled).
+//!
+//! Numbers are converted through their lexical representation. Numbers that
+//! cannot be represented by `serde_json` without loss are approximated with
+//! an `f64`.
+
+/// Converts a JSON value (typically a compacted document) into a
+/// [`serde_json::Value`].
+///
+/// If an object has duplicate entries, only the last one is kept.
+pub fn to_serde_json(value: &json_syntax::Value) -> serde_json::Value {
+	match value {
+		json_syntax::Value::Null => serde_json::Value::Null,
+		json_syntax::Value::Boolean(b) => serde_json::Value::Bool(*b),
+		json_syntax::Value::Number(n) => match n.as_str().parse() {
+			Ok(n) => serde_json::Value::Number(n),
+			Err(_) => serde_json::Number::from_f64(n.as_f64_lossy())
+				.map(serde_json::Value::Number)
+				.unwrap_or(serde_json::Value::Null),
+		},
+		json_syntax::Value::String(s) => serde_json::Value::String(s.to_string()),
+		json_syntax::Value::Array(items) => {
+			serde_json::Value::Array(items.iter().map(to_serde_json).collect())
+		}
+		json_syntax::Value::Object(object) => {
+			let mut map = serde_json::Map::new();
+			for entry in object.iter() {
+				map.insert(entry.key.to_string(), to_serde_json(&entry.value));
+			}
+
+			serde_json::Value::Object(map)
+		}
+	}
+}
+
+/// Converts a [`serde_json::Value`] into a JSON value that can be processed
+/// as a JSON-LD document.
+pub fn from_serde_json(value: serde_json::Value) -> json_syntax::Value {
+	match value {
+		serde_json::Value::Null => json_syntax::Value::Null,
+		serde_json::Value::Bool(b) => json_syntax::Value::Boolean(b),
+		serde_json::Value::Number(n) => {
+			use json_syntax::Parse;
+			let (value, _) = json_syntax::Value::parse_str(&n.to_string()).unwrap();
+			value
+		}
+		serde_json::Value::String(s) => json_syntax::Value::String(s.into()),
+		serde_json::Value::Array(items) => {
+			json_syntax::Value::Array(items.into_iter().map(from_serde_json).collect())
+		}
+		serde_json::Value::Object(map) => {
+			let mut object = json_syntax::Object::default();
+			for (key, value) in map {
+				object.insert(key.into(), from_serde_json(value));
+			}
+
+			json_syntax::Value::Object(object)
+		}
+	}
+}
@@ -21,6 +21,9 @@
 //! If you want to explore and/or transform [`ExpandedDocument`]s, you may also
 //! want to check out the [`Object`] type representing a JSON object.
 //!
+//! The [`prelude`] module re-exports the traits needed to use this library,
+//! so that they can be imported at once with `use json_ld::prelude::*`.
+//!
 //! [`JsonLdProcessor`]: crate::JsonLdProcessor
 //!
 //! ## Expansion
@@ -313,8 +316,24 @@ pub use compaction::Compact;
 pub use context_processing::Process;
 pub use expansion::Expand;
 
+pub mod blocking;
+#[cfg(feature = "serde_json")]
+pub mod bridge;
+pub mod metrics;
+mod preflight;
+pub mod prelude;
 mod processor;
+#[cfg(feature = "reqwest")]
+pub mod production;
+mod rewrite;
+mod simple;
+pub mod tabular;
+pub use preflight::{preflight, preflight_with};
 pub use processor::*;
+#[cfg(feature = "reqwest")]
+pub use production::Processor;
+pub use rewrite::*;
+pub use simple::*;
 
 #[doc(hidden)]
 pub use iref;
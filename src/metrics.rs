@@ -0,0 +1,158 @@
+//! Processing metrics.
+//!
+//! A [`Metrics`] observer can be registered in the processor
+//! [`Options`](crate::Options) to receive structured [`Event`]s while a
+//! document is processed, for instance to export timings of each phase.
+use crate::object::FragmentRef;
+use crate::{Context, ExpandedDocument, Loader, LoadingResult, Profile};
+use iref::{Iri, IriBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Processing phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+	/// Processing of the `expandContext` option and `Link` header context.
+	ContextProcessing,
+
+	/// Document expansion, including embedded context processing.
+	Expansion,
+
+	/// Document compaction.
+	Compaction,
+
+	/// Document flattening.
+	Flattening,
+}
+
+impl Phase {
+	/// Returns the name of the phase.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::ContextProcessing => "context_processing",
+			Self::Expansion => "expansion",
+			Self::Compaction => "compaction",
+			Self::Flattening => "flattening",
+		}
+	}
+}
+
+/// Metrics event.
+#[derive(Debug, Clone, Copy)]
+pub enum Event<'a> {
+	/// A phase started.
+	PhaseStart(Phase),
+
+	/// A phase ended (successfully or not) after the given duration.
+	PhaseEnd(Phase, Duration),
+
+	/// A remote context has been loaded to be processed.
+	///
+	/// Contexts prefetched ahead of processing (see
+	/// [`Options::prefetch_contexts`](crate::Options::prefetch_contexts)) are
+	/// only reported if they are actually processed.
+	ContextLoaded(&'a Iri),
+
+	/// The expansion algorithm produced the given number of node objects.
+	NodesExpanded(usize),
+}
+
+/// Metrics observer.
+///
+/// This trait is implemented by any `Fn(Event)` closure.
+pub trait Metrics: Send + Sync {
+	/// Receives a metrics event.
+	fn event(&self, event: Event);
+}
+
+impl<F: Fn(Event) + Send + Sync> Metrics for F {
+	fn event(&self, event: Event) {
+		self(event)
+	}
+}
+
+/// Phase timer, sending [`Event::PhaseEnd`] when dropped.
+pub(crate) struct Timer<'a> {
+	metrics: Option<&'a dyn Metrics>,
+	phase: Phase,
+	start: Instant,
+}
+
+impl<'a> Timer<'a> {
+	pub fn start(metrics: Option<&'a dyn Metrics>, phase: Phase) -> Self {
+		if let Some(m) = metrics {
+			m.event(Event::PhaseStart(phase))
+		}
+
+		Self {
+			metrics,
+			phase,
+			start: Instant::now(),
+		}
+	}
+}
+
+impl<'a> Drop for Timer<'a> {
+	fn drop(&mut self) {
+		if let Some(m) = self.metrics {
+			m.event(Event::PhaseEnd(self.phase, self.start.elapsed()))
+		}
+	}
+}
+
+/// Sends [`Event::NodesExpanded`] for the given expanded document.
+pub(crate) fn nodes_expanded<T, B>(
+	metrics: Option<&dyn Metrics>,
+	document: &ExpandedDocument<T, B>,
+) {
+	if let Some(m) = metrics {
+		let count = document.count(|f| match f {
+			FragmentRef::Object(o) => o.is_node(),
+			FragmentRef::IndexedObject(o) => o.is_node(),
+			FragmentRef::Node(_) | FragmentRef::IndexedNode(_) => true,
+			_ => false,
+		});
+
+		m.event(Event::NodesExpanded(count))
+	}
+}
+
+/// Loader sending [`Event::ContextLoaded`] for each loaded document.
+///
+/// It must only wrap the loader used to resolve contexts, so that the
+/// loaded documents are contexts.
+pub(crate) struct Observed<'a, L> {
+	metrics: Option<&'a dyn Metrics>,
+	loader: &'a L,
+}
+
+impl<'a, L> Observed<'a, L> {
+	pub fn new(metrics: Option<&'a dyn Metrics>, loader: &'a L) -> Self {
+		Self { metrics, loader }
+	}
+
+	/// Sends [`Event::ContextLoaded`] if the document has been loaded.
+	fn observe(&self, url: &Iri, result: LoadingResult<IriBuf>) -> LoadingResult<IriBuf> {
+		let document = result?;
+
+		if let Some(m) = self.metrics {
+			m.event(Event::ContextLoaded(url))
+		}
+
+		Ok(document)
+	}
+}
+
+impl<'a, L: Loader> Loader for Observed<'a, L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.observe(url, self.loader.load(url).await)
+	}
+
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		self.observe(url, self.loader.load_with_profile(url, profile).await)
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.loader.processed_context(url)
+	}
+}
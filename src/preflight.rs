@@ -0,0 +1,155 @@
+use futures::StreamExt;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf, IriRef};
+use json_ld_core::{Context, Loader, LoadingResult, Profile, RemoteDocument, CONTEXT_PROFILE};
+use rdf_types::vocabulary::IriVocabulary;
+use std::sync::Arc;
+
+/// Statically lists the remote contexts referenced by a document.
+///
+/// The document is scanned without running any JSON-LD algorithm, looking
+/// for every `@context` entry, including nested node objects, scoped
+/// contexts and `@import` entries. Relative references are resolved against
+/// the document URL, and are ignored if the document has no URL.
+/// The context referenced by the HTTP `Link` header (if any) comes first.
+///
+/// Contexts that can only be discovered by loading other contexts are not
+/// listed. This is meant to allow applications to prefetch, batch-load or
+/// validate the remote contexts of a document before processing it.
+///
+/// Each IRI appears only once, in the order of discovery.
+pub fn preflight(document: &RemoteDocument) -> Vec<IriBuf> {
+	preflight_with(&(), document)
+}
+
+/// Statically lists the remote contexts referenced by a document, using the
+/// given `vocabulary` to interpret its URLs.
+///
+/// See [`preflight`] for more details.
+pub fn preflight_with<V: IriVocabulary>(
+	vocabulary: &V,
+	document: &RemoteDocument<V::Iri>,
+) -> Vec<IriBuf> {
+	let mut result = Vec::new();
+
+	if let Some(context_url) = document.context_url() {
+		result.push(vocabulary.iri(context_url).unwrap().to_owned())
+	}
+
+	let base = document.url().map(|url| vocabulary.iri(url).unwrap());
+	scan_value(base, document.document(), &mut result);
+	result
+}
+
+/// Concurrently loads the given contexts, with at most `parallelism`
+/// simultaneous loads.
+///
+/// The contexts are requested with the [`CONTEXT_PROFILE`], as the context
+/// processing algorithm does.
+///
+/// Documents that could not be loaded are omitted. The error will be raised
+/// again when they are actually needed.
+pub(crate) async fn prefetch(
+	iris: Vec<IriBuf>,
+	loader: &impl Loader,
+	parallelism: usize,
+) -> HashMap<IriBuf, RemoteDocument> {
+	futures::stream::iter(iris)
+		.map(|iri| async move {
+			let result = loader.load_with_profile(&iri, CONTEXT_PROFILE).await;
+			(iri, result)
+		})
+		.buffer_unordered(parallelism.max(1))
+		.filter_map(|(iri, result)| async move { result.ok().map(|doc| (iri, doc)) })
+		.collect()
+		.await
+}
+
+/// Loader serving prefetched documents first.
+pub(crate) struct Prefetched<'a, L> {
+	documents: HashMap<IriBuf, RemoteDocument>,
+	loader: &'a L,
+}
+
+impl<'a, L> Prefetched<'a, L> {
+	pub fn new(documents: HashMap<IriBuf, RemoteDocument>, loader: &'a L) -> Self {
+		Self { documents, loader }
+	}
+}
+
+impl<'a, L: Loader> Loader for Prefetched<'a, L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.documents.get(url) {
+			Some(document) => Ok(document.clone()),
+			None => self.loader.load(url).await,
+		}
+	}
+
+	/// Prefetched documents were loaded with the [`CONTEXT_PROFILE`], so
+	/// documents requested with another profile are always loaded again.
+	async fn load_with_profile(&self, url: &Iri, profile: &[Profile]) -> LoadingResult<IriBuf> {
+		match self.documents.get(url) {
+			Some(document) if profile == CONTEXT_PROFILE => Ok(document.clone()),
+			_ => self.loader.load_with_profile(url, profile).await,
+		}
+	}
+
+	fn processed_context(&self, url: &Iri) -> Option<Arc<Context>> {
+		self.loader.processed_context(url)
+	}
+}
+
+fn scan_value(base: Option<&Iri>, value: &json_syntax::Value, result: &mut Vec<IriBuf>) {
+	match value {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				scan_value(base, item, result)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			for entry in object.iter() {
+				if entry.key.as_str() == "@context" {
+					scan_context(base, &entry.value, result)
+				}
+
+				scan_value(base, &entry.value, result)
+			}
+		}
+		_ => (),
+	}
+}
+
+fn scan_context(base: Option<&Iri>, value: &json_syntax::Value, result: &mut Vec<IriBuf>) {
+	match value {
+		json_syntax::Value::String(s) => add_reference(base, s, result),
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				scan_context(base, item, result)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			if let Some(json_syntax::Value::String(s)) = object.get_unique("@import").ok().flatten()
+			{
+				add_reference(base, s, result)
+			}
+		}
+		_ => (),
+	}
+}
+
+fn add_reference(base: Option<&Iri>, reference: &str, result: &mut Vec<IriBuf>) {
+	let iri = match IriRef::new(reference) {
+		Ok(iri_ref) => match iri_ref.as_iri() {
+			Some(iri) => iri.to_owned(),
+			None => match base {
+				Some(base) => iri_ref.resolved(base),
+				None => return,
+			},
+		},
+		Err(_) => return,
+	};
+
+	if !result.contains(&iri) {
+		result.push(iri)
+	}
+}
@@ -0,0 +1,37 @@
+//! Commonly used traits and types.
+//!
+//! The traits required to call the JSON-LD algorithms are spread across the
+//! sub-crates. This module re-exports them, along with the vocabulary
+//! traits, so that they can be brought into scope with a single glob import.
+//!
+//! ```
+//! use json_ld::prelude::*;
+//!
+//! # #[async_std::main]
+//! # async fn main() {
+//! let (json, _) = json_ld::syntax::Value::parse_str(r#"{
+//!   "http://xmlns.com/foaf/0.1/name": "Rust Programming Language"
+//! }"#).unwrap();
+//!
+//! println!("{}", json.pretty_print());
+//!
+//! let expanded = RemoteDocument::new(None, None, json)
+//!   .expand(&NoLoader)
+//!   .await
+//!   .expect("expansion failed");
+//!
+//! assert_eq!(expanded.len(), 1);
+//! # }
+//! ```
+pub use crate::syntax::{IntoJson, Parse, Print};
+pub use crate::{
+	Compact, Expand, Flatten, JsonLdProcessor, LdQuads, Loader, Process, RdfQuads, SyncLoader,
+};
+pub use crate::{
+	ExpandedDocument, NoLoader, Options, RemoteContextReference, RemoteDocument,
+	RemoteDocumentReference,
+};
+pub use rdf_types::vocabulary::{
+	BlankIdVocabulary, BlankIdVocabularyMut, IndexVocabulary, IriVocabulary, IriVocabularyMut,
+	LiteralVocabulary, LiteralVocabularyMut, Vocabulary, VocabularyMut,
+};
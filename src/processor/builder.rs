@@ -0,0 +1,491 @@
+use super::{CompareStrategy, Options};
+use crate::compaction::{IncludedShape, KeepValueObjects};
+use crate::expansion;
+use crate::metrics::Metrics;
+use crate::{DynLoader, ProcessingMode, RemoteContextReference};
+use iref::IriBuf;
+use json_ld_core::rdf::{GraphFilter, NestedLists, RdfDirection};
+use std::sync::Arc;
+
+/// Incoherent combination of options, rejected by [`OptionsBuilder::build`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidOptions {
+	/// `rdf_direction` is a JSON-LD 1.1 feature.
+	#[error("`rdfDirection` requires the `json-ld-1.1` processing mode")]
+	RdfDirectionRequiresJsonLd11,
+
+	/// `@included` only exists in JSON-LD 1.1, so its shape can only be
+	/// changed in this processing mode.
+	#[error("reshaping `@included` requires the `json-ld-1.1` processing mode")]
+	IncludedShapeRequiresJsonLd11,
+
+	/// Prefetching remote contexts with no concurrent load.
+	#[error("context prefetching must allow at least one concurrent load")]
+	ZeroPrefetch,
+
+	/// Maximum literal length set to zero, rejecting every non empty
+	/// string.
+	#[error("maximum literal length must be positive")]
+	ZeroMaxLiteralLength,
+}
+
+/// [`Options`] builder.
+///
+/// Unlike building [`Options`] directly, the [`build`](Self::build) method
+/// checks that the options are coherent with each other.
+///
+/// ```
+/// use json_ld::{Options, ProcessingMode};
+///
+/// let options: Options = Options::builder()
+///   .processing_mode(ProcessingMode::JsonLd1_1)
+///   .ordered(true)
+///   .build()
+///   .unwrap();
+///
+/// assert!(options.ordered)
+/// ```
+#[derive(Clone)]
+pub struct OptionsBuilder<I = IriBuf>(Options<I>);
+
+impl<I> Default for OptionsBuilder<I> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<I> OptionsBuilder<I> {
+	/// Creates a builder starting from the default options.
+	pub fn new() -> Self {
+		Self(Options::default())
+	}
+
+	/// Creates a builder starting from the defaults of the given processing
+	/// mode.
+	///
+	/// See [`Options::with_defaults_for`].
+	pub fn for_processing_mode(processing_mode: ProcessingMode) -> Self {
+		Self(Options::with_defaults_for(processing_mode))
+	}
+
+	/// Sets the base IRI.
+	pub fn base(mut self, base: I) -> Self {
+		self.0.base = Some(base);
+		self
+	}
+
+	/// Sets the `compact_arrays` flag.
+	pub fn compact_arrays(mut self, value: bool) -> Self {
+		self.0.compact_arrays = value;
+		self
+	}
+
+	/// Sets the `compact_to_relative` flag.
+	pub fn compact_to_relative(mut self, value: bool) -> Self {
+		self.0.compact_to_relative = value;
+		self
+	}
+
+	/// Sets the context used to initialize the active context when expanding.
+	pub fn expand_context(mut self, context: RemoteContextReference<I>) -> Self {
+		self.0.expand_context = Some(context);
+		self
+	}
+
+	/// Sets the additional contexts processed in order after the
+	/// `expand_context` when expanding.
+	pub fn expand_contexts(mut self, contexts: Vec<RemoteContextReference<I>>) -> Self {
+		self.0.expand_contexts = contexts;
+		self
+	}
+
+	/// Sets the `ordered` flag.
+	pub fn ordered(mut self, value: bool) -> Self {
+		self.0.ordered = value;
+		self
+	}
+
+	/// Sets the processing mode.
+	pub fn processing_mode(mut self, mode: ProcessingMode) -> Self {
+		self.0.processing_mode = mode;
+		self
+	}
+
+	/// Sets how value objects with a base direction are transformed to RDF.
+	pub fn rdf_direction(mut self, direction: RdfDirection) -> Self {
+		self.0.rdf_direction = Some(direction);
+		self
+	}
+
+	/// Sets the `produce_generalized_rdf` flag.
+	pub fn produce_generalized_rdf(mut self, value: bool) -> Self {
+		self.0.produce_generalized_rdf = value;
+		self
+	}
+
+	/// Sets the term expansion policy.
+	pub fn expansion_policy(mut self, policy: expansion::Policy) -> Self {
+		self.0.expansion_policy = policy;
+		self
+	}
+
+	/// Prefetches remote contexts with at most `concurrency` concurrent
+	/// loads.
+	pub fn prefetch_contexts(mut self, concurrency: usize) -> Self {
+		self.0.prefetch_contexts = Some(concurrency);
+		self
+	}
+
+	/// Sets the metrics observer.
+	pub fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+		self.0.metrics = Some(metrics);
+		self
+	}
+
+	/// Sets the loader used to fetch remote contexts.
+	pub fn context_loader(mut self, loader: Arc<dyn DynLoader>) -> Self {
+		self.0.context_loader = Some(loader);
+		self
+	}
+
+	/// Sets the transformer called on each expanded value object.
+	pub fn value_transformer(mut self, transformer: Arc<dyn expansion::ValueTransformer>) -> Self {
+		self.0.value_transformer = Some(transformer);
+		self
+	}
+
+	/// Sets the maximum length (in bytes) of string literal values.
+	pub fn max_literal_length(mut self, len: usize) -> Self {
+		self.0.max_literal_length = Some(len);
+		self
+	}
+
+	/// Sets the kinds of value objects kept in object form during compaction.
+	pub fn keep_value_objects_for(mut self, kinds: KeepValueObjects) -> Self {
+		self.0.keep_value_objects_for = kinds;
+		self
+	}
+
+	/// Sets the shape given to `@included` nodes during compaction.
+	pub fn included(mut self, shape: IncludedShape) -> Self {
+		self.0.included = shape;
+		self
+	}
+
+	/// Sets the `vocab_relative_values` flag.
+	pub fn vocab_relative_values(mut self, value: bool) -> Self {
+		self.0.vocab_relative_values = value;
+		self
+	}
+
+	/// Sets the `omit_defaults` flag.
+	pub fn omit_defaults(mut self, value: bool) -> Self {
+		self.0.omit_defaults = value;
+		self
+	}
+
+	/// Sets the `wrap_anonymous_graphs` flag.
+	pub fn wrap_anonymous_graphs(mut self, value: bool) -> Self {
+		self.0.wrap_anonymous_graphs = value;
+		self
+	}
+
+	/// Sets the graphs from which RDF quads are produced.
+	pub fn rdf_graphs(mut self, filter: GraphFilter<I>) -> Self {
+		self.0.rdf_graphs = filter;
+		self
+	}
+
+	/// Sets how lists of lists are serialized into RDF.
+	pub fn nested_lists(mut self, nested_lists: NestedLists) -> Self {
+		self.0.nested_lists = nested_lists;
+		self
+	}
+
+	/// Sets the property through which the `@index` of property values is
+	/// preserved in RDF.
+	pub fn rdf_index_property(mut self, property: I) -> Self {
+		self.0.rdf_index_property = Some(property);
+		self
+	}
+
+	/// Sets the `rdf_canonical_order` flag.
+	pub fn rdf_canonical_order(mut self, value: bool) -> Self {
+		self.0.rdf_canonical_order = value;
+		self
+	}
+
+	/// Sets the `reject_non_json_ld` flag.
+	pub fn reject_non_json_ld(mut self, value: bool) -> Self {
+		self.0.reject_non_json_ld = value;
+		self
+	}
+
+	/// Sets the `relabel_non_conforming_blank_ids` flag.
+	pub fn relabel_non_conforming_blank_ids(mut self, value: bool) -> Self {
+		self.0.relabel_non_conforming_blank_ids = value;
+		self
+	}
+
+	/// Sets the validation of the produced IRIs.
+	pub fn validate_iris(mut self, action: expansion::Action) -> Self {
+		self.0.validate_iris = action;
+		self
+	}
+
+	/// Sets the `materialize_defaults` flag.
+	pub fn materialize_defaults(mut self, value: bool) -> Self {
+		self.0.materialize_defaults = value;
+		self
+	}
+
+	/// Sets the `prune_contexts` flag.
+	///
+	/// Pruning is a heuristic that may change the expansion result, see
+	/// [`Options::prune_contexts`].
+	pub fn prune_contexts(mut self, value: bool) -> Self {
+		self.0.prune_contexts = value;
+		self
+	}
+
+	/// Sets the terms to prefer during compaction term selection.
+	pub fn term_preferences(mut self, terms: Vec<String>) -> Self {
+		self.0.term_preferences = terms;
+		self
+	}
+
+	/// Sets how blank node identifiers are compared by
+	/// [`JsonLdProcessor::compare_full`](crate::JsonLdProcessor::compare_full).
+	pub fn compare_strategy(mut self, strategy: CompareStrategy) -> Self {
+		self.0.compare_strategy = strategy;
+		self
+	}
+
+	/// Sets the expansion options.
+	///
+	/// ```
+	/// use json_ld::{expansion::Policy, Options};
+	///
+	/// let options: Options = Options::builder()
+	///   .expansion(|e| e.policy(Policy::strict_vc()).max_literal_length(1024))
+	///   .build()
+	///   .unwrap();
+	///
+	/// assert_eq!(options.max_literal_length, Some(1024))
+	/// ```
+	pub fn expansion(
+		mut self,
+		f: impl FnOnce(ExpansionOptionsBuilder<I>) -> ExpansionOptionsBuilder<I>,
+	) -> Self {
+		self.0 = f(ExpansionOptionsBuilder(self.0)).0;
+		self
+	}
+
+	/// Sets the compaction options.
+	pub fn compaction(
+		mut self,
+		f: impl FnOnce(CompactionOptionsBuilder<I>) -> CompactionOptionsBuilder<I>,
+	) -> Self {
+		self.0 = f(CompactionOptionsBuilder(self.0)).0;
+		self
+	}
+
+	/// Sets the RDF serialization options.
+	pub fn rdf(mut self, f: impl FnOnce(RdfOptionsBuilder<I>) -> RdfOptionsBuilder<I>) -> Self {
+		self.0 = f(RdfOptionsBuilder(self.0)).0;
+		self
+	}
+
+	/// Checks the options and returns them.
+	pub fn build(self) -> Result<Options<I>, InvalidOptions> {
+		let options = self.0;
+
+		if options.processing_mode == ProcessingMode::JsonLd1_0 {
+			if options.rdf_direction.is_some() {
+				return Err(InvalidOptions::RdfDirectionRequiresJsonLd11);
+			}
+
+			if options.included != IncludedShape::Preserve {
+				return Err(InvalidOptions::IncludedShapeRequiresJsonLd11);
+			}
+		}
+
+		if options.prefetch_contexts == Some(0) {
+			return Err(InvalidOptions::ZeroPrefetch);
+		}
+
+		if options.max_literal_length == Some(0) {
+			return Err(InvalidOptions::ZeroMaxLiteralLength);
+		}
+
+		Ok(options)
+	}
+}
+
+/// Expansion options builder.
+///
+/// See [`OptionsBuilder::expansion`].
+pub struct ExpansionOptionsBuilder<I = IriBuf>(Options<I>);
+
+impl<I> ExpansionOptionsBuilder<I> {
+	/// Sets the context used to initialize the active context.
+	pub fn context(mut self, context: RemoteContextReference<I>) -> Self {
+		self.0.expand_context = Some(context);
+		self
+	}
+
+	/// Sets the additional contexts processed in order after the
+	/// [`context`](Self::context).
+	pub fn contexts(mut self, contexts: Vec<RemoteContextReference<I>>) -> Self {
+		self.0.expand_contexts = contexts;
+		self
+	}
+
+	/// Sets the term expansion policy.
+	pub fn policy(mut self, policy: expansion::Policy) -> Self {
+		self.0.expansion_policy = policy;
+		self
+	}
+
+	/// Prefetches remote contexts with at most `concurrency` concurrent
+	/// loads.
+	pub fn prefetch_contexts(mut self, concurrency: usize) -> Self {
+		self.0.prefetch_contexts = Some(concurrency);
+		self
+	}
+
+	/// Sets the transformer called on each expanded value object.
+	pub fn value_transformer(mut self, transformer: Arc<dyn expansion::ValueTransformer>) -> Self {
+		self.0.value_transformer = Some(transformer);
+		self
+	}
+
+	/// Sets the maximum length (in bytes) of string literal values.
+	pub fn max_literal_length(mut self, len: usize) -> Self {
+		self.0.max_literal_length = Some(len);
+		self
+	}
+
+	/// Sets the `reject_non_json_ld` flag.
+	pub fn reject_non_json_ld(mut self, value: bool) -> Self {
+		self.0.reject_non_json_ld = value;
+		self
+	}
+
+	/// Sets the `relabel_non_conforming_blank_ids` flag.
+	pub fn relabel_non_conforming_blank_ids(mut self, value: bool) -> Self {
+		self.0.relabel_non_conforming_blank_ids = value;
+		self
+	}
+
+	/// Sets the validation of the produced IRIs.
+	pub fn validate_iris(mut self, action: expansion::Action) -> Self {
+		self.0.validate_iris = action;
+		self
+	}
+
+	/// Sets the `materialize_defaults` flag.
+	pub fn materialize_defaults(mut self, value: bool) -> Self {
+		self.0.materialize_defaults = value;
+		self
+	}
+}
+
+/// Compaction options builder.
+///
+/// See [`OptionsBuilder::compaction`].
+pub struct CompactionOptionsBuilder<I = IriBuf>(Options<I>);
+
+impl<I> CompactionOptionsBuilder<I> {
+	/// Sets the `compact_arrays` flag.
+	pub fn compact_arrays(mut self, value: bool) -> Self {
+		self.0.compact_arrays = value;
+		self
+	}
+
+	/// Sets the `compact_to_relative` flag.
+	pub fn compact_to_relative(mut self, value: bool) -> Self {
+		self.0.compact_to_relative = value;
+		self
+	}
+
+	/// Sets the kinds of value objects kept in object form.
+	pub fn keep_value_objects_for(mut self, kinds: KeepValueObjects) -> Self {
+		self.0.keep_value_objects_for = kinds;
+		self
+	}
+
+	/// Sets the shape given to `@included` nodes.
+	pub fn included(mut self, shape: IncludedShape) -> Self {
+		self.0.included = shape;
+		self
+	}
+
+	/// Sets the `vocab_relative_values` flag.
+	pub fn vocab_relative_values(mut self, value: bool) -> Self {
+		self.0.vocab_relative_values = value;
+		self
+	}
+
+	/// Sets the `omit_defaults` flag.
+	pub fn omit_defaults(mut self, value: bool) -> Self {
+		self.0.omit_defaults = value;
+		self
+	}
+
+	/// Sets the `wrap_anonymous_graphs` flag.
+	pub fn wrap_anonymous_graphs(mut self, value: bool) -> Self {
+		self.0.wrap_anonymous_graphs = value;
+		self
+	}
+
+	/// Sets the terms to prefer during term selection.
+	pub fn term_preferences(mut self, terms: Vec<String>) -> Self {
+		self.0.term_preferences = terms;
+		self
+	}
+}
+
+/// RDF serialization options builder.
+///
+/// See [`OptionsBuilder::rdf`].
+pub struct RdfOptionsBuilder<I = IriBuf>(Options<I>);
+
+impl<I> RdfOptionsBuilder<I> {
+	/// Sets how value objects with a base direction are transformed to RDF.
+	pub fn direction(mut self, direction: RdfDirection) -> Self {
+		self.0.rdf_direction = Some(direction);
+		self
+	}
+
+	/// Sets the `produce_generalized_rdf` flag.
+	pub fn generalized(mut self, value: bool) -> Self {
+		self.0.produce_generalized_rdf = value;
+		self
+	}
+
+	/// Sets the graphs from which RDF quads are produced.
+	pub fn graphs(mut self, filter: GraphFilter<I>) -> Self {
+		self.0.rdf_graphs = filter;
+		self
+	}
+
+	/// Sets how lists of lists are serialized.
+	pub fn nested_lists(mut self, nested_lists: NestedLists) -> Self {
+		self.0.nested_lists = nested_lists;
+		self
+	}
+
+	/// Sets the property through which the `@index` of property values is
+	/// preserved.
+	pub fn index_property(mut self, property: I) -> Self {
+		self.0.rdf_index_property = Some(property);
+		self
+	}
+
+	/// Sets whether quads are sorted in canonical order.
+	pub fn canonical_order(mut self, value: bool) -> Self {
+		self.0.rdf_canonical_order = value;
+		self
+	}
+}
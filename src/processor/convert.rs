@@ -0,0 +1,355 @@
+use super::{compact_expanded_full, framing, CompactError, ExpandError, JsonLdProcessor, Options};
+use crate::{
+	flattening::ConflictingIndexes, object::InvalidExpandedJson, Document, DocumentForm,
+	ExpandedDocument, ExtractContext, ExtractContextError, Flatten, LoadError, Loader, Profile,
+	RemoteContextReference, RemoteDocument, RemoteDocumentReference, TryFromJson,
+};
+use contextual::WithContext;
+use hashbrown::HashSet;
+use json_ld_syntax::{context::Context, IntoJson};
+use rdf_types::{vocabulary, BlankIdBuf, Generator, VocabularyMut};
+use std::hash::Hash;
+
+/// Error that can be raised by the [`ConvertDocument::convert_to`] function.
+#[derive(Debug, thiserror::Error)]
+pub enum ConvertError<I, B> {
+	#[error("Missing context for the compacted form")]
+	MissingContext,
+
+	#[error("Compaction failed: {0}")]
+	Compact(CompactError),
+
+	#[error("Conflicting indexes: {0}")]
+	ConflictingIndexes(ConflictingIndexes<I, B>),
+
+	#[error("Invalid frame")]
+	InvalidFrame,
+
+	#[error("Frame loading failed: {0}")]
+	FrameLoading(LoadError),
+
+	#[error("Frame expansion failed: {0}")]
+	FrameExpansion(ExpandError),
+
+	#[error("Framing failed: {0}")]
+	Framing(InvalidExpandedJson),
+}
+
+/// Result of the [`ConvertDocument::convert_to`] function.
+pub type ConvertResult<I, B> = Result<Document<I, B>, ConvertError<I, B>>;
+
+/// Conversion of a [`Document`] to another [form](DocumentForm).
+///
+/// The conversion starts from the expanded form of the document, so it does
+/// not depend on the form of the original document:
+/// - [`DocumentForm::Expanded`] serializes the expanded document;
+/// - [`DocumentForm::Compacted`] compacts it with the given context, which
+///   is required;
+/// - [`DocumentForm::Flattened`] flattens it, and compacts the result if a
+///   context is given;
+/// - [`DocumentForm::Framed`] frames it with a frame matching every node,
+///   and compacts the result with the given context, if any. Use
+///   [`Self::frame`] to provide a frame.
+///
+/// Framing supports the frame flags (`@embed`, `@explicit`, `@omitDefault`,
+/// `@requireAll`), `@default` values and the `@id`, `@type` and property
+/// patterns, including wildcards (`{}`) and match-none patterns (`[]`).
+/// Value patterns match any value, and only the default graph is framed.
+///
+/// The returned document keeps the URL and content type of the original one,
+/// with the `profile` of the new form.
+///
+/// # Example
+///
+/// ```
+/// use json_ld::{
+///   syntax::Parse, ConvertDocument, DocumentForm, JsonLdProcessor, RemoteDocument,
+/// };
+///
+/// # #[async_std::main]
+/// # async fn main() {
+/// let (json, _) = json_ld::syntax::Value::parse_str(r#"{
+///   "@context": { "name": "http://xmlns.com/foaf/0.1/name" },
+///   "name": "Alice"
+/// }"#).unwrap();
+///
+/// let document = RemoteDocument::new(None, None, json)
+///   .into_document(&json_ld::NoLoader)
+///   .await
+///   .unwrap();
+///
+/// assert_eq!(document.form(), None);
+///
+/// let mut generator = rdf_types::generator::Blank::new();
+/// let flattened = document
+///   .convert_to(&mut generator, DocumentForm::Flattened, None, &json_ld::NoLoader)
+///   .await
+///   .unwrap();
+///
+/// assert!(flattened.as_form(DocumentForm::Flattened).unwrap().is_array())
+/// # }
+/// ```
+pub trait ConvertDocument<I, B>: Sized {
+	/// Converts the document to the given form with the given `vocabulary`,
+	/// `generator` and `loader`, using the given `options`.
+	///
+	/// The `generator` is used to identify anonymous nodes when flattening.
+	#[allow(async_fn_in_trait)]
+	async fn convert_to_with_using<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		form: DocumentForm,
+		context: Option<RemoteContextReference<I>>,
+		loader: &'a impl Loader,
+		options: Options<I>,
+	) -> ConvertResult<I, B>
+	where
+		N: VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash,
+		B: 'a + Clone + Eq + Hash;
+
+	/// Converts the document to the given form with the given `generator` and
+	/// `loader`.
+	///
+	/// Default options are used.
+	#[allow(async_fn_in_trait)]
+	async fn convert_to<'a>(
+		&'a self,
+		generator: &'a mut impl Generator,
+		form: DocumentForm,
+		context: Option<RemoteContextReference<I>>,
+		loader: &'a impl Loader,
+	) -> ConvertResult<I, B>
+	where
+		(): VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash,
+		B: 'a + Clone + Eq + Hash,
+	{
+		self.convert_to_with_using(
+			vocabulary::no_vocabulary_mut(),
+			generator,
+			form,
+			context,
+			loader,
+			Options::default(),
+		)
+		.await
+	}
+
+	/// Frames the document with the given `vocabulary`, `generator`, `frame`
+	/// and `loader`, using the given `options`.
+	///
+	/// The framed document is compacted with the context of the frame, if
+	/// any.
+	#[allow(async_fn_in_trait)]
+	async fn frame_with_using<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		frame: RemoteDocumentReference<I>,
+		loader: &'a impl Loader,
+		options: Options<I>,
+	) -> ConvertResult<I, B>
+	where
+		N: VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash,
+		B: 'a + Clone + Eq + Hash;
+
+	/// Frames the document with the given `generator`, `frame` and `loader`.
+	///
+	/// Default options are used.
+	#[allow(async_fn_in_trait)]
+	async fn frame<'a>(
+		&'a self,
+		generator: &'a mut impl Generator,
+		frame: RemoteDocumentReference<I>,
+		loader: &'a impl Loader,
+	) -> ConvertResult<I, B>
+	where
+		(): VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash,
+		B: 'a + Clone + Eq + Hash,
+	{
+		self.frame_with_using(
+			vocabulary::no_vocabulary_mut(),
+			generator,
+			frame,
+			loader,
+			Options::default(),
+		)
+		.await
+	}
+}
+
+impl<I, B> ConvertDocument<I, B> for Document<I, B> {
+	async fn convert_to_with_using<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		form: DocumentForm,
+		context: Option<RemoteContextReference<I>>,
+		loader: &'a impl Loader,
+		options: Options<I>,
+	) -> ConvertResult<I, B>
+	where
+		N: VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash,
+		B: 'a + Clone + Eq + Hash,
+	{
+		let url = self.as_remote().url();
+		let expanded = self.as_expanded();
+
+		let (json, expanded) = match form {
+			DocumentForm::Expanded => (
+				expanded.objects().clone().into_with(vocabulary).into_json(),
+				expanded.clone(),
+			),
+			DocumentForm::Compacted => {
+				let context = context.ok_or(ConvertError::MissingContext)?;
+				let json =
+					compact_expanded_full(expanded, url, vocabulary, context, loader, options, ())
+						.await
+						.map_err(ConvertError::Compact)?;
+				(json, expanded.clone())
+			}
+			DocumentForm::Flattened => {
+				let ordered = options.ordered;
+				let flattened = Flatten::flatten_with(expanded, vocabulary, generator, ordered)
+					.map_err(ConvertError::ConflictingIndexes)?;
+
+				let json = match context {
+					Some(context) => compact_expanded_full(
+						&flattened,
+						url,
+						vocabulary,
+						context,
+						loader,
+						options,
+						(),
+					)
+					.await
+					.map_err(ConvertError::Compact)?,
+					None => flattened.clone().into_with(vocabulary).into_json(),
+				};
+
+				(json, ExpandedDocument::from(flattened))
+			}
+			DocumentForm::Framed => {
+				let frame =
+					RemoteDocument::new(None, None, json_syntax::Value::Object(Default::default()));
+				frame_json(self, vocabulary, generator, frame, context, loader, options).await?
+			}
+		};
+
+		Ok(converted(self, json, expanded, form))
+	}
+
+	async fn frame_with_using<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		frame: RemoteDocumentReference<I>,
+		loader: &'a impl Loader,
+		options: Options<I>,
+	) -> ConvertResult<I, B>
+	where
+		N: VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash,
+		B: 'a + Clone + Eq + Hash,
+	{
+		let frame = frame
+			.load_with(vocabulary, loader)
+			.await
+			.map_err(ConvertError::FrameLoading)?;
+
+		let context = match frame.clone().try_map(|json| json.into_ld_context()) {
+			Ok(context) => Some(RemoteContextReference::Loaded(context)),
+			Err(ExtractContextError::NoContext) => None,
+			Err(_) => return Err(ConvertError::InvalidFrame),
+		};
+
+		let (json, expanded) =
+			frame_json(self, vocabulary, generator, frame, context, loader, options).await?;
+
+		Ok(converted(self, json, expanded, DocumentForm::Framed))
+	}
+}
+
+/// Frames the given document, returning the framed document compacted with
+/// the given context (or an empty one), and in expanded form.
+async fn frame_json<'a, I, B, N>(
+	document: &'a Document<I, B>,
+	vocabulary: &'a mut N,
+	generator: &'a mut impl Generator<N>,
+	frame: RemoteDocument<I>,
+	context: Option<RemoteContextReference<I>>,
+	loader: &'a impl Loader,
+	options: Options<I>,
+) -> Result<(json_syntax::Value, ExpandedDocument<I, B>), ConvertError<I, B>>
+where
+	N: VocabularyMut<Iri = I, BlankId = B>,
+	I: Clone + Eq + Hash,
+	B: 'a + Clone + Eq + Hash,
+{
+	let frame = frame
+		.map(|mut json| {
+			framing::encode_frame(&mut json);
+			json
+		})
+		.expand_full(vocabulary, loader, options.clone(), ())
+		.await
+		.map_err(ConvertError::FrameExpansion)?;
+	let frame =
+		framing::Frame::decode(frame.into_objects().into_with(&mut *vocabulary).into_json())
+			.map_err(|_| ConvertError::InvalidFrame)?;
+
+	let flattened = Flatten::flatten_with(
+		document.as_expanded(),
+		vocabulary,
+		generator,
+		options.ordered,
+	)
+	.map_err(ConvertError::ConflictingIndexes)?;
+	let framed = framing::frame(flattened.into_with(&mut *vocabulary).into_json(), &frame);
+	let expanded =
+		ExpandedDocument::try_from_json_in(vocabulary, framed).map_err(ConvertError::Framing)?;
+
+	let context = context.unwrap_or_else(|| {
+		RemoteContextReference::Loaded(RemoteDocument::new(None, None, Context::default()))
+	});
+	let json = compact_expanded_full(
+		&expanded,
+		document.as_remote().url(),
+		vocabulary,
+		context,
+		loader,
+		options,
+		(),
+	)
+	.await
+	.map_err(ConvertError::Compact)?;
+
+	Ok((json, expanded))
+}
+
+/// Creates the result of the conversion of `document` into the given form.
+fn converted<I: Clone, B>(
+	document: &Document<I, B>,
+	json: json_syntax::Value,
+	expanded: ExpandedDocument<I, B>,
+	form: DocumentForm,
+) -> Document<I, B> {
+	let mut profile = HashSet::new();
+	profile.insert(Profile::Standard(form.profile()));
+
+	let remote = RemoteDocument::new_full(
+		document.as_remote().url().cloned(),
+		document.as_remote().content_type().cloned(),
+		None,
+		profile,
+		json,
+	);
+
+	Document::with_form(remote, expanded, form)
+}
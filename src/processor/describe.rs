@@ -0,0 +1,141 @@
+use super::Options;
+use json_ld_core::rdf::GraphFilter;
+use json_ld_core::RemoteDocumentReference;
+use std::fmt;
+
+/// Effective configuration of [`Options`], grouped by algorithm.
+///
+/// Returned by [`Options::describe`].
+pub struct Describe<'a, I>(pub(super) &'a Options<I>);
+
+impl<I: fmt::Display> fmt::Display for Describe<'_, I> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let o = self.0;
+
+		writeln!(f, "processing mode: {}", o.processing_mode)?;
+		writeln!(f, "base: {}", Optional(o.base.as_ref()))?;
+		writeln!(f, "ordered: {}", o.ordered)?;
+		writeln!(f, "metrics: {}", set(o.metrics.is_some()))?;
+		writeln!(f, "context loader: {}", set(o.context_loader.is_some()))?;
+		writeln!(f, "compare strategy: {:?}", o.compare_strategy)?;
+
+		writeln!(f, "expansion:")?;
+		writeln!(
+			f,
+			"  context: {}",
+			Optional(o.expand_context.as_ref().map(ContextRef))
+		)?;
+		write!(f, "  contexts: [")?;
+		for (i, context) in o.expand_contexts.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+
+			ContextRef(context).fmt(f)?
+		}
+		writeln!(f, "]")?;
+		writeln!(f, "  policy: {:?}", o.expansion_policy)?;
+		writeln!(
+			f,
+			"  prefetch contexts: {}",
+			Optional(o.prefetch_contexts.as_ref())
+		)?;
+		writeln!(
+			f,
+			"  value transformer: {}",
+			set(o.value_transformer.is_some())
+		)?;
+		writeln!(
+			f,
+			"  max literal length: {}",
+			Optional(o.max_literal_length.as_ref())
+		)?;
+		writeln!(f, "  reject non JSON-LD: {}", o.reject_non_json_ld)?;
+		writeln!(
+			f,
+			"  relabel non-conforming blank ids: {}",
+			o.relabel_non_conforming_blank_ids
+		)?;
+		writeln!(f, "  validate IRIs: {:?}", o.validate_iris)?;
+		writeln!(f, "  materialize defaults: {}", o.materialize_defaults)?;
+		writeln!(f, "  prune contexts: {}", o.prune_contexts)?;
+
+		writeln!(f, "compaction:")?;
+		writeln!(f, "  compact arrays: {}", o.compact_arrays)?;
+		writeln!(f, "  compact to relative: {}", o.compact_to_relative)?;
+		writeln!(
+			f,
+			"  keep value objects for: {:?}",
+			o.keep_value_objects_for
+		)?;
+		writeln!(f, "  included: {:?}", o.included)?;
+		writeln!(f, "  vocab relative values: {}", o.vocab_relative_values)?;
+		writeln!(f, "  omit defaults: {}", o.omit_defaults)?;
+		writeln!(f, "  wrap anonymous graphs: {}", o.wrap_anonymous_graphs)?;
+		writeln!(f, "  term preferences: {:?}", o.term_preferences)?;
+
+		writeln!(f, "rdf:")?;
+		writeln!(f, "  direction: {:?}", o.rdf_direction)?;
+		writeln!(f, "  generalized: {}", o.produce_generalized_rdf)?;
+		write!(f, "  graphs: ")?;
+		match &o.rdf_graphs {
+			GraphFilter::All => writeln!(f, "all")?,
+			GraphFilter::DefaultGraph => writeln!(f, "default graph")?,
+			GraphFilter::NamedGraphs => writeln!(f, "named graphs")?,
+			GraphFilter::Graphs(graphs) => {
+				write!(f, "[")?;
+				for (i, g) in graphs.iter().enumerate() {
+					if i > 0 {
+						write!(f, ", ")?;
+					}
+
+					write!(f, "<{g}>")?;
+				}
+				writeln!(f, "]")?
+			}
+		}
+		writeln!(f, "  nested lists: {:?}", o.nested_lists)?;
+		writeln!(
+			f,
+			"  index property: {}",
+			Optional(o.rdf_index_property.as_ref())
+		)?;
+		writeln!(f, "  canonical order: {}", o.rdf_canonical_order)
+	}
+}
+
+fn set(value: bool) -> &'static str {
+	if value {
+		"set"
+	} else {
+		"none"
+	}
+}
+
+struct Optional<T>(Option<T>);
+
+impl<T: fmt::Display> fmt::Display for Optional<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match &self.0 {
+			Some(value) => value.fmt(f),
+			None => f.write_str("none"),
+		}
+	}
+}
+
+struct ContextRef<'a, I, T>(&'a RemoteDocumentReference<I, T>);
+
+impl<I: fmt::Display, T> fmt::Display for ContextRef<'_, I, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.0 {
+			RemoteDocumentReference::Iri(iri) | RemoteDocumentReference::IriWithProfile(iri, _) => {
+				write!(f, "<{iri}>")
+			}
+			RemoteDocumentReference::Loaded(document) => match document.url() {
+				Some(url) => write!(f, "inline <{url}>"),
+				None => f.write_str("inline"),
+			},
+			_ => f.write_str("unknown"),
+		}
+	}
+}
@@ -0,0 +1,680 @@
+//! Framing of flattened documents.
+//!
+//! Implements the [JSON-LD 1.1 Framing algorithm][1] on the JSON
+//! serialization of a flattened document. Frames are expanded with the
+//! expansion algorithm, after the framing keywords and wildcards it does not
+//! know about are [encoded](encode_frame) as properties with reserved IRIs.
+//!
+//! Only the default graph is framed: named graphs are kept as is. Value
+//! patterns match any value, and a property missing from a node is only
+//! added when the frame gives it a `@default` value.
+//!
+//! [1]: <https://www.w3.org/TR/json-ld11-framing/#framing-algorithm>
+use hashbrown::{HashMap, HashSet};
+use json_syntax::{object::Key, Object, Value};
+use std::collections::BTreeMap;
+
+/// Prefix of the IRIs encoding the framing keywords and wildcards.
+const ENCODED: &str = "urn:x-json-ld-framing:";
+
+fn encoded(name: &str) -> Key {
+	format!("{ENCODED}{name}").into()
+}
+
+/// Object encoding the given wildcard.
+fn marker(name: &str) -> Value {
+	let mut object = Object::default();
+	object.insert(encoded(name), Value::Boolean(true));
+	Value::Object(object)
+}
+
+/// Encodes the framing keywords and wildcards of the given frame so that it
+/// can go through expansion.
+pub fn encode_frame(frame: &mut Value) {
+	encode(frame);
+
+	// Prevents expansion from dropping top-level frames matching on `@id`
+	// only.
+	if let Value::Object(object) = frame {
+		object.insert(encoded("frame"), Value::Boolean(true));
+	}
+}
+
+fn encode(frame: &mut Value) {
+	match frame {
+		Value::Array(items) => items.iter_mut().for_each(encode),
+		Value::Object(object) => encode_node_frame(object),
+		_ => (),
+	}
+}
+
+fn encode_node_frame(object: &mut Object) {
+	// Value patterns match any value.
+	if object.is_empty() || is_value(object) {
+		*object = marker("wildcard").into_object().unwrap();
+		return;
+	}
+
+	for entry in std::mem::take(object) {
+		let mut value = entry.value;
+		match entry.key.as_str() {
+			"@embed" | "@explicit" | "@omitDefault" | "@requireAll" => {
+				if let Value::String(s) = &value {
+					value = Value::String(s.trim_start_matches('@').into())
+				}
+
+				object.insert(encoded(&entry.key[1..]), value);
+			}
+			"@default" => {
+				object.insert(encoded("default"), value);
+			}
+			key @ ("@id" | "@type") => {
+				let wildcard = match &value {
+					Value::Object(o) => o.is_empty().then_some("Wildcard"),
+					Value::Array(items) if items.is_empty() => Some("None"),
+					Value::Array(items) => items
+						.iter()
+						.any(|item| item.as_object().is_some_and(Object::is_empty))
+						.then_some("Wildcard"),
+					_ => None,
+				};
+
+				match wildcard {
+					Some(wildcard) => {
+						object.insert(
+							encoded(&format!("{}{wildcard}", &key[1..])),
+							Value::Boolean(true),
+						);
+					}
+					None => {
+						object.insert(entry.key, value);
+					}
+				}
+			}
+			"@graph" | "@list" => {
+				encode(&mut value);
+				object.insert(entry.key, value);
+			}
+			key if key.starts_with('@') => {
+				object.insert(entry.key, value);
+			}
+			_ => {
+				match &mut value {
+					Value::Array(items) if items.is_empty() => items.push(marker("none")),
+					value => encode(value),
+				}
+
+				object.insert(entry.key, value);
+			}
+		}
+	}
+}
+
+/// Node identifier or type pattern.
+enum Pattern {
+	/// Matches any identifier or type (`{}`).
+	Wildcard,
+
+	/// Matches no identifier, or the absence of type (`[]`).
+	None,
+
+	/// Matches the given identifiers or types.
+	Values(Vec<String>),
+}
+
+/// Embedding of the nodes referenced by a framed node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Embed {
+	Always,
+	Once,
+	Never,
+}
+
+impl Embed {
+	fn from_literal(value: &Value) -> Option<Self> {
+		match value {
+			Value::String(s) => match s.as_str() {
+				"always" => Some(Self::Always),
+				"once" | "last" => Some(Self::Once),
+				"never" => Some(Self::Never),
+				_ => None,
+			},
+			Value::Boolean(true) => Some(Self::Once),
+			Value::Boolean(false) => Some(Self::Never),
+			_ => None,
+		}
+	}
+}
+
+/// Framing flags.
+#[derive(Debug, Clone, Copy)]
+struct Flags {
+	embed: Embed,
+	explicit: bool,
+	omit_default: bool,
+	require_all: bool,
+}
+
+impl Default for Flags {
+	fn default() -> Self {
+		Self {
+			embed: Embed::Once,
+			explicit: false,
+			omit_default: false,
+			require_all: false,
+		}
+	}
+}
+
+/// Expanded frame.
+#[derive(Default)]
+pub struct Frame {
+	id: Option<Pattern>,
+	types: Option<Pattern>,
+
+	/// Frames of the values of each property. No frame means that the node
+	/// must not have the property.
+	properties: BTreeMap<String, Vec<Frame>>,
+
+	embed: Option<Embed>,
+	explicit: Option<bool>,
+	omit_default: Option<bool>,
+	require_all: Option<bool>,
+
+	/// Default values, in expanded form, or `null`.
+	default: Option<Value>,
+}
+
+/// Invalid frame.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid frame")]
+pub struct InvalidFrame;
+
+impl Frame {
+	/// Decodes the given frame, expanded after being [encoded](encode_frame).
+	pub fn decode(frame: Value) -> Result<Self, InvalidFrame> {
+		match frame {
+			Value::Array(mut items) if items.len() <= 1 => match items.pop() {
+				Some(Value::Object(object)) => Ok(Self::decode_object(&object)),
+				Some(_) => Err(InvalidFrame),
+				None => Ok(Self::default()),
+			},
+			_ => Err(InvalidFrame),
+		}
+	}
+
+	fn decode_object(object: &Object) -> Self {
+		let mut frame = Self::default();
+
+		for entry in object {
+			let value = &entry.value;
+			match entry.key.strip_prefix(ENCODED) {
+				Some("embed") => frame.embed = literal(value).and_then(Embed::from_literal),
+				Some("explicit") => frame.explicit = literal(value).and_then(Value::as_bool),
+				Some("omitDefault") => frame.omit_default = literal(value).and_then(Value::as_bool),
+				Some("requireAll") => frame.require_all = literal(value).and_then(Value::as_bool),
+				Some("default") => {
+					frame.default = match literal(value).and_then(Value::as_str) {
+						Some("@null") => Some(Value::Null),
+						_ => Some(value.clone()),
+					}
+				}
+				Some("idWildcard") => frame.id = Some(Pattern::Wildcard),
+				Some("idNone") => frame.id = Some(Pattern::None),
+				Some("typeWildcard") => frame.types = Some(Pattern::Wildcard),
+				Some("typeNone") => frame.types = Some(Pattern::None),
+				Some(_) => (),
+				None => match entry.key.as_str() {
+					"@id" => {
+						if let Some(id) = value.as_str() {
+							frame.id = Some(Pattern::Values(vec![id.to_owned()]))
+						}
+					}
+					"@type" => {
+						let types = strings(value).map(str::to_owned).collect();
+						frame.types = Some(Pattern::Values(types))
+					}
+					key if key.starts_with('@') => (),
+					property => {
+						frame
+							.properties
+							.insert(property.to_owned(), Self::decode_property(value));
+					}
+				},
+			}
+		}
+
+		frame
+	}
+
+	fn decode_property(value: &Value) -> Vec<Self> {
+		let mut frames = Vec::new();
+
+		for item in value.as_array().into_iter().flatten() {
+			let Some(object) = item.as_object() else {
+				continue;
+			};
+
+			if object
+				.iter()
+				.any(|entry| entry.key.strip_prefix(ENCODED) == Some("none"))
+			{
+				return Vec::new();
+			}
+
+			let frame = match object.get_unique("@list").ok().flatten() {
+				Some(list) => list
+					.as_array()
+					.and_then(|items| items.first())
+					.and_then(Value::as_object)
+					.map(Self::decode_object)
+					.unwrap_or_default(),
+				None => Self::decode_object(object),
+			};
+
+			frames.push(frame)
+		}
+
+		frames
+	}
+
+	fn flags(&self, defaults: Flags) -> Flags {
+		Flags {
+			embed: self.embed.unwrap_or(defaults.embed),
+			explicit: self.explicit.unwrap_or(defaults.explicit),
+			omit_default: self.omit_default.unwrap_or(defaults.omit_default),
+			require_all: self.require_all.unwrap_or(defaults.require_all),
+		}
+	}
+
+	/// Checks if this frame only matches the given node identifiers.
+	fn references(&self) -> Option<&[String]> {
+		match &self.id {
+			Some(Pattern::Values(ids)) if self.types.is_none() && self.properties.is_empty() => {
+				Some(ids)
+			}
+			_ => None,
+		}
+	}
+}
+
+/// Frames the nodes of the given flattened document, in expanded form.
+///
+/// Returns the framed document in expanded form.
+pub fn frame(flattened: Value, frame: &Frame) -> Value {
+	let mut nodes = BTreeMap::new();
+	for node in flattened.into_array().into_iter().flatten() {
+		let Value::Object(node) = node else {
+			continue;
+		};
+
+		let id = node
+			.get_unique("@id")
+			.ok()
+			.flatten()
+			.and_then(Value::as_str)
+			.map(str::to_owned);
+
+		if let Some(id) = id {
+			nodes.insert(id, node);
+		}
+	}
+
+	let mut framer = Framer {
+		nodes: &nodes,
+		embedded: HashSet::new(),
+		stack: Vec::new(),
+	};
+
+	let ids: Vec<_> = nodes.keys().cloned().collect();
+	let mut result = Vec::new();
+	framer.frame(&ids, frame, Flags::default(), false, &mut result);
+
+	let mut counts = HashMap::new();
+	result
+		.iter()
+		.for_each(|value| count_blank_ids(value, &mut counts));
+	result
+		.iter_mut()
+		.for_each(|value| prune_blank_ids(value, &counts));
+
+	Value::Array(result)
+}
+
+/// Framing state.
+struct Framer<'a> {
+	/// Nodes of the flattened document, by identifier.
+	nodes: &'a BTreeMap<String, Object>,
+
+	/// Nodes embedded so far under the current top-level node.
+	embedded: HashSet<String>,
+
+	/// Nodes being framed, to detect circular references.
+	stack: Vec<String>,
+}
+
+impl Framer<'_> {
+	/// Returns the node with the given identifier.
+	///
+	/// Flattening leaves out the nodes that are only referenced.
+	fn node(&self, id: &str) -> Object {
+		self.nodes.get(id).cloned().unwrap_or_else(|| {
+			let mut node = Object::default();
+			node.insert("@id".into(), Value::String(id.into()));
+			node
+		})
+	}
+
+	/// Frames the given nodes, pushing the matching ones into `output`.
+	///
+	/// Flags missing from `frame` are taken from `defaults`.
+	fn frame(
+		&mut self,
+		ids: &[String],
+		frame: &Frame,
+		defaults: Flags,
+		embedded: bool,
+		output: &mut Vec<Value>,
+	) {
+		let flags = frame.flags(defaults);
+
+		for id in ids {
+			let node = self.node(id);
+			if !matches(&node, frame, flags.require_all) {
+				continue;
+			}
+
+			let mut result = Object::default();
+			result.insert("@id".into(), Value::String(id.as_str().into()));
+
+			if !embedded {
+				// Each top-level node is framed independently.
+				self.embedded.clear()
+			} else if flags.embed == Embed::Never
+				|| self.stack.contains(id)
+				|| (flags.embed == Embed::Once && self.embedded.contains(id))
+			{
+				output.push(Value::Object(result));
+				continue;
+			}
+
+			self.embedded.insert(id.clone());
+			self.stack.push(id.clone());
+
+			let mut entries: Vec<_> = node.iter().collect();
+			entries.sort_by(|a, b| a.key.cmp(&b.key));
+			for entry in entries {
+				match entry.key.as_str() {
+					"@id" => (),
+					key if key.starts_with('@') => {
+						result.insert(entry.key.clone(), entry.value.clone());
+					}
+					property => {
+						let frames = frame.properties.get(property);
+						if flags.explicit && frames.is_none() {
+							continue;
+						}
+
+						// Values without frame are framed with the flags of
+						// the current frame.
+						let implicit = Frame::default();
+						let (subframe, subframe_defaults) = match frames.and_then(|f| f.first()) {
+							Some(subframe) => (subframe, Flags::default()),
+							None => (&implicit, flags),
+						};
+
+						let mut values = Vec::new();
+						for value in entry.value.as_array().into_iter().flatten() {
+							match value
+								.as_object()
+								.and_then(|o| o.get_unique("@list").ok().flatten())
+							{
+								Some(list) => {
+									let mut items = Vec::new();
+									for item in list.as_array().into_iter().flatten() {
+										self.frame_value(
+											item,
+											subframe,
+											subframe_defaults,
+											&mut items,
+										)
+									}
+
+									let mut list = Object::default();
+									list.insert("@list".into(), Value::Array(items));
+									values.push(Value::Object(list))
+								}
+								None => self.frame_value(
+									value,
+									subframe,
+									subframe_defaults,
+									&mut values,
+								),
+							}
+						}
+
+						if !values.is_empty() {
+							result.insert(entry.key.clone(), Value::Array(values));
+						}
+					}
+				}
+			}
+
+			for (property, frames) in &frame.properties {
+				let Some(subframe) = frames.first() else {
+					continue;
+				};
+
+				let missing = result
+					.get_unique(property.as_str())
+					.ok()
+					.flatten()
+					.is_none();
+				let omit_default = subframe.omit_default.unwrap_or(flags.omit_default);
+
+				if missing && !omit_default {
+					if let Some(default) = subframe.default.as_ref().filter(|d| !d.is_null()) {
+						result.insert(property.as_str().into(), default.clone());
+					}
+				}
+			}
+
+			self.stack.pop();
+			output.push(Value::Object(result))
+		}
+	}
+
+	/// Frames the given property value, pushing the result into `output`.
+	fn frame_value(
+		&mut self,
+		value: &Value,
+		frame: &Frame,
+		defaults: Flags,
+		output: &mut Vec<Value>,
+	) {
+		match reference(value) {
+			Some(id) => self.frame(&[id.to_owned()], frame, defaults, true, output),
+			None => output.push(value.clone()),
+		}
+	}
+}
+
+/// Checks if the given node matches the given frame.
+fn matches(node: &Object, frame: &Frame, require_all: bool) -> bool {
+	let mut wildcard = true;
+	let mut matches_some = false;
+
+	if let Some(pattern) = &frame.id {
+		let id = node
+			.get_unique("@id")
+			.ok()
+			.flatten()
+			.and_then(Value::as_str);
+		let matched = match pattern {
+			Pattern::Wildcard => true,
+			Pattern::None => false,
+			Pattern::Values(ids) => id.is_some_and(|id| ids.iter().any(|i| i == id)),
+		};
+
+		if !require_all || !matched {
+			return matched;
+		}
+
+		matches_some = true
+	}
+
+	if let Some(pattern) = &frame.types {
+		wildcard = false;
+		let types: Vec<_> = node
+			.get_unique("@type")
+			.ok()
+			.flatten()
+			.map(|types| strings(types).collect())
+			.unwrap_or_default();
+
+		let matched = match pattern {
+			Pattern::Wildcard => !types.is_empty(),
+			Pattern::None => {
+				if !types.is_empty() {
+					return false;
+				}
+
+				true
+			}
+			Pattern::Values(expected) => {
+				let matched = types.iter().any(|ty| expected.iter().any(|e| e == ty));
+				if !require_all {
+					return matched;
+				}
+
+				matched
+			}
+		};
+
+		if !matched && require_all {
+			return false;
+		}
+
+		matches_some |= matched
+	}
+
+	for (property, frames) in &frame.properties {
+		wildcard = false;
+
+		let values = node
+			.get_unique(property.as_str())
+			.ok()
+			.flatten()
+			.and_then(Value::as_array)
+			.unwrap_or_default();
+
+		let matched = match frames.first() {
+			// The property must not be set.
+			None => {
+				if !values.is_empty() {
+					return false;
+				}
+
+				true
+			}
+			Some(subframe) if values.is_empty() && subframe.default.is_some() => continue,
+			Some(subframe) => match subframe.references() {
+				Some(ids) => values
+					.iter()
+					.any(|value| reference(value).is_some_and(|id| ids.iter().any(|i| i == id))),
+				None => !values.is_empty(),
+			},
+		};
+
+		if !matched && require_all {
+			return false;
+		}
+
+		matches_some |= matched
+	}
+
+	wildcard || matches_some
+}
+
+/// Checks if the given object is a value object.
+fn is_value(object: &Object) -> bool {
+	object.iter().any(|entry| entry.key.as_str() == "@value")
+}
+
+/// Returns the value of the first value object of the given array, in
+/// expanded form.
+fn literal(value: &Value) -> Option<&Value> {
+	value
+		.as_array()?
+		.first()?
+		.as_object()?
+		.get_unique("@value")
+		.ok()
+		.flatten()
+}
+
+/// Returns the strings of the given array, in expanded form.
+fn strings(value: &Value) -> impl Iterator<Item = &str> {
+	value
+		.as_array()
+		.into_iter()
+		.flatten()
+		.filter_map(Value::as_str)
+}
+
+/// Returns the identifier of the given node reference, if it is one.
+fn reference(value: &Value) -> Option<&str> {
+	let object = value.as_object()?;
+	if object.len() == 1 {
+		object.get_unique("@id").ok().flatten()?.as_str()
+	} else {
+		None
+	}
+}
+
+/// Counts the occurrences of each blank node identifier.
+fn count_blank_ids(value: &Value, counts: &mut HashMap<String, usize>) {
+	match value {
+		Value::Array(items) => items.iter().for_each(|item| count_blank_ids(item, counts)),
+		Value::Object(object) => {
+			for entry in object {
+				match (entry.key.as_str(), &entry.value) {
+					("@id", Value::String(id)) if id.starts_with("_:") => {
+						*counts.entry(id.as_str().to_owned()).or_default() += 1
+					}
+					("@value", _) => (),
+					(_, value) => count_blank_ids(value, counts),
+				}
+			}
+		}
+		_ => (),
+	}
+}
+
+/// Removes the identifiers of the blank nodes appearing only once.
+fn prune_blank_ids(value: &mut Value, counts: &HashMap<String, usize>) {
+	match value {
+		Value::Array(items) => items
+			.iter_mut()
+			.for_each(|item| prune_blank_ids(item, counts)),
+		Value::Object(object) => {
+			let unique = object
+				.get_unique("@id")
+				.ok()
+				.flatten()
+				.and_then(Value::as_str)
+				.is_some_and(|id| counts.get(id) == Some(&1));
+
+			if unique {
+				let _ = object.remove_unique("@id");
+			}
+
+			for (key, value) in object.iter_mut() {
+				if key.as_str() != "@value" {
+					prune_blank_ids(value, counts)
+				}
+			}
+		}
+		_ => (),
+	}
+}
@@ -1,17 +1,36 @@
 use crate::compaction::{self, Compact};
 use crate::context_processing::{self, Process};
 use crate::expansion;
+use crate::metrics::Metrics;
 use crate::syntax::ErrorCode;
-use crate::{flattening::ConflictingIndexes, Context, ExpandedDocument, Loader, ProcessingMode};
+use crate::{
+	flattening::ConflictingIndexes, Context, DynLoader, ExpandedDocument, Loader, ProcessingMode,
+	WithContextLoader,
+};
 use iref::IriBuf;
-use json_ld_core::rdf::RdfDirection;
+use json_ld_core::rdf::sink::SparqlInsert;
+use json_ld_core::rdf::{GraphFilter, NestedLists, QuadSink, RdfDirection};
+use json_ld_core::ValidId;
 use json_ld_core::{ContextLoadError, LoadError};
-use json_ld_core::{Document, RdfQuads, RemoteContextReference};
-use rdf_types::{vocabulary, BlankIdBuf, Generator, Vocabulary, VocabularyMut};
+use json_ld_core::{Document, LdQuads, RdfQuads, RemoteContextReference};
+use rdf_types::vocabulary::{IriVocabularyMut, LiteralVocabularyMut};
+use rdf_types::{
+	vocabulary, BlankIdBuf, Generator, RdfDisplayWithContext, Vocabulary, VocabularyMut,
+};
 use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
+mod builder;
+mod convert;
+mod describe;
+mod framing;
 mod remote_document;
 
+pub use builder::*;
+pub use convert::*;
+pub use describe::*;
+
 /// JSON-LD Processor options.
 #[derive(Clone)]
 pub struct Options<I = IriBuf> {
@@ -36,6 +55,16 @@ pub struct Options<I = IriBuf> {
 	/// A context that is used to initialize the active context when expanding a document.
 	pub expand_context: Option<RemoteContextReference<I>>,
 
+	/// Additional contexts used to initialize the active context when
+	/// expanding a document.
+	///
+	/// They are processed in order after `expand_context`, as if they were
+	/// the items of an array `@context`, and before the contexts provided by
+	/// the document itself.
+	///
+	/// Defaults to no context.
+	pub expand_contexts: Vec<RemoteContextReference<I>>,
+
 	/// If set to `true`, certain algorithm processing steps where indicated are
 	/// ordered lexicographically.
 	///
@@ -78,9 +107,220 @@ pub struct Options<I = IriBuf> {
 
 	/// Term expansion policy, passed to the document expansion algorithm.
 	pub expansion_policy: expansion::Policy,
+
+	/// If set, the remote contexts of the input document found by
+	/// [`preflight`](crate::preflight) are loaded before the expansion
+	/// algorithm runs, with at most the given number of concurrent loads.
+	///
+	/// Defaults to `None`, meaning that contexts are loaded one by one when
+	/// needed.
+	pub prefetch_contexts: Option<usize>,
+
+	/// Metrics observer, receiving events about the processing phases.
+	///
+	/// Defaults to `None`.
+	pub metrics: Option<Arc<dyn Metrics>>,
+
+	/// Transformer called on each value object produced by the expansion
+	/// algorithm.
+	///
+	/// See [`ValueTransformer`](expansion::ValueTransformer). Defaults to
+	/// `None`.
+	pub value_transformer: Option<Arc<dyn expansion::ValueTransformer>>,
+
+	/// Maximum length (in bytes) of string literal values.
+	///
+	/// If set, expansion fails when a property value exceeds this length.
+	/// This protects services relaying documents into RDF stores against
+	/// absurdly long literals.
+	///
+	/// Defaults to `None`.
+	pub max_literal_length: Option<usize>,
+
+	/// Kinds of value objects that must keep their object form during
+	/// compaction, regardless of the context term definitions.
+	///
+	/// Defaults to none.
+	pub keep_value_objects_for: compaction::KeepValueObjects,
+
+	/// Shape given to `@included` nodes during compaction.
+	///
+	/// Defaults to [`IncludedShape::Preserve`](compaction::IncludedShape::Preserve).
+	pub included: compaction::IncludedShape,
+
+	/// Compact node references that are values of a `@type: @vocab` term
+	/// relative to the vocabulary.
+	///
+	/// If `false`, such values are compacted to compact or absolute IRIs.
+	///
+	/// Defaults to `true`.
+	pub vocab_relative_values: bool,
+
+	/// Omit the properties equal to the default value of their term during
+	/// compaction.
+	///
+	/// See [`compaction::Options::omit_defaults`].
+	///
+	/// Defaults to `false`.
+	pub omit_defaults: bool,
+
+	/// Wrap the content of anonymous graphs in `@included` when compacting
+	/// them in `["@graph", "@id"]` containers.
+	///
+	/// See [`compaction::Options::wrap_anonymous_graphs`].
+	///
+	/// Defaults to `false`.
+	pub wrap_anonymous_graphs: bool,
+
+	/// Graphs from which RDF quads are produced when serializing the document
+	/// into an RDF dataset.
+	///
+	/// Defaults to [`GraphFilter::All`].
+	pub rdf_graphs: GraphFilter<I>,
+
+	/// How lists of lists are serialized into RDF.
+	///
+	/// Lists of lists rejected by [`NestedLists::Reject`] are reported as
+	/// an [`InvalidSetOrListObject`](expansion::Error::InvalidSetOrListObject)
+	/// error.
+	///
+	/// Defaults to [`NestedLists::Keep`].
+	pub nested_lists: NestedLists,
+
+	/// Property through which the `@index` of property values is preserved
+	/// when serializing the document into an RDF dataset.
+	///
+	/// When set, each quad whose object has an index is reified, and the
+	/// index is attached to the reification with this property. See
+	/// [`json_ld_core::rdf::Quads::with_index_property`].
+	///
+	/// Defaults to `None` (indexes are dropped, as per the specification).
+	pub rdf_index_property: Option<I>,
+
+	/// Sort the RDF quads in canonical order.
+	///
+	/// When set, [`ToRdf::collect_quads`] relabels the blank nodes with the
+	/// RDFC-1.0 canonical labels and returns the quads sorted by subject,
+	/// predicate, object and graph (see
+	/// [`json_ld_core::rdf::canon::canonicalize_quads`]). Hashes computed
+	/// over the emitted quads are then stable across runs and versions, and
+	/// do not depend on the blank node labels of the input document.
+	///
+	/// Defaults to `false` (quads are returned in generation order).
+	pub rdf_canonical_order: bool,
+
+	/// Fail instead of emitting a warning when the input document is
+	/// probably not JSON-LD (no `@context`, no keyword, no absolute IRI key
+	/// and no JSON media type).
+	///
+	/// Defaults to `false`.
+	pub reject_non_json_ld: bool,
+
+	/// Relabel non-conforming blank node identifiers (such as `_:node 1`)
+	/// instead of keeping them as invalid identifiers.
+	///
+	/// See [`expansion::Options::relabel_non_conforming_blank_ids`].
+	///
+	/// Defaults to `false`.
+	pub relabel_non_conforming_blank_ids: bool,
+
+	/// Validation of the IRIs produced by the expansion.
+	///
+	/// See [`expansion::Options::validate_iris`].
+	///
+	/// Defaults to [`Action::Keep`](expansion::Action::Keep) (no validation).
+	pub validate_iris: expansion::Action,
+
+	/// Restore the properties missing from node objects from the default
+	/// value of their term during expansion.
+	///
+	/// See [`expansion::Options::materialize_defaults`].
+	///
+	/// Defaults to `false`.
+	pub materialize_defaults: bool,
+
+	/// Remove from the loaded remote contexts the term definitions that the
+	/// expanded document does not seem to use, before processing them.
+	///
+	/// This is **not sound**: terms are selected with a syntactic heuristic,
+	/// so a term only referenced by a context loaded afterwards is removed
+	/// and the expansion result may then differ from the one obtained
+	/// without pruning. Only enable it for known contexts that do not depend
+	/// on each other, when the cost of processing huge contexts for small
+	/// documents matters. See [`PruningLoader`](crate::PruningLoader) for
+	/// details.
+	///
+	/// Defaults to `false`.
+	pub prune_contexts: bool,
+
+	/// Terms to prefer, in order, when several terms can be selected to
+	/// compact the same IRI (for instance two `@reverse` terms aliasing the
+	/// same property).
+	///
+	/// Terms not listed here come after the listed ones. Remaining ties are
+	/// broken by choosing the shortest term, then the lexicographically least
+	/// one.
+	///
+	/// Defaults to an empty list.
+	pub term_preferences: Vec<String>,
+
+	/// Loader used to fetch remote contexts, instead of the document loader.
+	///
+	/// This includes the contexts referenced by the document, the
+	/// `expand_context` and the compaction context. The document loader is
+	/// still used to fetch the input document.
+	///
+	/// Defaults to `None`, meaning that the document loader is also used to
+	/// fetch contexts.
+	pub context_loader: Option<Arc<dyn DynLoader>>,
+
+	/// How blank node identifiers are compared by
+	/// [`JsonLdProcessor::compare_full`].
+	///
+	/// Defaults to [`CompareStrategy::Labels`].
+	pub compare_strategy: CompareStrategy,
 }
 
 impl<I> Options<I> {
+	/// Returns the preset options for the given processing mode.
+	///
+	/// - [`ProcessingMode::JsonLd1_1`]: the default options.
+	/// - [`ProcessingMode::JsonLd1_0`]: the options reproducing the
+	///   JSON-LD 1.0 algorithms, which always process map entries in
+	///   lexicographic order (`ordered` is `true`).
+	///
+	/// In both cases the options pass the [`OptionsBuilder::build`] checks,
+	/// JSON-LD 1.1 only features (such as `rdf_direction`) being left
+	/// disabled.
+	pub fn with_defaults_for(processing_mode: ProcessingMode) -> Self {
+		match processing_mode {
+			ProcessingMode::JsonLd1_0 => Self {
+				processing_mode,
+				ordered: true,
+				..Self::default()
+			},
+			ProcessingMode::JsonLd1_1 => Self::default(),
+		}
+	}
+
+	/// Returns a builder checking the coherence of the options.
+	pub fn builder() -> OptionsBuilder<I> {
+		OptionsBuilder::new()
+	}
+
+	/// Returns a displayable description of the effective configuration,
+	/// grouped by algorithm.
+	///
+	/// ```
+	/// use json_ld::Options;
+	///
+	/// let options: Options = Options::default();
+	/// println!("{}", options.describe());
+	/// ```
+	pub fn describe(&self) -> Describe<'_, I> {
+		Describe(self)
+	}
+
 	/// Returns these options with the `ordered` flag set to `false`.
 	///
 	/// This means entries will not be ordered by keys before being processed.
@@ -104,6 +344,7 @@ impl<I> Options<I> {
 	pub fn context_processing_options(&self) -> context_processing::Options {
 		context_processing::Options {
 			processing_mode: self.processing_mode,
+			default_values: self.omit_defaults || self.materialize_defaults,
 			..Default::default()
 		}
 	}
@@ -114,6 +355,12 @@ impl<I> Options<I> {
 			processing_mode: self.processing_mode,
 			ordered: self.ordered,
 			policy: self.expansion_policy,
+			max_literal_length: self.max_literal_length,
+			reject_non_json_ld: self.reject_non_json_ld,
+			relabel_non_conforming_blank_ids: self.relabel_non_conforming_blank_ids,
+			validate_iris: self.validate_iris,
+			materialize_defaults: self.materialize_defaults,
+			value_transformer: self.value_transformer.clone(),
 		}
 	}
 
@@ -124,6 +371,11 @@ impl<I> Options<I> {
 			compact_to_relative: self.compact_to_relative,
 			compact_arrays: self.compact_arrays,
 			ordered: self.ordered,
+			keep_value_objects_for: self.keep_value_objects_for,
+			included: self.included,
+			vocab_relative_values: self.vocab_relative_values,
+			omit_defaults: self.omit_defaults,
+			wrap_anonymous_graphs: self.wrap_anonymous_graphs,
 		}
 	}
 }
@@ -135,11 +387,33 @@ impl<I> Default for Options<I> {
 			compact_arrays: true,
 			compact_to_relative: true,
 			expand_context: None,
+			expand_contexts: Vec::new(),
 			ordered: false,
 			processing_mode: ProcessingMode::JsonLd1_1,
 			rdf_direction: None,
 			produce_generalized_rdf: false,
 			expansion_policy: expansion::Policy::default(),
+			prefetch_contexts: None,
+			metrics: None,
+			value_transformer: None,
+			max_literal_length: None,
+			keep_value_objects_for: compaction::KeepValueObjects::default(),
+			included: compaction::IncludedShape::default(),
+			vocab_relative_values: true,
+			omit_defaults: false,
+			wrap_anonymous_graphs: false,
+			rdf_graphs: GraphFilter::All,
+			nested_lists: NestedLists::Keep,
+			rdf_index_property: None,
+			rdf_canonical_order: false,
+			reject_non_json_ld: false,
+			relabel_non_conforming_blank_ids: false,
+			validate_iris: expansion::Action::Keep,
+			materialize_defaults: false,
+			prune_contexts: false,
+			term_preferences: Vec::new(),
+			context_loader: None,
+			compare_strategy: CompareStrategy::Labels,
 		}
 	}
 }
@@ -170,7 +444,7 @@ impl ExpandError {
 			Self::Expansion(e) => e.code(),
 			Self::ContextProcessing(e) => e.code(),
 			Self::Loading(_) => ErrorCode::LoadingDocumentFailed,
-			Self::ContextLoading(_) => ErrorCode::LoadingRemoteContextFailed,
+			Self::ContextLoading(e) => e.code(),
 		}
 	}
 }
@@ -212,7 +486,7 @@ impl CompactError {
 			Self::ContextProcessing(e) => e.code(),
 			Self::Compaction(e) => e.code(),
 			Self::Loading(_) => ErrorCode::LoadingDocumentFailed,
-			Self::ContextLoading(_) => ErrorCode::LoadingRemoteContextFailed,
+			Self::ContextLoading(e) => e.code(),
 		}
 	}
 }
@@ -245,9 +519,9 @@ impl<I, B> FlattenError<I, B> {
 		match self {
 			Self::Expand(e) => e.code(),
 			Self::Compact(e) => e.code(),
-			Self::ConflictingIndexes(_) => ErrorCode::ConflictingIndexes,
+			Self::ConflictingIndexes(e) => e.code(),
 			Self::Loading(_) => ErrorCode::LoadingDocumentFailed,
-			Self::ContextLoading(_) => ErrorCode::LoadingRemoteContextFailed,
+			Self::ContextLoading(e) => e.code(),
 		}
 	}
 }
@@ -275,9 +549,44 @@ impl ToRdfError {
 /// Error that can be raised by the [`JsonLdProcessor::to_rdf`] function.
 pub type ToRdfResult<V, G> = Result<ToRdf<V, G>, ToRdfError>;
 
+/// Document comparison strategy.
+///
+/// See [`Options::compare_strategy`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CompareStrategy {
+	/// Blank node identifiers must be equal in both documents.
+	#[default]
+	Labels,
+
+	/// Documents are compared up to a renaming of their blank node
+	/// identifiers, as in the W3C test suite.
+	///
+	/// See [`ExpandedDocument::is_isomorphic_to`].
+	Isomorphism,
+}
+
+/// Outcome of a document comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+	/// Whether or not the documents are equivalent.
+	pub equal: bool,
+
+	/// Strategy used to compare the documents.
+	pub strategy: CompareStrategy,
+}
+
+impl From<Comparison> for bool {
+	fn from(value: Comparison) -> Self {
+		value.equal
+	}
+}
+
 /// Result of the [`JsonLdProcessor::compare`] function.
 pub type CompareResult = Result<bool, ExpandError>;
 
+/// Result of the [`JsonLdProcessor::compare_detailed`] function.
+pub type ComparisonResult = Result<Comparison, ExpandError>;
+
 /// Application Programming Interface.
 ///
 /// The `JsonLdProcessor` interface is the high-level programming structure that
@@ -328,6 +637,9 @@ pub trait JsonLdProcessor<Iri>: Sized {
 	/// Compare this document against `other` with a custom vocabulary using the
 	/// given `options` and warnings handler.
 	///
+	/// Both documents are expanded then compared according to
+	/// [`Options::compare_strategy`].
+	///
 	/// # Example
 	///
 	/// ```
@@ -371,6 +683,30 @@ pub trait JsonLdProcessor<Iri>: Sized {
 		Iri: Clone + Eq + Hash,
 		N::BlankId: Clone + Eq + Hash;
 
+	/// Compare this document against `other` like
+	/// [`compare_full`](Self::compare_full), returning the strategy used
+	/// along with the outcome of the comparison.
+	#[allow(async_fn_in_trait)]
+	async fn compare_detailed<N>(
+		&self,
+		other: &Self,
+		vocabulary: &mut N,
+		loader: &impl Loader,
+		options: Options<Iri>,
+		warnings: impl context_processing::WarningHandler<N> + expansion::WarningHandler<N>,
+	) -> ComparisonResult
+	where
+		N: VocabularyMut<Iri = Iri>,
+		Iri: Clone + Eq + Hash,
+		N::BlankId: Clone + Eq + Hash,
+	{
+		let strategy = options.compare_strategy;
+		let equal = self
+			.compare_full(other, vocabulary, loader, options, warnings)
+			.await?;
+		Ok(Comparison { equal, strategy })
+	}
+
 	/// Compare this document against `other` with a custom vocabulary using the
 	/// given `options`.
 	///
@@ -1494,16 +1830,32 @@ pub trait JsonLdProcessor<Iri>: Sized {
 	{
 		let rdf_direction = options.rdf_direction;
 		let produce_generalized_rdf = options.produce_generalized_rdf;
-		let expanded_input = self
+		let graph_filter = options.rdf_graphs.clone();
+		let nested_lists = options.nested_lists;
+		let index_property = options.rdf_index_property.clone();
+		let canonical_order = options.rdf_canonical_order;
+		let mut expanded_input = self
 			.expand_full(&mut vocabulary, loader, options.unordered(), warnings)
 			.await
 			.map_err(ToRdfError::Expand)?;
+
+		// Lists of lists rejected by `NestedLists::Reject` are reported as
+		// invalid list objects.
+		nested_lists.apply(&mut expanded_input).map_err(|_| {
+			ToRdfError::Expand(ExpandError::Expansion(
+				expansion::Error::InvalidSetOrListObject,
+			))
+		})?;
+
 		Ok(ToRdf::new(
 			vocabulary,
 			generator,
 			expanded_input,
 			rdf_direction,
 			produce_generalized_rdf,
+			graph_filter,
+			index_property,
+			canonical_order,
 		))
 	}
 
@@ -1798,6 +2150,10 @@ pub struct ToRdf<V: Vocabulary, G> {
 	doc: ExpandedDocument<V::Iri, V::BlankId>,
 	rdf_direction: Option<RdfDirection>,
 	produce_generalized_rdf: bool,
+	graph_filter: GraphFilter<V::Iri>,
+	index_property: Option<V::Iri>,
+	canonical_order: bool,
+	dropped: AtomicUsize,
 }
 
 impl<V: Vocabulary, G: rdf_types::Generator<V>> ToRdf<V, G> {
@@ -1807,6 +2163,9 @@ impl<V: Vocabulary, G: rdf_types::Generator<V>> ToRdf<V, G> {
 		mut doc: ExpandedDocument<V::Iri, V::BlankId>,
 		rdf_direction: Option<RdfDirection>,
 		produce_generalized_rdf: bool,
+		graph_filter: GraphFilter<V::Iri>,
+		index_property: Option<V::Iri>,
+		canonical_order: bool,
 	) -> Self
 	where
 		V::Iri: Clone + Eq + Hash,
@@ -1819,16 +2178,43 @@ impl<V: Vocabulary, G: rdf_types::Generator<V>> ToRdf<V, G> {
 			doc,
 			rdf_direction,
 			produce_generalized_rdf,
+			graph_filter,
+			index_property,
+			canonical_order,
+			dropped: AtomicUsize::new(0),
 		}
 	}
 
 	pub fn quads(&mut self) -> json_ld_core::rdf::Quads<'_, V, G> {
-		self.doc.rdf_quads_full(
-			&mut self.vocabulary,
-			&mut self.generator,
-			self.rdf_direction,
-			self.produce_generalized_rdf,
-		)
+		self.dropped.store(0, Ordering::Relaxed);
+		let quads = self
+			.doc
+			.rdf_quads_full(
+				&mut self.vocabulary,
+				&mut self.generator,
+				self.rdf_direction,
+				self.produce_generalized_rdf,
+			)
+			.with_graph_filter(&self.graph_filter)
+			.count_dropped(&self.dropped);
+
+		match &self.index_property {
+			Some(property) => quads.with_index_property(property),
+			None => quads,
+		}
+	}
+
+	/// Returns the number of quads omitted because their predicate is a blank
+	/// node identifier, counted by the quad iterator while producing the
+	/// quads.
+	///
+	/// The count is reset each time the quads are produced (with
+	/// [`Self::quads`] or any method using it), and only covers the quads
+	/// iterated so far. Such quads are only produced when the
+	/// `produce_generalized_rdf` option is set, in which case this returns
+	/// `0`.
+	pub fn dropped(&self) -> usize {
+		self.dropped.load(Ordering::Relaxed)
 	}
 
 	#[inline(always)]
@@ -1836,6 +2222,87 @@ impl<V: Vocabulary, G: rdf_types::Generator<V>> ToRdf<V, G> {
 		self.quads().cloned()
 	}
 
+	/// Collects the RDF quads of the document.
+	///
+	/// If the `rdf_canonical_order` option is set, the blank nodes are
+	/// relabeled with their canonical labels and the quads are sorted in
+	/// canonical order. Otherwise they are returned in the same order as
+	/// [`Self::cloned_quads`].
+	pub fn collect_quads(&mut self) -> Vec<json_ld_core::rdf::Quad<V::Iri, V::BlankId, V::Literal>>
+	where
+		V: VocabularyMut,
+		V::Iri: Clone + PartialEq,
+		V::BlankId: Clone + Eq + Hash,
+		V::Literal: Clone + PartialEq,
+		ValidId<V::Iri, V::BlankId>: RdfDisplayWithContext<V>,
+		json_ld_core::rdf::Value<V::Iri, V::BlankId, V::Literal>: RdfDisplayWithContext<V>,
+	{
+		let mut quads: Vec<_> = self.cloned_quads().collect();
+
+		if self.canonical_order {
+			json_ld_core::rdf::canon::canonicalize_quads(&mut self.vocabulary, &mut quads)
+		}
+
+		quads
+	}
+
+	/// Pushes all the RDF quads of the document into the given `sink`.
+	pub fn drain_into<S>(&mut self, sink: &mut S) -> Result<(), S::Error>
+	where
+		V: IriVocabularyMut + LiteralVocabularyMut,
+		V::Iri: Clone + PartialEq,
+		V::BlankId: Clone + PartialEq,
+		V::Literal: Clone,
+		S: QuadSink<V::Iri, V::BlankId, V::Literal>,
+	{
+		json_ld_core::rdf::sink::drain_into(self.cloned_quads(), sink)
+	}
+
+	/// Generates a SPARQL `INSERT DATA` request inserting all the RDF quads
+	/// of the document.
+	///
+	/// If `graph_iri` is given, the quads of the default graph are inserted
+	/// into this graph. Quads of named graphs are inserted into their own
+	/// graph.
+	pub fn to_sparql_insert(&mut self, graph_iri: Option<&V::Iri>) -> String
+	where
+		V: VocabularyMut,
+		V::Iri: Clone + PartialEq,
+		V::BlankId: Clone + Eq + Hash,
+		V::Literal: Clone + PartialEq,
+		ValidId<V::Iri, V::BlankId>: RdfDisplayWithContext<V>,
+		json_ld_core::rdf::Value<V::Iri, V::BlankId, V::Literal>: RdfDisplayWithContext<V>,
+	{
+		let quads = self.collect_quads();
+
+		let mut sink = match graph_iri {
+			Some(iri) => SparqlInsert::new_in_graph(&self.vocabulary, &ValidId::Iri(iri.clone())),
+			None => SparqlInsert::new(&self.vocabulary),
+		};
+
+		json_ld_core::rdf::sink::drain_into(quads, &mut sink).unwrap_or_else(|e| match e {});
+		sink.into_string()
+	}
+
+	/// Checks that the document is turned into well-formed RDF.
+	///
+	/// Reports the invalid IRIs (such as relative IRIs left by lenient
+	/// expansion) and ill-formed language tags of the document, which are
+	/// dropped from the produced quads, and the literals whose lexical form
+	/// is not valid for their XSD datatype.
+	pub fn validate(&mut self) -> json_ld_core::rdf::validation::Report
+	where
+		V: IriVocabularyMut + LiteralVocabularyMut,
+		V::Iri: Clone + PartialEq,
+		V::BlankId: Clone,
+		V::Literal: Clone,
+	{
+		let quads: Vec<_> = self.cloned_quads().collect();
+		let mut report = json_ld_core::rdf::validation::validate(&self.vocabulary, &quads);
+		report.dropped = json_ld_core::rdf::validation::validate_document(&self.doc);
+		report
+	}
+
 	pub fn vocabulary(&self) -> &V {
 		&self.vocabulary
 	}
@@ -1890,6 +2357,7 @@ where
 	L: Loader,
 {
 	let context_base = url.or(options.base.as_ref());
+	let loader = &WithContextLoader::new(options.context_loader.as_deref(), loader);
 
 	let context = context
 		.load_context_with(vocabulary, loader)
@@ -1897,10 +2365,15 @@ where
 		.map_err(CompactError::ContextLoading)?
 		.into_document();
 
+	let mut initial_context = Context::new(None);
+	if !options.term_preferences.is_empty() {
+		initial_context.set_term_preferences(Some(options.term_preferences.clone().into()))
+	}
+
 	let mut active_context = context
 		.process_full(
 			vocabulary,
-			&Context::new(None),
+			&initial_context,
 			loader,
 			context_base.cloned(),
 			options.context_processing_options(),
@@ -1932,16 +2405,59 @@ where
 #[cfg(test)]
 mod tests {
 	use futures::Future;
-	use json_ld_core::{NoLoader, RemoteDocument};
+	use json_ld_core::{
+		ChainLoader, Document, DocumentForm, FsLoader, NoLoader, RemoteContextReference,
+		RemoteDocument, RemoteDocumentReference,
+	};
 	use json_syntax::Value;
 	use rdf_types::generator;
+	use static_iref::iri;
 
-	use crate::JsonLdProcessor;
+	use crate::{ConvertDocument, JsonLdProcessor, Options};
 
 	async fn assert_send<F: Future + Send>(f: F) -> F::Output {
 		f.await
 	}
 
+	/// Fails to compile if the given future is not `Send`.
+	fn assert_send_future<F: Future + Send>(_: F) {}
+
+	/// Checks at compile time that the futures returned by the processing
+	/// functions are `Send` with the given loader. The futures are never
+	/// polled.
+	macro_rules! assert_processing_send {
+		($loader:expr) => {{
+			let loader = $loader;
+			let context = || RemoteContextReference::iri(iri!("https://example.com/c").to_owned());
+			let mut generator = generator::Blank::new();
+
+			let document = RemoteDocument::new(None, None, Value::Null);
+			assert_send_future(document.compare(&document, &loader));
+			assert_send_future(document.expand(&loader));
+			assert_send_future(document.expand_using(&loader, Options::default()));
+			assert_send_future(document.compact(context(), &loader));
+			assert_send_future(document.flatten(&mut generator, &loader));
+			assert_send_future(document.to_rdf(&mut generator, &loader));
+			assert_send_future(document.clone().into_document(&loader));
+
+			let reference = RemoteDocumentReference::iri(iri!("https://example.com/d").to_owned());
+			assert_send_future(reference.compare(&reference, &loader));
+			assert_send_future(reference.expand(&loader));
+			assert_send_future(reference.compact(context(), &loader));
+			assert_send_future(reference.flatten(&mut generator, &loader));
+			assert_send_future(reference.to_rdf(&mut generator, &loader));
+			assert_send_future(reference.clone().into_document(&loader));
+
+			let document = Document::new(document, Default::default());
+			assert_send_future(document.convert_to(
+				&mut generator,
+				DocumentForm::Compacted,
+				Some(context()),
+				&loader,
+			));
+		}};
+	}
+
 	#[async_std::test]
 	async fn to_rdf_is_send() {
 		let generator = generator::Blank::new();
@@ -1949,4 +2465,19 @@ mod tests {
 		let f = document.to_rdf(generator, &NoLoader);
 		let _ = assert_send(f).await;
 	}
+
+	#[test]
+	fn processing_is_send() {
+		assert_processing_send!(NoLoader);
+		assert_processing_send!(FsLoader::new());
+		assert_processing_send!(std::collections::HashMap::<iref::IriBuf, RemoteDocument>::new());
+		assert_processing_send!(ChainLoader::new(FsLoader::new(), NoLoader));
+		assert_processing_send!(Box::new(NoLoader) as Box<dyn json_ld_core::loader::DynLoader>);
+
+		#[cfg(feature = "reqwest")]
+		assert_processing_send!(json_ld_core::ReqwestLoader::new());
+
+		#[cfg(feature = "ureq")]
+		assert_processing_send!(json_ld_core::UreqLoader::new());
+	}
 }
@@ -1,12 +1,18 @@
 use super::{
-	compact_expanded_full, CompactError, CompactResult, CompareResult, ExpandError, ExpandResult,
-	FlattenError, FlattenResult, JsonLdProcessor, Options,
+	compact_expanded_full, CompactError, CompactResult, CompareResult, CompareStrategy,
+	ExpandError, ExpandResult, FlattenError, FlattenResult, JsonLdProcessor, Options,
 };
 use crate::context_processing::{self, Process};
 use crate::expansion::{self, Expand};
+use crate::metrics::{self, Observed, Phase, Timer};
+use crate::preflight::{prefetch, Prefetched};
 use crate::IntoDocumentResult;
-use crate::{Context, Flatten, Loader, RemoteDocument, RemoteDocumentReference};
+use crate::{
+	Context, ExpandedDocument, Flatten, Loader, PruningLoader, RemoteDocument,
+	RemoteDocumentReference, WithContextLoader,
+};
 use contextual::WithContext;
+use hashbrown::HashMap;
 use json_ld_core::{Document, RemoteContextReference};
 use rdf_types::{Generator, VocabularyMut};
 use std::hash::Hash;
@@ -18,27 +24,29 @@ impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
 		vocabulary: &mut N,
 		loader: &impl Loader,
 		options: Options<I>,
-		mut warnings: impl context_processing::WarningHandler<N> + expansion::WarningHandler<N>,
+		warnings: impl context_processing::WarningHandler<N> + expansion::WarningHandler<N>,
 	) -> CompareResult
 	where
 		N: VocabularyMut<Iri = I>,
 		I: Clone + Eq + Hash,
 		N::BlankId: Clone + Eq + Hash,
 	{
-		if json_ld_syntax::Compare::compare(self.document(), other.document()) {
-			let a = JsonLdProcessor::expand_full(
-				self,
-				vocabulary,
-				loader,
-				options.clone(),
-				&mut warnings,
-			)
-			.await?;
-			let b = JsonLdProcessor::expand_full(other, vocabulary, loader, options, &mut warnings)
-				.await?;
-			Ok(a == b)
-		} else {
-			Ok(false)
+		match options.compare_strategy {
+			CompareStrategy::Labels => {
+				if json_ld_syntax::Compare::compare(self.document(), other.document()) {
+					let (a, b) =
+						expand_both(self, other, vocabulary, loader, options, warnings).await?;
+					Ok(a == b)
+				} else {
+					Ok(false)
+				}
+			}
+			CompareStrategy::Isomorphism => {
+				// The syntactic comparison would compare blank node labels.
+				let (a, b) =
+					expand_both(self, other, vocabulary, loader, options, warnings).await?;
+				Ok(a.is_isomorphic_to(&b))
+			}
 		}
 	}
 
@@ -54,9 +62,39 @@ impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
 		I: Clone + Eq + Hash,
 		N::BlankId: Clone + Eq + Hash,
 	{
+		let context_loader = options.context_loader.clone();
+		let loader = &WithContextLoader::new(context_loader.as_deref(), loader);
+
+		let metrics = options.metrics.clone();
+		let metrics = metrics.as_deref();
+
+		let prefetched = match options.prefetch_contexts {
+			Some(parallelism) => {
+				let iris = crate::preflight_with(vocabulary, self);
+				prefetch(iris, loader, parallelism).await
+			}
+			None => HashMap::new(),
+		};
+		let loader = &Prefetched::new(prefetched, loader);
+		let loader = &if options.prune_contexts {
+			PruningLoader::new(loader, self.document())
+		} else {
+			PruningLoader::disabled(loader)
+		};
+
+		// From now on the loader is only used to resolve contexts. Prefetched
+		// contexts are reported once actually used.
+		let loader = &Observed::new(metrics, loader);
+
+		let context_processing_timer = Timer::start(metrics, Phase::ContextProcessing);
 		let mut active_context = Context::new(options.base.clone().or_else(|| self.url().cloned()));
 
-		if let Some(expand_context) = options.expand_context.take() {
+		let expand_contexts = options
+			.expand_context
+			.take()
+			.into_iter()
+			.chain(std::mem::take(&mut options.expand_contexts));
+		for expand_context in expand_contexts {
 			active_context = expand_context
 				.load_context_with(vocabulary, loader)
 				.await
@@ -73,7 +111,7 @@ impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
 				.await
 				.map_err(ExpandError::ContextProcessing)?
 				.into_processed()
-		};
+		}
 
 		if let Some(context_url) = self.context_url() {
 			active_context = RemoteDocumentReference::Iri(context_url.clone())
@@ -94,7 +132,11 @@ impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
 				.into_processed()
 		}
 
-		self.document()
+		drop(context_processing_timer);
+
+		let expansion_timer = Timer::start(metrics, Phase::Expansion);
+		let expanded = self
+			.document()
 			.expand_full(
 				vocabulary,
 				active_context,
@@ -104,7 +146,11 @@ impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
 				warnings,
 			)
 			.await
-			.map_err(ExpandError::Expansion)
+			.map_err(ExpandError::Expansion)?;
+		drop(expansion_timer);
+
+		metrics::nodes_expanded(metrics, &expanded);
+		Ok(expanded)
 	}
 
 	async fn into_document_full<'a, N>(
@@ -147,6 +193,8 @@ impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
 		.await
 		.map_err(CompactError::Expand)?;
 
+		let metrics = options.metrics.clone();
+		let _timer = Timer::start(metrics.as_deref(), Phase::Compaction);
 		compact_expanded_full(
 			&expanded_input,
 			self.url(),
@@ -183,22 +231,28 @@ impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
 		.await
 		.map_err(FlattenError::Expand)?;
 
+		let metrics = options.metrics.clone();
+		let flattening_timer = Timer::start(metrics.as_deref(), Phase::Flattening);
 		let flattened_output =
 			Flatten::flatten_with(expanded_input, vocabulary, generator, options.ordered)
 				.map_err(FlattenError::ConflictingIndexes)?;
+		drop(flattening_timer);
 
 		match context {
-			Some(context) => compact_expanded_full(
-				&flattened_output,
-				self.url(),
-				vocabulary,
-				context,
-				loader,
-				options,
-				warnings,
-			)
-			.await
-			.map_err(FlattenError::Compact),
+			Some(context) => {
+				let _timer = Timer::start(metrics.as_deref(), Phase::Compaction);
+				compact_expanded_full(
+					&flattened_output,
+					self.url(),
+					vocabulary,
+					context,
+					loader,
+					options,
+					warnings,
+				)
+				.await
+				.map_err(FlattenError::Compact)
+			}
 			None => Ok(json_ld_syntax::IntoJson::into_json(
 				flattened_output.into_with(vocabulary),
 			)),
@@ -310,3 +364,29 @@ impl<I> JsonLdProcessor<I> for RemoteDocumentReference<I, json_syntax::Value> {
 		.await
 	}
 }
+
+/// Expands both `a` and `b` with the same options.
+async fn expand_both<I, N>(
+	a: &RemoteDocument<I>,
+	b: &RemoteDocument<I>,
+	vocabulary: &mut N,
+	loader: &impl Loader,
+	options: Options<I>,
+	mut warnings: impl context_processing::WarningHandler<N> + expansion::WarningHandler<N>,
+) -> Result<
+	(
+		ExpandedDocument<I, N::BlankId>,
+		ExpandedDocument<I, N::BlankId>,
+	),
+	ExpandError,
+>
+where
+	N: VocabularyMut<Iri = I>,
+	I: Clone + Eq + Hash,
+	N::BlankId: Clone + Eq + Hash,
+{
+	let a =
+		JsonLdProcessor::expand_full(a, vocabulary, loader, options.clone(), &mut warnings).await?;
+	let b = JsonLdProcessor::expand_full(b, vocabulary, loader, options, &mut warnings).await?;
+	Ok((a, b))
+}
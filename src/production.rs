@@ -0,0 +1,306 @@
+//! Ready-made processor for production services.
+//!
+//! The [`JsonLdProcessor`] trait is generic over the vocabulary, loader,
+//! generator and warning handler, and comes with many knobs. [`Processor`]
+//! wraps it with a fixed configuration that is suitable for services
+//! processing untrusted documents, and only exposes functions working on
+//! `&str` inputs.
+use crate::{
+	expansion,
+	loader::{ChainLoader, HttpCache, Restrictions, StaticLoader},
+	CompactError, ExpandError, FlattenError, JsonLdProcessor, LoadError, Loader, Options,
+	RemoteContextReference, RemoteDocument, ReqwestLoader,
+};
+use iref::{Iri, IriBuf};
+use json_syntax::Parse;
+use rdf_types::{generator, BlankIdBuf, RdfDisplay};
+use std::sync::Arc;
+
+/// Hosts from which remote contexts are allowed to be loaded (over HTTPS)
+/// by [`Processor::default_production`].
+pub const DEFAULT_ALLOWED_HOSTS: &[&str] = &["www.w3.org", "w3id.org"];
+
+/// W3C contexts loaded by [`Processor::preload_defaults`].
+pub const DEFAULT_PRELOADED_CONTEXTS: &[&str] = &[
+	"https://www.w3.org/2018/credentials/v1",
+	"https://www.w3.org/ns/credentials/v2",
+	"https://www.w3.org/ns/did/v1",
+	"https://www.w3.org/ns/activitystreams",
+	"https://w3id.org/security/v1",
+	"https://w3id.org/security/v2",
+	"https://w3id.org/security/data-integrity/v2",
+	"https://w3id.org/security/multikey/v1",
+	"https://w3id.org/security/suites/ed25519-2020/v1",
+];
+
+/// Maximum length (in bytes) of string literal values accepted by
+/// [`Processor::default_production`].
+pub const DEFAULT_MAX_LITERAL_LENGTH: usize = 1024 * 1024;
+
+/// Maximum size (in bytes) of the remote contexts loaded by
+/// [`Processor::default_production`].
+pub const DEFAULT_MAX_CONTEXT_SIZE: usize = 4 * 1024 * 1024;
+
+/// Error returned by the [`Processor`] functions.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("JSON parse error: {0}")]
+	Parse(json_syntax::parse::Error),
+
+	#[error(transparent)]
+	Expand(ExpandError),
+
+	#[error(transparent)]
+	Compact(CompactError),
+
+	#[error(transparent)]
+	Flatten(FlattenError<IriBuf, BlankIdBuf>),
+
+	#[error(transparent)]
+	ToRdf(crate::ToRdfError),
+}
+
+impl Error {
+	/// Returns the code of this error.
+	pub fn code(&self) -> crate::syntax::ErrorCode {
+		match self {
+			Self::Parse(_) => crate::syntax::ErrorCode::LoadingDocumentFailed,
+			Self::Expand(e) => e.code(),
+			Self::Compact(e) => e.code(),
+			Self::Flatten(e) => e.code(),
+			Self::ToRdf(e) => e.code(),
+		}
+	}
+}
+
+/// JSON-LD processor with a fixed configuration.
+///
+/// Build one with [`Processor::default_production`] (or
+/// [`Processor::builder`] to change the allowed hosts) and share it between
+/// requests.
+pub struct Processor {
+	preloaded: StaticLoader,
+	remote: ReqwestLoader,
+	options: Options,
+}
+
+/// [`Processor`] builder, starting from the production defaults.
+pub struct Builder {
+	preloaded: StaticLoader,
+	restrictions: Restrictions,
+	cache: Arc<HttpCache>,
+}
+
+impl Default for Builder {
+	fn default() -> Self {
+		let mut restrictions = Restrictions::new();
+		restrictions.deny_scheme("http");
+		for host in DEFAULT_ALLOWED_HOSTS {
+			restrictions.allow_host(host)
+		}
+
+		Self {
+			preloaded: StaticLoader::new(),
+			restrictions,
+			cache: Arc::new(HttpCache::new()),
+		}
+	}
+}
+
+impl Builder {
+	/// Creates a builder with the production defaults (see
+	/// [`Processor::default_production`]).
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Allows remote contexts to be loaded from the given host.
+	pub fn allow_host(mut self, host: &str) -> Self {
+		self.restrictions.allow_host(host);
+		self
+	}
+
+	/// Replaces the restrictions on the URLs of the remote contexts, which
+	/// default to HTTPS URLs on the [`DEFAULT_ALLOWED_HOSTS`].
+	pub fn restrictions(mut self, restrictions: Restrictions) -> Self {
+		self.restrictions = restrictions;
+		self
+	}
+
+	/// Sets the cache of the remote contexts, for instance to share it
+	/// between processors.
+	pub fn cache(mut self, cache: Arc<HttpCache>) -> Self {
+		self.cache = cache;
+		self
+	}
+
+	/// Preloads a context, so that it is never fetched from the network.
+	///
+	/// Preloaded contexts are not subject to the allowed hosts.
+	pub fn preload(mut self, iri: IriBuf, context: json_syntax::Value) -> Self {
+		self.preloaded.insert(iri, context);
+		self
+	}
+
+	/// Builds the processor.
+	///
+	/// # Panics
+	///
+	/// Panics if the HTTP client cannot be initialized, like
+	/// `reqwest::Client::new`.
+	pub fn build(self) -> Processor {
+		let remote = ReqwestLoader::builder()
+			.max_response_bytes(DEFAULT_MAX_CONTEXT_SIZE)
+			.cache(self.cache)
+			.restrictions(self.restrictions)
+			.build()
+			.expect("HTTP client initialization failed");
+
+		Processor {
+			preloaded: self.preloaded,
+			remote,
+			options: Options {
+				expansion_policy: expansion::Policy::strict_vc(),
+				max_literal_length: Some(DEFAULT_MAX_LITERAL_LENGTH),
+				prefetch_contexts: Some(4),
+				reject_non_json_ld: true,
+				..Options::default()
+			},
+		}
+	}
+}
+
+impl Processor {
+	/// Creates a processor with production defaults:
+	///   - remote contexts are loaded with a [`ReqwestLoader`], only over
+	///     HTTPS from the [`DEFAULT_ALLOWED_HOSTS`] (W3C contexts), HTTP
+	///     redirections and resolved addresses included, limited
+	///     to [`DEFAULT_MAX_CONTEXT_SIZE`] bytes, and kept in an
+	///     [`HttpCache`] following the caching headers of the responses;
+	///   - terms are expanded with the [`strict_vc`](expansion::Policy::strict_vc)
+	///     policy, and documents that are probably not JSON-LD are rejected;
+	///   - literals are limited to [`DEFAULT_MAX_LITERAL_LENGTH`] bytes, and
+	///     remote contexts are prefetched four at a time.
+	///
+	/// Call [`Self::preload_defaults`] once at startup to also preload the
+	/// usual W3C contexts.
+	///
+	/// # Panics
+	///
+	/// Panics if the HTTP client cannot be initialized, like
+	/// `reqwest::Client::new`.
+	pub fn default_production() -> Self {
+		Self::builder().build()
+	}
+
+	/// Returns a builder starting from the production defaults, to allow
+	/// other hosts or preload contexts before building the HTTP client once.
+	pub fn builder() -> Builder {
+		Builder::new()
+	}
+
+	/// Preloads a context, so that it is never fetched from the network.
+	///
+	/// Preloaded contexts are not subject to the allowed hosts.
+	pub fn preload(mut self, iri: IriBuf, context: json_syntax::Value) -> Self {
+		self.preloaded.insert(iri, context);
+		self
+	}
+
+	/// Preloads the [`DEFAULT_PRELOADED_CONTEXTS`], fetching them once so
+	/// that they are never fetched again.
+	pub async fn preload_defaults(mut self) -> Result<Self, LoadError> {
+		for iri in DEFAULT_PRELOADED_CONTEXTS {
+			let iri = Iri::new(*iri).unwrap();
+			if !self.preloaded.contains(iri) {
+				let document = self.remote.load(iri).await?;
+				self.preloaded
+					.insert(iri.to_owned(), document.into_document());
+			}
+		}
+
+		Ok(self)
+	}
+
+	/// Returns the loader used to load remote contexts.
+	fn loader(&self) -> ChainLoader<&StaticLoader, &ReqwestLoader> {
+		ChainLoader::new(&self.preloaded, &self.remote)
+	}
+
+	/// Returns the processing options.
+	pub fn options(&self) -> &Options {
+		&self.options
+	}
+
+	/// Expands the given JSON-LD document.
+	pub async fn expand(&self, input: &str) -> Result<json_syntax::Value, Error> {
+		use json_ld_syntax::IntoJsonWithContext;
+		let document = parse(input)?;
+		let expanded = document
+			.expand_using(&self.loader(), self.options.clone())
+			.await
+			.map_err(Error::Expand)?;
+
+		Ok(expanded.into_objects().into_json_with(&()))
+	}
+
+	/// Compacts the given JSON-LD document with the context found at the
+	/// given IRI.
+	pub async fn compact(&self, input: &str, context: &Iri) -> Result<json_syntax::Value, Error> {
+		let document = parse(input)?;
+		document
+			.compact_using(
+				RemoteContextReference::iri(context.to_owned()),
+				&self.loader(),
+				self.options.clone(),
+			)
+			.await
+			.map_err(Error::Compact)
+	}
+
+	/// Flattens the given JSON-LD document.
+	pub async fn flatten(&self, input: &str) -> Result<json_syntax::Value, Error> {
+		let document = parse(input)?;
+		let mut generator = generator::Blank::new();
+		document
+			.flatten_using(&mut generator, &self.loader(), self.options.clone())
+			.await
+			.map_err(Error::Flatten)
+	}
+
+	/// Serializes the given JSON-LD document into N-Quads.
+	pub async fn to_rdf(&self, input: &str) -> Result<String, Error> {
+		let document = parse(input)?;
+		let mut rdf = document
+			.to_rdf_using(
+				generator::Blank::new(),
+				&self.loader(),
+				self.options.clone(),
+			)
+			.await
+			.map_err(Error::ToRdf)?;
+
+		let mut result = String::new();
+		for rdf_types::Quad(s, p, o, g) in rdf.cloned_quads() {
+			result.push_str(&format!(
+				"{} {} {}",
+				s.rdf_display(),
+				p.rdf_display(),
+				o.rdf_display()
+			));
+
+			if let Some(g) = g {
+				result.push_str(&format!(" {}", g.rdf_display()));
+			}
+
+			result.push_str(" .\n")
+		}
+
+		Ok(result)
+	}
+}
+
+fn parse(input: &str) -> Result<RemoteDocument, Error> {
+	let (json, _) = json_syntax::Value::parse_str(input).map_err(Error::Parse)?;
+	Ok(RemoteDocument::new(None, None, json))
+}
@@ -0,0 +1,198 @@
+use crate::{ExpandError, JsonLdProcessor, Loader, RemoteDocument};
+use iref::{Iri, IriBuf, IriRef};
+
+/// How [`rewrite_context`] combines the new context with the `@context`
+/// entries of the document.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum ContextRewrite {
+	/// The new context replaces the top-level document context.
+	#[default]
+	Replace,
+
+	/// The new context is appended after the top-level document context, so
+	/// that its definitions take precedence.
+	Append,
+
+	/// The new context replaces every reference to the given remote context,
+	/// wherever it appears in the document: in the top-level context, in the
+	/// contexts of nested node objects, in scoped contexts and in `@import`
+	/// entries (the latter only if the new context is itself a reference).
+	///
+	/// Relative references are resolved against the document URL.
+	ReplaceRemote(IriBuf),
+}
+
+/// Error that can be raised by [`rewrite_context`].
+#[derive(Debug, thiserror::Error)]
+pub enum RewriteError {
+	/// The document is not a JSON object with at most one `@context` entry.
+	#[error("document is not a JSON object with at most one `@context` entry")]
+	InvalidDocument,
+
+	/// One of the documents could not be expanded.
+	#[error(transparent)]
+	Expand(#[from] ExpandError),
+
+	/// The document does not have the same meaning with the new context.
+	#[error("the new context changes the meaning of the document")]
+	SemanticsChanged,
+}
+
+/// Replaces or augments the `@context` entries of a compacted document.
+///
+/// The rewritten document is expanded and compared with the original one,
+/// and is only returned if both are equivalent, meaning that the new
+/// context defines every term used by the document the same way. This is
+/// useful to migrate published documents to a newly hosted context URL.
+///
+/// ```
+/// use json_ld::{rewrite_context, syntax::Parse, ContextRewrite, RemoteDocument};
+///
+/// # #[async_std::main]
+/// # async fn main() {
+/// let (json, _) = json_ld::syntax::Value::parse_str(r#"{
+///   "@context": { "name": "http://xmlns.com/foaf/0.1/name" },
+///   "name": "Alice"
+/// }"#).unwrap();
+///
+/// let (context, _) = json_ld::syntax::Value::parse_str(r#"{
+///   "@vocab": "http://xmlns.com/foaf/0.1/"
+/// }"#).unwrap();
+///
+/// let rewritten = rewrite_context(
+///   &RemoteDocument::new(None, None, json),
+///   context,
+///   ContextRewrite::Replace,
+///   &json_ld::NoLoader
+/// ).await.unwrap();
+///
+/// assert!(rewritten.as_object().unwrap().get_unique("name").unwrap().is_some())
+/// # }
+/// ```
+pub async fn rewrite_context(
+	document: &RemoteDocument,
+	new_context: json_syntax::Value,
+	mode: ContextRewrite,
+	loader: &impl Loader,
+) -> Result<json_syntax::Value, RewriteError> {
+	let mut rewritten = document.document().clone();
+
+	match mode {
+		ContextRewrite::Replace | ContextRewrite::Append => {
+			let object = rewritten
+				.as_object_mut()
+				.ok_or(RewriteError::InvalidDocument)?;
+			rewrite_top_level(object, new_context, mode == ContextRewrite::Append)?
+		}
+		ContextRewrite::ReplaceRemote(old_context) => replace_remote(
+			&mut rewritten,
+			document.url().map(IriBuf::as_iri),
+			&old_context,
+			&new_context,
+		),
+	}
+
+	let rewritten_document = RemoteDocument::new_full(
+		document.url().cloned(),
+		document.content_type().cloned(),
+		document.context_url().cloned(),
+		document.profile.clone(),
+		rewritten,
+	);
+
+	if document.compare(&rewritten_document, loader).await? {
+		Ok(rewritten_document.into_document())
+	} else {
+		Err(RewriteError::SemanticsChanged)
+	}
+}
+
+/// Replaces or augments the top-level `@context` entry of a document.
+fn rewrite_top_level(
+	object: &mut json_syntax::Object,
+	new_context: json_syntax::Value,
+	append: bool,
+) -> Result<(), RewriteError> {
+	match object
+		.get_unique_mut("@context")
+		.map_err(|_| RewriteError::InvalidDocument)?
+	{
+		Some(context) if append => match context {
+			json_syntax::Value::Array(items) => items.push(new_context),
+			context => {
+				let old_context = std::mem::replace(context, json_syntax::Value::Null);
+				*context = json_syntax::Value::Array(vec![old_context, new_context])
+			}
+		},
+		Some(context) => *context = new_context,
+		None => {
+			object.insert("@context".into(), new_context);
+		}
+	}
+
+	Ok(())
+}
+
+/// Replaces every reference to `old_context` found in the `@context` and
+/// `@import` entries of the given value and its descendants.
+fn replace_remote(
+	value: &mut json_syntax::Value,
+	base: Option<&Iri>,
+	old_context: &Iri,
+	new_context: &json_syntax::Value,
+) {
+	match value {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				replace_remote(item, base, old_context, new_context)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			for (key, entry) in object.iter_mut() {
+				match key.as_str() {
+					"@context" => replace_reference(entry, base, old_context, new_context, true),
+					"@import" if new_context.is_string() => {
+						replace_reference(entry, base, old_context, new_context, false)
+					}
+					_ => (),
+				}
+
+				replace_remote(entry, base, old_context, new_context)
+			}
+		}
+		_ => (),
+	}
+}
+
+/// Replaces the given context reference with `new_context` if it refers to
+/// `old_context`, looking into arrays of contexts if `array` is `true`.
+fn replace_reference(
+	value: &mut json_syntax::Value,
+	base: Option<&Iri>,
+	old_context: &Iri,
+	new_context: &json_syntax::Value,
+	array: bool,
+) {
+	match value {
+		json_syntax::Value::String(reference) => {
+			if resolve(base, reference).as_deref() == Some(old_context) {
+				*value = new_context.clone()
+			}
+		}
+		json_syntax::Value::Array(items) if array => {
+			for item in items {
+				replace_reference(item, base, old_context, new_context, false)
+			}
+		}
+		_ => (),
+	}
+}
+
+/// Resolves a context reference against the document URL.
+fn resolve(base: Option<&Iri>, reference: &str) -> Option<IriBuf> {
+	let iri_ref = IriRef::new(reference).ok()?;
+	match iri_ref.as_iri() {
+		Some(iri) => Some(iri.to_owned()),
+		None => Some(iri_ref.resolved(base?)),
+	}
+}
@@ -0,0 +1,128 @@
+//! Convenience layer for users working with plain IRIs.
+//!
+//! Most of the API is generic over the vocabulary used to represent IRIs and
+//! blank node identifiers, which forces users to learn about
+//! `vocabulary::no_vocabulary_mut()` even when they never use an indexed
+//! vocabulary. The types of this module fix the representation to [`IriBuf`]
+//! and [`BlankIdBuf`].
+use crate::{
+	rdf, CompactResult, CompareResult, ExpandResult, ExpandedDocument, FlattenResult,
+	JsonLdProcessor, Loader, NoLoader, Options, RemoteContextReference, RemoteDocument, ToRdfError,
+};
+use iref::IriBuf;
+use rdf_types::{generator, BlankIdBuf, Literal};
+
+/// Expanded document using [`IriBuf`] and [`BlankIdBuf`].
+pub type SimpleExpandedDocument = ExpandedDocument<IriBuf, BlankIdBuf>;
+
+/// RDF quad using [`IriBuf`] and [`BlankIdBuf`].
+pub type SimpleQuad = rdf::Quad<IriBuf, BlankIdBuf, Literal>;
+
+/// JSON-LD processor using [`IriBuf`] and [`BlankIdBuf`].
+///
+/// ```
+/// use json_ld::{syntax::Parse, RemoteDocument, SimpleProcessor};
+///
+/// # #[async_std::main]
+/// # async fn main() {
+/// let (json, _) = json_ld::syntax::Value::parse_str(
+///   r#"{ "http://xmlns.com/foaf/0.1/name": "Alice" }"#
+/// ).unwrap();
+///
+/// let processor = SimpleProcessor::new();
+/// let expanded = processor
+///   .expand(&RemoteDocument::new(None, None, json))
+///   .await
+///   .unwrap();
+///
+/// assert_eq!(expanded.len(), 1)
+/// # }
+/// ```
+pub struct SimpleProcessor<L = NoLoader> {
+	loader: L,
+	options: Options,
+}
+
+impl SimpleProcessor {
+	/// Creates a processor that cannot load remote documents.
+	pub fn new() -> Self {
+		Self::with_loader(NoLoader)
+	}
+}
+
+impl Default for SimpleProcessor {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<L: Loader> SimpleProcessor<L> {
+	/// Creates a processor using the given loader to load remote documents.
+	pub fn with_loader(loader: L) -> Self {
+		Self {
+			loader,
+			options: Options::default(),
+		}
+	}
+
+	/// Sets the processing options.
+	pub fn with_options(self, options: Options) -> Self {
+		Self { options, ..self }
+	}
+
+	/// Returns the loader.
+	pub fn loader(&self) -> &L {
+		&self.loader
+	}
+
+	/// Returns the processing options.
+	pub fn options(&self) -> &Options {
+		&self.options
+	}
+
+	/// Expands the given document.
+	pub async fn expand(&self, document: &RemoteDocument) -> ExpandResult<IriBuf, BlankIdBuf> {
+		document
+			.expand_using(&self.loader, self.options.clone())
+			.await
+	}
+
+	/// Compacts the given document with the given context.
+	pub async fn compact(
+		&self,
+		document: &RemoteDocument,
+		context: RemoteContextReference,
+	) -> CompactResult {
+		document
+			.compact_using(context, &self.loader, self.options.clone())
+			.await
+	}
+
+	/// Flattens the given document.
+	///
+	/// Anonymous nodes are given fresh blank node identifiers `_:0`, `_:1`,
+	/// etc.
+	pub async fn flatten(&self, document: &RemoteDocument) -> FlattenResult<IriBuf, BlankIdBuf> {
+		let mut generator = generator::Blank::new();
+		document
+			.flatten_using(&mut generator, &self.loader, self.options.clone())
+			.await
+	}
+
+	/// Serializes the given document into RDF quads.
+	///
+	/// Anonymous nodes are given fresh blank node identifiers `_:0`, `_:1`,
+	/// etc.
+	pub async fn to_rdf(&self, document: &RemoteDocument) -> Result<Vec<SimpleQuad>, ToRdfError> {
+		let mut rdf = document
+			.to_rdf_using(generator::Blank::new(), &self.loader, self.options.clone())
+			.await?;
+
+		Ok(rdf.cloned_quads().collect())
+	}
+
+	/// Checks that the given documents are equivalent once expanded.
+	pub async fn compare(&self, a: &RemoteDocument, b: &RemoteDocument) -> CompareResult {
+		a.compare_using(b, &self.loader, self.options.clone()).await
+	}
+}
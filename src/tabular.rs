@@ -0,0 +1,338 @@
+//! Extraction of tabular data from expanded documents.
+//!
+//! An [`Extractor`] describes a table: each row is a node of the document,
+//! and each column gives the value found by following a property path from
+//! this node.
+//!
+//! ```
+//! use json_ld::{syntax::Parse, tabular::Extractor, Id, JsonLdProcessor, RemoteDocument};
+//! use static_iref::iri;
+//!
+//! # #[async_std::main]
+//! # async fn main() {
+//! let (json, _) = json_ld::syntax::Value::parse_str(r#"{
+//!   "@context": { "@vocab": "http://schema.org/" },
+//!   "@type": "Person",
+//!   "name": "Alice",
+//!   "address": { "addressLocality": "Paris" }
+//! }"#).unwrap();
+//!
+//! let expanded = RemoteDocument::new(None, None, json)
+//!   .expand(&json_ld::NoLoader)
+//!   .await
+//!   .unwrap();
+//!
+//! let prop = |iri: &json_ld::Iri| Id::iri(iri.to_owned());
+//! let table = Extractor::new()
+//!   .with_type(prop(iri!("http://schema.org/Person")))
+//!   .column("name", vec![prop(iri!("http://schema.org/name"))])
+//!   .column("city", vec![
+//!     prop(iri!("http://schema.org/address")),
+//!     prop(iri!("http://schema.org/addressLocality"))
+//!   ])
+//!   .extract(&expanded);
+//!
+//! assert_eq!(table.len(), 1);
+//! assert_eq!(table.column("city").unwrap()[0].as_ref().unwrap().to_text_with(&()), "Paris");
+//!
+//! let mut csv = Vec::new();
+//! table.write_csv_with(&(), &mut csv).unwrap();
+//! assert_eq!(csv, b"name,city\r\nAlice,Paris\r\n")
+//! # }
+//! ```
+use crate::object::{Literal, Ref};
+use crate::{ExpandedDocument, Id, Node, Object, Value};
+use contextual::WithContext;
+use hashbrown::HashMap;
+use iref::IriBuf;
+use json_syntax::Print;
+use rdf_types::{BlankIdBuf, Vocabulary};
+use std::hash::Hash;
+
+/// Table column.
+#[derive(Debug, Clone)]
+pub struct Column<T = IriBuf, B = BlankIdBuf> {
+	/// Name of the column.
+	pub name: String,
+
+	/// Properties followed from the row node to the cell value.
+	///
+	/// An empty path gives the identifier of the row node.
+	pub path: Vec<Id<T, B>>,
+}
+
+/// Table description.
+#[derive(Debug, Clone)]
+pub struct Extractor<T = IriBuf, B = BlankIdBuf> {
+	ty: Option<Id<T, B>>,
+	columns: Vec<Column<T, B>>,
+}
+
+impl<T, B> Default for Extractor<T, B> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T, B> Extractor<T, B> {
+	/// Creates a table description with no columns, whose rows are the top
+	/// level node objects.
+	pub fn new() -> Self {
+		Self {
+			ty: None,
+			columns: Vec::new(),
+		}
+	}
+
+	/// Only makes rows out of the nodes having the given type.
+	///
+	/// The nodes are then searched at any depth in the document, not only
+	/// at the top level.
+	pub fn with_type(self, ty: Id<T, B>) -> Self {
+		Self {
+			ty: Some(ty),
+			..self
+		}
+	}
+
+	/// Adds a column.
+	pub fn column(mut self, name: impl Into<String>, path: Vec<Id<T, B>>) -> Self {
+		self.columns.push(Column {
+			name: name.into(),
+			path,
+		});
+		self
+	}
+
+	/// Returns the columns.
+	pub fn columns(&self) -> &[Column<T, B>] {
+		&self.columns
+	}
+
+	/// Extracts the table from the given document.
+	///
+	/// When following a path, node references are resolved against the
+	/// node objects of the document having the same identifier. If a path
+	/// leads to multiple values, the first one (in document order) is taken.
+	/// If it leads to none, the cell is empty.
+	pub fn extract(&self, document: &ExpandedDocument<T, B>) -> Table<T, B>
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		let mut nodes: HashMap<&Id<T, B>, Vec<&Node<T, B>>> = HashMap::new();
+		let mut rows = Vec::new();
+		for fragment in document.traverse() {
+			if let Some(Ref::Node(node)) = fragment.into_ref() {
+				if let Some(id) = &node.id {
+					nodes.entry(id).or_default().push(node)
+				}
+
+				if let Some(ty) = &self.ty {
+					if node.has_type(ty) {
+						rows.push(node)
+					}
+				}
+			}
+		}
+
+		if self.ty.is_none() {
+			rows = document
+				.iter()
+				.filter_map(|object| object.inner().as_node())
+				.collect();
+		}
+
+		let columns = self
+			.columns
+			.iter()
+			.map(|column| {
+				rows.iter()
+					.map(|node| follow(&nodes, node, &column.path))
+					.collect()
+			})
+			.collect();
+
+		Table {
+			len: rows.len(),
+			names: self.columns.iter().map(|c| c.name.clone()).collect(),
+			columns,
+		}
+	}
+}
+
+/// Follows `path` from `node` and returns the first value found.
+fn follow<T: Clone + Eq + Hash, B: Clone + Eq + Hash>(
+	nodes: &HashMap<&Id<T, B>, Vec<&Node<T, B>>>,
+	node: &Node<T, B>,
+	path: &[Id<T, B>],
+) -> Option<Cell<T, B>> {
+	match path.split_first() {
+		None => node.id.clone().map(Cell::Id),
+		Some((prop, rest)) => {
+			// The node may only be a reference to a node described elsewhere.
+			let described = node.id.as_ref().and_then(|id| nodes.get(id));
+			let candidates = std::iter::once(node).chain(described.into_iter().flatten().copied());
+
+			for candidate in candidates {
+				for object in candidate.get(prop) {
+					let cell = match object.inner() {
+						Object::Node(next) => follow(nodes, next, rest),
+						Object::Value(value) if rest.is_empty() => Some(Cell::Value(value.clone())),
+						_ => None,
+					};
+
+					if cell.is_some() {
+						return cell;
+					}
+				}
+			}
+
+			None
+		}
+	}
+}
+
+/// Table cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell<T = IriBuf, B = BlankIdBuf> {
+	/// Node identifier.
+	Id(Id<T, B>),
+
+	/// Value.
+	Value(Value<T>),
+}
+
+impl<T, B> Cell<T, B> {
+	/// Returns a textual representation of the cell.
+	///
+	/// Identifiers are written as IRIs or blank node identifiers, strings
+	/// without their language or datatype, and JSON literals in compact
+	/// form.
+	pub fn to_text_with<V: Vocabulary<Iri = T, BlankId = B>>(&self, vocabulary: &V) -> String {
+		match self {
+			Self::Id(id) => id.with(vocabulary).to_string(),
+			Self::Value(Value::Literal(lit, _)) => match lit {
+				Literal::Null => "null".to_string(),
+				Literal::Boolean(b) => b.to_string(),
+				Literal::Number(n) => n.to_string(),
+				Literal::String(s) => s.as_str().to_string(),
+			},
+			Self::Value(Value::LangString(s)) => s.as_str().to_string(),
+			Self::Value(Value::Json(json)) => json.compact_print().to_string(),
+		}
+	}
+}
+
+/// Extracted table, stored by columns.
+#[derive(Debug, Clone)]
+pub struct Table<T = IriBuf, B = BlankIdBuf> {
+	len: usize,
+	names: Vec<String>,
+	columns: Vec<Vec<Option<Cell<T, B>>>>,
+}
+
+impl<T, B> Table<T, B> {
+	/// Returns the number of rows.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Checks if the table has no rows.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the column names.
+	pub fn names(&self) -> &[String] {
+		&self.names
+	}
+
+	/// Returns the cells of the column with the given name.
+	pub fn column(&self, name: &str) -> Option<&[Option<Cell<T, B>>]> {
+		self.names
+			.iter()
+			.position(|n| n == name)
+			.map(|i| self.columns[i].as_slice())
+	}
+
+	/// Returns an iterator over the columns, with their name.
+	pub fn columns(&self) -> impl Iterator<Item = (&str, &[Option<Cell<T, B>>])> {
+		self.names
+			.iter()
+			.map(String::as_str)
+			.zip(self.columns.iter().map(Vec::as_slice))
+	}
+
+	/// Writes the table as CSV ([RFC 4180]), with a header row made of the
+	/// column names.
+	///
+	/// Cells are converted using [`Cell::to_text_with`], and empty cells are
+	/// written as empty fields. Fields containing a comma, a double quote or
+	/// a line break are quoted.
+	///
+	/// [RFC 4180]: https://www.rfc-editor.org/rfc/rfc4180
+	pub fn write_csv_with<V: Vocabulary<Iri = T, BlankId = B>>(
+		&self,
+		vocabulary: &V,
+		mut writer: impl std::io::Write,
+	) -> std::io::Result<()> {
+		write_csv_record(&mut writer, self.names.iter().map(String::as_str))?;
+
+		for i in 0..self.len {
+			let fields: Vec<_> = self
+				.columns
+				.iter()
+				.map(|cells| {
+					cells[i]
+						.as_ref()
+						.map(|cell| cell.to_text_with(vocabulary))
+						.unwrap_or_default()
+				})
+				.collect();
+			write_csv_record(&mut writer, fields.iter().map(String::as_str))?;
+		}
+
+		Ok(())
+	}
+
+	/// Converts the table into an Arrow record batch, with one string
+	/// column per table column.
+	///
+	/// Cells are converted using [`Cell::to_text_with`].
+	#[cfg(feature = "arrow")]
+	pub fn to_record_batch_with<V: Vocabulary<Iri = T, BlankId = B>>(
+		&self,
+		vocabulary: &V,
+	) -> Result<arrow_array::RecordBatch, arrow_schema::ArrowError> {
+		use std::sync::Arc;
+
+		arrow_array::RecordBatch::try_from_iter(self.columns().map(|(name, cells)| {
+			let array: arrow_array::StringArray = cells
+				.iter()
+				.map(|cell| cell.as_ref().map(|cell| cell.to_text_with(vocabulary)))
+				.collect();
+			(name, Arc::new(array) as arrow_array::ArrayRef)
+		}))
+	}
+}
+
+/// Writes a CSV record, quoting the fields when needed.
+fn write_csv_record<'a>(
+	writer: &mut impl std::io::Write,
+	fields: impl IntoIterator<Item = &'a str>,
+) -> std::io::Result<()> {
+	for (i, field) in fields.into_iter().enumerate() {
+		if i > 0 {
+			writer.write_all(b",")?;
+		}
+
+		if field.contains([',', '"', '\r', '\n']) {
+			write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+		} else {
+			writer.write_all(field.as_bytes())?;
+		}
+	}
+
+	writer.write_all(b"\r\n")
+}
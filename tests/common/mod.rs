@@ -0,0 +1,34 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use json_ld::{syntax::Parse, Iri, IriBuf, RemoteDocument};
+use static_iref::iri;
+use std::collections::HashMap;
+
+/// Returns the IRI of the context served by [`loader_with_context`].
+pub fn context_iri() -> IriBuf {
+	iri!("http://example.org/context.jsonld").to_owned()
+}
+
+/// Parses the given JSON-LD document.
+pub fn document(content: &str) -> RemoteDocument {
+	let (json, _) = json_ld::syntax::Value::parse_str(content).unwrap();
+	RemoteDocument::new(None, None, json)
+}
+
+/// Creates a loader serving the given documents.
+pub fn loader(documents: &[(&Iri, &str)]) -> HashMap<IriBuf, RemoteDocument> {
+	documents
+		.iter()
+		.map(|&(url, content)| {
+			let (json, _) = json_ld::syntax::Value::parse_str(content).unwrap();
+			let url = url.to_owned();
+			(url.clone(), RemoteDocument::new(Some(url), None, json))
+		})
+		.collect()
+}
+
+/// Creates a loader serving the given context document at [`context_iri`].
+pub fn loader_with_context(content: &str) -> HashMap<IriBuf, RemoteDocument> {
+	loader(&[(&*context_iri(), content)])
+}
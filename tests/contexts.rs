@@ -0,0 +1,589 @@
+//! Context processing, loading and rewriting.
+
+mod common;
+
+use json_ld::{
+	syntax::Parse, JsonLdProcessor, RemoteContextReference, RemoteDocument, RemoteDocumentReference,
+};
+use static_iref::iri;
+use std::collections::HashMap;
+
+#[async_std::test]
+async fn upgrade_context_to_1_1() {
+	use json_ld::context_processing::{Error, Options, Process};
+	use json_ld::syntax::context::{definition::Version, Context};
+
+	let (json, _) = json_ld::syntax::Value::parse_str(
+		r#"{
+			"foaf": "http://xmlns.com/foaf/0.1/",
+			"name": "foaf:name"
+		}"#,
+	)
+	.unwrap();
+
+	let mut context = Context::try_from_json_ref(&json).unwrap();
+	context.upgrade_to_1_1();
+
+	let processed = context
+		.process(&mut (), &json_ld::NoLoader, None)
+		.await
+		.unwrap()
+		.into_processed();
+
+	assert_eq!(processed.declared_version(), Some(Version::V1_1));
+	assert!(processed.get("foaf").unwrap().prefix());
+	assert!(!processed.get("name").unwrap().prefix());
+
+	let (json, _) = json_ld::syntax::Value::parse_str(r#"{ "name": "foaf:givenName" }"#).unwrap();
+	let result = Context::try_from_json_ref(&json)
+		.unwrap()
+		.process_with(
+			&mut (),
+			&processed,
+			&json_ld::NoLoader,
+			None,
+			Options::with_defaults_for(json_ld::ProcessingMode::JsonLd1_0),
+		)
+		.await;
+	assert!(matches!(result, Err(Error::ProcessingModeConflict)));
+
+	// `null` resets the active context, including its version.
+	let reset = Context::null()
+		.process_with(
+			&mut (),
+			&processed,
+			&json_ld::NoLoader,
+			None,
+			Options::with_defaults_for(json_ld::ProcessingMode::JsonLd1_0),
+		)
+		.await
+		.unwrap()
+		.into_processed();
+	assert_eq!(reset.declared_version(), None)
+}
+
+/// Expands `document` with the given context.
+async fn expand_with_context(
+	context: json_ld::syntax::Value,
+	document: &str,
+) -> json_ld::ExpandedDocument {
+	let (mut document, _) = json_ld::syntax::Value::parse_str(document).unwrap();
+	document
+		.as_object_mut()
+		.unwrap()
+		.insert("@context".into(), context);
+
+	RemoteDocument::new(None, None, document)
+		.expand(&json_ld::NoLoader)
+		.await
+		.unwrap()
+}
+
+/// Upgrading a context does not change the meaning of the terms, including
+/// the terms redefined by scoped contexts and the compact IRIs using them,
+/// as covered by the prefix and scoped context expansion tests of the W3C
+/// test suite.
+#[async_std::test]
+async fn upgraded_context_expansion() {
+	use json_ld::syntax::{context::Context, IntoJson};
+
+	let (json, _) = json_ld::syntax::Value::parse_str(
+		r#"{
+			"@version": 1.1,
+			"foaf": "http://xmlns.com/foaf/0.1/",
+			"ex": "http://example.org/ns",
+			"e": "foaf:",
+			"name": "foaf:name",
+			"id": "@id",
+			"knows": {
+				"@id": "foaf:knows",
+				"@context": {
+					"foaf": "http://example.org/other#",
+					"e": "foaf:",
+					"name": "foaf:name"
+				}
+			}
+		}"#,
+	)
+	.unwrap();
+
+	let document = r#"{
+		"id": "http://example.org/alice",
+		"name": "Alice",
+		"e:nick": "al",
+		"ex:p": "x",
+		"knows": { "name": "Bob", "e:nick": "bob", "foaf:age": 42 }
+	}"#;
+
+	let mut upgraded = Context::try_from_json_ref(&json).unwrap();
+	upgraded.upgrade_to_1_1();
+	let upgraded = upgraded.into_json();
+	assert_ne!(upgraded, json);
+
+	let expected = expand_with_context(json, document).await;
+	let expanded = expand_with_context(upgraded, document).await;
+	assert_eq!(expanded, expected)
+}
+
+#[async_std::test]
+async fn processed_contexts() {
+	use json_ld::context_processing::Process;
+	use json_ld::loader::WithProcessedContexts;
+	use std::sync::Arc;
+
+	let context_iri = common::context_iri();
+	let (json, _) =
+		json_ld::syntax::Value::parse_str(r#"{ "name": "http://xmlns.com/foaf/0.1/name" }"#)
+			.unwrap();
+
+	let processed = json_ld::syntax::context::Context::try_from_json_ref(&json)
+		.unwrap()
+		.process(&mut (), &json_ld::NoLoader, None)
+		.await
+		.unwrap()
+		.into_processed();
+
+	let mut contexts = HashMap::new();
+	contexts.insert(context_iri.clone(), Arc::new(processed));
+	let loader = WithProcessedContexts::new(contexts, json_ld::NoLoader);
+
+	let expanded =
+		common::document(r#"{ "@context": "http://example.org/context.jsonld", "name": "Alice" }"#)
+			.expand(&loader)
+			.await
+			.unwrap();
+	let node = expanded.objects().iter().next().unwrap().as_node().unwrap();
+	assert!(node.properties().contains(&json_ld::Id::iri(
+		iri!("http://xmlns.com/foaf/0.1/name").to_owned()
+	)));
+
+	// The processed context cannot be used on top of another context.
+	assert!(common::document(
+		r#"{
+			"@context": [{ "@vocab": "http://example.org/" }, "http://example.org/context.jsonld"],
+			"name": "Alice"
+		}"#,
+	)
+	.expand(&loader)
+	.await
+	.is_err())
+}
+
+#[async_std::test]
+async fn processed_contexts_processing_rules() {
+	use json_ld::context_processing::{Error, Options, Process};
+	use json_ld::loader::WithProcessedContexts;
+	use json_ld::syntax::context::Context;
+	use std::sync::Arc;
+
+	let context_iri = iri!("http://example.org/context.jsonld").to_owned();
+	let (json, _) = json_ld::syntax::Value::parse_str(
+		r#"{
+			"@context": {
+				"@version": 1.1,
+				"@propagate": false,
+				"name": "http://xmlns.com/foaf/0.1/name",
+				"knows": "http://xmlns.com/foaf/0.1/knows"
+			}
+		}"#,
+	)
+	.unwrap();
+
+	let processed = Context::try_from_json_ref(
+		json.as_object()
+			.unwrap()
+			.get_unique("@context")
+			.unwrap()
+			.unwrap(),
+	)
+	.unwrap()
+	.process(&mut (), &json_ld::NoLoader, None)
+	.await
+	.unwrap()
+	.into_processed();
+
+	let documents = HashMap::from([(
+		context_iri.clone(),
+		RemoteDocument::new(Some(context_iri.clone()), None, json),
+	)]);
+	let contexts = HashMap::from([(context_iri.clone(), Arc::new(processed))]);
+	let loader = WithProcessedContexts::new(contexts, &documents);
+
+	// The processed context does not propagate to nested nodes, as the
+	// remote context.
+	let document = common::document(
+		r#"{
+			"@context": "http://example.org/context.jsonld",
+			"name": "Alice",
+			"knows": { "name": "Bob" }
+		}"#,
+	);
+	assert_eq!(
+		document.expand(&loader).await.unwrap(),
+		document.expand(&documents).await.unwrap()
+	);
+
+	// The remote context uses `@propagate`, forbidden in `json-ld-1.0` mode.
+	let (json, _) =
+		json_ld::syntax::Value::parse_str(r#""http://example.org/context.jsonld""#).unwrap();
+	let result = Context::try_from_json_ref(&json)
+		.unwrap()
+		.process_with(
+			&mut (),
+			&json_ld::Context::new(None),
+			&loader,
+			None,
+			Options::with_defaults_for(json_ld::ProcessingMode::JsonLd1_0),
+		)
+		.await;
+	assert!(matches!(result, Err(Error::InvalidContextEntry)))
+}
+
+#[async_std::test]
+async fn expand_context_stack() {
+	let loader = common::loader(&[
+		(
+			iri!("http://example.org/base.jsonld"),
+			r#"{ "@context": { "@vocab": "http://example.org/", "name": "http://example.org/label" } }"#,
+		),
+		(
+			iri!("http://example.org/foaf.jsonld"),
+			r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name" } }"#,
+		),
+	]);
+
+	let options: json_ld::Options = json_ld::Options::builder()
+		.expand_contexts(vec![
+			RemoteContextReference::iri(iri!("http://example.org/base.jsonld").to_owned()),
+			RemoteContextReference::iri(iri!("http://example.org/foaf.jsonld").to_owned()),
+		])
+		.build()
+		.unwrap();
+
+	let expanded = common::document(r#"{ "name": "Alice", "age": 42 }"#)
+		.expand_using(&loader, options)
+		.await
+		.unwrap();
+
+	let node = expanded.main_node().unwrap();
+	for prop in [
+		iri!("http://xmlns.com/foaf/0.1/name"),
+		iri!("http://example.org/age"),
+	] {
+		assert!(node.get_any(&json_ld::Id::iri(prop.to_owned())).is_some())
+	}
+}
+
+#[async_std::test]
+async fn term_definition_source() {
+	use json_ld::context_processing::Process;
+	use json_ld::syntax::context::Context;
+
+	let loader = common::loader_with_context(
+		r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name", "knows": "http://xmlns.com/foaf/0.1/knows" } }"#,
+	);
+
+	let (json, _) = json_ld::syntax::Value::parse_str(
+		r#"[
+			"http://example.org/context.jsonld",
+			{ "knows": "http://schema.org/knows" }
+		]"#,
+	)
+	.unwrap();
+
+	let processed = Context::try_from_json_ref(&json)
+		.unwrap()
+		.process(&mut (), &loader, None)
+		.await
+		.unwrap()
+		.into_processed();
+
+	assert_eq!(
+		processed.get("name").unwrap().source(),
+		Some(&common::context_iri())
+	);
+	assert_eq!(processed.get("knows").unwrap().source(), None)
+}
+
+#[async_std::test]
+async fn remote_context_array() {
+	let loader = common::loader(&[
+		(
+			iri!("http://example.org/a.jsonld"),
+			r#"{ "@context": { "name": "http://example.org/name", "knows": "http://example.org/knows" } }"#,
+		),
+		(
+			iri!("http://example.org/b.jsonld"),
+			r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name" } }"#,
+		),
+	]);
+
+	let expanded = common::document(
+		r#"{
+			"@context": ["http://example.org/a.jsonld", "http://example.org/b.jsonld"],
+			"name": "Alice",
+			"knows": "Bob"
+		}"#,
+	)
+	.expand(&loader)
+	.await
+	.unwrap();
+
+	let node = expanded.main_node().unwrap();
+	assert!(node
+		.get_any(&json_ld::Id::iri(
+			iri!("http://xmlns.com/foaf/0.1/name").to_owned()
+		))
+		.is_some());
+	assert!(node
+		.get_any(&json_ld::Id::iri(
+			iri!("http://example.org/knows").to_owned()
+		))
+		.is_some());
+}
+
+#[async_std::test]
+async fn context_loader_option() {
+	use json_ld::loader::{ChainLoader, RetryLoader, RetryOptions, StaticLoader};
+	use std::sync::Arc;
+
+	let mut registry = StaticLoader::new();
+	registry
+		.insert_str(
+			iri!("http://example.org/context.jsonld").to_owned(),
+			r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name" } }"#,
+		)
+		.unwrap();
+
+	let document =
+		common::document(r#"{ "@context": "http://example.org/context.jsonld", "name": "Alice" }"#);
+
+	assert!(document.expand(&json_ld::NoLoader).await.is_err());
+
+	let options = json_ld::Options {
+		context_loader: Some(Arc::new(RetryLoader::new(
+			ChainLoader::new(json_ld::NoLoader, registry),
+			RetryOptions::default(),
+		))),
+		..Default::default()
+	};
+
+	let expanded = document
+		.expand_using(&json_ld::NoLoader, options)
+		.await
+		.unwrap();
+	assert_eq!(expanded.len(), 1)
+}
+
+#[async_std::test]
+async fn pruned_contexts_expansion() {
+	let context_iri = iri!("http://example.org/context.jsonld");
+	let imported_iri = iri!("http://example.org/imported.jsonld");
+
+	let loader = common::loader(&[
+		(
+			context_iri,
+			r#"{
+				"@context": {
+					"@version": 1.1,
+					"@import": "http://example.org/imported.jsonld",
+					"@vocab": "http://example.org/vocab/",
+					"name": { "@id": "fullName" },
+					"Person": {
+						"@id": "http://example.org/Person",
+						"@context": { "knows": { "@id": "acquaintance", "@type": "@id" } }
+					},
+					"unused": "http://example.org/unused"
+				}
+			}"#,
+		),
+		(
+			imported_iri,
+			r#"{
+				"@context": {
+					"fullName": "http://example.org/fullName",
+					"acquaintance": "http://example.org/acquaintance",
+					"other": "http://example.org/other"
+				}
+			}"#,
+		),
+	]);
+
+	let input = common::document(
+		r#"{
+			"@context": "http://example.org/context.jsonld",
+			"@type": "Person",
+			"name": "Alice",
+			"knows": "http://example.org/bob",
+			"age": 42
+		}"#,
+	);
+
+	let expected = input.expand(&loader).await.unwrap();
+	let options = json_ld::Options {
+		prune_contexts: true,
+		..Default::default()
+	};
+	let pruned = input.expand_using(&loader, options).await.unwrap();
+	assert_eq!(pruned, expected);
+
+	let pruning = json_ld::PruningLoader::new(&loader, input.document());
+	let context = json_ld::Loader::load(&pruning, context_iri).await.unwrap();
+	let context = context.document().as_object().unwrap();
+	let context = context.get_unique("@context").unwrap().unwrap();
+	assert!(context
+		.as_object()
+		.unwrap()
+		.get_unique("unused")
+		.unwrap()
+		.is_none());
+
+	let imported = json_ld::Loader::load(&pruning, imported_iri).await.unwrap();
+	let imported = imported.document().as_object().unwrap();
+	let imported = imported.get_unique("@context").unwrap().unwrap();
+	let imported = imported.as_object().unwrap();
+	assert!(imported.get_unique("fullName").unwrap().is_some());
+	assert!(imported.get_unique("other").unwrap().is_none())
+}
+
+#[async_std::test]
+async fn context_loaded_metrics() {
+	use json_ld::metrics::Event;
+	use std::sync::{Arc, Mutex};
+
+	let context_iri = iri!("http://example.org/context.jsonld");
+	let document_iri = iri!("http://example.org/document.jsonld");
+	let loader = common::loader(&[
+		(
+			context_iri,
+			r#"{ "@context": { "name": "http://example.org/name" } }"#,
+		),
+		(
+			document_iri,
+			r#"{ "@context": "http://example.org/context.jsonld", "name": "Jane" }"#,
+		),
+	]);
+
+	let loaded = Arc::new(Mutex::new(Vec::new()));
+	let events = loaded.clone();
+	let options = json_ld::Options {
+		prefetch_contexts: Some(2),
+		metrics: Some(Arc::new(move |event: Event| {
+			if let Event::ContextLoaded(iri) = event {
+				events.lock().unwrap().push(iri.to_owned())
+			}
+		})),
+		..Default::default()
+	};
+
+	RemoteDocumentReference::iri(document_iri.to_owned())
+		.expand_using(&loader, options)
+		.await
+		.unwrap();
+	assert_eq!(*loaded.lock().unwrap(), [context_iri.to_owned()])
+}
+
+#[async_std::test]
+async fn remote_contexts_requested_with_context_profile() {
+	use json_ld::{Loader, LoadingResult, Profile, StandardProfile};
+	use std::sync::Mutex;
+
+	/// Loader recording the profiles each document is requested with.
+	struct Recording {
+		documents: HashMap<iref::IriBuf, RemoteDocument>,
+		requests: Mutex<Vec<(iref::IriBuf, Vec<Profile>)>>,
+	}
+
+	impl Loader for Recording {
+		async fn load(&self, url: &iref::Iri) -> LoadingResult {
+			self.load_with_profile(url, &[]).await
+		}
+
+		async fn load_with_profile(&self, url: &iref::Iri, profile: &[Profile]) -> LoadingResult {
+			self.requests
+				.lock()
+				.unwrap()
+				.push((url.to_owned(), profile.to_vec()));
+			self.documents.load(url).await
+		}
+	}
+
+	let context_iri = iri!("http://example.org/context.jsonld");
+	let import_iri = iri!("http://example.org/import.jsonld");
+	let loader = Recording {
+		documents: common::loader(&[
+			(
+				context_iri,
+				r#"{ "@context": { "@version": 1.1, "@import": "import.jsonld" } }"#,
+			),
+			(
+				import_iri,
+				r#"{ "@context": { "name": "http://example.org/name" } }"#,
+			),
+		]),
+		requests: Mutex::new(Vec::new()),
+	};
+
+	common::document(r#"{ "@context": "http://example.org/context.jsonld", "name": "Jane" }"#)
+		.expand(&loader)
+		.await
+		.unwrap();
+
+	let context_profile = vec![Profile::Standard(StandardProfile::Context)];
+	assert_eq!(
+		*loader.requests.lock().unwrap(),
+		[
+			(context_iri.to_owned(), context_profile.clone()),
+			(import_iri.to_owned(), context_profile)
+		]
+	)
+}
+
+#[async_std::test]
+async fn rewrite_nested_remote_contexts() {
+	use json_ld::{rewrite_context, ContextRewrite};
+
+	let old_iri = iri!("http://example.org/old.jsonld");
+	let new_iri = iri!("http://example.org/new.jsonld");
+	let context = r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name", "knows": "http://xmlns.com/foaf/0.1/knows" } }"#;
+	let loader = common::loader(&[(old_iri, context), (new_iri, context)]);
+
+	let (input, _) = json_ld::syntax::Value::parse_str(
+		r#"{
+			"@context": "old.jsonld",
+			"name": "Alice",
+			"knows": {
+				"@context": ["http://example.org/old.jsonld", { "@vocab": "http://example.org/" }],
+				"name": "Bob"
+			}
+		}"#,
+	)
+	.unwrap();
+	let document = RemoteDocument::new(
+		Some(iri!("http://example.org/alice.jsonld").to_owned()),
+		None,
+		input,
+	);
+
+	let rewritten = rewrite_context(
+		&document,
+		json_ld::syntax::Value::String(new_iri.as_str().into()),
+		ContextRewrite::ReplaceRemote(old_iri.to_owned()),
+		&loader,
+	)
+	.await
+	.unwrap();
+
+	let (expected, _) = json_ld::syntax::Value::parse_str(
+		r#"{
+			"@context": "http://example.org/new.jsonld",
+			"name": "Alice",
+			"knows": {
+				"@context": ["http://example.org/new.jsonld", { "@vocab": "http://example.org/" }],
+				"name": "Bob"
+			}
+		}"#,
+	)
+	.unwrap();
+	assert_eq!(rewritten, expected)
+}
@@ -0,0 +1,85 @@
+//! Default values of terms, declared with the `@default` extension entry.
+
+mod common;
+
+use json_ld::{JsonLdProcessor, RemoteContextReference, RemoteDocument};
+use static_iref::iri;
+
+#[async_std::test]
+async fn context_defaults() {
+	let context_iri = common::context_iri();
+	let loader = common::loader_with_context(
+		r#"{
+			"@context": {
+				"@vocab": "http://example.org/",
+				"level": { "@id": "http://example.org/level", "@default": "info" },
+				"message": "http://example.org/message"
+			}
+		}"#,
+	);
+
+	let options = json_ld::Options {
+		omit_defaults: true,
+		materialize_defaults: true,
+		..Default::default()
+	};
+
+	let compacted = common::document(
+		r#"[
+			{
+				"http://example.org/level": [{ "@value": "info" }],
+				"http://example.org/message": "started"
+			},
+			{
+				"http://example.org/level": "error",
+				"http://example.org/message": "failed"
+			}
+		]"#,
+	)
+	.compact_using(
+		RemoteContextReference::iri(context_iri.clone()),
+		&loader,
+		options.clone(),
+	)
+	.await
+	.unwrap();
+
+	let graph = compacted
+		.as_object()
+		.unwrap()
+		.get_unique("@graph")
+		.ok()
+		.unwrap()
+		.unwrap()
+		.as_array()
+		.unwrap();
+	let levels: Vec<_> = graph
+		.iter()
+		.map(|node| {
+			node.as_object()
+				.unwrap()
+				.get_unique("level")
+				.ok()
+				.unwrap()
+				.and_then(|v| v.as_str())
+		})
+		.collect();
+	assert_eq!(levels, [None, Some("error")]);
+
+	let expanded = RemoteDocument::new(None, None, compacted.clone())
+		.expand_using(&loader, options)
+		.await
+		.unwrap();
+
+	let level = json_ld::Id::iri(iri!("http://example.org/level").to_owned());
+	assert_eq!(expanded.len(), 2);
+	for node in &expanded {
+		assert_eq!(node.as_node().unwrap().get(&level).count(), 1)
+	}
+
+	// `@default` is an invalid term definition unless enabled.
+	assert!(RemoteDocument::new(None, None, compacted)
+		.expand_using(&loader, json_ld::Options::default())
+		.await
+		.is_err())
+}
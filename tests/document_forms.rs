@@ -0,0 +1,139 @@
+//! Conversions between document forms.
+
+mod common;
+
+use json_ld::{JsonLdProcessor, RemoteContextReference, RemoteDocumentReference};
+
+#[async_std::test]
+async fn document_forms() {
+	use json_ld::{ConvertDocument, DocumentForm, Profile, StandardProfile};
+
+	let context_iri = common::context_iri();
+	let loader =
+		common::loader_with_context(r#"{ "@context": { "@vocab": "http://example.org/" } }"#);
+
+	let mut remote = common::document(
+		r#"{
+			"@context": { "@vocab": "http://example.org/" },
+			"name": "Alice",
+			"knows": { "name": "Bob" }
+		}"#,
+	);
+	remote
+		.profile
+		.insert(Profile::Standard(StandardProfile::Framed));
+	let document = remote.into_document(&loader).await.unwrap();
+	assert_eq!(document.form(), Some(DocumentForm::Framed));
+	assert!(document.as_form(DocumentForm::Framed).is_some());
+	assert!(document.as_form(DocumentForm::Compacted).is_none());
+
+	let mut generator = rdf_types::generator::Blank::new();
+
+	let expanded = document
+		.convert_to(&mut generator, DocumentForm::Expanded, None, &loader)
+		.await
+		.unwrap();
+	assert_eq!(expanded.form(), Some(DocumentForm::Expanded));
+	assert!(expanded
+		.as_remote()
+		.has_profile(&Profile::Standard(StandardProfile::Expanded)));
+	assert_eq!(expanded.as_expanded(), document.as_expanded());
+
+	let compacted = expanded
+		.convert_to(
+			&mut generator,
+			DocumentForm::Compacted,
+			Some(RemoteContextReference::iri(context_iri.clone())),
+			&loader,
+		)
+		.await
+		.unwrap();
+	assert_eq!(
+		compacted
+			.as_form(DocumentForm::Compacted)
+			.unwrap()
+			.as_object()
+			.unwrap()
+			.get_unique("name")
+			.unwrap()
+			.unwrap()
+			.as_str(),
+		Some("Alice")
+	);
+
+	let flattened = compacted
+		.convert_to(&mut generator, DocumentForm::Flattened, None, &loader)
+		.await
+		.unwrap();
+	assert_eq!(flattened.as_compact().as_array().unwrap().len(), 2);
+
+	assert!(matches!(
+		document
+			.convert_to(&mut generator, DocumentForm::Compacted, None, &loader)
+			.await,
+		Err(json_ld::ConvertError::MissingContext)
+	));
+
+	let framed = document
+		.convert_to(&mut generator, DocumentForm::Framed, None, &loader)
+		.await
+		.unwrap();
+	let graph = framed
+		.as_form(DocumentForm::Framed)
+		.unwrap()
+		.as_object()
+		.unwrap()
+		.get_unique("@graph")
+		.unwrap()
+		.unwrap();
+	assert_eq!(graph.as_array().unwrap().len(), 2);
+
+	let frame = common::document(
+		r#"{
+			"@context": { "@vocab": "http://example.org/" },
+			"knows": {}
+		}"#,
+	);
+	let framed = document
+		.frame(
+			&mut generator,
+			RemoteDocumentReference::Loaded(frame),
+			&loader,
+		)
+		.await
+		.unwrap();
+	let alice = framed
+		.as_form(DocumentForm::Framed)
+		.unwrap()
+		.as_object()
+		.unwrap();
+	let bob = alice
+		.get_unique("knows")
+		.unwrap()
+		.unwrap()
+		.as_object()
+		.unwrap();
+	assert_eq!(
+		alice.get_unique("name").unwrap().unwrap().as_str(),
+		Some("Alice")
+	);
+	assert_eq!(
+		bob.get_unique("name").unwrap().unwrap().as_str(),
+		Some("Bob")
+	);
+	assert!(alice.get_unique("@id").unwrap().is_none());
+
+	let expanded = common::document(r#"[{ "http://example.org/name": [{ "@value": "Alice" }] }]"#)
+		.into_document(&loader)
+		.await
+		.unwrap();
+	assert_eq!(expanded.form(), Some(DocumentForm::Expanded));
+	assert_eq!(compacted.form(), Some(DocumentForm::Compacted));
+
+	let unknown = common::document(r#"{ "http://example.org/name": "Alice" }"#)
+		.into_document(&loader)
+		.await
+		.unwrap();
+	assert_eq!(unknown.form(), None);
+	assert!(unknown.as_form(DocumentForm::Compacted).is_none());
+}
@@ -0,0 +1,290 @@
+//! Manipulation of expanded documents.
+
+mod common;
+
+use json_ld::{syntax::Parse, JsonLdProcessor};
+
+#[async_std::test]
+async fn partition_expanded_document() {
+	let expanded = common::document(
+		r#"[
+			{ "@id": "http://example.org/a", "http://example.org/p": "a" },
+			{ "@id": "http://example.org/b", "http://example.org/p": "b" },
+			{
+				"@id": "http://example.org/g",
+				"@graph": { "@id": "http://example.org/c", "http://example.org/p": "c" }
+			}
+		]"#,
+	)
+	.expand(&json_ld::NoLoader)
+	.await
+	.unwrap();
+
+	let graphs = expanded.clone().partition_by_graph();
+	assert_eq!(graphs.len(), 2);
+	assert_eq!(graphs[0].len(), 2);
+	assert_eq!(graphs[1].len(), 1);
+
+	let chunks = expanded.partition_by_subject(std::num::NonZeroUsize::new(2).unwrap());
+	assert_eq!(chunks.len(), 2);
+	assert_eq!(chunks[0].len(), 2);
+	assert_eq!(chunks[1].len(), 1);
+}
+
+#[async_std::test]
+async fn page_expanded_document() {
+	let expanded = common::document(
+		r#"[
+			{ "@id": "http://example.org/c", "http://example.org/knows": { "@id": "http://example.org/x" } },
+			{ "@id": "http://example.org/a", "http://example.org/knows": { "@id": "http://example.org/y" } },
+			{ "@id": "http://example.org/b" },
+			{ "@id": "http://example.org/x", "http://example.org/knows": { "@id": "http://example.org/y" } },
+			{ "@id": "http://example.org/y" }
+		]"#,
+	)
+	.expand(&json_ld::NoLoader)
+	.await
+	.unwrap();
+
+	let ids = |page: &json_ld::ExpandedDocument| -> Vec<String> {
+		page.iter()
+			.map(|object| object.id().unwrap().to_string())
+			.collect()
+	};
+
+	let by_id = |object: &json_ld::IndexedObject<json_ld::IriBuf, json_ld::BlankIdBuf>| {
+		object.id().map(|id| id.to_string())
+	};
+
+	let page = expanded.page(1, 1, 0, by_id);
+	assert_eq!(ids(&page), ["http://example.org/b"]);
+
+	let page = expanded.page(2, 1, 1, by_id);
+	assert_eq!(ids(&page), ["http://example.org/c", "http://example.org/x"]);
+
+	let page = expanded.page(2, 1, 2, by_id);
+	assert_eq!(
+		ids(&page),
+		[
+			"http://example.org/c",
+			"http://example.org/x",
+			"http://example.org/y"
+		]
+	);
+}
+
+#[async_std::test]
+async fn display_without_vocabulary() {
+	let expanded = common::document(
+		r#"{
+			"@context": { "name": "http://xmlns.com/foaf/0.1/name" },
+			"@id": "http://example.org/alice",
+			"name": "Alice"
+		}"#,
+	)
+	.expand(&json_ld::NoLoader)
+	.await
+	.unwrap();
+
+	let (expected, _) = json_ld::syntax::Value::parse_str(
+		r#"{
+			"@id": "http://example.org/alice",
+			"http://xmlns.com/foaf/0.1/name": [{ "@value": "Alice" }]
+		}"#,
+	)
+	.unwrap();
+
+	let (printed, _) = json_ld::syntax::Value::parse_str(&expanded.to_string()).unwrap();
+	assert_eq!(
+		printed,
+		json_ld::syntax::Value::Array(vec![expected.clone()])
+	);
+
+	let node = expanded.main_node().unwrap();
+	let (printed, _) = json_ld::syntax::Value::parse_str(&node.to_string()).unwrap();
+	assert_eq!(printed, expected)
+}
+
+#[async_std::test]
+async fn flattened_expanded_conversion() {
+	let document = common::document(
+		r#"{
+			"@id": "http://example.org/a",
+			"http://example.org/knows": {
+				"@id": "http://example.org/b",
+				"http://example.org/name": "B"
+			}
+		}"#,
+	);
+	let mut generator = json_ld::rdf_types::generator::Blank::new();
+	let flattened = document
+		.flatten(&mut generator, &json_ld::NoLoader)
+		.await
+		.unwrap();
+	assert_eq!(flattened.len(), 2);
+
+	let expanded = json_ld::ExpandedDocument::from(flattened.clone());
+	assert_eq!(expanded.len(), 2);
+
+	let back: Result<json_ld::FlattenedDocument<_, _>, _> = expanded.try_into();
+	assert_eq!(back, Ok(flattened));
+
+	let nested = document.expand(&json_ld::NoLoader).await.unwrap();
+	let back: Result<json_ld::FlattenedDocument<_, _>, _> = nested.try_into();
+	assert_eq!(back, Err(json_ld::NotFlattened::EmbeddedNode));
+}
+
+#[async_std::test]
+async fn expanded_document_sort() {
+	async fn expand_sorted(input: &str) -> json_ld::ExpandedDocument {
+		let mut expanded = common::document(input)
+			.expand(&json_ld::NoLoader)
+			.await
+			.unwrap();
+		expanded.sort_by_id();
+		expanded.sort_properties();
+		expanded
+	}
+
+	fn layout(document: &json_ld::ExpandedDocument) -> Vec<(String, Vec<String>)> {
+		document
+			.iter()
+			.map(|object| {
+				let node = object.as_node().unwrap();
+				(
+					node.id.as_ref().unwrap().to_string(),
+					node.properties.iter().map(|(p, _)| p.to_string()).collect(),
+				)
+			})
+			.collect()
+	}
+
+	let a = expand_sorted(
+		r#"[
+			{ "@id": "http://example.org/b", "http://example.org/q": 1, "http://example.org/p": 2 },
+			{ "@id": "http://example.org/a", "http://example.org/p": 3 }
+		]"#,
+	)
+	.await;
+
+	let b = expand_sorted(
+		r#"[
+			{ "@id": "http://example.org/a", "http://example.org/p": 3 },
+			{ "http://example.org/p": 2, "@id": "http://example.org/b", "http://example.org/q": 1 }
+		]"#,
+	)
+	.await;
+
+	assert_eq!(layout(&a), layout(&b));
+	assert_eq!(
+		layout(&a),
+		[
+			(
+				"http://example.org/a".to_owned(),
+				vec!["http://example.org/p".to_owned()]
+			),
+			(
+				"http://example.org/b".to_owned(),
+				vec![
+					"http://example.org/p".to_owned(),
+					"http://example.org/q".to_owned()
+				]
+			)
+		]
+	)
+}
+
+#[async_std::test]
+async fn shared_expanded_document() {
+	use json_ld::{Flatten, RdfQuads};
+	use std::sync::Arc;
+
+	let document = common::document(
+		r#"{
+			"@id": "http://example.org/a",
+			"http://example.org/knows": {
+				"@id": "http://example.org/b",
+				"http://example.org/name": "B"
+			}
+		}"#,
+	);
+	let expanded = Arc::new(document.expand(&json_ld::NoLoader).await.unwrap());
+
+	let mut generator = json_ld::rdf_types::generator::Blank::new();
+	let flattened = expanded.clone().flatten(&mut generator, true).unwrap();
+	assert_eq!(flattened.len(), 2);
+
+	let borrowed = (&*expanded).flatten(&mut generator, true).unwrap();
+	assert_eq!(borrowed, flattened);
+
+	assert_eq!(expanded.rdf_quads(&mut generator, None).count(), 2);
+	assert_eq!(Arc::strong_count(&expanded), 1);
+}
+
+#[async_std::test]
+async fn merge_isolates_blank_nodes() {
+	let mut a =
+		common::document(r#"{ "@id": "_:alice", "http://example.org/knows": { "@id": "_:bob" } }"#)
+			.expand(&json_ld::NoLoader)
+			.await
+			.unwrap();
+	let b = a.clone();
+
+	a.merge(b);
+	assert_eq!(a.len(), 2);
+	assert_eq!(a.blank_ids().len(), 4);
+
+	assert!(a.isolate_blank_nodes("not valid").is_err());
+	a.isolate_blank_nodes("doc-").unwrap();
+	assert!(a
+		.blank_ids()
+		.iter()
+		.all(|b| b.as_str().starts_with("_:doc-")))
+}
+
+#[async_std::test]
+async fn compare_isomorphic_documents() {
+	let a = common::document(
+		r#"{
+			"@id": "_:alice",
+			"http://xmlns.com/foaf/0.1/knows": { "@id": "_:bob" }
+		}"#,
+	);
+	let b = common::document(
+		r#"{
+			"@id": "_:b0",
+			"http://xmlns.com/foaf/0.1/knows": { "@id": "_:b1" }
+		}"#,
+	);
+	let c = common::document(
+		r#"{
+			"@id": "_:b0",
+			"http://xmlns.com/foaf/0.1/knows": { "@id": "_:b0" }
+		}"#,
+	);
+
+	assert!(!a.compare(&b, &json_ld::NoLoader).await.unwrap());
+
+	let options: json_ld::Options = json_ld::Options::builder()
+		.compare_strategy(json_ld::CompareStrategy::Isomorphism)
+		.build()
+		.unwrap();
+
+	let isomorphism = a
+		.compare_detailed(
+			&b,
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			&json_ld::NoLoader,
+			options.clone(),
+			(),
+		)
+		.await
+		.unwrap();
+	assert!(isomorphism.equal);
+	assert_eq!(isomorphism.strategy, json_ld::CompareStrategy::Isomorphism);
+
+	assert!(!a
+		.compare_using(&c, &json_ld::NoLoader, options)
+		.await
+		.unwrap())
+}
@@ -0,0 +1,229 @@
+//! Expansion options and warnings.
+
+mod common;
+
+use json_ld::JsonLdProcessor;
+use static_iref::iri;
+
+#[async_std::test]
+async fn value_transformer() {
+	use json_ld::expansion::ValueMut;
+
+	let document = common::document(
+		r#"{
+			"@context": {
+				"@vocab": "http://example.org/",
+				"title": { "@container": "@language" }
+			},
+			"@id": "http://example.org/alice",
+			"name": "  Alice ",
+			"ssn": "123-45-6789",
+			"title": { "en": "Doctor", "fr": "Docteur" }
+		}"#,
+	);
+
+	let options = json_ld::Options {
+		value_transformer: Some(std::sync::Arc::new(
+			|property: Option<&str>, value: ValueMut| match value {
+				ValueMut::Literal(json_ld::object::Literal::String(s), _) => {
+					*s = s.trim().into();
+					property != Some("ssn")
+				}
+				ValueMut::LangString(s) => s.language().map(|tag| tag.as_str()) != Some("fr"),
+				_ => true,
+			},
+		)),
+		..Default::default()
+	};
+
+	let expanded = document
+		.expand_using(&json_ld::NoLoader, options)
+		.await
+		.unwrap();
+
+	let node = expanded.main_node().unwrap();
+	let name = node.get_any(&iri!("http://example.org/name")).unwrap();
+	assert_eq!(name.as_str(), Some("Alice"));
+	assert!(node.get_any(&iri!("http://example.org/ssn")).is_none());
+	assert_eq!(node.get(&iri!("http://example.org/title")).count(), 1)
+}
+
+#[async_std::test]
+async fn warning_locations() {
+	use json_ld::warning::{Handler, Location};
+
+	#[derive(Default)]
+	struct Locations(Vec<String>);
+
+	impl<N, W> Handler<N, W> for Locations {
+		fn handle(&mut self, _vocabulary: &N, _warning: W) {
+			self.0.push("unknown".to_owned())
+		}
+
+		fn handle_at(&mut self, _vocabulary: &N, _warning: W, location: &Location) {
+			self.0.push(location.pointer().to_owned())
+		}
+	}
+
+	let document = common::document(
+		r#"[
+			{ "@id": "http://example.org/a" },
+			{ "@id": "http://example.org/b", "http://example.org/p": { "@id": "a b" } }
+		]"#,
+	);
+
+	let mut locations = Locations::default();
+	document
+		.expand_full(
+			&mut (),
+			&json_ld::NoLoader,
+			json_ld::Options::default(),
+			&mut locations,
+		)
+		.await
+		.unwrap();
+
+	assert_eq!(locations.0, ["/1/http:~1~1example.org~1p/@id"])
+}
+
+#[async_std::test]
+async fn relabel_non_conforming_blank_ids() {
+	use json_ld::warning::Handler;
+
+	/// Collects the relabeled identifiers.
+	#[derive(Default)]
+	struct Relabeled(Vec<String>);
+
+	impl<N> Handler<N, json_ld::context_processing::Warning> for Relabeled {
+		fn handle(&mut self, _vocabulary: &N, _warning: json_ld::context_processing::Warning) {}
+	}
+
+	impl<N, B> Handler<N, json_ld::expansion::Warning<B>> for Relabeled {
+		fn handle(&mut self, _vocabulary: &N, warning: json_ld::expansion::Warning<B>) {
+			if let json_ld::expansion::Warning::RelabeledBlankId(original, _) = warning {
+				self.0.push(original)
+			}
+		}
+	}
+
+	let document = common::document(
+		r#"{
+			"@id": "_:node 1",
+			"http://example.org/p": [ { "@id": "_:node 1" }, { "@id": "_:node_1" } ]
+		}"#,
+	);
+
+	let options = json_ld::Options::builder()
+		.expansion(|e| e.relabel_non_conforming_blank_ids(true))
+		.build()
+		.unwrap();
+
+	let mut warnings = Relabeled::default();
+	let expanded = document
+		.expand_full(&mut (), &json_ld::NoLoader, options, &mut warnings)
+		.await
+		.unwrap();
+
+	let expected = common::document(
+		r#"{
+			"@id": "_:node_1-1",
+			"http://example.org/p": [ { "@id": "_:node_1-1" }, { "@id": "_:node_1" } ]
+		}"#,
+	)
+	.expand(&json_ld::NoLoader)
+	.await
+	.unwrap();
+
+	assert_eq!(expanded, expected);
+	assert_eq!(warnings.0, ["_:node 1"])
+}
+
+#[async_std::test]
+async fn validate_iris() {
+	use json_ld::expansion::{Action, Warning};
+	use json_ld::warning::Handler;
+
+	/// Collects the invalid IRI warnings.
+	#[derive(Default)]
+	struct InvalidIris(Vec<(String, String)>);
+
+	impl<N> Handler<N, json_ld::context_processing::Warning> for InvalidIris {
+		fn handle(&mut self, _vocabulary: &N, _warning: json_ld::context_processing::Warning) {}
+	}
+
+	impl<N, B> Handler<N, Warning<B>> for InvalidIris {
+		fn handle(&mut self, _vocabulary: &N, warning: Warning<B>) {
+			if let Warning::InvalidIri(term, iri) = warning {
+				self.0.push((term, iri))
+			}
+		}
+	}
+
+	let document = common::document(
+		r#"{
+			"@context": { "@vocab": "http://example.org/" },
+			"@id": "http://example.org/a b",
+			"first name": "Alice",
+			"name": "Alice"
+		}"#,
+	);
+
+	let expand = |action| {
+		let document = document.clone();
+		async move {
+			let options = json_ld::Options::builder()
+				.expansion(|e| e.validate_iris(action))
+				.build()
+				.unwrap();
+
+			let mut warnings = InvalidIris::default();
+			let result = document
+				.expand_full(&mut (), &json_ld::NoLoader, options, &mut warnings)
+				.await;
+			(result, warnings.0)
+		}
+	};
+
+	let (result, warnings) = expand(Action::Keep).await;
+	let node = result.unwrap().into_main_node().unwrap();
+	assert!(node.id.is_some_and(|id| !id.is_valid()));
+	assert_eq!(node.properties().len(), 2);
+	assert!(warnings.is_empty());
+
+	let (result, warnings) = expand(Action::Drop).await;
+	let node = result.unwrap().into_main_node().unwrap();
+	assert!(node.id.is_none());
+	assert_eq!(node.properties().len(), 1);
+	assert_eq!(
+		warnings,
+		[
+			(
+				"http://example.org/a b".to_owned(),
+				"http://example.org/a b".to_owned()
+			),
+			(
+				"first name".to_owned(),
+				"http://example.org/first name".to_owned()
+			)
+		]
+	);
+
+	match expand(Action::Reject).await.0 {
+		Err(json_ld::ExpandError::Expansion(json_ld::expansion::Error::InvalidIri(e))) => {
+			assert_eq!(e.iri, "http://example.org/a b")
+		}
+		other => panic!("unexpected result: {:?}", other.map(|_| ())),
+	}
+}
+
+#[test]
+fn blocking_expand() {
+	let loader = common::loader_with_context(
+		r#"{ "@context": { "name": "http://xmlns.com/foaf/0.1/name" } }"#,
+	);
+	let document =
+		common::document(r#"{ "@context": "http://example.org/context.jsonld", "name": "Alice" }"#);
+
+	let expanded = json_ld::blocking::expand(&document, &loader).unwrap();
+	assert_eq!(expanded.len(), 1)
+}
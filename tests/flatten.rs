@@ -147,7 +147,10 @@ impl flatten::Test {
 					let mut expect = loader.load_with(&mut vocabulary, expect).await.unwrap();
 					expect.set_url(Some(input));
 
-					let expand_options: json_ld::Options<IriIndex> = json_ld::Options::default();
+					let expand_options: json_ld::Options<IriIndex> = json_ld::Options {
+						compare_strategy: json_ld::CompareStrategy::Isomorphism,
+						..Default::default()
+					};
 					let success = flattened
 						.compare_full(&expect, &mut vocabulary, &mut loader, expand_options, ())
 						.await
@@ -0,0 +1,71 @@
+//! Compaction of graph containers.
+
+mod common;
+
+use json_ld::{JsonLdProcessor, RemoteContextReference, RemoteDocument};
+use std::collections::HashMap;
+
+async fn quads_summary(
+	document: RemoteDocument,
+	loader: &HashMap<json_ld::IriBuf, RemoteDocument>,
+) -> (usize, usize) {
+	let mut generator = rdf_types::generator::Blank::new();
+	let mut rdf = document.to_rdf(&mut generator, loader).await.unwrap();
+	let quads: Vec<_> = rdf.cloned_quads().collect();
+	let graphs: std::collections::HashSet<_> = quads.iter().map(|q| q.3.clone()).collect();
+	(quads.len(), graphs.len())
+}
+
+#[async_std::test]
+async fn graph_id_map_round_trip() {
+	let context_iri = common::context_iri();
+	let loader = common::loader_with_context(
+		r#"{
+			"@context": {
+				"@vocab": "http://example.org/",
+				"graphs": { "@id": "http://example.org/graphs", "@container": ["@graph", "@id"] }
+			}
+		}"#,
+	);
+
+	let input = common::document(
+		r#"{
+			"@id": "http://example.org/a",
+			"http://example.org/graphs": [
+				{
+					"@id": "http://example.org/g",
+					"@graph": [{ "@id": "http://example.org/x", "http://example.org/p": "1" }]
+				},
+				{
+					"@graph": [
+						{ "@id": "http://example.org/y", "http://example.org/p": "2" },
+						{ "@id": "http://example.org/z", "http://example.org/p": "3" }
+					]
+				}
+			]
+		}"#,
+	);
+
+	let context = RemoteContextReference::iri(context_iri.clone());
+
+	// 3 triples in the two named graphs, 2 linking `a` to the graphs.
+	let expected = (5, 3);
+	assert_eq!(quads_summary(input.clone(), &loader).await, expected);
+
+	// Following the compaction algorithm, the two nodes of the anonymous
+	// graph become two anonymous graphs once expanded back.
+	let compacted = input.compact(context.clone(), &loader).await.unwrap();
+	let compacted = RemoteDocument::new(None, None, compacted);
+	assert_eq!(quads_summary(compacted, &loader).await, (6, 4));
+
+	let options = json_ld::Options {
+		wrap_anonymous_graphs: true,
+		..Default::default()
+	};
+	let compacted = input
+		.compact_using(context, &loader, options)
+		.await
+		.unwrap();
+	let compacted = RemoteDocument::new(None, None, compacted);
+	assert_eq!(quads_summary(compacted, &loader).await, expected)
+}
@@ -0,0 +1,64 @@
+//! Caching of the remote contexts loaded over HTTP.
+#![cfg(feature = "reqwest")]
+
+#[path = "../crates/core/src/loader/fixture/http.rs"]
+mod http;
+
+use http::{Response, Server};
+use json_ld::{
+	loader::{HttpCache, Restrictions},
+	syntax::Parse,
+	JsonLdProcessor, Processor, RemoteDocument, ReqwestLoader,
+};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn remote_context() {
+	let server = Server::start(|_| {
+		Response::json_ld(r#"{ "@context": { "name": "http://schema.org/name" } }"#)
+			.header("Cache-Control", "max-age=60")
+	});
+
+	let loader = ReqwestLoader::builder()
+		.cache(Arc::new(HttpCache::new()))
+		.build()
+		.unwrap();
+
+	let input = format!(
+		r#"{{ "@context": "{}", "name": "Alice" }}"#,
+		server.url("/context.jsonld")
+	);
+
+	for _ in 0..2 {
+		let (json, _) = json_syntax::Value::parse_str(&input).unwrap();
+		let expanded = RemoteDocument::new(None, None, json)
+			.expand(&loader)
+			.await
+			.unwrap();
+		assert_eq!(expanded.len(), 1)
+	}
+
+	// Remote contexts are loaded with the context profile, and cached.
+	assert_eq!(server.requests().len(), 1)
+}
+
+#[tokio::test]
+async fn production_processor() {
+	let server = Server::start(|_| {
+		Response::json_ld(r#"{ "@context": { "name": "http://schema.org/name" } }"#)
+			.header("Cache-Control", "max-age=60")
+	});
+
+	let mut restrictions = Restrictions::new();
+	restrictions.set_allow_private_addresses(true);
+	let processor = Processor::builder().restrictions(restrictions).build();
+
+	let input = format!(
+		r#"{{ "@context": "{}", "name": "Alice" }}"#,
+		server.url("/context.jsonld")
+	);
+
+	let expanded = processor.expand(&input).await.unwrap();
+	assert_eq!(processor.expand(&input).await.unwrap(), expanded);
+	assert_eq!(server.requests().len(), 1)
+}
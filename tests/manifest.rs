@@ -0,0 +1,53 @@
+use json_ld::{JsonLdProcessor, Loader, RemoteDocumentReference, TryFromJson};
+use static_iref::iri;
+
+#[json_ld_testing::test_suite("https://example.org/tests/manifest.jsonld")]
+#[mount("https://example.org/tests/", "tests/custom/manifest")]
+#[iri_prefix("ex" = "https://example.org/vocab#")]
+mod manifest {
+	use iref::Iri;
+
+	#[iri("ex:ContextTest")]
+	pub struct Test {
+		#[iri("ex:name")]
+		pub name: &'static str,
+
+		#[iri("ex:input")]
+		pub input: &'static Iri,
+
+		#[iri("ex:expect")]
+		pub expect: Option<&'static Iri>,
+	}
+}
+
+impl manifest::Test {
+	fn run(self) {
+		async_std::task::block_on(self.async_run())
+	}
+
+	async fn async_run(self) {
+		let mut loader = json_ld::FsLoader::default();
+		loader.mount(
+			iri!("https://example.org/tests/").to_owned(),
+			"tests/custom/manifest",
+		);
+
+		let result = RemoteDocumentReference::iri(self.input.to_owned())
+			.expand(&loader)
+			.await;
+
+		match self.expect {
+			Some(expect) => {
+				let expected = loader.load(expect).await.unwrap().into_document();
+				let expected = json_ld::ExpandedDocument::try_from_json_in(
+					rdf_types::vocabulary::no_vocabulary_mut(),
+					expected,
+				)
+				.unwrap();
+
+				assert_eq!(result.unwrap(), expected, "{}", self.name)
+			}
+			None => assert!(result.is_err(), "{}", self.name),
+		}
+	}
+}
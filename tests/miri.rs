@@ -0,0 +1,73 @@
+//! Small end-to-end tests meant to be run under Miri:
+//!
+//! ```sh
+//! cargo +nightly miri test --test miri
+//! ```
+//!
+//! Documents are loaded from memory and futures are polled by the
+//! single-threaded `futures` executor, so that the whole pipeline runs
+//! deterministically and without any I/O.
+use futures::executor::block_on;
+use json_ld::{syntax::Parse, IriBuf, JsonLdProcessor, RemoteContextReference, RemoteDocument};
+use static_iref::iri;
+use std::collections::HashMap;
+
+const CONTEXT: &str = r#"{
+	"@context": {
+		"@vocab": "http://example.org/vocab#",
+		"name": { "@id": "http://xmlns.com/foaf/0.1/name" },
+		"knows": { "@id": "http://xmlns.com/foaf/0.1/knows", "@type": "@id" },
+		"tags": { "@container": "@list" }
+	}
+}"#;
+
+const INPUT: &str = r#"{
+	"@context": "http://example.org/context.jsonld",
+	"@id": "http://example.org/alice",
+	"name": "Alice",
+	"knows": {
+		"name": "Bob",
+		"tags": ["a", "b"]
+	}
+}"#;
+
+fn loader() -> HashMap<IriBuf, RemoteDocument> {
+	let iri = iri!("http://example.org/context.jsonld").to_owned();
+	let (context, _) = json_ld::syntax::Value::parse_str(CONTEXT).unwrap();
+
+	let mut loader = HashMap::new();
+	loader.insert(iri.clone(), RemoteDocument::new(Some(iri), None, context));
+	loader
+}
+
+fn input() -> RemoteDocument {
+	let (json, _) = json_ld::syntax::Value::parse_str(INPUT).unwrap();
+	RemoteDocument::new(None, None, json)
+}
+
+#[test]
+fn expand() {
+	let expanded = block_on(input().expand(&loader())).unwrap();
+	assert_eq!(expanded.len(), 1)
+}
+
+#[test]
+fn compact() {
+	let context = RemoteContextReference::iri(iri!("http://example.org/context.jsonld").to_owned());
+	let compacted = block_on(input().compact(context, &loader())).unwrap();
+	assert!(compacted.is_object())
+}
+
+#[test]
+fn flatten() {
+	let mut generator = rdf_types::generator::Blank::new();
+	let flattened = block_on(input().flatten(&mut generator, &loader())).unwrap();
+	assert!(flattened.is_array())
+}
+
+#[test]
+fn to_rdf() {
+	let mut generator = rdf_types::generator::Blank::new();
+	let mut rdf = block_on(input().to_rdf(&mut generator, &loader())).unwrap();
+	assert_eq!(rdf.cloned_quads().count(), 8)
+}
@@ -0,0 +1,131 @@
+//! Processing options.
+
+mod common;
+
+use json_ld::JsonLdProcessor;
+
+#[test]
+fn options_string_forms() {
+	use json_ld::{expansion::Policy, rdf::RdfDirection, ProcessingMode};
+
+	assert_eq!(
+		"json-ld-1.0".parse::<ProcessingMode>().unwrap(),
+		ProcessingMode::JsonLd1_0
+	);
+	assert!("json-ld-2.0".parse::<ProcessingMode>().is_err());
+	assert_eq!(
+		"compound-literal".parse::<RdfDirection>().unwrap(),
+		RdfDirection::CompoundLiteral
+	);
+	assert_eq!(RdfDirection::I18nDatatype.to_string(), "i18n-datatype");
+
+	for name in ["strict-vc", "lossless-ingest", "permissive-web"] {
+		assert_eq!(name.parse::<Policy>().unwrap().to_string(), name)
+	}
+	assert_eq!("strict-vc".parse::<Policy>().unwrap(), Policy::strict_vc());
+	assert!("strict".parse::<Policy>().is_err());
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+fn options_serde() {
+	use json_ld::expansion::{self, Action, Policy};
+
+	let options: expansion::Options = serde_json::from_str(
+		r#"{ "processingMode": "json-ld-1.0", "policy": "strict-vc", "ordered": true }"#,
+	)
+	.unwrap();
+	assert_eq!(options.processing_mode, json_ld::ProcessingMode::JsonLd1_0);
+	assert_eq!(options.policy, Policy::strict_vc());
+	assert!(options.ordered);
+
+	let policy: Policy =
+		serde_json::from_str(r#"{ "invalid": "drop", "vocab": "reject", "allowUndefined": true }"#)
+			.unwrap();
+	assert_eq!(policy.invalid, Action::Drop);
+	assert_eq!(policy.vocab, Action::Reject);
+
+	let json = serde_json::to_value(Policy::strict_vc()).unwrap();
+	assert_eq!(
+		serde_json::from_value::<Policy>(json).unwrap(),
+		Policy::strict_vc()
+	);
+}
+
+#[test]
+fn grouped_options_builder() {
+	let options: json_ld::Options = json_ld::Options::builder()
+		.processing_mode(json_ld::ProcessingMode::JsonLd1_0)
+		.expansion(|e| e.max_literal_length(16).materialize_defaults(true))
+		.compaction(|c| c.compact_arrays(false))
+		.rdf(|r| r.generalized(true))
+		.build()
+		.unwrap();
+
+	assert_eq!(options.max_literal_length, Some(16));
+	assert!(options.materialize_defaults);
+	assert!(!options.compact_arrays);
+	assert!(options.produce_generalized_rdf);
+
+	let description = options.describe().to_string();
+	assert!(description.contains("processing mode: json-ld-1.0"));
+	assert!(description.contains("  max literal length: 16"));
+	assert!(description.contains("  generalized: true"));
+
+	let invalid = json_ld::Options::<json_ld::IriBuf>::builder()
+		.processing_mode(json_ld::ProcessingMode::JsonLd1_0)
+		.rdf(|r| r.direction(json_ld::rdf::RdfDirection::I18nDatatype))
+		.build();
+	assert!(matches!(
+		invalid,
+		Err(json_ld::InvalidOptions::RdfDirectionRequiresJsonLd11)
+	))
+}
+
+#[async_std::test]
+async fn options_presets() {
+	use json_ld::{context_processing, rdf::RdfDirection, ExpandError, InvalidOptions, Options};
+	use json_ld::{OptionsBuilder, ProcessingMode};
+
+	let options: Options = Options::with_defaults_for(ProcessingMode::JsonLd1_0);
+	assert_eq!(options.processing_mode, ProcessingMode::JsonLd1_0);
+	assert!(options.ordered);
+
+	let result = common::document(
+		r#"{
+			"@context": { "@version": 1.1, "@vocab": "http://example.org/" },
+			"name": "Alice"
+		}"#,
+	)
+	.expand_using(&json_ld::NoLoader, options)
+	.await;
+	assert!(matches!(
+		result,
+		Err(ExpandError::ContextProcessing(
+			context_processing::Error::ProcessingModeConflict
+		))
+	));
+
+	let result = OptionsBuilder::<json_ld::IriBuf>::for_processing_mode(ProcessingMode::JsonLd1_0)
+		.rdf_direction(RdfDirection::I18nDatatype)
+		.build();
+	assert_eq!(
+		result.err(),
+		Some(InvalidOptions::RdfDirectionRequiresJsonLd11)
+	);
+
+	let result = OptionsBuilder::<json_ld::IriBuf>::new()
+		.prefetch_contexts(0)
+		.build();
+	assert_eq!(result.err(), Some(InvalidOptions::ZeroPrefetch));
+
+	// The expansion policy does not apply to the term definitions.
+	let options: Options = Options::builder()
+		.expansion_policy(json_ld::expansion::Policy::strict_vc())
+		.build()
+		.unwrap();
+	assert_eq!(
+		options.context_processing_options().vocab,
+		context_processing::Options::default().vocab
+	)
+}
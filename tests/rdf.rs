@@ -0,0 +1,220 @@
+//! Serialization to RDF.
+
+mod common;
+
+use json_ld::JsonLdProcessor;
+use static_iref::iri;
+
+async fn list_quads(
+	input: &str,
+	nested_lists: json_ld::rdf::NestedLists,
+) -> Result<usize, json_ld::ToRdfError> {
+	let document = common::document(input);
+	let options = json_ld::Options {
+		nested_lists,
+		..Default::default()
+	};
+
+	let mut generator = rdf_types::generator::Blank::new();
+	let mut rdf = document
+		.to_rdf_using(&mut generator, &json_ld::NoLoader, options)
+		.await?;
+	Ok(rdf.cloned_quads().count())
+}
+
+#[async_std::test]
+async fn empty_list_to_rdf_nil() {
+	use json_ld::rdf::NestedLists;
+
+	// <a> <p> rdf:nil .
+	let input = r#"{ "@id": "http://example.org/a", "http://example.org/p": { "@list": [] } }"#;
+	for nested_lists in [NestedLists::Keep, NestedLists::Flatten, NestedLists::Reject] {
+		assert_eq!(list_quads(input, nested_lists).await.unwrap(), 1)
+	}
+}
+
+#[async_std::test]
+async fn nested_lists_to_rdf() {
+	use json_ld::rdf::NestedLists;
+
+	let input = r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/p": { "@list": [ "x", { "@list": [ "y", "z" ] }, { "@list": [] } ] }
+	}"#;
+
+	// <a> <p> _:l0 .
+	// _:l0 first "x" ; rest _:l1 . _:l1 first _:n0 ; rest _:l2 . _:l2 first nil ; rest nil .
+	// _:n0 first "y" ; rest _:n1 . _:n1 first "z" ; rest nil .
+	assert_eq!(list_quads(input, NestedLists::Keep).await.unwrap(), 11);
+
+	// <a> <p> _:l0 .
+	// _:l0 first "x" ; rest _:l1 . _:l1 first "y" ; rest _:l2 . _:l2 first "z" ; rest nil .
+	assert_eq!(list_quads(input, NestedLists::Flatten).await.unwrap(), 7);
+
+	assert!(matches!(
+		list_quads(input, NestedLists::Reject).await,
+		Err(json_ld::ToRdfError::Expand(
+			json_ld::ExpandError::Expansion(json_ld::expansion::Error::InvalidSetOrListObject)
+		))
+	));
+
+	// Same behavior when producing the quads of an expanded document.
+	use json_ld::rdf::RdfQuads;
+	let mut expanded = common::document(input)
+		.expand(&json_ld::NoLoader)
+		.await
+		.unwrap();
+	assert!(NestedLists::Reject.apply(&mut expanded).is_err());
+	NestedLists::Flatten.apply(&mut expanded).unwrap();
+	let mut generator = rdf_types::generator::Blank::new();
+	assert_eq!(expanded.rdf_quads(&mut generator, None).count(), 7);
+}
+
+#[async_std::test]
+async fn to_rdf_dropped_quads() {
+	let document = common::document(
+		r#"{
+			"@id": "http://example.org/a",
+			"_:p": "v",
+			"http://example.org/q": "w"
+		}"#,
+	);
+
+	let mut generator = rdf_types::generator::Blank::new();
+	let mut rdf = document
+		.to_rdf(&mut generator, &json_ld::NoLoader)
+		.await
+		.unwrap();
+
+	assert_eq!(rdf.cloned_quads().count(), 1);
+	assert_eq!(rdf.dropped(), 1)
+}
+
+#[async_std::test]
+async fn rdf_index_round_trip() {
+	let index_property = iri!("http://example.org/index").to_owned();
+
+	let document = common::document(
+		r#"{
+			"@id": "http://example.org/a",
+			"http://example.org/p": { "@value": "v", "@index": "i" }
+		}"#,
+	);
+
+	// <a> <p> "v" .
+	// _:r rdf:subject <a> ; rdf:predicate <p> ; rdf:object "v" ; <index> "i" .
+	let options = json_ld::Options::builder()
+		.rdf(|r| r.index_property(index_property.clone()))
+		.build()
+		.unwrap();
+	let mut generator = rdf_types::generator::Blank::new();
+	let mut rdf = document
+		.to_rdf_using(&mut generator, &json_ld::NoLoader, options)
+		.await
+		.unwrap();
+	assert_eq!(rdf.cloned_quads().count(), 5);
+
+	let mut restored = common::document(
+		r#"[
+			{ "@id": "http://example.org/a", "http://example.org/p": "v" },
+			{
+				"@id": "_:r",
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#subject": { "@id": "http://example.org/a" },
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#predicate": { "@id": "http://example.org/p" },
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#object": "v",
+				"http://example.org/index": "i"
+			}
+		]"#,
+	)
+	.expand(&json_ld::NoLoader)
+	.await
+	.unwrap();
+	restored.restore_rdf_indexes(&index_property);
+
+	let expected = document.expand(&json_ld::NoLoader).await.unwrap();
+	assert_eq!(restored, expected)
+}
+
+#[async_std::test]
+async fn rdf_canonical_order() {
+	use rdf_types::RdfDisplay;
+
+	let document = common::document(
+		r#"[
+			{ "@id": "http://example.org/b", "http://example.org/q": "y", "http://example.org/p": "x" },
+			{ "@id": "http://example.org/a", "http://example.org/p": "z" }
+		]"#,
+	);
+
+	let options = json_ld::Options::builder()
+		.rdf(|r| r.canonical_order(true))
+		.build()
+		.unwrap();
+	let mut generator = rdf_types::generator::Blank::new();
+	let mut rdf = document
+		.to_rdf_using(&mut generator, &json_ld::NoLoader, options)
+		.await
+		.unwrap();
+
+	let quads: Vec<_> = rdf
+		.collect_quads()
+		.into_iter()
+		.map(|rdf_types::Quad(s, p, _, _)| format!("{} {}", s.rdf_display(), p.rdf_display()))
+		.collect();
+	assert_eq!(
+		quads,
+		[
+			"<http://example.org/a> <http://example.org/p>",
+			"<http://example.org/b> <http://example.org/p>",
+			"<http://example.org/b> <http://example.org/q>"
+		]
+	)
+}
+
+#[async_std::test]
+async fn rdf_canonical_order_blank_nodes() {
+	use rdf_types::RdfDisplay;
+
+	async fn canonical_quads(input: &str) -> Vec<String> {
+		let options = json_ld::Options::builder()
+			.rdf(|r| r.canonical_order(true))
+			.build()
+			.unwrap();
+		let mut generator = rdf_types::generator::Blank::new();
+		let mut rdf = common::document(input)
+			.to_rdf_using(&mut generator, &json_ld::NoLoader, options)
+			.await
+			.unwrap();
+
+		rdf.collect_quads()
+			.into_iter()
+			.map(|rdf_types::Quad(s, p, o, _)| {
+				format!(
+					"{} {} {}",
+					s.rdf_display(),
+					p.rdf_display(),
+					o.rdf_display()
+				)
+			})
+			.collect()
+	}
+
+	// Isomorphic graphs, with different blank node labels and orders.
+	let a = canonical_quads(
+		r#"[
+			{ "@id": "_:x", "http://example.org/p": { "@id": "_:y" }, "@type": "http://example.org/C" },
+			{ "@id": "_:y", "http://example.org/p": { "@id": "_:x" } }
+		]"#,
+	)
+	.await;
+	let b = canonical_quads(
+		r#"[
+			{ "@id": "_:b1", "http://example.org/p": { "@id": "_:b0" } },
+			{ "@id": "_:b0", "@type": "http://example.org/C", "http://example.org/p": { "@id": "_:b1" } }
+		]"#,
+	)
+	.await;
+
+	assert_eq!(a, b);
+	assert!(a.iter().any(|quad| quad.starts_with("_:c14n0")))
+}
@@ -0,0 +1,170 @@
+//! Handling of invalid, adversarial and oversized inputs.
+
+mod common;
+
+use json_ld::{syntax::Parse, JsonLdProcessor, RemoteContextReference, RemoteDocument};
+use static_iref::iri;
+
+#[async_std::test]
+async fn adversarial_inputs_do_not_panic() {
+	let context_iri = common::context_iri();
+	let loader = common::loader_with_context(
+		r#"{
+			"@context": {
+				"@vocab": "http://example.org/",
+				"ref": { "@id": "http://example.org/ref", "@type": "@id" },
+				"byId": { "@id": "http://example.org/byId", "@container": "@id" },
+				"byType": { "@id": "http://example.org/byType", "@container": "@type" },
+				"byIndex": { "@id": "http://example.org/byIndex", "@container": "@index" },
+				"nested": { "@id": "http://example.org/nested", "@nest": "nest" },
+				"nest": "@nest"
+			}
+		}"#,
+	);
+
+	let inputs = [
+		r#"{ "@context": "http://example.org/context.jsonld", "ref": "@foo" }"#,
+		r#"{ "@context": "http://example.org/context.jsonld", "ref": "a", "ref": "b" }"#,
+		r#"{ "@context": "http://example.org/context.jsonld", "@id": "http://example.org/a", "@id": "http://example.org/b" }"#,
+		r#"{ "@context": "http://example.org/context.jsonld", "byId": { "http://example.org/a": { "p": 1 }, "http://example.org/a": { "p": 2 } } }"#,
+		r#"{ "@context": "http://example.org/context.jsonld", "byType": { "T": { "p": 1 }, "T": { "@type": "U" } } }"#,
+		r#"{ "@context": "http://example.org/context.jsonld", "byIndex": { "i": "a", "i": "b" } }"#,
+		r#"{ "@context": "http://example.org/context.jsonld", "nested": "a", "nest": "b" }"#,
+		r#"{ "@context": "http://example.org/context.jsonld", "p": { "@value": "a", "@value": "b" } }"#,
+	];
+
+	for input in inputs {
+		let document = common::document(input);
+
+		// Errors are fine, panics are not.
+		let _ = document.expand(&loader).await;
+		let _ = document
+			.compact(RemoteContextReference::iri(context_iri.clone()), &loader)
+			.await;
+	}
+}
+
+#[async_std::test]
+async fn invalid_reverse_property_value() {
+	let error = common::document(
+		r#"{
+			"@id": "http://example.org/a",
+			"@reverse": { "http://example.org/knows": "Bob" }
+		}"#,
+	)
+	.expand(&json_ld::NoLoader)
+	.await
+	.unwrap_err();
+
+	match error {
+		json_ld::ExpandError::Expansion(
+			json_ld::expansion::Error::InvalidReversePropertyValue { property, value },
+		) => {
+			assert_eq!(property, "http://example.org/knows");
+			assert_eq!(value, r#""Bob""#)
+		}
+		e => panic!("unexpected error: {e}"),
+	}
+}
+
+#[async_std::test]
+async fn max_literal_length() {
+	let options = json_ld::Options {
+		max_literal_length: Some(4),
+		..Default::default()
+	};
+
+	for input in [
+		r#"{ "http://example.org/p": "too long" }"#,
+		r#"{
+			"@context": { "rev": { "@reverse": "http://example.org/p" } },
+			"@reverse": { "rev": "too long" }
+		}"#,
+	] {
+		let error = common::document(input)
+			.expand_using(&json_ld::NoLoader, options.clone())
+			.await
+			.unwrap_err();
+		assert!(matches!(
+			error,
+			json_ld::ExpandError::Expansion(json_ld::expansion::Error::LiteralTooLong { .. })
+		))
+	}
+}
+
+#[async_std::test]
+async fn not_json_ld_media_type() {
+	let (input, _) = json_ld::syntax::Value::parse_str(r#"{ "name": "Jane Doe" }"#).unwrap();
+	let options = json_ld::Options {
+		reject_non_json_ld: true,
+		..Default::default()
+	};
+
+	let json = RemoteDocument::new(
+		None,
+		Some("application/json".parse().unwrap()),
+		input.clone(),
+	);
+	assert!(json
+		.expand_using(&json_ld::NoLoader, options.clone())
+		.await
+		.unwrap()
+		.is_empty());
+
+	let text = RemoteDocument::new(None, Some("text/plain".parse().unwrap()), input);
+	let error = text
+		.expand_using(&json_ld::NoLoader, options)
+		.await
+		.unwrap_err();
+	assert_eq!(error.code(), json_ld::syntax::ErrorCode::NotJsonLd)
+}
+
+#[async_std::test]
+async fn deep_compaction() {
+	use json_ld::compaction::CompactFragment;
+	use json_ld::{Id, Indexed, Node, Object};
+
+	const DEPTH: usize = 10_000;
+	let next = iri!("http://example.org/next");
+
+	let mut node: Node = Node::new();
+	for _ in 0..DEPTH {
+		let mut parent = Node::new();
+		parent.insert(Id::iri(next.to_owned()), Indexed::none(Object::node(node)));
+		node = parent
+	}
+
+	let object = Indexed::none(Object::node(node));
+	let context: json_ld::Context = json_ld::Context::new(None);
+	let mut compacted = object
+		.compact_fragment_full(
+			&mut (),
+			&context,
+			&context,
+			None,
+			&json_ld::NoLoader,
+			json_ld::compaction::Options::default(),
+		)
+		.await
+		.unwrap();
+
+	// Both trees are dismantled level by level, since dropping them
+	// recursively would also overflow the stack.
+	let mut depth = 0;
+	while let Some(entry) = compacted
+		.as_object_mut()
+		.and_then(|o| o.remove_unique(next.as_str()).unwrap())
+	{
+		compacted = entry.value;
+		depth += 1
+	}
+	assert_eq!(depth, DEPTH);
+
+	let mut object = object.into_inner();
+	while let Some(node) = object.as_node_mut() {
+		match node.properties_mut().remove(&Id::iri(next.to_owned())) {
+			Some(values) => object = values.into_iter().next().unwrap().into_inner(),
+			None => break,
+		}
+	}
+}
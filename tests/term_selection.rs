@@ -0,0 +1,176 @@
+//! Term selection and IRI compaction.
+
+mod common;
+
+use json_ld::{syntax::Parse, JsonLdProcessor, RemoteContextReference};
+
+#[async_std::test]
+async fn language_map_keys_case_insensitive() {
+	let context_iri = common::context_iri();
+	let loader = common::loader_with_context(
+		r#"{
+			"@context": {
+				"label": { "@id": "http://example.org/label", "@container": "@language" }
+			}
+		}"#,
+	);
+
+	let compacted = common::document(
+		r#"{
+			"http://example.org/label": [
+				{ "@value": "color", "@language": "en-US" },
+				{ "@value": "colour", "@language": "en-us" }
+			]
+		}"#,
+	)
+	.compact(RemoteContextReference::iri(context_iri), &loader)
+	.await
+	.unwrap();
+
+	let (expected, _) =
+		json_ld::syntax::Value::parse_str(r#"{ "en-US": ["color", "colour"] }"#).unwrap();
+
+	assert_eq!(
+		compacted
+			.as_object()
+			.unwrap()
+			.get_unique("label")
+			.ok()
+			.unwrap(),
+		Some(&expected)
+	)
+}
+
+#[async_std::test]
+async fn language_term_selection_case_insensitive() {
+	let context_iri = common::context_iri();
+	let loader = common::loader_with_context(
+		r#"{
+			"@context": {
+				"label": "http://example.org/label",
+				"label_en": { "@id": "http://example.org/label", "@language": "en-US" }
+			}
+		}"#,
+	);
+
+	let compacted = common::document(
+		r#"{
+			"http://example.org/label": { "@value": "colour", "@language": "EN-us" }
+		}"#,
+	)
+	.compact(RemoteContextReference::iri(context_iri), &loader)
+	.await
+	.unwrap();
+
+	let compacted = compacted.as_object().unwrap();
+	assert!(compacted.get_unique("label").unwrap().is_none());
+	assert_eq!(
+		compacted
+			.get_unique("label_en")
+			.unwrap()
+			.and_then(|value| value.as_str()),
+		Some("colour")
+	)
+}
+
+#[async_std::test]
+async fn reverse_term_preferences() {
+	let context_iri = common::context_iri();
+	let loader = common::loader_with_context(
+		r#"{
+			"@context": {
+				"parent": { "@reverse": "http://example.org/child" },
+				"ancestor": { "@reverse": "http://example.org/child" }
+			}
+		}"#,
+	);
+
+	let document = common::document(
+		r#"{
+			"@id": "http://example.org/a",
+			"@reverse": { "http://example.org/child": { "@id": "http://example.org/b" } }
+		}"#,
+	);
+
+	for (preferences, expected) in [
+		(vec![], "parent"),
+		(vec!["ancestor".to_string()], "ancestor"),
+	] {
+		let options = json_ld::Options {
+			term_preferences: preferences,
+			..Default::default()
+		};
+
+		let compacted = document
+			.compact_using(
+				RemoteContextReference::iri(context_iri.clone()),
+				&loader,
+				options,
+			)
+			.await
+			.unwrap();
+
+		assert!(compacted
+			.as_object()
+			.unwrap()
+			.get_unique(expected)
+			.unwrap()
+			.is_some())
+	}
+}
+
+#[async_std::test]
+async fn compact_vocab_values() {
+	let context_iri = common::context_iri();
+	let loader = common::loader_with_context(
+		r#"{
+			"@context": {
+				"@vocab": "http://example.org/",
+				"Active": "http://example.org/status/Active",
+				"status": { "@id": "http://example.org/status", "@type": "@vocab" }
+			}
+		}"#,
+	);
+
+	let document = common::document(
+		r#"{
+			"http://example.org/status": [
+				{ "@id": "http://example.org/status/Active" },
+				{ "@id": "http://example.org/Pending" }
+			]
+		}"#,
+	);
+
+	for (vocab_relative_values, expected) in [
+		(true, r#"["Active", "Pending"]"#),
+		(
+			false,
+			r#"["http://example.org/status/Active", "http://example.org/Pending"]"#,
+		),
+	] {
+		let options = json_ld::Options {
+			vocab_relative_values,
+			..Default::default()
+		};
+
+		let compacted = document
+			.compact_using(
+				RemoteContextReference::iri(context_iri.clone()),
+				&loader,
+				options,
+			)
+			.await
+			.unwrap();
+
+		let (expected, _) = json_ld::syntax::Value::parse_str(expected).unwrap();
+		assert_eq!(
+			compacted
+				.as_object()
+				.unwrap()
+				.get_unique("status")
+				.ok()
+				.unwrap(),
+			Some(&expected)
+		)
+	}
+}